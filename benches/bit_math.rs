@@ -0,0 +1,91 @@
+//! Benchmarks most_significant_bit and least_significant_bit in bit_math on samples covering every bit
+//! position, against the binary search cascade of BitMath.sol as the baseline
+use alloy_primitives::{keccak256, U256};
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use uniswap_v3_math::bit_math::{least_significant_bit, most_significant_bit};
+
+const SAMPLES: u64 = 1000;
+
+// pseudo random but reproducible samples, shifted so that every bit position is covered
+fn samples() -> Vec<U256> {
+    (0..SAMPLES)
+        .map(|i| U256::from_be_bytes(keccak256(i.to_be_bytes()).0) >> (i % 256) as usize)
+        .filter(|x| !x.is_zero())
+        .collect()
+}
+
+// the BitMath.sol binary search the limb based implementation replaced, benchmarked as the baseline
+fn most_significant_bit_cascade(mut x: U256) -> u8 {
+    let mut r = 0;
+    for shift in [128_usize, 64, 32, 16, 8, 4, 2, 1] {
+        if x >= U256::from(1) << shift {
+            x >>= shift;
+            r += shift;
+        }
+    }
+    r as u8
+}
+
+fn least_significant_bit_cascade(mut x: U256) -> u8 {
+    let mut r = 255;
+    for shift in [128_usize, 64, 32, 16, 8, 4, 2, 1] {
+        if !(x & ((U256::from(1) << shift) - U256::from(1))).is_zero() {
+            r -= shift;
+        } else {
+            x >>= shift;
+        }
+    }
+    r as u8
+}
+
+fn bench_most_significant_bit(c: &mut Criterion) {
+    let samples = samples();
+
+    let mut group = c.benchmark_group("bit_math/most_significant_bit");
+    group.throughput(Throughput::Elements(samples.len() as u64));
+    group.bench_function("limbs", |b| {
+        b.iter(|| {
+            for &x in &samples {
+                black_box(most_significant_bit(black_box(x)).unwrap());
+            }
+        })
+    });
+    group.bench_function("cascade", |b| {
+        b.iter(|| {
+            for &x in &samples {
+                black_box(most_significant_bit_cascade(black_box(x)));
+            }
+        })
+    });
+    group.finish();
+}
+
+fn bench_least_significant_bit(c: &mut Criterion) {
+    let samples = samples();
+
+    let mut group = c.benchmark_group("bit_math/least_significant_bit");
+    group.throughput(Throughput::Elements(samples.len() as u64));
+    group.bench_function("limbs", |b| {
+        b.iter(|| {
+            for &x in &samples {
+                black_box(least_significant_bit(black_box(x)).unwrap());
+            }
+        })
+    });
+    group.bench_function("cascade", |b| {
+        b.iter(|| {
+            for &x in &samples {
+                black_box(least_significant_bit_cascade(black_box(x)));
+            }
+        })
+    });
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    // changes below 10% are treated as noise, anything above is reported as a regression
+    config = Criterion::default().noise_threshold(0.10);
+    targets = bench_most_significant_bit, bench_least_significant_bit
+}
+criterion_main!(benches);
@@ -0,0 +1,93 @@
+//! Benchmarks mul_div and mul_div_rounding_up in full_math. Products that fit into 256 bits take the single
+//! division shortcut and are measured apart from products that need the full 512 by 256 bit division, and the
+//! rounding up path is measured on quotients with and without a remainder
+use alloy_primitives::{keccak256, U256};
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use uniswap_v3_math::full_math::{mul_div, mul_div_rounding_up};
+
+const SAMPLES: u64 = 1000;
+
+// pseudo random but reproducible samples so runs stay comparable between commits
+fn random_u256(i: u64) -> U256 {
+    U256::from_be_bytes(keccak256(i.to_be_bytes()).0)
+}
+
+// (a, b, denominator) whose product fits into 256 bits
+fn inputs_256_bit() -> Vec<(U256, U256, U256)> {
+    (0..SAMPLES)
+        .map(|i| {
+            (
+                random_u256(3 * i) >> 128,
+                random_u256(3 * i + 1) >> 129,
+                (random_u256(3 * i + 2) >> 64) | U256::from(1),
+            )
+        })
+        .collect()
+}
+
+// (a, b, denominator) whose product overflows 256 bits, denominator > a keeps the result below 2^256
+fn inputs_512_bit() -> Vec<(U256, U256, U256)> {
+    (0..SAMPLES)
+        .map(|i| {
+            (
+                random_u256(3 * i) >> 1,
+                random_u256(3 * i + 1) | (U256::from(1) << 255),
+                random_u256(3 * i + 2) | (U256::from(1) << 255),
+            )
+        })
+        .collect()
+}
+
+fn bench_mul_div(c: &mut Criterion) {
+    let mut group = c.benchmark_group("full_math/mul_div");
+    group.throughput(Throughput::Elements(SAMPLES));
+    for (name, inputs) in [
+        ("256_bit_product", inputs_256_bit()),
+        ("512_bit_product", inputs_512_bit()),
+    ] {
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                for &(a, b, denominator) in &inputs {
+                    black_box(mul_div(black_box(a), black_box(b), black_box(denominator)).unwrap());
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_mul_div_rounding_up(c: &mut Criterion) {
+    // denominator == b divides the product without a remainder, the rounding check passes without an increment
+    let exact: Vec<(U256, U256, U256)> = inputs_512_bit()
+        .into_iter()
+        .map(|(a, b, _)| (a, b, b))
+        .collect();
+    let with_remainder: Vec<(U256, U256, U256)> = inputs_512_bit()
+        .into_iter()
+        .filter(|&(a, b, denominator)| !a.mul_mod(b, denominator).is_zero())
+        .collect();
+
+    let mut group = c.benchmark_group("full_math/mul_div_rounding_up");
+    for (name, inputs) in [("exact", exact), ("with_remainder", with_remainder)] {
+        group.throughput(Throughput::Elements(inputs.len() as u64));
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                for &(a, b, denominator) in &inputs {
+                    black_box(
+                        mul_div_rounding_up(black_box(a), black_box(b), black_box(denominator))
+                            .unwrap(),
+                    );
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    // changes below 10% are treated as noise, anything above is reported as a regression
+    config = Criterion::default().noise_threshold(0.10);
+    targets = bench_mul_div, bench_mul_div_rounding_up
+}
+criterion_main!(benches);
@@ -0,0 +1,84 @@
+//! Benchmarks compute_swap_step in swap_math for exact input and exact output steps. The steps move in both
+//! directions over up to 2000 ticks with realistic liquidity and the common fee tiers, so that steps ending at
+//! the target sqrt ratio and steps running out of the amount remaining are both covered
+use alloy_primitives::{keccak256, I256, U256};
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use uniswap_v3_math::{
+    fixed_point::SqrtPriceX96, swap_math::compute_swap_step, tick_math::get_sqrt_ratio_at_tick,
+};
+
+const SAMPLES: u64 = 1000;
+const FEE_TIERS: [u32; 4] = [100, 500, 3000, 10000];
+
+// pseudo random but reproducible samples so runs stay comparable between commits
+fn random_u256(i: u64) -> U256 {
+    U256::from_be_bytes(keccak256(i.to_be_bytes()).0)
+}
+
+struct SwapStepInput {
+    sqrt_ratio_current_x_96: SqrtPriceX96,
+    sqrt_ratio_target_x_96: SqrtPriceX96,
+    liquidity: u128,
+    amount_remaining: I256,
+    fee_pips: u32,
+}
+
+// the amount remaining is positive for exact_in and negative otherwise
+fn inputs(exact_in: bool) -> Vec<SwapStepInput> {
+    (0..SAMPLES)
+        .map(|i| {
+            let random = |j: u64| random_u256(5 * i + j);
+            let tick_current = (random(0) % U256::from(200_001)).to::<i32>() - 100_000;
+            let tick_delta = (random(1) % U256::from(2000)).to::<i32>() + 1;
+            let tick_target = if random(2).bit(0) {
+                tick_current - tick_delta
+            } else {
+                tick_current + tick_delta
+            };
+            // 10^15 to 10^24
+            let liquidity =
+                10_u128.pow(15) + (random(3) % U256::from(10_u128.pow(24))).to::<u128>();
+            let amount = I256::from_raw(random(4) % U256::from(10_u128.pow(21)) + U256::from(1));
+
+            SwapStepInput {
+                sqrt_ratio_current_x_96: get_sqrt_ratio_at_tick(tick_current).unwrap(),
+                sqrt_ratio_target_x_96: get_sqrt_ratio_at_tick(tick_target).unwrap(),
+                liquidity,
+                amount_remaining: if exact_in { amount } else { -amount },
+                fee_pips: FEE_TIERS[i as usize % FEE_TIERS.len()],
+            }
+        })
+        .collect()
+}
+
+fn bench_compute_swap_step(c: &mut Criterion) {
+    let mut group = c.benchmark_group("swap_math/compute_swap_step");
+    group.throughput(Throughput::Elements(SAMPLES));
+    for (name, inputs) in [("exact_in", inputs(true)), ("exact_out", inputs(false))] {
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                for input in &inputs {
+                    black_box(
+                        compute_swap_step(
+                            black_box(input.sqrt_ratio_current_x_96),
+                            black_box(input.sqrt_ratio_target_x_96),
+                            black_box(input.liquidity),
+                            black_box(input.amount_remaining),
+                            black_box(input.fee_pips),
+                        )
+                        .unwrap(),
+                    );
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    // changes below 10% are treated as noise, anything above is reported as a regression
+    config = Criterion::default().noise_threshold(0.10);
+    targets = bench_compute_swap_step
+}
+criterion_main!(benches);
@@ -0,0 +1,125 @@
+//! Benchmarks the next initialized tick search in tick_bitmap on dense and sparse bitmaps.
+//! next_initialized_tick_within_word is measured on words with about half of the bits set and on words with a
+//! single bit set, next_initialized_tick on bitmaps with every tick initialized and with one initialized tick
+//! every 16 words, where the search has to walk over empty words
+use alloy_primitives::{keccak256, U256};
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use uniswap_v3_math::{
+    tick_bitmap::{next_initialized_tick, next_initialized_tick_within_word, TickBitmap},
+    tick_math::{MAX_TICK, MIN_TICK},
+};
+
+const SAMPLES: u64 = 1000;
+const TICK_SPACING: i32 = 60;
+
+// pseudo random but reproducible samples so runs stay comparable between commits
+fn random_u256(i: u64) -> U256 {
+    U256::from_be_bytes(keccak256(i.to_be_bytes()).0)
+}
+
+// SAMPLES initializable ticks between MIN_TICK and MAX_TICK
+fn random_ticks() -> Vec<i32> {
+    let range = U256::from((MAX_TICK - MIN_TICK) / TICK_SPACING);
+    (0..SAMPLES)
+        .map(|i| {
+            let compressed = (random_u256(i) % range).to::<i32>() + MIN_TICK / TICK_SPACING + 1;
+            compressed * TICK_SPACING
+        })
+        .collect()
+}
+
+fn bench_next_initialized_tick_within_word(c: &mut Criterion) {
+    let ticks = random_ticks();
+    let dense: Vec<(U256, i32)> = ticks
+        .iter()
+        .enumerate()
+        .map(|(i, &tick)| (random_u256(SAMPLES + i as u64), tick))
+        .collect();
+    let sparse: Vec<(U256, i32)> = ticks
+        .iter()
+        .enumerate()
+        .map(|(i, &tick)| (U256::from(1) << (i % 256), tick))
+        .collect();
+
+    let mut group = c.benchmark_group("tick_bitmap/next_initialized_tick_within_word");
+    group.throughput(Throughput::Elements(2 * SAMPLES));
+    for (name, inputs) in [("dense", dense), ("sparse", sparse)] {
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                for &(word, tick) in &inputs {
+                    for lte in [true, false] {
+                        black_box(
+                            next_initialized_tick_within_word(
+                                black_box(word),
+                                black_box(tick),
+                                TICK_SPACING,
+                                lte,
+                            )
+                            .unwrap(),
+                        );
+                    }
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_next_initialized_tick(c: &mut Criterion) {
+    let min_compressed = MIN_TICK / TICK_SPACING + 1;
+    let max_compressed = MAX_TICK / TICK_SPACING - 1;
+
+    let mut dense = TickBitmap::new();
+    for compressed in min_compressed..=max_compressed {
+        dense
+            .flip_tick(compressed * TICK_SPACING, TICK_SPACING)
+            .unwrap();
+    }
+    let mut sparse = TickBitmap::new();
+    for compressed in (min_compressed..=max_compressed).step_by(16 * 256) {
+        sparse
+            .flip_tick(compressed * TICK_SPACING, TICK_SPACING)
+            .unwrap();
+    }
+
+    let ticks = random_ticks();
+    let mut group = c.benchmark_group("tick_bitmap/next_initialized_tick");
+    group.throughput(Throughput::Elements(2 * SAMPLES));
+    for (name, bitmap) in [("dense", &dense), ("sparse", &sparse)] {
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                for &tick in &ticks {
+                    black_box(
+                        next_initialized_tick(
+                            bitmap,
+                            black_box(tick),
+                            TICK_SPACING,
+                            true,
+                            MIN_TICK,
+                        )
+                        .unwrap(),
+                    );
+                    black_box(
+                        next_initialized_tick(
+                            bitmap,
+                            black_box(tick),
+                            TICK_SPACING,
+                            false,
+                            MAX_TICK,
+                        )
+                        .unwrap(),
+                    );
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    // changes below 10% are treated as noise, anything above is reported as a regression
+    config = Criterion::default().noise_threshold(0.10);
+    targets = bench_next_initialized_tick_within_word, bench_next_initialized_tick
+}
+criterion_main!(benches);
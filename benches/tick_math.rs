@@ -0,0 +1,97 @@
+//! Benchmarks the conversions between ticks and sqrt ratios in tick_math. get_sqrt_ratio_at_tick is measured on
+//! ticks close to 0, ticks far from 0 and MIN/MAX_TICK, as the number of ratio multiplications grows with the bits
+//! set in |tick|. get_tick_at_sqrt_ratio is measured on sqrt ratios spread evenly over the tick range as well as
+//! on uniformly random sqrt ratios, which mostly fall into the highest prices
+use alloy_primitives::{keccak256, U256};
+use criterion::{black_box, criterion_group, criterion_main, Criterion, Throughput};
+use uniswap_v3_math::{
+    fixed_point::SqrtPriceX96,
+    tick_math::{
+        get_sqrt_ratio_at_tick, get_tick_at_sqrt_ratio, MAX_SQRT_RATIO, MAX_TICK, MIN_SQRT_RATIO,
+        MIN_TICK,
+    },
+};
+
+const SAMPLES: u64 = 1000;
+
+// pseudo random but reproducible samples so runs stay comparable between commits
+fn random_u256(i: u64) -> U256 {
+    U256::from_be_bytes(keccak256(i.to_be_bytes()).0)
+}
+
+// SAMPLES ticks drawn from [min, max]
+fn random_ticks(min: i32, max: i32) -> Vec<i32> {
+    let range = U256::from(max - min + 1);
+    (0..SAMPLES)
+        .map(|i| min + (random_u256(i) % range).to::<i32>())
+        .collect()
+}
+
+fn bench_get_sqrt_ratio_at_tick(c: &mut Criterion) {
+    let inputs = [
+        ("small_ticks", random_ticks(-1000, 1000)),
+        (
+            "large_ticks",
+            random_ticks(500_000, MAX_TICK)
+                .into_iter()
+                .enumerate()
+                .map(|(i, tick)| if i % 2 == 0 { tick } else { -tick })
+                .collect(),
+        ),
+        ("min_max_ticks", vec![MIN_TICK, MAX_TICK]),
+        ("random_ticks", random_ticks(MIN_TICK, MAX_TICK)),
+    ];
+
+    let mut group = c.benchmark_group("tick_math/get_sqrt_ratio_at_tick");
+    for (name, ticks) in &inputs {
+        group.throughput(Throughput::Elements(ticks.len() as u64));
+        group.bench_function(*name, |b| {
+            b.iter(|| {
+                for &tick in ticks {
+                    black_box(get_sqrt_ratio_at_tick(black_box(tick)).unwrap());
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+fn bench_get_tick_at_sqrt_ratio(c: &mut Criterion) {
+    let step = (MAX_TICK - MIN_TICK) / SAMPLES as i32;
+    let price_range: Vec<SqrtPriceX96> = (0..SAMPLES as i32)
+        .map(|i| get_sqrt_ratio_at_tick(MIN_TICK + i * step).unwrap())
+        .collect();
+    let range = MAX_SQRT_RATIO - MIN_SQRT_RATIO;
+    let random_sqrt_ratios: Vec<SqrtPriceX96> = (0..SAMPLES)
+        .map(|i| SqrtPriceX96(MIN_SQRT_RATIO + random_u256(i) % range))
+        .collect();
+    let min_max_sqrt_ratios = vec![
+        SqrtPriceX96(MIN_SQRT_RATIO),
+        SqrtPriceX96(MAX_SQRT_RATIO - U256::from(1)),
+    ];
+
+    let mut group = c.benchmark_group("tick_math/get_tick_at_sqrt_ratio");
+    for (name, sqrt_ratios) in [
+        ("price_range", &price_range),
+        ("random_sqrt_ratios", &random_sqrt_ratios),
+        ("min_max_sqrt_ratios", &min_max_sqrt_ratios),
+    ] {
+        group.throughput(Throughput::Elements(sqrt_ratios.len() as u64));
+        group.bench_function(name, |b| {
+            b.iter(|| {
+                for &sqrt_ratio in sqrt_ratios {
+                    black_box(get_tick_at_sqrt_ratio(black_box(sqrt_ratio)).unwrap());
+                }
+            })
+        });
+    }
+    group.finish();
+}
+
+criterion_group! {
+    name = benches;
+    // changes below 10% are treated as noise, anything above is reported as a regression
+    config = Criterion::default().noise_threshold(0.10);
+    targets = bench_get_sqrt_ratio_at_tick, bench_get_tick_at_sqrt_ratio
+}
+criterion_main!(benches);
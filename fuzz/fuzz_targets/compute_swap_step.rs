@@ -0,0 +1,40 @@
+#![no_main]
+
+use alloy_primitives::I256;
+use libfuzzer_sys::fuzz_target;
+use uniswap_v3_math::{fuzz_support::SwapStepInput, swap_math::compute_swap_step};
+
+fuzz_target!(|data: &[u8]| {
+    let input = SwapStepInput::decode(data);
+
+    let Ok((sqrt_ratio_next_x_96, amount_in, amount_out, fee_amount)) = compute_swap_step(
+        input.sqrt_ratio_current_x_96,
+        input.sqrt_ratio_target_x_96,
+        input.liquidity,
+        input.amount_remaining,
+        input.fee_pips,
+    ) else {
+        return;
+    };
+
+    // the price moves towards the target without passing it
+    let (lower, upper) = if input.sqrt_ratio_current_x_96 >= input.sqrt_ratio_target_x_96 {
+        (input.sqrt_ratio_target_x_96, input.sqrt_ratio_current_x_96)
+    } else {
+        (input.sqrt_ratio_current_x_96, input.sqrt_ratio_target_x_96)
+    };
+    assert!((lower..=upper).contains(&sqrt_ratio_next_x_96));
+
+    if input.amount_remaining >= I256::ZERO {
+        // an exact input step never takes more than the amount remaining
+        assert!(amount_in + fee_amount <= input.amount_remaining.into_raw());
+    } else {
+        // an exact output step never pays out more than requested
+        assert!(amount_out <= input.amount_remaining.unsigned_abs());
+    }
+
+    if sqrt_ratio_next_x_96 != input.sqrt_ratio_target_x_96 && input.amount_remaining >= I256::ZERO {
+        // the whole remainder is consumed when the target is not reached
+        assert_eq!(amount_in + fee_amount, input.amount_remaining.into_raw());
+    }
+});
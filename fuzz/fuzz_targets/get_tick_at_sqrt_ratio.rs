@@ -0,0 +1,21 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use uniswap_v3_math::{
+    fixed_point::SqrtPriceX96,
+    fuzz_support::u256_from_bytes,
+    tick_math::{get_sqrt_ratio_at_tick, get_tick_at_sqrt_ratio, MAX_SQRT_RATIO, MIN_SQRT_RATIO},
+};
+
+fuzz_target!(|data: &[u8]| {
+    let sqrt_price_x_96 = SqrtPriceX96(u256_from_bytes(data));
+
+    let Ok(tick) = get_tick_at_sqrt_ratio(sqrt_price_x_96) else {
+        assert!(!(MIN_SQRT_RATIO..MAX_SQRT_RATIO).contains(&sqrt_price_x_96));
+        return;
+    };
+
+    // the input lies in [ratio(tick), ratio(tick + 1))
+    assert!(get_sqrt_ratio_at_tick(tick).unwrap() <= sqrt_price_x_96);
+    assert!(get_sqrt_ratio_at_tick(tick + 1).unwrap() > sqrt_price_x_96);
+});
@@ -0,0 +1,35 @@
+#![no_main]
+
+use alloy_primitives::{U256, U512};
+use libfuzzer_sys::fuzz_target;
+use uniswap_v3_math::{
+    full_math::{mul_div, mul_div_rounding_up},
+    fuzz_support::MulDivInput,
+};
+
+fuzz_target!(|data: &[u8]| {
+    let MulDivInput { a, b, denominator } = MulDivInput::decode(data);
+
+    let result = mul_div(a, b, denominator);
+    let result_rounding_up = mul_div_rounding_up(a, b, denominator);
+    if denominator.is_zero() {
+        assert!(result.is_err() && result_rounding_up.is_err());
+        return;
+    }
+
+    // 512 bit reference
+    let product = U512::from(a) * U512::from(b);
+    let (quotient, remainder) = product.div_rem(U512::from(denominator));
+    let quotient_rounding_up = quotient + U512::from(!remainder.is_zero() as u8);
+
+    if quotient.bit_len() <= 256 {
+        assert_eq!(result.unwrap(), quotient.to::<U256>());
+    } else {
+        assert!(result.is_err());
+    }
+    if quotient_rounding_up.bit_len() <= 256 {
+        assert_eq!(result_rounding_up.unwrap(), quotient_rounding_up.to::<U256>());
+    } else {
+        assert!(result_rounding_up.is_err());
+    }
+});
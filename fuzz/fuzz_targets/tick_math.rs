@@ -0,0 +1,26 @@
+#![no_main]
+
+use libfuzzer_sys::fuzz_target;
+use uniswap_v3_math::tick_math::{
+    get_sqrt_ratio_at_tick, get_tick_at_sqrt_ratio, MAX_SQRT_RATIO, MAX_TICK, MIN_SQRT_RATIO,
+    MIN_TICK,
+};
+
+fuzz_target!(|tick: i32| {
+    let Ok(sqrt_ratio) = get_sqrt_ratio_at_tick(tick) else {
+        assert!(!(MIN_TICK..=MAX_TICK).contains(&tick));
+        return;
+    };
+    assert!((MIN_SQRT_RATIO..=MAX_SQRT_RATIO).contains(&sqrt_ratio));
+
+    // MAX_SQRT_RATIO is outside of the domain of get_tick_at_sqrt_ratio
+    if tick == MAX_TICK {
+        assert_eq!(sqrt_ratio, MAX_SQRT_RATIO);
+        return;
+    }
+
+    let round_trip = get_tick_at_sqrt_ratio(sqrt_ratio).unwrap();
+    assert!(round_trip.abs_diff(tick) <= 1);
+    // the sqrt ratio at a tick is the lower bound of that tick, so the round trip is exact
+    assert_eq!(round_trip, tick);
+});
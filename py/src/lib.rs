@@ -0,0 +1,199 @@
+use alloy_primitives::{Sign, I256, U256};
+use num_bigint::{BigInt, BigUint};
+use pyo3::{create_exception, exceptions::PyException, prelude::*};
+use uniswap_v3_math::{error, sqrt_price_math, swap_math, tick_math};
+
+// 256 bit values are taken and returned as python ints, errors are raised as UniswapV3MathError carrying the
+// message of the rust error
+create_exception!(uniswap_v3_math, UniswapV3MathError, PyException);
+
+fn to_py_err(err: error::UniswapV3MathError) -> PyErr {
+    UniswapV3MathError::new_err(err.to_string())
+}
+
+fn to_u256<T: From<U256>>(value: BigUint) -> PyResult<T> {
+    U256::try_from_le_slice(&value.to_bytes_le())
+        .map(T::from)
+        .ok_or_else(|| UniswapV3MathError::new_err(format!("{value} does not fit into uint256")))
+}
+
+fn to_i256(value: BigInt) -> PyResult<I256> {
+    let (sign, magnitude) = value.clone().into_parts();
+    let sign = match sign {
+        num_bigint::Sign::Minus => Sign::Negative,
+        _ => Sign::Positive,
+    };
+
+    to_u256::<U256>(magnitude)
+        .ok()
+        .and_then(|magnitude| I256::checked_from_sign_and_abs(sign, magnitude))
+        .ok_or_else(|| UniswapV3MathError::new_err(format!("{value} does not fit into int256")))
+}
+
+fn to_big_uint<T: Into<U256>>(value: T) -> BigUint {
+    BigUint::from_bytes_le(&value.into().to_le_bytes::<32>())
+}
+
+// returns (uint160 sqrtPriceX96)
+#[pyfunction]
+fn get_sqrt_ratio_at_tick(tick: i32) -> PyResult<BigUint> {
+    tick_math::get_sqrt_ratio_at_tick(tick)
+        .map(to_big_uint)
+        .map_err(to_py_err)
+}
+
+// returns (int24 tick)
+#[pyfunction]
+fn get_tick_at_sqrt_ratio(sqrt_price_x_96: BigUint) -> PyResult<i32> {
+    tick_math::get_tick_at_sqrt_ratio(to_u256(sqrt_price_x_96)?).map_err(to_py_err)
+}
+
+// returns (uint256 amount0)
+#[pyfunction]
+fn get_amount_0_delta(
+    sqrt_ratio_a_x_96: BigUint,
+    sqrt_ratio_b_x_96: BigUint,
+    liquidity: u128,
+    round_up: bool,
+) -> PyResult<BigUint> {
+    sqrt_price_math::_get_amount_0_delta(
+        to_u256(sqrt_ratio_a_x_96)?,
+        to_u256(sqrt_ratio_b_x_96)?,
+        liquidity,
+        round_up,
+    )
+    .map(to_big_uint)
+    .map_err(to_py_err)
+}
+
+// returns (uint256 amount1)
+#[pyfunction]
+fn get_amount_1_delta(
+    sqrt_ratio_a_x_96: BigUint,
+    sqrt_ratio_b_x_96: BigUint,
+    liquidity: u128,
+    round_up: bool,
+) -> PyResult<BigUint> {
+    sqrt_price_math::_get_amount_1_delta(
+        to_u256(sqrt_ratio_a_x_96)?,
+        to_u256(sqrt_ratio_b_x_96)?,
+        liquidity,
+        round_up,
+    )
+    .map(to_big_uint)
+    .map_err(to_py_err)
+}
+
+// returns (uint160 sqrtRatioNextX96, uint256 amountIn, uint256 amountOut, uint256 feeAmount), amount_remaining is
+// positive for an exact input and negative for an exact output
+#[pyfunction]
+fn compute_swap_step(
+    sqrt_ratio_current_x_96: BigUint,
+    sqrt_ratio_target_x_96: BigUint,
+    liquidity: u128,
+    amount_remaining: BigInt,
+    fee_pips: u32,
+) -> PyResult<(BigUint, BigUint, BigUint, BigUint)> {
+    let (sqrt_ratio_next_x_96, amount_in, amount_out, fee_amount) = swap_math::compute_swap_step(
+        to_u256(sqrt_ratio_current_x_96)?,
+        to_u256(sqrt_ratio_target_x_96)?,
+        liquidity,
+        to_i256(amount_remaining)?,
+        fee_pips,
+    )
+    .map_err(to_py_err)?;
+
+    Ok((
+        to_big_uint(sqrt_ratio_next_x_96),
+        to_big_uint(amount_in),
+        to_big_uint(amount_out),
+        to_big_uint(fee_amount),
+    ))
+}
+
+// returns (uint128 liquidity)
+#[pyfunction]
+fn get_liquidity_for_amount_0(
+    sqrt_ratio_a_x_96: BigUint,
+    sqrt_ratio_b_x_96: BigUint,
+    amount_0: BigUint,
+) -> PyResult<u128> {
+    sqrt_price_math::get_liquidity_for_amount_0(
+        to_u256(sqrt_ratio_a_x_96)?,
+        to_u256(sqrt_ratio_b_x_96)?,
+        to_u256(amount_0)?,
+    )
+    .map_err(to_py_err)
+}
+
+// returns (uint128 liquidity)
+#[pyfunction]
+fn get_liquidity_for_amount_1(
+    sqrt_ratio_a_x_96: BigUint,
+    sqrt_ratio_b_x_96: BigUint,
+    amount_1: BigUint,
+) -> PyResult<u128> {
+    sqrt_price_math::get_liquidity_for_amount_1(
+        to_u256(sqrt_ratio_a_x_96)?,
+        to_u256(sqrt_ratio_b_x_96)?,
+        to_u256(amount_1)?,
+    )
+    .map_err(to_py_err)
+}
+
+// returns (uint128 liquidity)
+#[pyfunction]
+fn get_liquidity_for_amounts(
+    sqrt_ratio_x_96: BigUint,
+    sqrt_ratio_a_x_96: BigUint,
+    sqrt_ratio_b_x_96: BigUint,
+    amount_0: BigUint,
+    amount_1: BigUint,
+) -> PyResult<u128> {
+    sqrt_price_math::get_liquidity_for_amounts(
+        to_u256(sqrt_ratio_x_96)?,
+        to_u256(sqrt_ratio_a_x_96)?,
+        to_u256(sqrt_ratio_b_x_96)?,
+        to_u256(amount_0)?,
+        to_u256(amount_1)?,
+    )
+    .map_err(to_py_err)
+}
+
+// returns (uint256 amount0, uint256 amount1)
+#[pyfunction]
+fn get_amounts_for_liquidity(
+    sqrt_ratio_x_96: BigUint,
+    sqrt_ratio_a_x_96: BigUint,
+    sqrt_ratio_b_x_96: BigUint,
+    liquidity: u128,
+) -> PyResult<(BigUint, BigUint)> {
+    let (amount_0, amount_1) = sqrt_price_math::get_amounts_for_liquidity(
+        to_u256(sqrt_ratio_x_96)?,
+        to_u256(sqrt_ratio_a_x_96)?,
+        to_u256(sqrt_ratio_b_x_96)?,
+        liquidity,
+    )
+    .map_err(to_py_err)?;
+
+    Ok((to_big_uint(amount_0), to_big_uint(amount_1)))
+}
+
+#[pymodule]
+#[pyo3(name = "uniswap_v3_math")]
+fn uniswap_v3_math_py(m: &Bound<'_, PyModule>) -> PyResult<()> {
+    m.add(
+        "UniswapV3MathError",
+        m.py().get_type::<UniswapV3MathError>(),
+    )?;
+    m.add_function(wrap_pyfunction!(get_sqrt_ratio_at_tick, m)?)?;
+    m.add_function(wrap_pyfunction!(get_tick_at_sqrt_ratio, m)?)?;
+    m.add_function(wrap_pyfunction!(get_amount_0_delta, m)?)?;
+    m.add_function(wrap_pyfunction!(get_amount_1_delta, m)?)?;
+    m.add_function(wrap_pyfunction!(compute_swap_step, m)?)?;
+    m.add_function(wrap_pyfunction!(get_liquidity_for_amount_0, m)?)?;
+    m.add_function(wrap_pyfunction!(get_liquidity_for_amount_1, m)?)?;
+    m.add_function(wrap_pyfunction!(get_liquidity_for_amounts, m)?)?;
+    m.add_function(wrap_pyfunction!(get_amounts_for_liquidity, m)?)?;
+    Ok(())
+}
@@ -0,0 +1,72 @@
+import pytest
+
+import uniswap_v3_math as m
+
+# sqrt ratios of the prices 1, 1.0001^1000 and 1.21
+PRICE_1 = 79228162514264337593543950336
+PRICE_TICK_1000 = 83290069058676223003182343270
+PRICE_1_21 = 87150978765690771352898345369
+
+
+@pytest.mark.parametrize(
+    "tick, sqrt_price_x_96",
+    [
+        (-887272, 4295128739),
+        (0, 2**96),
+        (50, 79426470787362580746886972461),
+        (150000, 143194173941309278083010301478497),
+        (887272, 1461446703485210103287273052203988822378723970342),
+    ],
+)
+def test_tick_math_round_trip(tick, sqrt_price_x_96):
+    assert m.get_sqrt_ratio_at_tick(tick) == sqrt_price_x_96
+    if tick < 887272:
+        assert m.get_tick_at_sqrt_ratio(sqrt_price_x_96) == tick
+
+
+def test_tick_math_errors():
+    with pytest.raises(m.UniswapV3MathError, match="maximum tick"):
+        m.get_sqrt_ratio_at_tick(887273)
+    with pytest.raises(m.UniswapV3MathError):
+        m.get_tick_at_sqrt_ratio(2**160)
+    with pytest.raises(m.UniswapV3MathError, match="uint256"):
+        m.get_tick_at_sqrt_ratio(2**256)
+
+
+@pytest.mark.parametrize(
+    "round_up, amount_0, amount_1",
+    [
+        (True, 90909090909090910, 100000000000000000),
+        (False, 90909090909090909, 99999999999999999),
+    ],
+)
+def test_amount_deltas(round_up, amount_0, amount_1):
+    assert m.get_amount_0_delta(PRICE_1, PRICE_1_21, 10**18, round_up) == amount_0
+    assert m.get_amount_1_delta(PRICE_1, PRICE_1_21, 10**18, round_up) == amount_1
+
+
+def test_compute_swap_step():
+    assert m.compute_swap_step(PRICE_1, PRICE_1_21, 2 * 10**18, 10**18, 600) == (
+        PRICE_1_21,
+        200000000000000000,
+        181818181818181818,
+        120072043225936,
+    )
+
+    with pytest.raises(m.UniswapV3MathError):
+        m.compute_swap_step(PRICE_1, PRICE_1_21, 2 * 10**18, -(10**18), 1_000_000)
+    with pytest.raises(m.UniswapV3MathError, match="int256"):
+        m.compute_swap_step(PRICE_1, PRICE_1_21, 2 * 10**18, 2**255, 600)
+
+
+def test_liquidity_amounts():
+    assert m.get_liquidity_for_amount_0(PRICE_TICK_1000, PRICE_1_21, 10**18) == 23729919349859225842
+    assert m.get_liquidity_for_amount_1(PRICE_1, PRICE_TICK_1000, 10**18) == 19505166268106646078
+    assert (
+        m.get_liquidity_for_amounts(PRICE_TICK_1000, PRICE_1, PRICE_1_21, 10**18, 10**18)
+        == 19505166268106646078
+    )
+    assert m.get_amounts_for_liquidity(PRICE_TICK_1000, PRICE_1, PRICE_1_21, 10**18) == (
+        42140893327812020,
+        51268468376766590,
+    )
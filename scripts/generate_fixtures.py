@@ -0,0 +1,392 @@
+#!/usr/bin/env python3
+"""Generates the fixtures in tests/fixtures from a Python port of the Solidity reference.
+
+TickMath, SqrtPriceMath, SwapMath and FullMath are ported line by line from the Solidity sources in Uniswap/,
+with python ints standing in for uint256 and every require turned into a Revert. Inputs are drawn from a fixed
+seed so that regenerating the fixtures is reproducible, rows whose reference call reverts are left out.
+
+    python3 scripts/generate_fixtures.py
+"""
+
+import json
+import random
+from pathlib import Path
+
+FIXTURES = Path(__file__).resolve().parent.parent / "tests" / "fixtures"
+
+MAX_UINT256 = 2**256 - 1
+MAX_UINT160 = 2**160 - 1
+MAX_UINT128 = 2**128 - 1
+Q96 = 2**96
+RESOLUTION = 96
+
+MIN_TICK = -887272
+MAX_TICK = 887272
+MIN_SQRT_RATIO = 4295128739
+MAX_SQRT_RATIO = 1461446703485210103287273052203988822378723970342
+
+
+class Revert(Exception):
+    pass
+
+
+def require(condition):
+    if not condition:
+        raise Revert()
+
+
+# FullMath
+
+
+def mul_div(a, b, denominator):
+    require(denominator > 0)
+    result = a * b // denominator
+    # denominator > prod1 in the 512 bit division
+    require(result <= MAX_UINT256)
+    return result
+
+
+def mul_div_rounding_up(a, b, denominator):
+    result = mul_div(a, b, denominator)
+    if a * b % denominator > 0:
+        require(result < MAX_UINT256)
+        result += 1
+    return result
+
+
+# UnsafeMath
+
+
+def div_rounding_up(x, y):
+    return x // y + (1 if x % y > 0 else 0)
+
+
+# SafeCast
+
+
+def to_uint160(y):
+    require(y <= MAX_UINT160)
+    return y
+
+
+# TickMath
+
+RATIO_MULTIPLIERS = [
+    (0x2, 0xFFF97272373D413259A46990580E213A),
+    (0x4, 0xFFF2E50F5F656932EF12357CF3C7FDCC),
+    (0x8, 0xFFE5CACA7E10E4E61C3624EAA0941CD0),
+    (0x10, 0xFFCB9843D60F6159C9DB58835C926644),
+    (0x20, 0xFF973B41FA98C081472E6896DFB254C0),
+    (0x40, 0xFF2EA16466C96A3843EC78B326B52861),
+    (0x80, 0xFE5DEE046A99A2A811C461F1969C3053),
+    (0x100, 0xFCBE86C7900A88AEDCFFC83B479AA3A4),
+    (0x200, 0xF987A7253AC413176F2B074CF7815E54),
+    (0x400, 0xF3392B0822B70005940C7A398E4B70F3),
+    (0x800, 0xE7159475A2C29B7443B29C7FA6E889D9),
+    (0x1000, 0xD097F3BDFD2022B8845AD8F792AA5825),
+    (0x2000, 0xA9F746462D870FDF8A65DC1F90E061E5),
+    (0x4000, 0x70D869A156D2A1B890BB3DF62BAF32F7),
+    (0x8000, 0x31BE135F97D08FD981231505542FCFA6),
+    (0x10000, 0x9AA508B5B7A84E1C677DE54F3E99BC9),
+    (0x20000, 0x5D6AF8DEDB81196699C329225EE604),
+    (0x40000, 0x2216E584F5FA1EA926041BEDFE98),
+    (0x80000, 0x48A170391F7DC42444E8FA2),
+]
+
+
+def get_sqrt_ratio_at_tick(tick):
+    abs_tick = abs(tick)
+    require(abs_tick <= MAX_TICK)
+
+    ratio = 0xFFFCB933BD6FAD37AA2D162D1A594001 if abs_tick & 0x1 else 0x100000000000000000000000000000000
+    for bit, multiplier in RATIO_MULTIPLIERS:
+        if abs_tick & bit:
+            ratio = (ratio * multiplier) >> 128
+
+    if tick > 0:
+        ratio = MAX_UINT256 // ratio
+
+    return (ratio >> 32) + (0 if ratio % (1 << 32) == 0 else 1)
+
+
+def get_tick_at_sqrt_ratio(sqrt_price_x_96):
+    require(MIN_SQRT_RATIO <= sqrt_price_x_96 < MAX_SQRT_RATIO)
+    ratio = sqrt_price_x_96 << 32
+
+    r = ratio
+    msb = 0
+    for shift, bound in [(7, 2**128 - 1), (6, 2**64 - 1), (5, 2**32 - 1), (4, 0xFFFF), (3, 0xFF), (2, 0xF), (1, 0x3)]:
+        f = (1 if r > bound else 0) << shift
+        msb |= f
+        r >>= f
+    msb |= 1 if r > 0x1 else 0
+
+    if msb >= 128:
+        r = ratio >> (msb - 127)
+    else:
+        r = ratio << (127 - msb)
+
+    log_2 = (msb - 128) << 64
+    for shift in range(63, 49, -1):
+        r = (r * r) >> 127
+        f = r >> 128
+        log_2 |= f << shift
+        r >>= f
+
+    log_sqrt10001 = log_2 * 255738958999603826347141
+    tick_low = (log_sqrt10001 - 3402992956809132418596140100660247210) >> 128
+    tick_hi = (log_sqrt10001 + 291339464771989622907027621153398088495) >> 128
+
+    if tick_low == tick_hi:
+        return tick_low
+    return tick_hi if get_sqrt_ratio_at_tick(tick_hi) <= sqrt_price_x_96 else tick_low
+
+
+# SqrtPriceMath
+
+
+def get_next_sqrt_price_from_amount_0_rounding_up(sqrt_p_x_96, liquidity, amount, add):
+    if amount == 0:
+        return sqrt_p_x_96
+    numerator_1 = liquidity << RESOLUTION
+
+    if add:
+        product = amount * sqrt_p_x_96
+        if product <= MAX_UINT256:
+            denominator = numerator_1 + product
+            if denominator <= MAX_UINT256:
+                return mul_div_rounding_up(numerator_1, sqrt_p_x_96, denominator)
+        return div_rounding_up(numerator_1, numerator_1 // sqrt_p_x_96 + amount)
+
+    product = amount * sqrt_p_x_96
+    require(product <= MAX_UINT256 and numerator_1 > product)
+    denominator = numerator_1 - product
+    return to_uint160(mul_div_rounding_up(numerator_1, sqrt_p_x_96, denominator))
+
+
+def get_next_sqrt_price_from_amount_1_rounding_down(sqrt_p_x_96, liquidity, amount, add):
+    if add:
+        if amount <= MAX_UINT160:
+            quotient = (amount << RESOLUTION) // liquidity
+        else:
+            quotient = mul_div(amount, Q96, liquidity)
+        return to_uint160(sqrt_p_x_96 + quotient)
+
+    if amount <= MAX_UINT160:
+        quotient = div_rounding_up(amount << RESOLUTION, liquidity)
+    else:
+        quotient = mul_div_rounding_up(amount, Q96, liquidity)
+    require(sqrt_p_x_96 > quotient)
+    return sqrt_p_x_96 - quotient
+
+
+def get_next_sqrt_price_from_input(sqrt_p_x_96, liquidity, amount_in, zero_for_one):
+    require(sqrt_p_x_96 > 0)
+    require(liquidity > 0)
+    if zero_for_one:
+        return get_next_sqrt_price_from_amount_0_rounding_up(sqrt_p_x_96, liquidity, amount_in, True)
+    return get_next_sqrt_price_from_amount_1_rounding_down(sqrt_p_x_96, liquidity, amount_in, True)
+
+
+def get_next_sqrt_price_from_output(sqrt_p_x_96, liquidity, amount_out, zero_for_one):
+    require(sqrt_p_x_96 > 0)
+    require(liquidity > 0)
+    if zero_for_one:
+        return get_next_sqrt_price_from_amount_1_rounding_down(sqrt_p_x_96, liquidity, amount_out, False)
+    return get_next_sqrt_price_from_amount_0_rounding_up(sqrt_p_x_96, liquidity, amount_out, False)
+
+
+def get_amount_0_delta(sqrt_ratio_a_x_96, sqrt_ratio_b_x_96, liquidity, round_up):
+    if sqrt_ratio_a_x_96 > sqrt_ratio_b_x_96:
+        sqrt_ratio_a_x_96, sqrt_ratio_b_x_96 = sqrt_ratio_b_x_96, sqrt_ratio_a_x_96
+    numerator_1 = liquidity << RESOLUTION
+    numerator_2 = sqrt_ratio_b_x_96 - sqrt_ratio_a_x_96
+    require(sqrt_ratio_a_x_96 > 0)
+
+    if round_up:
+        return div_rounding_up(mul_div_rounding_up(numerator_1, numerator_2, sqrt_ratio_b_x_96), sqrt_ratio_a_x_96)
+    return mul_div(numerator_1, numerator_2, sqrt_ratio_b_x_96) // sqrt_ratio_a_x_96
+
+
+def get_amount_1_delta(sqrt_ratio_a_x_96, sqrt_ratio_b_x_96, liquidity, round_up):
+    if sqrt_ratio_a_x_96 > sqrt_ratio_b_x_96:
+        sqrt_ratio_a_x_96, sqrt_ratio_b_x_96 = sqrt_ratio_b_x_96, sqrt_ratio_a_x_96
+
+    if round_up:
+        return mul_div_rounding_up(liquidity, sqrt_ratio_b_x_96 - sqrt_ratio_a_x_96, Q96)
+    return mul_div(liquidity, sqrt_ratio_b_x_96 - sqrt_ratio_a_x_96, Q96)
+
+
+# SwapMath
+
+
+def compute_swap_step(sqrt_ratio_current_x_96, sqrt_ratio_target_x_96, liquidity, amount_remaining, fee_pips):
+    zero_for_one = sqrt_ratio_current_x_96 >= sqrt_ratio_target_x_96
+    exact_in = amount_remaining >= 0
+    amount_in = 0
+    amount_out = 0
+
+    if exact_in:
+        amount_remaining_less_fee = mul_div(amount_remaining, 10**6 - fee_pips, 10**6)
+        if zero_for_one:
+            amount_in = get_amount_0_delta(sqrt_ratio_target_x_96, sqrt_ratio_current_x_96, liquidity, True)
+        else:
+            amount_in = get_amount_1_delta(sqrt_ratio_current_x_96, sqrt_ratio_target_x_96, liquidity, True)
+        if amount_remaining_less_fee >= amount_in:
+            sqrt_ratio_next_x_96 = sqrt_ratio_target_x_96
+        else:
+            sqrt_ratio_next_x_96 = get_next_sqrt_price_from_input(
+                sqrt_ratio_current_x_96, liquidity, amount_remaining_less_fee, zero_for_one
+            )
+    else:
+        if zero_for_one:
+            amount_out = get_amount_1_delta(sqrt_ratio_target_x_96, sqrt_ratio_current_x_96, liquidity, False)
+        else:
+            amount_out = get_amount_0_delta(sqrt_ratio_current_x_96, sqrt_ratio_target_x_96, liquidity, False)
+        if -amount_remaining >= amount_out:
+            sqrt_ratio_next_x_96 = sqrt_ratio_target_x_96
+        else:
+            sqrt_ratio_next_x_96 = get_next_sqrt_price_from_output(
+                sqrt_ratio_current_x_96, liquidity, -amount_remaining, zero_for_one
+            )
+
+    reached_target = sqrt_ratio_target_x_96 == sqrt_ratio_next_x_96
+
+    if zero_for_one:
+        if not (reached_target and exact_in):
+            amount_in = get_amount_0_delta(sqrt_ratio_next_x_96, sqrt_ratio_current_x_96, liquidity, True)
+        if not (reached_target and not exact_in):
+            amount_out = get_amount_1_delta(sqrt_ratio_next_x_96, sqrt_ratio_current_x_96, liquidity, False)
+    else:
+        if not (reached_target and exact_in):
+            amount_in = get_amount_1_delta(sqrt_ratio_current_x_96, sqrt_ratio_next_x_96, liquidity, True)
+        if not (reached_target and not exact_in):
+            amount_out = get_amount_0_delta(sqrt_ratio_current_x_96, sqrt_ratio_next_x_96, liquidity, False)
+
+    if not exact_in and amount_out > -amount_remaining:
+        amount_out = -amount_remaining
+
+    if exact_in and sqrt_ratio_next_x_96 != sqrt_ratio_target_x_96:
+        fee_amount = amount_remaining - amount_in
+    else:
+        fee_amount = mul_div_rounding_up(amount_in, fee_pips, 10**6 - fee_pips)
+
+    return sqrt_ratio_next_x_96, amount_in, amount_out, fee_amount
+
+
+# Fixtures
+
+
+def log_uniform(rng, bits):
+    return rng.getrandbits(rng.randint(1, bits))
+
+
+def random_sqrt_ratio(rng):
+    tick = rng.randint(MIN_TICK, MAX_TICK - 1)
+    low = get_sqrt_ratio_at_tick(tick)
+    high = get_sqrt_ratio_at_tick(tick + 1)
+    return rng.randrange(low, high)
+
+
+def tick_fixtures():
+    ticks = sorted(set(range(-887200, MAX_TICK + 1, 100)) | {MIN_TICK, MAX_TICK})
+    return [{"tick": tick, "sqrt_ratio_x_96": hex(get_sqrt_ratio_at_tick(tick))} for tick in ticks]
+
+
+def sqrt_ratio_fixtures(rng):
+    sqrt_ratios = {MIN_SQRT_RATIO, MAX_SQRT_RATIO - 1}
+    # exact tick boundaries and their neighbours, where the tick_low / tick_high choice matters
+    for _ in range(1000):
+        sqrt_ratio = get_sqrt_ratio_at_tick(rng.randint(MIN_TICK + 1, MAX_TICK - 1))
+        sqrt_ratios |= {sqrt_ratio - 1, sqrt_ratio, sqrt_ratio + 1}
+    while len(sqrt_ratios) < 5000:
+        sqrt_ratios.add(random_sqrt_ratio(rng))
+
+    return [
+        {"sqrt_ratio_x_96": hex(sqrt_ratio), "tick": get_tick_at_sqrt_ratio(sqrt_ratio)}
+        for sqrt_ratio in sorted(sqrt_ratios)
+    ]
+
+
+def amount_delta_fixtures():
+    ticks = [MIN_TICK, -500000, -100000, -10000, -60, -1, 0, 1, 60, 10000, 100000, 500000, MAX_TICK]
+    sqrt_ratios = [get_sqrt_ratio_at_tick(tick) for tick in ticks] + [Q96 + 1, MAX_UINT160]
+    liquidities = [0, 1, 10**6, 10**18, 2**64, 2**96 + 12345, MAX_UINT128]
+
+    rows = []
+    for sqrt_ratio_a in sqrt_ratios:
+        for sqrt_ratio_b in sqrt_ratios:
+            for liquidity in liquidities:
+                for round_up in [True, False]:
+                    rows.append(
+                        {
+                            "sqrt_ratio_a_x_96": hex(sqrt_ratio_a),
+                            "sqrt_ratio_b_x_96": hex(sqrt_ratio_b),
+                            "liquidity": liquidity,
+                            "round_up": round_up,
+                            "amount_0": hex(get_amount_0_delta(sqrt_ratio_a, sqrt_ratio_b, liquidity, round_up)),
+                            "amount_1": hex(get_amount_1_delta(sqrt_ratio_a, sqrt_ratio_b, liquidity, round_up)),
+                        }
+                    )
+    return rows
+
+
+def swap_step_fixtures(rng):
+    fee_tiers = [0, 100, 500, 3000, 10000]
+
+    rows = []
+    while len(rows) < 3000:
+        sqrt_ratio_current = random_sqrt_ratio(rng)
+        if rng.random() < 0.5:
+            # a target a few ticks away, like a step towards the next initialized tick
+            tick = get_tick_at_sqrt_ratio(sqrt_ratio_current) + rng.randint(-2000, 2000)
+            sqrt_ratio_target = get_sqrt_ratio_at_tick(max(MIN_TICK, min(MAX_TICK - 1, tick)))
+        else:
+            sqrt_ratio_target = random_sqrt_ratio(rng)
+        liquidity = log_uniform(rng, 128)
+        amount_remaining = log_uniform(rng, 255)
+        if rng.random() < 0.5:
+            amount_remaining = -amount_remaining
+        fee_pips = rng.choice(fee_tiers) if rng.random() < 0.8 else rng.randrange(10**6)
+
+        try:
+            sqrt_ratio_next, amount_in, amount_out, fee_amount = compute_swap_step(
+                sqrt_ratio_current, sqrt_ratio_target, liquidity, amount_remaining, fee_pips
+            )
+        except Revert:
+            continue
+
+        rows.append(
+            {
+                "sqrt_ratio_current_x_96": hex(sqrt_ratio_current),
+                "sqrt_ratio_target_x_96": hex(sqrt_ratio_target),
+                "liquidity": liquidity,
+                "amount_remaining": hex(amount_remaining),
+                "fee_pips": fee_pips,
+                "sqrt_ratio_next_x_96": hex(sqrt_ratio_next),
+                "amount_in": hex(amount_in),
+                "amount_out": hex(amount_out),
+                "fee_amount": hex(fee_amount),
+            }
+        )
+    return rows
+
+
+def write(name, rows):
+    # one row per line keeps diffs of regenerated fixtures readable
+    lines = ",\n".join("  " + json.dumps(row, separators=(", ", ": ")) for row in rows)
+    (FIXTURES / name).write_text("[\n" + lines + "\n]\n")
+
+
+def main():
+    rng = random.Random(0x756E6973776170)
+    FIXTURES.mkdir(parents=True, exist_ok=True)
+
+    write("get_sqrt_ratio_at_tick.json", tick_fixtures())
+    write("get_tick_at_sqrt_ratio.json", sqrt_ratio_fixtures(rng))
+    write("amount_deltas.json", amount_delta_fixtures())
+    write("compute_swap_step.json", swap_step_fixtures(rng))
+
+
+if __name__ == "__main__":
+    main()
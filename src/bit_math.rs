@@ -1,27 +1,106 @@
 use crate::error::UniswapV3MathError;
-use alloy::primitives::U256;
+use alloy_primitives::U256;
 
+// index of the highest set bit, ruint counts the leading zeros limb by limb with u64::leading_zeros instead of
+// the binary search of BitMath.sol
 pub fn most_significant_bit(x: U256) -> Result<u8, UniswapV3MathError> {
     if x.is_zero() {
-        return Err(UniswapV3MathError::ZeroValue);
+        return Err(UniswapV3MathError::BitMathZeroInput);
     }
     Ok(255 - x.leading_zeros() as u8)
 }
 
+// index of the lowest set bit, counted limb by limb with u64::trailing_zeros
 pub fn least_significant_bit(x: U256) -> Result<u8, UniswapV3MathError> {
     if x.is_zero() {
-        return Err(UniswapV3MathError::ZeroValue);
+        return Err(UniswapV3MathError::BitMathZeroInput);
+    }
+    Ok(x.trailing_zeros() as u8)
+}
+
+// number of set bits
+pub fn population_count(x: U256) -> u32 {
+    x.count_ones() as u32
+}
+
+// index of the highest set bit at or below bit, None if all of those bits are clear
+pub fn next_set_bit_lte(word: U256, bit: u8) -> Option<u8> {
+    most_significant_bit(word & (U256::MAX >> (255 - bit))).ok()
+}
+
+// index of the lowest set bit at or above bit, None if all of those bits are clear
+pub fn next_set_bit_gte(word: U256, bit: u8) -> Option<u8> {
+    least_significant_bit(word & (U256::MAX << bit)).ok()
+}
+
+// most_significant_bit for values that fit into a u128
+pub fn most_significant_bit_u128(x: u128) -> Result<u8, UniswapV3MathError> {
+    if x == 0 {
+        return Err(UniswapV3MathError::BitMathZeroInput);
+    }
+    Ok(127 - x.leading_zeros() as u8)
+}
+
+// least_significant_bit for values that fit into a u128
+pub fn least_significant_bit_u128(x: u128) -> Result<u8, UniswapV3MathError> {
+    if x == 0 {
+        return Err(UniswapV3MathError::BitMathZeroInput);
+    }
+    Ok(x.trailing_zeros() as u8)
+}
+
+// most_significant_bit for values that fit into a u64
+pub fn most_significant_bit_u64(x: u64) -> Result<u8, UniswapV3MathError> {
+    if x == 0 {
+        return Err(UniswapV3MathError::BitMathZeroInput);
+    }
+    Ok(63 - x.leading_zeros() as u8)
+}
+
+// least_significant_bit for values that fit into a u64
+pub fn least_significant_bit_u64(x: u64) -> Result<u8, UniswapV3MathError> {
+    if x == 0 {
+        return Err(UniswapV3MathError::BitMathZeroInput);
     }
     Ok(x.trailing_zeros() as u8)
 }
 
 #[cfg(test)]
 mod test {
-    use super::most_significant_bit;
+    use super::{
+        least_significant_bit_u128, least_significant_bit_u64, most_significant_bit,
+        most_significant_bit_u128, most_significant_bit_u64, next_set_bit_gte, next_set_bit_lte,
+        population_count,
+    };
     use crate::{bit_math::least_significant_bit, U256_1};
-    use alloy::primitives::U256;
+    use alloy_primitives::{keccak256, U256};
+    use proptest::prelude::*;
     use std::str::FromStr;
 
+    // the BitMath.sol binary search, kept as the reference for the limb based implementation
+    fn most_significant_bit_reference(mut x: U256) -> u8 {
+        let mut r = 0;
+        for shift in [128_usize, 64, 32, 16, 8, 4, 2, 1] {
+            if x >= U256_1 << shift {
+                x >>= shift;
+                r += shift;
+            }
+        }
+        r as u8
+    }
+
+    fn least_significant_bit_reference(mut x: U256) -> u8 {
+        let mut r = 255;
+        for shift in [128_usize, 64, 32, 16, 8, 4, 2, 1] {
+            if !(x & ((U256_1 << shift) - U256_1)).is_zero() {
+                r -= shift;
+            } else {
+                x >>= shift;
+            }
+        }
+        r as u8
+    }
+
     #[test]
     fn test_most_significant_bit() {
         //0
@@ -89,4 +168,152 @@ mod test {
         );
         assert_eq!(result.unwrap(), 0);
     }
+
+    #[test]
+    fn test_bit_math_random_values() {
+        // pseudo random values derived from hashing the iteration index
+        for i in 0..1000_u32 {
+            let x = U256::from_be_bytes(keccak256(i.to_be_bytes()).0) >> (i % 256);
+            if x.is_zero() {
+                continue;
+            }
+
+            assert_eq!(
+                most_significant_bit(x).unwrap(),
+                (255 - x.leading_zeros()) as u8
+            );
+            assert_eq!(least_significant_bit(x).unwrap(), x.trailing_zeros() as u8);
+        }
+    }
+
+    #[test]
+    fn test_bit_math_single_bits() {
+        for i in 0..256 {
+            let x = U256_1 << i;
+            assert_eq!(most_significant_bit(x).unwrap(), i as u8);
+            assert_eq!(least_significant_bit(x).unwrap(), i as u8);
+            assert_eq!(most_significant_bit_reference(x), i as u8);
+            assert_eq!(least_significant_bit_reference(x), i as u8);
+        }
+    }
+
+    #[test]
+    fn test_population_count() {
+        assert_eq!(population_count(U256::ZERO), 0);
+        assert_eq!(population_count(U256::MAX), 256);
+        assert_eq!(
+            population_count((U256_1 << 255) | (U256_1 << 128) | U256_1),
+            3
+        );
+        for i in 0..256 {
+            assert_eq!(population_count(U256_1 << i), 1);
+            assert_eq!(population_count(U256::MAX >> i), 256 - i as u32);
+        }
+    }
+
+    #[test]
+    fn test_next_set_bit() {
+        let boundaries = [0_u8, 127, 128, 255];
+
+        //a word with only the boundary bits set
+        let word = boundaries
+            .iter()
+            .fold(U256::ZERO, |word, &bit| word | (U256_1 << bit));
+        for bit in 0..=255_u8 {
+            let lte = boundaries.iter().rev().find(|&&b| b <= bit).copied();
+            let gte = boundaries.iter().find(|&&b| b >= bit).copied();
+            assert_eq!(next_set_bit_lte(word, bit), lte, "{bit}");
+            assert_eq!(next_set_bit_gte(word, bit), gte, "{bit}");
+        }
+
+        for set in boundaries {
+            let word = U256_1 << set;
+            for bit in boundaries {
+                assert_eq!(
+                    next_set_bit_lte(word, bit),
+                    (set <= bit).then_some(set),
+                    "{set} {bit}"
+                );
+                assert_eq!(
+                    next_set_bit_gte(word, bit),
+                    (set >= bit).then_some(set),
+                    "{set} {bit}"
+                );
+            }
+        }
+
+        for bit in boundaries {
+            assert_eq!(next_set_bit_lte(U256::ZERO, bit), None);
+            assert_eq!(next_set_bit_gte(U256::ZERO, bit), None);
+            assert_eq!(next_set_bit_lte(U256::MAX, bit), Some(bit));
+            assert_eq!(next_set_bit_gte(U256::MAX, bit), Some(bit));
+        }
+
+        //bits on the other side of bit are masked out
+        assert_eq!(next_set_bit_lte(U256_1 << 128, 127), None);
+        assert_eq!(next_set_bit_gte(U256_1 << 127, 128), None);
+        assert_eq!(next_set_bit_lte(U256::MAX << 1, 0), None);
+        assert_eq!(next_set_bit_gte(U256::MAX >> 1, 255), None);
+    }
+
+    proptest! {
+        #[test]
+        fn test_bit_math_matches_reference(limbs in any::<[u64; 4]>(), shift in 0..256_usize) {
+            let x = U256::from_limbs(limbs) >> shift;
+            prop_assume!(!x.is_zero());
+
+            prop_assert_eq!(most_significant_bit(x).unwrap(), most_significant_bit_reference(x));
+            prop_assert_eq!(least_significant_bit(x).unwrap(), least_significant_bit_reference(x));
+        }
+    }
+
+    #[test]
+    fn test_bit_math_u128_and_u64() {
+        for x in [
+            most_significant_bit_u128(0),
+            least_significant_bit_u128(0),
+            most_significant_bit_u64(0),
+            least_significant_bit_u64(0),
+        ] {
+            assert_eq!(
+                x.unwrap_err().to_string(),
+                "Can not get most significant bit or least significant bit on zero value"
+            );
+        }
+
+        //all powers of 2
+        for i in 0..128 {
+            assert_eq!(most_significant_bit_u128(1 << i).unwrap(), i as u8);
+            assert_eq!(least_significant_bit_u128(1 << i).unwrap(), i as u8);
+        }
+        for i in 0..64 {
+            assert_eq!(most_significant_bit_u64(1 << i).unwrap(), i as u8);
+            assert_eq!(least_significant_bit_u64(1 << i).unwrap(), i as u8);
+        }
+
+        assert_eq!(most_significant_bit_u128(u128::MAX).unwrap(), 127);
+        assert_eq!(least_significant_bit_u128(u128::MAX).unwrap(), 0);
+        assert_eq!(most_significant_bit_u64(u64::MAX).unwrap(), 63);
+        assert_eq!(least_significant_bit_u64(u64::MAX).unwrap(), 0);
+    }
+
+    proptest! {
+        #[test]
+        fn test_bit_math_u128_matches_u256(x in any::<u128>(), shift in 0..128_u32) {
+            let x = x >> shift;
+            prop_assume!(x != 0);
+
+            prop_assert_eq!(most_significant_bit_u128(x).unwrap(), most_significant_bit(U256::from(x)).unwrap());
+            prop_assert_eq!(least_significant_bit_u128(x).unwrap(), least_significant_bit(U256::from(x)).unwrap());
+        }
+
+        #[test]
+        fn test_bit_math_u64_matches_u256(x in any::<u64>(), shift in 0..64_u32) {
+            let x = x >> shift;
+            prop_assume!(x != 0);
+
+            prop_assert_eq!(most_significant_bit_u64(x).unwrap(), most_significant_bit(U256::from(x)).unwrap());
+            prop_assert_eq!(least_significant_bit_u64(x).unwrap(), least_significant_bit(U256::from(x)).unwrap());
+        }
+    }
 }
@@ -1,44 +1,184 @@
-use alloy::primitives::ruint::ParseError;
+use alloc::string::{String, ToString};
+
+use alloy_primitives::{ruint::ParseError, U256};
 use thiserror::Error;
 
-// TODO: make these errors better, some errors in univ3 libs are just require(condition) without a message.
+// Display messages mirror the revert reasons of the univ3 libs, the offending values are carried by each variant.
 #[derive(Error, Debug)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[non_exhaustive]
 pub enum UniswapV3MathError {
     #[error("Denominator is 0")]
     DenominatorIsZero,
     #[error("Result is U256::MAX")]
     ResultIsU256MAX,
+    #[error("Result does not fit into I256")]
+    ResultOverflowsI256,
     #[error("Sqrt price is 0")]
     SqrtPriceIsZero,
     #[error("Sqrt price is less than or equal to quotient")]
-    SqrtPriceIsLteQuotient,
+    SqrtPriceIsLteQuotient {
+        sqrt_price_x_96: U256,
+        quotient: U256,
+    },
     #[error("Can not get most significant bit or least significant bit on zero value")]
-    ZeroValue,
+    BitMathZeroInput,
     #[error("Liquidity is 0")]
     LiquidityIsZero,
-    //TODO: Update this, shield your eyes for now
     #[error(
         "require((product = amount * sqrtPX96) / amount == sqrtPX96 && numerator1 > product);"
     )]
-    ProductDivAmount,
+    ProductDivAmount {
+        sqrt_price_x_96: U256,
+        liquidity: u128,
+        amount: U256,
+    },
     #[error("Denominator is less than or equal to prod_1")]
-    DenominatorIsLteProdOne,
+    DenominatorIsLteProdOne { denominator: U256, prod_1: U256 },
     #[error("Liquidity Sub")]
-    LiquiditySub,
+    LiquidityUnderflow { liquidity: u128, delta: i128 },
     #[error("Liquidity Add")]
-    LiquidityAdd,
+    LiquidityOverflow { liquidity: u128, delta: i128 },
+    #[error("Liquidity gross exceeds the max liquidity per tick")]
+    TickLiquidityOverflow {
+        liquidity_gross: u128,
+        max_liquidity: u128,
+    },
+    #[error("Liquidity net does not fit into int128")]
+    LiquidityNetOverflow { liquidity_net: i128, delta: i128 },
+    #[error("Tokens owed overflow uint128")]
+    TokensOwedOverflow {
+        tokens_owed: u128,
+        fee_growth_delta_x_128: U256,
+    },
+    #[error("Liquidity delta at index {index} over or underflows")]
+    LiquidityDeltaFailed {
+        index: usize,
+        liquidity: u128,
+        delta: i128,
+    },
     #[error("The given tick must be less than, or equal to, the maximum tick")]
-    T,
+    TickOutOfBounds { tick: i32 },
     #[error(
         "Second inequality must be < because the price can never reach the price at the max tick"
     )]
-    R,
+    SqrtPriceOutOfBounds { sqrt_price_x_96: U256 },
     #[error("Overflow when casting to U160")]
-    SafeCastToU160Overflow,
-    #[error("Tick spacing error")]
-    TickSpacingError,
+    SafeCastToU160Overflow { value: U256 },
+    #[error("Overflow when casting to I128")]
+    SafeCastToI128Overflow { value: u128 },
+    #[error("Overflow when casting to U128")]
+    SafeCastToU128Overflow { value: U256 },
+    #[error("Price must be positive and finite")]
+    InvalidPrice { price: f64 },
+    #[error("Decimal is not exactly representable with {resolution} fractional bits")]
+    InexactDecimal { resolution: usize },
+    #[error("Decimal is negative or out of the range of the fixed point value")]
+    DecimalOutOfRange,
+    #[error("At least one significant digit is required")]
+    InvalidSignificantDigits { significant_digits: usize },
+    #[error("Tick is out of bounds or not a multiple of the tick spacing")]
+    InvalidTick { tick: i32, tick_spacing: i32 },
+    #[error("Tick spacing must be positive, and below 16384 for a fee tier")]
+    InvalidTickSpacing { tick_spacing: i32 },
+    #[error("Lower tick must be less than upper tick")]
+    InvalidTickRange { tick_lower: i32, tick_upper: i32 },
+    #[error("Unknown fee tier")]
+    UnknownFeeTier { fee: u32 },
+    #[error("Fee pips must not exceed 1e6, or be 1e6 for an exact output swap")]
+    InvalidFeePips { fee_pips: u32 },
+    #[error("Protocol fee must be 0 or between 4 and 10")]
+    InvalidFeeProtocol { fee_protocol: u8 },
+    #[error("Flash loan fee does not fit into uint128")]
+    FeeOverflow { fee: U256 },
+    #[error("Amount specified is 0")]
+    AmountSpecifiedIsZero,
+    #[error("Sqrt price target is on the wrong side of the current sqrt price")]
+    InvalidSqrtPriceTarget {
+        sqrt_price_x_96: U256,
+        sqrt_price_target_x_96: U256,
+    },
+    #[error("Not enough liquidity to receive the requested amount out")]
+    InsufficientLiquidity {
+        amount_out: U256,
+        amount_out_received: U256,
+    },
+    #[error("Path must hold one more token than there are pools, with at least one pool")]
+    InvalidPathLength { path_length: usize, pools: usize },
+    #[error("Position holds less liquidity than the amount to burn")]
+    InsufficientPositionLiquidity { liquidity: u128, amount: u128 },
+    #[error(
+        "Observation cardinality must be greater than 0 and at most the number of observations"
+    )]
+    InvalidObservationCardinality { cardinality: u16 },
+    #[error("Target is older than the oldest observation")]
+    ObservationTooOld { target: u32, oldest: u32 },
+    #[error("Observation cardinality is too low to cover the requested period")]
+    ObservationCardinalityTooLow { cardinality: u16, seconds_ago: u32 },
+    #[error("Sqrt price limit is out of bounds")]
+    SqrtPriceLimitOutOfBounds {
+        sqrt_price_x_96: U256,
+        sqrt_price_limit_x_96: U256,
+    },
+    #[error("Storage word must be at most 32 bytes")]
+    InvalidStorageWord { length: usize },
     #[error("Middleware error when getting next_initialized_tick_within_one_word")]
     MiddlewareError(String),
-    #[error("Parse error")]
-    ParseError(#[from] ParseError),
+    #[error("Parse error: {0}")]
+    ParseError(String),
+}
+
+// the parse error is kept as its message so that the enum stays serializable and does not depend on the ruint type
+impl From<ParseError> for UniswapV3MathError {
+    fn from(error: ParseError) -> Self {
+        Self::ParseError(error.to_string())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_error_serde() -> eyre::Result<()> {
+        use super::UniswapV3MathError;
+        use alloy_primitives::U256;
+
+        let errors = [
+            UniswapV3MathError::DenominatorIsZero,
+            UniswapV3MathError::TickOutOfBounds { tick: 887273 },
+            UniswapV3MathError::SqrtPriceOutOfBounds {
+                sqrt_price_x_96: U256::MAX,
+            },
+            UniswapV3MathError::LiquidityUnderflow {
+                liquidity: 3,
+                delta: -4,
+            },
+            UniswapV3MathError::MiddlewareError("timeout".to_string()),
+            UniswapV3MathError::from(alloy_primitives::ruint::ParseError::InvalidRadix(1)),
+        ];
+
+        for error in errors {
+            let json = serde_json::to_string(&error)?;
+            let decoded = serde_json::from_str::<UniswapV3MathError>(&json)?;
+            assert_eq!(format!("{decoded:?}"), format!("{error:?}"));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_parse_error() {
+        use super::UniswapV3MathError;
+        use alloy_primitives::U256;
+        use std::str::FromStr;
+
+        let error = UniswapV3MathError::from(U256::from_str("0xzz").unwrap_err());
+        assert!(matches!(error, UniswapV3MathError::ParseError(_)));
+        assert_eq!(
+            error.to_string(),
+            "Parse error: digit 35 is out of range for base 16"
+        );
+
+        // usable as a boxed std error
+        let _: Box<dyn std::error::Error> = Box::new(error);
+    }
 }
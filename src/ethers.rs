@@ -0,0 +1,426 @@
+//Entry points taking and returning primitive_types::U256, the U256 of ethers-rs. Values are converted limb by
+//limb, both types store four little endian u64 limbs. Signed values are passed as their two's complement, as
+//ethers' I256::from_raw and I256::into_raw expect
+use alloy_primitives::{I256, U256};
+
+use crate::fixed_point::SqrtPriceX96;
+
+//Wrapper converting between the alloy and primitive-types U256
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct EthersU256(pub primitive_types::U256);
+
+impl From<primitive_types::U256> for EthersU256 {
+    fn from(value: primitive_types::U256) -> Self {
+        Self(value)
+    }
+}
+
+impl From<EthersU256> for primitive_types::U256 {
+    fn from(value: EthersU256) -> Self {
+        value.0
+    }
+}
+
+impl From<U256> for EthersU256 {
+    fn from(value: U256) -> Self {
+        Self(primitive_types::U256(value.into_limbs()))
+    }
+}
+
+impl From<EthersU256> for U256 {
+    fn from(value: EthersU256) -> Self {
+        U256::from_limbs(value.0 .0)
+    }
+}
+
+impl From<SqrtPriceX96> for EthersU256 {
+    fn from(value: SqrtPriceX96) -> Self {
+        value.0.into()
+    }
+}
+
+impl From<I256> for EthersU256 {
+    fn from(value: I256) -> Self {
+        value.into_raw().into()
+    }
+}
+
+impl From<EthersU256> for I256 {
+    fn from(value: EthersU256) -> Self {
+        I256::from_raw(value.into())
+    }
+}
+
+//Converts into an alloy U256 or one of the fixed point wrappers over it
+fn to_alloy<T: From<U256>>(value: primitive_types::U256) -> T {
+    let value: U256 = EthersU256(value).into();
+    value.into()
+}
+
+fn to_ethers<T: Into<EthersU256>>(value: T) -> primitive_types::U256 {
+    value.into().0
+}
+
+pub mod tick_math {
+    use primitive_types::U256;
+
+    use super::{to_alloy, to_ethers};
+    use crate::{error::UniswapV3MathError, tick_math};
+
+    // returns (uint160 sqrtPriceX96)
+    pub fn get_sqrt_ratio_at_tick(tick: i32) -> Result<U256, UniswapV3MathError> {
+        tick_math::get_sqrt_ratio_at_tick(tick).map(to_ethers)
+    }
+
+    // returns (int24 tick)
+    pub fn get_tick_at_sqrt_ratio(sqrt_price_x_96: U256) -> Result<i32, UniswapV3MathError> {
+        tick_math::get_tick_at_sqrt_ratio(to_alloy(sqrt_price_x_96))
+    }
+}
+
+pub mod sqrt_price_math {
+    use primitive_types::U256;
+
+    use super::{to_alloy, to_ethers};
+    use crate::{error::UniswapV3MathError, sqrt_price_math};
+
+    // returns (sqrtQX96)
+    pub fn get_next_sqrt_price_from_input(
+        sqrt_price: U256,
+        liquidity: u128,
+        amount_in: U256,
+        zero_for_one: bool,
+    ) -> Result<U256, UniswapV3MathError> {
+        sqrt_price_math::get_next_sqrt_price_from_input(
+            to_alloy(sqrt_price),
+            liquidity,
+            to_alloy(amount_in),
+            zero_for_one,
+        )
+        .map(to_ethers)
+    }
+
+    // returns (sqrtQX96)
+    pub fn get_next_sqrt_price_from_output(
+        sqrt_price: U256,
+        liquidity: u128,
+        amount_out: U256,
+        zero_for_one: bool,
+    ) -> Result<U256, UniswapV3MathError> {
+        sqrt_price_math::get_next_sqrt_price_from_output(
+            to_alloy(sqrt_price),
+            liquidity,
+            to_alloy(amount_out),
+            zero_for_one,
+        )
+        .map(to_ethers)
+    }
+
+    // returns (uint256 amount0)
+    pub fn _get_amount_0_delta(
+        sqrt_ratio_a_x_96: U256,
+        sqrt_ratio_b_x_96: U256,
+        liquidity: u128,
+        round_up: bool,
+    ) -> Result<U256, UniswapV3MathError> {
+        sqrt_price_math::_get_amount_0_delta(
+            to_alloy(sqrt_ratio_a_x_96),
+            to_alloy(sqrt_ratio_b_x_96),
+            liquidity,
+            round_up,
+        )
+        .map(to_ethers)
+    }
+
+    // returns (uint256 amount1)
+    pub fn _get_amount_1_delta(
+        sqrt_ratio_a_x_96: U256,
+        sqrt_ratio_b_x_96: U256,
+        liquidity: u128,
+        round_up: bool,
+    ) -> Result<U256, UniswapV3MathError> {
+        sqrt_price_math::_get_amount_1_delta(
+            to_alloy(sqrt_ratio_a_x_96),
+            to_alloy(sqrt_ratio_b_x_96),
+            liquidity,
+            round_up,
+        )
+        .map(to_ethers)
+    }
+
+    // returns (int256 amount0) as its two's complement
+    pub fn get_amount_0_delta(
+        sqrt_ratio_a_x_96: U256,
+        sqrt_ratio_b_x_96: U256,
+        liquidity: i128,
+    ) -> Result<U256, UniswapV3MathError> {
+        sqrt_price_math::get_amount_0_delta(
+            to_alloy(sqrt_ratio_a_x_96),
+            to_alloy(sqrt_ratio_b_x_96),
+            liquidity,
+        )
+        .map(to_ethers)
+    }
+
+    // returns (int256 amount1) as its two's complement
+    pub fn get_amount_1_delta(
+        sqrt_ratio_a_x_96: U256,
+        sqrt_ratio_b_x_96: U256,
+        liquidity: i128,
+    ) -> Result<U256, UniswapV3MathError> {
+        sqrt_price_math::get_amount_1_delta(
+            to_alloy(sqrt_ratio_a_x_96),
+            to_alloy(sqrt_ratio_b_x_96),
+            liquidity,
+        )
+        .map(to_ethers)
+    }
+}
+
+pub mod swap_math {
+    use alloy_primitives::I256;
+    use primitive_types::U256;
+
+    use super::{to_alloy, to_ethers};
+    use crate::{error::UniswapV3MathError, swap_math};
+
+    // returns (uint160 sqrtRatioNextX96, uint256 amountIn, uint256 amountOut, uint256 feeAmount), amount_remaining
+    // is the two's complement of the signed amount
+    pub fn compute_swap_step(
+        sqrt_ratio_current_x_96: U256,
+        sqrt_ratio_target_x_96: U256,
+        liquidity: u128,
+        amount_remaining: U256,
+        fee_pips: u32,
+    ) -> Result<(U256, U256, U256, U256), UniswapV3MathError> {
+        let (sqrt_ratio_next_x_96, amount_in, amount_out, fee_amount) =
+            swap_math::compute_swap_step(
+                to_alloy(sqrt_ratio_current_x_96),
+                to_alloy(sqrt_ratio_target_x_96),
+                liquidity,
+                I256::from_raw(to_alloy(amount_remaining)),
+                fee_pips,
+            )?;
+
+        Ok((
+            to_ethers(sqrt_ratio_next_x_96),
+            to_ethers(amount_in),
+            to_ethers(amount_out),
+            to_ethers(fee_amount),
+        ))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloy_primitives::{I256, U256};
+
+    use super::{sqrt_price_math, swap_math, tick_math, EthersU256};
+    use crate::{
+        fixed_point::SqrtPriceX96,
+        sqrt_price_math as alloy_sqrt_price_math, swap_math as alloy_swap_math,
+        tick_math::{self as alloy_tick_math, MAX_SQRT_RATIO, MAX_TICK, MIN_SQRT_RATIO, MIN_TICK},
+    };
+
+    fn ethers(value: U256) -> primitive_types::U256 {
+        EthersU256::from(value).0
+    }
+
+    #[test]
+    fn test_conversion_round_trip() {
+        for value in [
+            U256::ZERO,
+            U256::from(1),
+            MIN_SQRT_RATIO,
+            MAX_SQRT_RATIO,
+            U256::from(u128::MAX) << 64,
+            U256::MAX,
+        ] {
+            let converted = ethers(value);
+            assert_eq!(converted.to_string(), value.to_string());
+            let round_trip: U256 = EthersU256::from(converted).into();
+            assert_eq!(round_trip, value);
+        }
+
+        assert_eq!(ethers(U256::MAX), primitive_types::U256::MAX);
+        assert_eq!(
+            primitive_types::U256::from(EthersU256::from(I256::MINUS_ONE)),
+            primitive_types::U256::MAX
+        );
+        assert_eq!(
+            I256::from(EthersU256(primitive_types::U256::one() << 255)),
+            I256::MIN
+        );
+    }
+
+    #[test]
+    fn test_tick_math_matches_alloy() {
+        for tick in [MIN_TICK, -50000, -1, 0, 1, 50000, MAX_TICK] {
+            let sqrt_price_x_96 = tick_math::get_sqrt_ratio_at_tick(tick).unwrap();
+            assert_eq!(
+                sqrt_price_x_96,
+                ethers(*alloy_tick_math::get_sqrt_ratio_at_tick(tick).unwrap())
+            );
+        }
+        assert_eq!(
+            tick_math::get_sqrt_ratio_at_tick(MAX_TICK).unwrap(),
+            ethers(MAX_SQRT_RATIO)
+        );
+
+        for sqrt_price_x_96 in [
+            MIN_SQRT_RATIO,
+            U256::from(1) << 96,
+            MAX_SQRT_RATIO - U256::from(1),
+        ] {
+            assert_eq!(
+                tick_math::get_tick_at_sqrt_ratio(ethers(sqrt_price_x_96)).unwrap(),
+                alloy_tick_math::get_tick_at_sqrt_ratio(SqrtPriceX96(sqrt_price_x_96)).unwrap()
+            );
+        }
+        assert!(tick_math::get_tick_at_sqrt_ratio(ethers(MAX_SQRT_RATIO)).is_err());
+        assert!(tick_math::get_tick_at_sqrt_ratio(primitive_types::U256::MAX).is_err());
+    }
+
+    #[test]
+    fn test_sqrt_price_math_matches_alloy() {
+        let price = SqrtPriceX96(U256::from(1) << 96);
+        let target = alloy_tick_math::get_sqrt_ratio_at_tick(-600).unwrap();
+        let liquidity = 10_u128.pow(21);
+        let amount = U256::from(10_u128.pow(18));
+
+        for zero_for_one in [true, false] {
+            assert_eq!(
+                sqrt_price_math::get_next_sqrt_price_from_input(
+                    ethers(*price),
+                    liquidity,
+                    ethers(amount),
+                    zero_for_one
+                )
+                .unwrap(),
+                ethers(
+                    *alloy_sqrt_price_math::get_next_sqrt_price_from_input(
+                        price,
+                        liquidity,
+                        amount,
+                        zero_for_one
+                    )
+                    .unwrap()
+                )
+            );
+            assert_eq!(
+                sqrt_price_math::get_next_sqrt_price_from_output(
+                    ethers(*price),
+                    liquidity,
+                    ethers(amount),
+                    zero_for_one
+                )
+                .unwrap(),
+                ethers(
+                    *alloy_sqrt_price_math::get_next_sqrt_price_from_output(
+                        price,
+                        liquidity,
+                        amount,
+                        zero_for_one
+                    )
+                    .unwrap()
+                )
+            );
+        }
+
+        for round_up in [true, false] {
+            assert_eq!(
+                sqrt_price_math::_get_amount_0_delta(
+                    ethers(*price),
+                    ethers(*target),
+                    liquidity,
+                    round_up
+                )
+                .unwrap(),
+                ethers(
+                    alloy_sqrt_price_math::_get_amount_0_delta(price, target, liquidity, round_up)
+                        .unwrap()
+                )
+            );
+            assert_eq!(
+                sqrt_price_math::_get_amount_1_delta(
+                    ethers(*price),
+                    ethers(*target),
+                    liquidity,
+                    round_up
+                )
+                .unwrap(),
+                ethers(
+                    alloy_sqrt_price_math::_get_amount_1_delta(price, target, liquidity, round_up)
+                        .unwrap()
+                )
+            );
+        }
+
+        // negative deltas come back as their two's complement
+        for liquidity in [liquidity as i128, -(liquidity as i128)] {
+            let amount_0 =
+                alloy_sqrt_price_math::get_amount_0_delta(price, target, liquidity).unwrap();
+            let amount_1 =
+                alloy_sqrt_price_math::get_amount_1_delta(price, target, liquidity).unwrap();
+            assert_eq!(
+                sqrt_price_math::get_amount_0_delta(ethers(*price), ethers(*target), liquidity)
+                    .unwrap(),
+                ethers(amount_0.into_raw())
+            );
+            assert_eq!(
+                I256::from(EthersU256(
+                    sqrt_price_math::get_amount_1_delta(ethers(*price), ethers(*target), liquidity)
+                        .unwrap()
+                )),
+                amount_1
+            );
+        }
+
+        assert!(sqrt_price_math::get_next_sqrt_price_from_input(
+            primitive_types::U256::zero(),
+            liquidity,
+            ethers(amount),
+            true
+        )
+        .is_err());
+    }
+
+    #[test]
+    fn test_swap_math_matches_alloy() {
+        let price = SqrtPriceX96(U256::from(1) << 96);
+        let target = alloy_tick_math::get_sqrt_ratio_at_tick(-600).unwrap();
+        let liquidity = 10_u128.pow(21);
+
+        for amount_remaining in [
+            I256::try_from(10_u128.pow(18)).unwrap(),
+            I256::try_from(-(10_i128.pow(18))).unwrap(),
+            I256::MAX,
+        ] {
+            let (sqrt_ratio_next_x_96, amount_in, amount_out, fee_amount) =
+                alloy_swap_math::compute_swap_step(
+                    price,
+                    target,
+                    liquidity,
+                    amount_remaining,
+                    3000,
+                )
+                .unwrap();
+            assert_eq!(
+                swap_math::compute_swap_step(
+                    ethers(*price),
+                    ethers(*target),
+                    liquidity,
+                    EthersU256::from(amount_remaining).0,
+                    3000
+                )
+                .unwrap(),
+                (
+                    ethers(*sqrt_ratio_next_x_96),
+                    ethers(amount_in),
+                    ethers(amount_out),
+                    ethers(fee_amount)
+                )
+            );
+        }
+    }
+}
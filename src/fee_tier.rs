@@ -0,0 +1,209 @@
+use crate::{
+    error::UniswapV3MathError, swap_math::MAX_SWAP_FEE,
+    tick_math::tick_spacing_to_max_liquidity_per_tick,
+};
+
+//Exclusive upper bound of the tick spacing of a fee amount enabled in UniswapV3Factory.enableFeeAmount
+pub const MAX_FACTORY_TICK_SPACING: i32 = 16384;
+
+//Fee tiers enabled by the Uniswap V3 factory, fees are in hundredths of a bip (pips)
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum FeeTier {
+    //0.01%, tick spacing 1
+    HundredthBip,
+    //0.05%, tick spacing 10
+    LowVolatility,
+    //0.3%, tick spacing 60
+    Standard,
+    //1%, tick spacing 200
+    HighVolatility,
+    //Any other fee / tick spacing pair, e.g. governance enabled V3 tiers or V4 hook pools
+    Custom { fee: u32, tick_spacing: i32 },
+}
+
+impl FeeTier {
+    //Fee tier of a fee and tick spacing pair, one of the default tiers if they match or else a validated Custom tier
+    pub fn new(fee: u32, tick_spacing: i32) -> Result<Self, UniswapV3MathError> {
+        match FeeTier::try_from(fee) {
+            Ok(fee_tier) if fee_tier.tick_spacing() == tick_spacing => Ok(fee_tier),
+            _ => FeeTier::custom(fee, tick_spacing),
+        }
+    }
+
+    //Custom tier checked against the bounds of UniswapV3Factory.enableFeeAmount, fee < 1e6 and
+    //0 < tick_spacing < 16384
+    pub fn custom(fee: u32, tick_spacing: i32) -> Result<Self, UniswapV3MathError> {
+        if fee >= MAX_SWAP_FEE {
+            return Err(UniswapV3MathError::InvalidFeePips { fee_pips: fee });
+        }
+        if tick_spacing <= 0 || tick_spacing >= MAX_FACTORY_TICK_SPACING {
+            return Err(UniswapV3MathError::InvalidTickSpacing { tick_spacing });
+        }
+
+        Ok(FeeTier::Custom { fee, tick_spacing })
+    }
+
+    pub fn tick_spacing(&self) -> i32 {
+        match self {
+            FeeTier::HundredthBip => 1,
+            FeeTier::LowVolatility => 10,
+            FeeTier::Standard => 60,
+            FeeTier::HighVolatility => 200,
+            FeeTier::Custom { tick_spacing, .. } => *tick_spacing,
+        }
+    }
+
+    pub fn fee_pips(&self) -> u32 {
+        match self {
+            FeeTier::HundredthBip => 100,
+            FeeTier::LowVolatility => 500,
+            FeeTier::Standard => 3000,
+            FeeTier::HighVolatility => 10000,
+            FeeTier::Custom { fee, .. } => *fee,
+        }
+    }
+
+    //Tick.tickSpacingToMaxLiquidityPerTick for the tier's tick spacing
+    pub fn max_liquidity_per_tick(&self) -> Result<u128, UniswapV3MathError> {
+        tick_spacing_to_max_liquidity_per_tick(self.tick_spacing())
+    }
+}
+
+//Name of the tier enum in the Uniswap SDKs
+pub type FeeAmount = FeeTier;
+
+//Lets a tier be passed wherever the fee pips are expected, e.g. compute_swap_step(.., fee_tier.into())
+impl From<FeeTier> for u32 {
+    fn from(fee_tier: FeeTier) -> Self {
+        fee_tier.fee_pips()
+    }
+}
+
+impl TryFrom<u32> for FeeTier {
+    type Error = UniswapV3MathError;
+
+    fn try_from(fee: u32) -> Result<Self, Self::Error> {
+        match fee {
+            100 => Ok(FeeTier::HundredthBip),
+            500 => Ok(FeeTier::LowVolatility),
+            3000 => Ok(FeeTier::Standard),
+            10000 => Ok(FeeTier::HighVolatility),
+            _ => Err(UniswapV3MathError::UnknownFeeTier { fee }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::{FeeAmount, FeeTier};
+    use crate::error::UniswapV3MathError;
+
+    #[test]
+    fn test_fee_tier_mappings() -> eyre::Result<()> {
+        for (fee_tier, fee, tick_spacing, max_liquidity_per_tick) in [
+            (
+                FeeTier::HundredthBip,
+                100,
+                1,
+                191757530477355301479181766273477_u128,
+            ),
+            (
+                FeeTier::LowVolatility,
+                500,
+                10,
+                1917569901783203986719870431555990,
+            ),
+            (
+                FeeTier::Standard,
+                3000,
+                60,
+                11505743598341114571880798222544994,
+            ),
+            (
+                FeeTier::HighVolatility,
+                10000,
+                200,
+                38350317471085141830651933667504588,
+            ),
+        ] {
+            assert_eq!(fee_tier.fee_pips(), fee);
+            assert_eq!(fee_tier.tick_spacing(), tick_spacing);
+            assert_eq!(fee_tier.max_liquidity_per_tick()?, max_liquidity_per_tick);
+            assert_eq!(FeeTier::try_from(fee)?, fee_tier);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_fee_tier_custom() -> eyre::Result<()> {
+        let fee_tier = FeeTier::Custom {
+            fee: 2500,
+            tick_spacing: 2302,
+        };
+        assert_eq!(fee_tier.fee_pips(), 2500);
+        assert_eq!(fee_tier.tick_spacing(), 2302);
+        assert_eq!(
+            fee_tier.max_liquidity_per_tick()?,
+            441351967472034323558203122479595605
+        );
+
+        let result = FeeTier::try_from(fee_tier.fee_pips());
+        assert_eq!(result.unwrap_err().to_string(), "Unknown fee tier");
+
+        //the variant can be built without FeeTier::custom, an unchecked spacing is an error rather than a panic
+        for tick_spacing in [0, -60] {
+            assert!(matches!(
+                FeeTier::Custom {
+                    fee: 2500,
+                    tick_spacing
+                }
+                .max_liquidity_per_tick(),
+                Err(UniswapV3MathError::InvalidTickSpacing { .. })
+            ));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_fee_tier_new() -> eyre::Result<()> {
+        assert_eq!(FeeTier::new(3000, 60)?, FeeTier::Standard);
+        assert_eq!(FeeTier::new(100, 1)?, FeeTier::HundredthBip);
+
+        //unknown fees, or known fees with another spacing, parse into custom tiers
+        assert_eq!(
+            FeeTier::new(2500, 50)?,
+            FeeTier::Custom {
+                fee: 2500,
+                tick_spacing: 50
+            }
+        );
+        assert_eq!(
+            FeeTier::new(3000, 1)?,
+            FeeTier::Custom {
+                fee: 3000,
+                tick_spacing: 1
+            }
+        );
+
+        assert!(matches!(
+            FeeTier::new(1_000_000, 60),
+            Err(UniswapV3MathError::InvalidFeePips {
+                fee_pips: 1_000_000
+            })
+        ));
+        for tick_spacing in [0, -60, 16384] {
+            assert!(matches!(
+                FeeTier::custom(2500, tick_spacing),
+                Err(UniswapV3MathError::InvalidTickSpacing { .. })
+            ));
+        }
+        assert!(FeeTier::custom(999_999, 16383).is_ok());
+
+        assert_eq!(u32::from(FeeTier::LowVolatility), 500);
+        assert_eq!(FeeAmount::try_from(3000)?, FeeAmount::Standard);
+        let fee_pips: u32 = FeeTier::new(2500, 50)?.into();
+        assert_eq!(fee_pips, 2500);
+        Ok(())
+    }
+}
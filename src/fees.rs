@@ -0,0 +1,143 @@
+use alloy_primitives::{U256, U512};
+
+use crate::fixed_point::FeeGrowthX128;
+
+// fee_delta * 2^128 / liquidity rounded down, the increase of a fee growth accumulator when fee_delta is paid to
+// liquidity. Wraps like the accumulators instead of reverting when the growth exceeds 256 bits, and is 0 without
+// liquidity as the pool then does not accrue fee growth
+pub fn compute_fee_growth_per_unit_liquidity(fee_delta: U256, liquidity: u128) -> FeeGrowthX128 {
+    if liquidity == 0 {
+        return FeeGrowthX128::default();
+    }
+
+    let fee_growth_x_128: U512 = (U512::from(fee_delta) << 128) / U512::from(liquidity);
+    FeeGrowthX128(fee_growth_x_128.wrapping_to())
+}
+
+// FullMath.mulDiv(feeGrowthInsideX128 - feeGrowthInsideLastX128, liquidity, FixedPoint128.Q128), the fees earned by
+// liquidity since fee_growth_inside_last. The growth delta wraps like in Position.update, the product can not
+// overflow 512 bits and the result always fits into 256 bits. Position.update truncates it to uint128
+pub fn compute_uncollected_fees(
+    fee_growth_inside_x_128: FeeGrowthX128,
+    fee_growth_inside_last_x_128: FeeGrowthX128,
+    liquidity: u128,
+) -> U256 {
+    let fee_growth_delta_x_128 = fee_growth_inside_x_128.wrapping_sub(fee_growth_inside_last_x_128);
+
+    let fees: U512 = (U512::from(fee_growth_delta_x_128.0) * U512::from(liquidity)) >> 128;
+    fees.wrapping_to()
+}
+
+#[cfg(test)]
+mod test {
+    use alloy_primitives::U256;
+
+    use super::{compute_fee_growth_per_unit_liquidity, compute_uncollected_fees};
+    use crate::{
+        fixed_point::{FeeGrowthX128, Q128},
+        swap_math::accumulate_fee_growth,
+    };
+
+    #[test]
+    fn test_compute_fee_growth_per_unit_liquidity() {
+        assert_eq!(
+            compute_fee_growth_per_unit_liquidity(U256::from(1000), 0),
+            U256::ZERO
+        );
+        assert_eq!(
+            compute_fee_growth_per_unit_liquidity(U256::ZERO, 1000),
+            U256::ZERO
+        );
+        assert_eq!(
+            compute_fee_growth_per_unit_liquidity(U256::from(3), 1),
+            U256::from(3) * Q128
+        );
+        //rounds down
+        assert_eq!(
+            compute_fee_growth_per_unit_liquidity(U256::from(1), 3),
+            Q128 / U256::from(3)
+        );
+
+        //matches the fee growth accrued by a swap step while it fits
+        let fee_amount = U256::from(123_456_789_u64);
+        let liquidity = 10_u128.pow(18);
+        assert_eq!(
+            compute_fee_growth_per_unit_liquidity(fee_amount, liquidity),
+            accumulate_fee_growth(FeeGrowthX128(U256::ZERO), fee_amount, liquidity).unwrap()
+        );
+
+        //growth beyond 256 bits wraps instead of reverting like mul_div
+        assert_eq!(
+            compute_fee_growth_per_unit_liquidity(U256::MAX, 1),
+            U256::MAX << 128
+        );
+        assert_eq!(
+            compute_fee_growth_per_unit_liquidity(Q128 + U256::from(5), 1),
+            U256::from(5) << 128
+        );
+    }
+
+    #[test]
+    fn test_compute_uncollected_fees() {
+        let liquidity = 10_u128.pow(18);
+        assert_eq!(
+            compute_uncollected_fees(
+                FeeGrowthX128(U256::from(7) * Q128),
+                FeeGrowthX128(U256::from(2) * Q128),
+                liquidity
+            ),
+            U256::from(5 * liquidity)
+        );
+        assert_eq!(
+            compute_uncollected_fees(FeeGrowthX128(Q128), FeeGrowthX128(Q128), liquidity),
+            U256::ZERO
+        );
+        assert_eq!(
+            compute_uncollected_fees(FeeGrowthX128(Q128), FeeGrowthX128(U256::ZERO), 0),
+            U256::ZERO
+        );
+        //rounds down
+        assert_eq!(
+            compute_uncollected_fees(
+                FeeGrowthX128(Q128 - U256::from(1)),
+                FeeGrowthX128(U256::ZERO),
+                1
+            ),
+            U256::ZERO
+        );
+
+        //fee growth inside wrapped around since the last update, the delta is still 3 * 2^128
+        let last = U256::MAX - Q128 + U256::from(1);
+        let inside = U256::from(2) * Q128;
+        assert_eq!(
+            compute_uncollected_fees(FeeGrowthX128(inside), FeeGrowthX128(last), liquidity),
+            U256::from(3 * liquidity)
+        );
+
+        //fee growth inside below the last value, as get_fee_growth_inside wraps for ranges the price has left
+        assert_eq!(
+            compute_uncollected_fees(FeeGrowthX128(U256::ZERO), FeeGrowthX128(U256::from(1)), 1),
+            U256::MAX >> 128
+        );
+
+        //the largest delta and liquidity still fit into 256 bits, (2^256 - 1) * (2^128 - 1) >> 128
+        assert_eq!(
+            compute_uncollected_fees(
+                FeeGrowthX128(U256::MAX),
+                FeeGrowthX128(U256::ZERO),
+                u128::MAX
+            ),
+            U256::MAX - Q128
+        );
+
+        //accrued fees round trip through the fee growth of the swap that paid them
+        let fee_amount = U256::from(10_u128.pow(15));
+        let growth = compute_fee_growth_per_unit_liquidity(fee_amount, liquidity);
+        let fees = compute_uncollected_fees(
+            FeeGrowthX128(U256::MAX.wrapping_add(*growth)),
+            FeeGrowthX128(U256::MAX),
+            liquidity,
+        );
+        assert!(fees <= fee_amount && fee_amount - fees <= U256::from(1));
+    }
+}
@@ -0,0 +1,661 @@
+use alloc::string::{String, ToString};
+use core::{fmt, ops::Deref, str::FromStr};
+
+use alloy_primitives::{ruint::Uint, U256, U512};
+#[cfg(feature = "decimal")]
+use bigdecimal::{
+    num_bigint::{BigInt, Sign},
+    num_traits::Zero,
+    BigDecimal,
+};
+#[cfg(feature = "decimal")]
+use rust_decimal::Decimal;
+
+use crate::{
+    error::UniswapV3MathError,
+    full_math::{mul_div, mul_div_rounding_up, Rounding},
+};
+
+type U1024 = Uint<1024, 16>;
+
+// FixedPoint96.RESOLUTION, the number of fractional bits of a Q64.96 value
+pub const RESOLUTION: usize = 96;
+pub const Q96: U256 = U256::from_limbs([0, 1 << 32, 0, 0]);
+pub const Q128: U256 = U256::from_limbs([0, 0, 1, 0]);
+// Q96 squared, the scale of a sqrt price squared
+pub const Q192: U256 = U256::from_limbs([0, 0, 0, 1]);
+
+// Floor rounds like Down as all values are unsigned
+fn mul_div_rounded(
+    a: U256,
+    b: U256,
+    denominator: U256,
+    rounding: Rounding,
+) -> Result<U256, UniswapV3MathError> {
+    match rounding {
+        Rounding::Up => mul_div_rounding_up(a, b, denominator),
+        Rounding::Down | Rounding::Floor => mul_div(a, b, denominator),
+    }
+}
+
+// a * b / 2^96, the product of two Q96 values or of a Q96 value and an integer
+pub fn mul_x96(a: U256, b: U256, rounding: Rounding) -> Result<U256, UniswapV3MathError> {
+    mul_div_rounded(a, b, Q96, rounding)
+}
+
+// a * 2^96 / b, the quotient of two values as Q96
+pub fn div_x96(a: U256, b: U256, rounding: Rounding) -> Result<U256, UniswapV3MathError> {
+    mul_div_rounded(a, Q96, b, rounding)
+}
+
+// rescales a Q96 value to Q128, which is exact so there is nothing to round. Errors if the result does not fit
+pub fn x96_to_x128(x: U256) -> Result<U256, UniswapV3MathError> {
+    mul_div(x, Q128, Q96)
+}
+
+// rescales a Q128 value to Q96, dropping 32 fractional bits
+pub fn x128_to_x96(x: U256, rounding: Rounding) -> Result<U256, UniswapV3MathError> {
+    mul_div_rounded(x, Q96, Q128, rounding)
+}
+
+//A Q64.96 sqrt price, as stored in slot0.sqrtPriceX96
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct SqrtPriceX96(pub U256);
+
+//A Q128.128 fee growth accumulator, global, outside or inside a tick range
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, PartialOrd, Ord, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+#[cfg_attr(feature = "serde", serde(transparent))]
+pub struct FeeGrowthX128(pub U256);
+
+// exact decimal expansion of value / 2^resolution. Every such value has at most resolution fractional digits as
+// 10^resolution / 2^resolution = 5^resolution, trailing zeros are dropped
+fn to_decimal_string(value: U256, resolution: usize) -> String {
+    let integer = value >> resolution;
+    let fraction = value - (integer << resolution);
+    if fraction.is_zero() {
+        return integer.to_string();
+    }
+
+    // fraction / 2^resolution = fraction * 5^resolution / 10^resolution, the product fits 512 bits up to 128 bits
+    let digits = (U512::from(fraction) * U512::from(5).pow(U512::from(resolution))).to_string();
+    let padded = alloc::format!("{digits:0>resolution$}");
+    alloc::format!("{integer}.{}", padded.trim_end_matches('0'))
+}
+
+// inverse of to_decimal_string, errors with InexactDecimal if the decimal has no exact representation with
+// resolution fractional bits instead of truncating it
+fn from_decimal_str(decimal: &str, resolution: usize) -> Result<U256, UniswapV3MathError> {
+    let (integer, fraction) = decimal.split_once('.').unwrap_or((decimal, ""));
+    let is_digits = |part: &str| part.bytes().all(|byte| byte.is_ascii_digit());
+    if integer.is_empty() || !is_digits(integer) || !is_digits(fraction) {
+        return Err(UniswapV3MathError::ParseError(alloc::format!(
+            "invalid decimal {decimal}"
+        )));
+    }
+    let fraction = fraction.trim_end_matches('0');
+
+    // after trimming, a decimal with more fractional digits than bits always leaves a factor 5 in the denominator
+    if fraction.len() > resolution {
+        return Err(UniswapV3MathError::InexactDecimal { resolution });
+    }
+    // up to 128 fractional digits shifted by 128 bits need more than 512 bits
+    let scale = U1024::from(10).pow(U1024::from(fraction.len()));
+    let (fraction, remainder) = if fraction.is_empty() {
+        (U1024::ZERO, U1024::ZERO)
+    } else {
+        (U1024::from_str(fraction)? << resolution).div_rem(scale)
+    };
+    if !remainder.is_zero() {
+        return Err(UniswapV3MathError::InexactDecimal { resolution });
+    }
+
+    let value = (U1024::from(U256::from_str(integer)?) << resolution) + fraction;
+    if value.bit_len() > 256 {
+        return Err(UniswapV3MathError::ParseError(alloc::format!(
+            "{decimal} does not fit into 256 bits"
+        )));
+    }
+
+    Ok(value.to())
+}
+
+// value / 2^resolution as an exact BigDecimal with resolution fractional digits, see to_decimal_string
+#[cfg(feature = "decimal")]
+fn to_big_decimal(value: U256, resolution: usize) -> BigDecimal {
+    let digits = U1024::from(value) * U1024::from(5).pow(U1024::from(resolution));
+    BigDecimal::new(
+        BigInt::from_bytes_be(Sign::Plus, &digits.to_be_bytes::<128>()),
+        resolution as i64,
+    )
+}
+
+// inverse of to_big_decimal, errors with InexactDecimal like from_decimal_str instead of truncating and with
+// DecimalOutOfRange for negative values and values that do not fit into 256 bits
+#[cfg(feature = "decimal")]
+fn from_big_decimal(decimal: &BigDecimal, resolution: usize) -> Result<U256, UniswapV3MathError> {
+    let (mut digits, mut scale) = decimal.as_bigint_and_exponent();
+    if digits.is_zero() {
+        return Ok(U256::ZERO);
+    }
+    if digits.sign() == Sign::Minus {
+        return Err(UniswapV3MathError::DecimalOutOfRange);
+    }
+
+    // trailing zeros do not count as fractional digits, past them the value is inexact as in from_decimal_str
+    let ten = BigInt::from(10);
+    while scale > resolution as i64 {
+        if !(&digits % &ten).is_zero() {
+            return Err(UniswapV3MathError::InexactDecimal { resolution });
+        }
+        digits /= &ten;
+        scale -= 1;
+    }
+    // 10^78 does not fit into 256 bits
+    if scale < -78 {
+        return Err(UniswapV3MathError::DecimalOutOfRange);
+    }
+    if scale < 0 {
+        digits *= ten.pow(scale.unsigned_abs() as u32);
+        scale = 0;
+    }
+
+    let denominator = ten.pow(scale as u32);
+    let shifted = digits << resolution;
+    if !(&shifted % &denominator).is_zero() {
+        return Err(UniswapV3MathError::InexactDecimal { resolution });
+    }
+    let (_, bytes) = (shifted / denominator).to_bytes_be();
+    if bytes.len() > 32 {
+        return Err(UniswapV3MathError::DecimalOutOfRange);
+    }
+    Ok(U256::from_be_slice(&bytes))
+}
+
+// value / 2^resolution rounded half to even to the most fractional digits, at most 28, that keep the mantissa
+// within 96 bits. The mantissa then has at least 28 significant digits for values of at least 1, so the relative
+// error stays below 10^-28, smaller values are off by at most 0.5 * 10^-28. Errors with DecimalOutOfRange for
+// values of 2^96 and above and for values other than zero that round to zero instead of truncating them
+#[cfg(feature = "decimal")]
+fn to_rust_decimal(value: U256, resolution: usize) -> Result<Decimal, UniswapV3MathError> {
+    let denominator = U512::from(1) << resolution;
+    let half = denominator >> 1;
+    for scale in (0..=Decimal::MAX_SCALE).rev() {
+        let (mut mantissa, remainder) =
+            (U512::from(value) * U512::from(10).pow(U512::from(scale))).div_rem(denominator);
+        if remainder > half || (remainder == half && mantissa.bit(0)) {
+            mantissa += U512::from(1);
+        }
+        if mantissa.bit_len() <= 96 {
+            if mantissa.is_zero() && !value.is_zero() {
+                return Err(UniswapV3MathError::DecimalOutOfRange);
+            }
+            return Ok(Decimal::from_i128_with_scale(mantissa.to::<i128>(), scale).normalize());
+        }
+    }
+    Err(UniswapV3MathError::DecimalOutOfRange)
+}
+
+// inverse of to_rust_decimal, every Decimal with at most 28 fractional digits fits, but only those without a
+// factor 5 left in the denominator are exact
+#[cfg(feature = "decimal")]
+fn from_rust_decimal(decimal: Decimal, resolution: usize) -> Result<U256, UniswapV3MathError> {
+    if decimal.is_sign_negative() && !decimal.is_zero() {
+        return Err(UniswapV3MathError::DecimalOutOfRange);
+    }
+    let (value, remainder) = (U512::from(decimal.mantissa().unsigned_abs()) << resolution)
+        .div_rem(U512::from(10).pow(U512::from(decimal.scale())));
+    if !remainder.is_zero() {
+        return Err(UniswapV3MathError::InexactDecimal { resolution });
+    }
+    Ok(value.to())
+}
+
+macro_rules! impl_fixed_point {
+    ($name:ident, $resolution:expr) => {
+        impl $name {
+            // exact decimal value, e.g. "1.5" for 1.5 * 2^resolution
+            pub fn to_decimal_string(&self) -> String {
+                to_decimal_string(self.0, $resolution)
+            }
+        }
+
+        // parses an exact decimal value such as "1.5", see to_decimal_string
+        impl FromStr for $name {
+            type Err = UniswapV3MathError;
+
+            fn from_str(decimal: &str) -> Result<Self, Self::Err> {
+                from_decimal_str(decimal, $resolution).map(Self)
+            }
+        }
+
+        // exact, the value has at most resolution fractional digits
+        #[cfg(feature = "decimal")]
+        impl From<$name> for BigDecimal {
+            fn from(value: $name) -> Self {
+                to_big_decimal(value.0, $resolution)
+            }
+        }
+
+        #[cfg(feature = "decimal")]
+        impl TryFrom<BigDecimal> for $name {
+            type Error = UniswapV3MathError;
+
+            fn try_from(decimal: BigDecimal) -> Result<Self, Self::Error> {
+                from_big_decimal(&decimal, $resolution).map(Self)
+            }
+        }
+
+        // rounds to 28 significant digits or fewer, see to_rust_decimal
+        #[cfg(feature = "decimal")]
+        impl TryFrom<$name> for Decimal {
+            type Error = UniswapV3MathError;
+
+            fn try_from(value: $name) -> Result<Self, Self::Error> {
+                to_rust_decimal(value.0, $resolution)
+            }
+        }
+
+        #[cfg(feature = "decimal")]
+        impl TryFrom<Decimal> for $name {
+            type Error = UniswapV3MathError;
+
+            fn try_from(decimal: Decimal) -> Result<Self, Self::Error> {
+                from_rust_decimal(decimal, $resolution).map(Self)
+            }
+        }
+
+        impl From<U256> for $name {
+            fn from(value: U256) -> Self {
+                Self(value)
+            }
+        }
+
+        impl From<$name> for U256 {
+            fn from(value: $name) -> Self {
+                value.0
+            }
+        }
+
+        impl Deref for $name {
+            type Target = U256;
+
+            fn deref(&self) -> &U256 {
+                &self.0
+            }
+        }
+
+        impl fmt::Display for $name {
+            fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+                fmt::Display::fmt(&self.0, f)
+            }
+        }
+
+        // compares with raw values such as MIN_SQRT_RATIO and MAX_SQRT_RATIO
+        impl PartialEq<U256> for $name {
+            fn eq(&self, other: &U256) -> bool {
+                self.0 == *other
+            }
+        }
+
+        impl PartialEq<$name> for U256 {
+            fn eq(&self, other: &$name) -> bool {
+                *self == other.0
+            }
+        }
+
+        impl PartialOrd<U256> for $name {
+            fn partial_cmp(&self, other: &U256) -> Option<core::cmp::Ordering> {
+                self.0.partial_cmp(other)
+            }
+        }
+
+        impl PartialOrd<$name> for U256 {
+            fn partial_cmp(&self, other: &$name) -> Option<core::cmp::Ordering> {
+                self.partial_cmp(&other.0)
+            }
+        }
+    };
+}
+
+impl_fixed_point!(SqrtPriceX96, RESOLUTION);
+impl_fixed_point!(FeeGrowthX128, 128);
+
+// fee growth accumulators overflow by design, the differences taken of them stay fee growths
+impl FeeGrowthX128 {
+    pub fn wrapping_add(self, rhs: Self) -> Self {
+        Self(self.0.wrapping_add(rhs.0))
+    }
+
+    pub fn wrapping_sub(self, rhs: Self) -> Self {
+        Self(self.0.wrapping_sub(rhs.0))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloy_primitives::U256;
+
+    use super::{
+        div_x96, mul_x96, x128_to_x96, x96_to_x128, FeeGrowthX128, SqrtPriceX96, Q128, Q192, Q96,
+        RESOLUTION,
+    };
+    use crate::{
+        error::UniswapV3MathError,
+        full_math::Rounding,
+        tick_math::{
+            get_sqrt_ratio_at_tick, get_tick_at_sqrt_ratio, MAX_SQRT_RATIO, MAX_TICK,
+            MIN_SQRT_RATIO, MIN_TICK,
+        },
+    };
+
+    #[test]
+    fn test_constants() {
+        assert_eq!(Q96, U256::from(1) << RESOLUTION);
+        assert_eq!(Q128, U256::from(1) << 128);
+        assert_eq!(Q192, Q96 * Q96);
+    }
+
+    #[test]
+    fn test_q96_helpers() -> eyre::Result<()> {
+        let one_and_a_half = Q96 + (Q96 >> 1);
+        assert_eq!(
+            mul_x96(one_and_a_half, one_and_a_half, Rounding::Down)?,
+            Q96 * U256::from(9) / U256::from(4)
+        );
+        assert_eq!(mul_x96(U256::from(3), Q96, Rounding::Up)?, U256::from(3));
+
+        // 1 / 3 in Q96 rounds either way
+        let third = div_x96(U256::from(1), U256::from(3), Rounding::Down)?;
+        assert_eq!(third, Q96 / U256::from(3));
+        assert_eq!(
+            div_x96(U256::from(1), U256::from(3), Rounding::Up)?,
+            third + U256::from(1)
+        );
+        assert_eq!(
+            div_x96(U256::from(1), U256::from(3), Rounding::Floor)?,
+            third
+        );
+        assert!(matches!(
+            div_x96(U256::from(1), U256::ZERO, Rounding::Down),
+            Err(UniswapV3MathError::DenominatorIsZero)
+        ));
+
+        // Q96 to Q128 and back is lossless, the other way drops the low 32 bits
+        let sqrt_price = *get_sqrt_ratio_at_tick(-12345)?;
+        assert_eq!(x96_to_x128(sqrt_price)?, sqrt_price << 32);
+        assert_eq!(
+            x128_to_x96(x96_to_x128(sqrt_price)?, Rounding::Down)?,
+            sqrt_price
+        );
+        let fee_growth = Q128 + U256::from(1);
+        assert_eq!(x128_to_x96(fee_growth, Rounding::Down)?, Q96);
+        assert_eq!(x128_to_x96(fee_growth, Rounding::Up)?, Q96 + U256::from(1));
+        assert!(x96_to_x128(U256::MAX).is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_sqrt_price_x_96() -> eyre::Result<()> {
+        let sqrt_price = get_sqrt_ratio_at_tick(0)?;
+        let raw: U256 = sqrt_price.into();
+        assert_eq!(raw, U256::from(1) << 96);
+        assert_eq!(sqrt_price.to_string(), "79228162514264337593543950336");
+
+        // U256 methods and operators are available through deref
+        assert_eq!(sqrt_price.bit_len(), 97);
+        assert_eq!(*sqrt_price >> 96, U256::from(1));
+        assert_eq!(get_tick_at_sqrt_ratio(sqrt_price)?, 0);
+        assert_eq!(SqrtPriceX96::from(U256::from(1) << 96), sqrt_price);
+
+        // and raw values compare with the wrapper in both directions
+        assert!(get_sqrt_ratio_at_tick(1)? > sqrt_price);
+        assert!(MIN_SQRT_RATIO < sqrt_price && sqrt_price < MAX_SQRT_RATIO);
+        Ok(())
+    }
+
+    #[test]
+    fn test_decimal_string() -> eyre::Result<()> {
+        assert_eq!(SqrtPriceX96(Q96).to_decimal_string(), "1");
+        assert_eq!(SqrtPriceX96(Q96 + (Q96 >> 1)).to_decimal_string(), "1.5");
+        assert_eq!(SqrtPriceX96(U256::ZERO).to_decimal_string(), "0");
+        assert_eq!(
+            SqrtPriceX96(U256::from(1)).to_decimal_string(),
+            "0.000000000000000000000000000012621774483536188886587657044524579674771302961744368076324462890625"
+        );
+        assert_eq!(
+            FeeGrowthX128(Q128 * U256::from(3) / U256::from(4)).to_decimal_string(),
+            "0.75"
+        );
+        assert_eq!(
+            FeeGrowthX128(U256::MAX)
+                .to_decimal_string()
+                .parse::<FeeGrowthX128>()?,
+            FeeGrowthX128(U256::MAX)
+        );
+
+        // tick sqrt ratios survive the round trip, every tick takes over a minute in debug builds so the range is
+        // sampled with a prime step and both bounds
+        for tick in (MIN_TICK..=MAX_TICK)
+            .step_by(61)
+            .chain([MAX_TICK - 1, MAX_TICK])
+        {
+            let sqrt_price = get_sqrt_ratio_at_tick(tick)?;
+            assert_eq!(
+                sqrt_price.to_decimal_string().parse::<SqrtPriceX96>()?,
+                sqrt_price
+            );
+        }
+
+        // trailing zeros and a missing fraction are accepted
+        assert_eq!(
+            "1.500".parse::<SqrtPriceX96>()?,
+            SqrtPriceX96(Q96 + (Q96 >> 1))
+        );
+        assert_eq!(
+            "2".parse::<SqrtPriceX96>()?,
+            SqrtPriceX96(Q96 * U256::from(2))
+        );
+        assert_eq!(
+            "2.".parse::<SqrtPriceX96>()?,
+            SqrtPriceX96(Q96 * U256::from(2))
+        );
+
+        // decimals without an exact representation error instead of being truncated
+        assert!(matches!(
+            "0.1".parse::<SqrtPriceX96>(),
+            Err(UniswapV3MathError::InexactDecimal { resolution: 96 })
+        ));
+        let too_precise = alloc::format!("0.{}1", "0".repeat(128));
+        assert!(matches!(
+            too_precise.parse::<FeeGrowthX128>(),
+            Err(UniswapV3MathError::InexactDecimal { resolution: 128 })
+        ));
+        for invalid in ["", ".5", "1.2.3", "-1", "1e5", "0x10"] {
+            assert!(
+                matches!(
+                    invalid.parse::<SqrtPriceX96>(),
+                    Err(UniswapV3MathError::ParseError(_))
+                ),
+                "{invalid}"
+            );
+        }
+        // integers below 2^160 fit in Q96
+        assert!("1461501637330902918203684832716283019655932542975"
+            .parse::<SqrtPriceX96>()
+            .is_ok());
+        assert!(matches!(
+            "1461501637330902918203684832716283019655932542976".parse::<SqrtPriceX96>(),
+            Err(UniswapV3MathError::ParseError(_))
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_fee_growth_x_128() {
+        let fee_growth = FeeGrowthX128(U256::MAX);
+        assert_eq!(
+            fee_growth.wrapping_add(FeeGrowthX128(U256::from(2))),
+            FeeGrowthX128(U256::from(1))
+        );
+        assert_eq!(
+            FeeGrowthX128(U256::from(1)).wrapping_sub(fee_growth),
+            FeeGrowthX128(U256::from(2))
+        );
+        // U256 methods and operators are available through deref
+        assert_eq!(fee_growth.count_ones(), 256);
+        assert_eq!(FeeGrowthX128::default().to_string(), "0");
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn test_big_decimal() -> eyre::Result<()> {
+        use bigdecimal::BigDecimal;
+        use std::str::FromStr;
+
+        // the conversion is exact, so it matches the decimal string and every tick survives the round trip
+        for tick in MIN_TICK..=MAX_TICK {
+            let sqrt_price = get_sqrt_ratio_at_tick(tick)?;
+            let decimal = BigDecimal::from(sqrt_price);
+            assert_eq!(SqrtPriceX96::try_from(decimal)?, sqrt_price, "tick {tick}");
+        }
+        for tick in [MIN_TICK, -1, 0, 1, MAX_TICK] {
+            let sqrt_price = get_sqrt_ratio_at_tick(tick)?;
+            assert_eq!(
+                BigDecimal::from(sqrt_price),
+                BigDecimal::from_str(&sqrt_price.to_decimal_string())?
+            );
+        }
+        assert_eq!(
+            FeeGrowthX128::try_from(BigDecimal::from(FeeGrowthX128(U256::MAX)))?,
+            FeeGrowthX128(U256::MAX)
+        );
+
+        // trailing zeros and exponents are accepted, the scale of the decimal does not matter
+        assert_eq!(
+            SqrtPriceX96::try_from(BigDecimal::from_str("1.500")?)?,
+            SqrtPriceX96(Q96 + (Q96 >> 1))
+        );
+        assert_eq!(
+            SqrtPriceX96::try_from(BigDecimal::from_str("2e3")?)?,
+            SqrtPriceX96(Q96 * U256::from(2000))
+        );
+        assert_eq!(
+            SqrtPriceX96::try_from(BigDecimal::from_str("0e-1000")?)?,
+            SqrtPriceX96(U256::ZERO)
+        );
+
+        // values without an exact representation error instead of being truncated
+        assert!(matches!(
+            SqrtPriceX96::try_from(BigDecimal::from_str("0.1")?),
+            Err(UniswapV3MathError::InexactDecimal { resolution: 96 })
+        ));
+        assert!(matches!(
+            FeeGrowthX128::try_from(BigDecimal::from_str("1e-129")?),
+            Err(UniswapV3MathError::InexactDecimal { resolution: 128 })
+        ));
+        for out_of_range in [
+            "-1",
+            "1461501637330902918203684832716283019655932542976",
+            "1e79",
+        ] {
+            assert!(
+                matches!(
+                    SqrtPriceX96::try_from(BigDecimal::from_str(out_of_range)?),
+                    Err(UniswapV3MathError::DecimalOutOfRange)
+                ),
+                "{out_of_range}"
+            );
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "decimal")]
+    #[test]
+    fn test_rust_decimal() -> eyre::Result<()> {
+        use bigdecimal::BigDecimal;
+        use rust_decimal::Decimal;
+        use std::str::FromStr;
+
+        // the error against the exact value is below 10^-28 relative for values of at least 1 and below
+        // 0.5 * 10^-28 absolute for smaller ones
+        let relative_bound = BigDecimal::from_str("1e-28")?;
+        let absolute_bound = BigDecimal::from_str("0.5e-28")?;
+        for tick in (MIN_TICK..=MAX_TICK)
+            .step_by(7)
+            .chain([MAX_TICK - 1, MAX_TICK])
+        {
+            let sqrt_price = get_sqrt_ratio_at_tick(tick)?;
+            let exact = BigDecimal::from(sqrt_price);
+            let decimal = Decimal::try_from(sqrt_price)?;
+            let error = (BigDecimal::from_str(&decimal.to_string())? - &exact).abs();
+            if tick >= 0 {
+                assert!(error < &exact * &relative_bound, "tick {tick}");
+            } else {
+                assert!(error <= absolute_bound, "tick {tick}");
+            }
+        }
+
+        // values that fit exactly convert both ways without loss
+        for (value, expected) in [
+            (Q96, "1"),
+            (Q96 + (Q96 >> 1), "1.5"),
+            (Q96 * U256::from(2000), "2000"),
+            (U256::ZERO, "0"),
+        ] {
+            let decimal = Decimal::try_from(SqrtPriceX96(value))?;
+            assert_eq!(decimal, Decimal::from_str(expected)?);
+            assert_eq!(SqrtPriceX96::try_from(decimal)?, SqrtPriceX96(value));
+        }
+        assert_eq!(
+            Decimal::try_from(FeeGrowthX128(Q128 * U256::from(3) / U256::from(4)))?,
+            Decimal::from_str("0.75")?
+        );
+        // rounds half to even at the 28th fractional digit, 2^-96 is 1.26 * 10^-29
+        assert_eq!(
+            Decimal::try_from(SqrtPriceX96(U256::from(4)))?,
+            Decimal::from_str("0.0000000000000000000000000001")?
+        );
+        // the largest value below Decimal::MAX keeps its integer digits
+        let largest = Decimal::MAX.trunc();
+        assert_eq!(
+            Decimal::try_from(SqrtPriceX96::try_from(largest)?)?,
+            largest
+        );
+
+        // values Decimal cannot hold error instead of being truncated or rounded to zero
+        assert!(matches!(
+            Decimal::try_from(SqrtPriceX96(U256::from(1))),
+            Err(UniswapV3MathError::DecimalOutOfRange)
+        ));
+        assert!(matches!(
+            Decimal::try_from(FeeGrowthX128(U256::MAX)),
+            Err(UniswapV3MathError::DecimalOutOfRange)
+        ));
+        assert!(matches!(
+            Decimal::try_from(SqrtPriceX96(U256::from(1) << 192)),
+            Err(UniswapV3MathError::DecimalOutOfRange)
+        ));
+        assert!(matches!(
+            SqrtPriceX96::try_from(Decimal::from_str("0.1")?),
+            Err(UniswapV3MathError::InexactDecimal { resolution: 96 })
+        ));
+        assert!(matches!(
+            FeeGrowthX128::try_from(Decimal::from_str("-1")?),
+            Err(UniswapV3MathError::DecimalOutOfRange)
+        ));
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_fixed_point_serde() -> eyre::Result<()> {
+        let sqrt_price = SqrtPriceX96(U256::from(1) << 96);
+        let json = serde_json::to_string(&sqrt_price)?;
+        assert_eq!(json, serde_json::to_string(&(U256::from(1) << 96))?);
+        assert_eq!(serde_json::from_str::<SqrtPriceX96>(&json)?, sqrt_price);
+        Ok(())
+    }
+}
@@ -1,11 +1,22 @@
-use std::ops::{Add, BitAnd, BitOrAssign, BitXor, Div, Mul, MulAssign};
+use core::ops::{Add, BitOrAssign, Div, Mul, MulAssign, Rem};
 
-use alloy::primitives::U256;
+use alloy_primitives::{I256, U256};
 
 use crate::{error::UniswapV3MathError, U256_1, U256_2, U256_3};
 
-// returns (uint256 result)
-pub fn mul_div(a: U256, b: U256, mut denominator: U256) -> Result<U256, UniswapV3MathError> {
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum Rounding {
+    // Round toward zero
+    Down,
+    // Round away from zero
+    Up,
+    // Round toward negative infinity, equivalent to Down for unsigned values
+    Floor,
+}
+
+// returns (uint256 prod1, uint256 prod0) such that a * b = prod1 * 2**256 + prod0
+pub fn mul_512(a: U256, b: U256) -> (U256, U256) {
     // 512-bit multiply [prod1 prod0] = a * b
     // Compute the product mod 2**256 and mod 2**256 - 1
     // then use the Chinese Remainder Theorem to reconstruct
@@ -13,25 +24,45 @@ pub fn mul_div(a: U256, b: U256, mut denominator: U256) -> Result<U256, UniswapV
     // variables such that product = prod1 * 2**256 + prod0
     let mm = a.mul_mod(b, U256::MAX);
 
-    let mut prod_0 = a.overflowing_mul(b).0; // Least significant 256 bits of the product
-    let mut prod_1 = mm
+    let prod_0 = a.overflowing_mul(b).0; // Least significant 256 bits of the product
+    let prod_1 = mm
         .overflowing_sub(prod_0)
         .0
         .overflowing_sub(U256::from((mm < prod_0) as u8))
         .0;
 
+    (prod_1, prod_0)
+}
+
+// returns (uint256 result)
+pub fn mul_div(a: U256, b: U256, denominator: U256) -> Result<U256, UniswapV3MathError> {
+    mul_div_with_remainder(a, b, denominator).map(|(result, _)| result)
+}
+
+// returns (uint256 result, uint256 remainder) such that a * b = result * denominator + remainder. The remainder
+// falls out of the 512 by 256 division, so it costs nothing on top of mul_div
+pub fn mul_div_with_remainder(
+    a: U256,
+    b: U256,
+    mut denominator: U256,
+) -> Result<(U256, U256), UniswapV3MathError> {
+    let (mut prod_1, mut prod_0) = mul_512(a, b);
+
     // Handle non-overflow cases, 256 by 256 division
     if prod_1 == U256::ZERO {
         if denominator == U256::ZERO {
             return Err(UniswapV3MathError::DenominatorIsZero);
         }
-        return Ok(U256::from_limbs(*prod_0.div(denominator).as_limbs()));
+        return Ok((prod_0.div(denominator), prod_0.rem(denominator)));
     }
 
     // Make sure the result is less than 2**256.
     // Also prevents denominator == 0
     if denominator <= prod_1 {
-        return Err(UniswapV3MathError::DenominatorIsLteProdOne);
+        return Err(UniswapV3MathError::DenominatorIsLteProdOne {
+            denominator,
+            prod_1,
+        });
     }
 
     ///////////////////////////////////////////////
@@ -52,10 +83,7 @@ pub fn mul_div(a: U256, b: U256, mut denominator: U256) -> Result<U256, UniswapV
     // Factor powers of two out of denominator
     // Compute largest power of two divisor of denominator.
     // Always >= 1.
-    let mut twos = U256::ZERO
-        .overflowing_sub(denominator)
-        .0
-        .bitand(denominator);
+    let mut twos = U256::ZERO.overflowing_sub(denominator).0 & denominator;
 
     // Divide denominator by power of two
 
@@ -78,7 +106,7 @@ pub fn mul_div(a: U256, b: U256, mut denominator: U256) -> Result<U256, UniswapV
     // Compute the inverse by starting with a seed that is correct
     // for four bits. That is, denominator * inv = 1 mod 2**4
 
-    let mut inv = U256_3.mul(denominator).bitxor(U256_2);
+    let mut inv = U256_3.mul(denominator) ^ U256_2;
 
     // Now use Newton-Raphson iteration to improve the precision.
     // Thanks to Hensel's lifting lemma, this also works in modular
@@ -98,7 +126,7 @@ pub fn mul_div(a: U256, b: U256, mut denominator: U256) -> Result<U256, UniswapV
     // We don't need to compute the high bits of the result and prod1
     // is no longer required.
 
-    Ok(U256::from_le_slice((prod_0 * inv).as_le_slice()))
+    Ok((prod_0 * inv, remainder))
 }
 
 pub fn mul_div_rounding_up(
@@ -106,9 +134,9 @@ pub fn mul_div_rounding_up(
     b: U256,
     denominator: U256,
 ) -> Result<U256, UniswapV3MathError> {
-    let result = mul_div(a, b, denominator)?;
+    let (result, remainder) = mul_div_with_remainder(a, b, denominator)?;
 
-    if a.mul_mod(b, denominator) > U256::ZERO {
+    if remainder > U256::ZERO {
         if result == U256::MAX {
             Err(UniswapV3MathError::ResultIsU256MAX)
         } else {
@@ -119,6 +147,57 @@ pub fn mul_div_rounding_up(
     }
 }
 
+// returns (int256 result)
+pub fn mul_div_signed(
+    a: I256,
+    b: I256,
+    denominator: I256,
+    rounding: Rounding,
+) -> Result<I256, UniswapV3MathError> {
+    if denominator.is_zero() {
+        return Err(UniswapV3MathError::DenominatorIsZero);
+    }
+
+    let negative = a.is_negative() ^ b.is_negative() ^ denominator.is_negative();
+
+    let (mut result, remainder) = mul_div_with_remainder(
+        a.unsigned_abs(),
+        b.unsigned_abs(),
+        denominator.unsigned_abs(),
+    )?;
+
+    if !remainder.is_zero()
+        && (rounding == Rounding::Up || (rounding == Rounding::Floor && negative))
+    {
+        if result == U256::MAX {
+            return Err(UniswapV3MathError::ResultIsU256MAX);
+        }
+        result += U256_1;
+    }
+
+    if negative {
+        // |I256::MIN| is one larger than I256::MAX
+        if result > I256::MIN.unsigned_abs() {
+            return Err(UniswapV3MathError::ResultOverflowsI256);
+        }
+        Ok(I256::from_raw(result.wrapping_neg()))
+    } else {
+        I256::try_from(result).map_err(|_| UniswapV3MathError::ResultOverflowsI256)
+    }
+}
+
+// returns (uint256 fee), the fee of UniswapV3Pool.flash for amount, rounded up in favor of the pool. Errors with
+// FeeOverflow if the fee does not fit into the uint128 protocol fee and flash event amounts
+pub fn get_flash_loan_fee(amount: U256, fee_pips: u32) -> Result<U256, UniswapV3MathError> {
+    let fee = mul_div_rounding_up(amount, U256::from(fee_pips), U256::from(1_000_000))?;
+
+    if fee > U256::from(u128::MAX) {
+        return Err(UniswapV3MathError::FeeOverflow { fee });
+    }
+
+    Ok(fee)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -149,13 +228,21 @@ mod tests {
 
 #[cfg(test)]
 mod test {
-    use std::ops::{Div, Mul, Sub};
+    use std::{
+        ops::{Div, Mul, Sub},
+        str::FromStr,
+    };
 
-    use alloy::primitives::U256;
+    use alloy_primitives::{I256, U256};
+    use num_bigint::BigUint;
+    use proptest::prelude::*;
 
     use crate::U256_1;
 
-    use super::mul_div;
+    use super::{
+        get_flash_loan_fee, mul_512, mul_div, mul_div_signed, mul_div_with_remainder, Rounding,
+    };
+    use crate::error::UniswapV3MathError;
 
     const Q128: U256 = U256::from_limbs([0, 0, 1, 0]);
 
@@ -209,4 +296,240 @@ mod test {
         let result = mul_div(Q128, U256::from(1000).mul(Q128), U256::from(3000).mul(Q128));
         assert_eq!(result.unwrap(), Q128.div(U256::from(3)));
     }
+
+    #[test]
+    fn test_mul_512() {
+        // All max inputs
+        let (prod_1, prod_0) = mul_512(U256::MAX, U256::MAX);
+        assert_eq!(prod_1, U256::MAX.sub(U256_1));
+        assert_eq!(prod_0, U256_1);
+    }
+
+    #[test]
+    fn test_mul_div_with_remainder() {
+        // Revert if the denominator is zero
+        let result = mul_div_with_remainder(Q128, U256::from(5), U256::ZERO);
+        assert_eq!(result.err().unwrap().to_string(), "Denominator is 0");
+
+        // Accurate with phantom overflow and repeating decimal
+        let (result, remainder) =
+            mul_div_with_remainder(Q128, U256::from(1000).mul(Q128), U256::from(3000).mul(Q128))
+                .unwrap();
+        assert_eq!(result, Q128.div(U256::from(3)));
+        assert_eq!(remainder, U256::from(1000).mul(Q128));
+    }
+
+    #[test]
+    fn test_mul_div_signed() {
+        let one = I256::ONE;
+        let minus_one = I256::MINUS_ONE;
+
+        // Revert if the denominator is zero
+        let result = mul_div_signed(one, one, I256::ZERO, Rounding::Down);
+        assert_eq!(result.err().unwrap().to_string(), "Denominator is 0");
+
+        // Mixed signs
+        let seven = I256::try_from(7).unwrap();
+        let two = I256::try_from(2).unwrap();
+        let three = I256::try_from(3).unwrap();
+        for (a, b, denominator, down, up, floor) in [
+            (seven, one, two, 3, 4, 3),
+            (-seven, one, two, -3, -4, -4),
+            (seven, -one, two, -3, -4, -4),
+            (seven, one, -two, -3, -4, -4),
+            (-seven, -one, two, 3, 4, 3),
+            (-seven, one, -two, 3, 4, 3),
+            (-seven, -one, -two, -3, -4, -4),
+            (three, two, two, 3, 3, 3),
+            (-three, two, two, -3, -3, -3),
+        ] {
+            for (rounding, expected) in [
+                (Rounding::Down, down),
+                (Rounding::Up, up),
+                (Rounding::Floor, floor),
+            ] {
+                assert_eq!(
+                    mul_div_signed(a, b, denominator, rounding).unwrap(),
+                    I256::try_from(expected).unwrap()
+                );
+            }
+        }
+
+        // Denominators of 1 and -1
+        let x = I256::try_from(-123456789).unwrap();
+        assert_eq!(
+            mul_div_signed(x, three, one, Rounding::Up).unwrap(),
+            x * three
+        );
+        assert_eq!(
+            mul_div_signed(x, three, minus_one, Rounding::Floor).unwrap(),
+            -(x * three)
+        );
+
+        // I256::MIN operands
+        assert_eq!(
+            mul_div_signed(I256::MIN, one, one, Rounding::Down).unwrap(),
+            I256::MIN
+        );
+        assert_eq!(
+            mul_div_signed(I256::MIN, minus_one, minus_one, Rounding::Down).unwrap(),
+            I256::MIN
+        );
+        assert_eq!(
+            mul_div_signed(I256::MIN, two, two, Rounding::Up).unwrap(),
+            I256::MIN
+        );
+        assert_eq!(
+            mul_div_signed(I256::MIN, three, I256::MIN, Rounding::Down).unwrap(),
+            three
+        );
+
+        // -I256::MIN does not fit into I256
+        let result = mul_div_signed(I256::MIN, minus_one, one, Rounding::Down);
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            "Result does not fit into I256"
+        );
+        let result = mul_div_signed(I256::MAX, two, one, Rounding::Down);
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            "Result does not fit into I256"
+        );
+
+        // Rounding away from zero can push the result past I256::MIN
+        let result = mul_div_signed(I256::MIN + one, three, -three, Rounding::Down);
+        assert_eq!(result.unwrap(), I256::MAX);
+        let result = mul_div_signed(I256::MIN, I256::MAX, I256::MAX - one, Rounding::Floor);
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            "Result does not fit into I256"
+        );
+
+        // Results that do not fit into U256 revert like mul_div
+        let result = mul_div_signed(I256::MIN, I256::MIN, one, Rounding::Down);
+        assert_eq!(
+            result.err().unwrap().to_string(),
+            "Denominator is less than or equal to prod_1"
+        );
+    }
+
+    #[test]
+    fn test_get_flash_loan_fee() {
+        //rounds up, a single wei borrowed pays a fee of 1
+        assert_eq!(get_flash_loan_fee(U256_1, 100).unwrap(), U256_1);
+        assert_eq!(get_flash_loan_fee(U256::ZERO, 3000).unwrap(), U256::ZERO);
+        assert_eq!(
+            get_flash_loan_fee(U256::from(1_000_000), 3000).unwrap(),
+            U256::from(3000)
+        );
+        assert_eq!(
+            get_flash_loan_fee(U256::from(1_000_001), 3000).unwrap(),
+            U256::from(3001)
+        );
+
+        //expected values from python's integer arithmetic, -(-amount * fee // 10**6) and
+        //(2**128 - 1) * 10**6 // fee for the largest amount whose fee fits into uint128
+        for (fee_pips, fee_of_u128_max, max_amount) in [
+            (
+                100,
+                "34028236692093846346337460743176822",
+                "3402823669209384634633746074317682114550000",
+            ),
+            (
+                500,
+                "170141183460469231731687303715884106",
+                "680564733841876926926749214863536422910000",
+            ),
+            (
+                3000,
+                "1020847100762815390390123822295304635",
+                "113427455640312821154458202477256070485000",
+            ),
+            (
+                10000,
+                "3402823669209384634633746074317682115",
+                "34028236692093846346337460743176821145500",
+            ),
+        ] {
+            assert_eq!(
+                get_flash_loan_fee(U256::from(u128::MAX), fee_pips).unwrap(),
+                U256::from_str(fee_of_u128_max).unwrap()
+            );
+
+            let max_amount = U256::from_str(max_amount).unwrap();
+            assert!(get_flash_loan_fee(max_amount, fee_pips).unwrap() <= U256::from(u128::MAX));
+            assert!(matches!(
+                get_flash_loan_fee(max_amount + U256_1, fee_pips),
+                Err(UniswapV3MathError::FeeOverflow { fee }) if fee == U256::from(u128::MAX) + U256_1
+            ));
+        }
+    }
+
+    // values with a uniformly drawn bit length, so that small and large operands are equally likely. Shrinks
+    // towards shorter values with fewer bits set
+    fn u256_strategy() -> impl Strategy<Value = U256> {
+        (0..=256_usize, any::<[u64; 4]>()).prop_map(|(bits, limbs)| {
+            U256::from_limbs(limbs)
+                .checked_shr(256 - bits)
+                .unwrap_or(U256::ZERO)
+        })
+    }
+
+    fn to_big_uint(value: U256) -> BigUint {
+        BigUint::from_bytes_le(&value.to_le_bytes::<32>())
+    }
+
+    proptest! {
+        #[test]
+        fn test_mul_div_by_self(a in u256_strategy(), b in u256_strategy()) {
+            prop_assume!(!b.is_zero());
+            prop_assert_eq!(mul_div(a, b, b).unwrap(), a);
+        }
+
+        #[test]
+        fn test_mul_512_matches_reference(a in u256_strategy(), b in u256_strategy()) {
+            let (prod_1, prod_0) = mul_512(a, b);
+
+            prop_assert_eq!(
+                (to_big_uint(prod_1) << 256) + to_big_uint(prod_0),
+                to_big_uint(a) * to_big_uint(b)
+            );
+        }
+
+        #[test]
+        fn test_mul_div_with_remainder_matches_reference(
+            a in u256_strategy(),
+            b in u256_strategy(),
+            denominator in u256_strategy(),
+        ) {
+            let product = to_big_uint(a) * to_big_uint(b);
+
+            for denominator in [U256_1, U256::MAX, denominator] {
+                match mul_div_with_remainder(a, b, denominator) {
+                    Ok((result, remainder)) => {
+                        prop_assert!(remainder < denominator);
+                        prop_assert_eq!(
+                            to_big_uint(result) * to_big_uint(denominator) + to_big_uint(remainder),
+                            product.clone()
+                        );
+                        prop_assert_eq!(mul_div(a, b, denominator).unwrap(), result);
+                    }
+                    // Only a zero denominator or a quotient that does not fit into 256 bits reverts
+                    Err(_) => prop_assert!(
+                        denominator.is_zero()
+                            || &product / to_big_uint(denominator) > to_big_uint(U256::MAX)
+                    ),
+                }
+            }
+        }
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_rounding_serde() {
+        for rounding in [Rounding::Down, Rounding::Up, Rounding::Floor] {
+            let json = serde_json::to_string(&rounding).unwrap();
+            assert_eq!(serde_json::from_str::<Rounding>(&json).unwrap(), rounding);
+        }
+    }
 }
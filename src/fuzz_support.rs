@@ -0,0 +1,157 @@
+use alloy_primitives::{I256, U256};
+
+use crate::{
+    fixed_point::SqrtPriceX96,
+    swap_math::MAX_SWAP_FEE,
+    tick_math::{MAX_SQRT_RATIO, MIN_SQRT_RATIO},
+};
+
+// Decoders from raw fuzzer bytes to the inputs of the fuzz targets. The layouts are fixed so that corpus entries
+// stay meaningful across runs, inputs shorter than a layout are zero padded and longer ones are truncated
+
+//Big endian U256 from the first 32 bytes of data
+pub fn u256_from_bytes(data: &[u8]) -> U256 {
+    let mut word = [0; 32];
+    let len = data.len().min(32);
+    word[..len].copy_from_slice(&data[..len]);
+    U256::from_be_bytes(word)
+}
+
+// the bytes of data at [offset, offset + len), cut short at the end of data
+fn field(data: &[u8], offset: usize, len: usize) -> &[u8] {
+    let start = offset.min(data.len());
+    let end = (offset + len).min(data.len());
+    &data[start..end]
+}
+
+//Inputs of compute_swap_step, decoded from 116 bytes: current and target sqrt ratio (32 bytes each), liquidity
+//(16 bytes), amount remaining (32 bytes, two's complement) and fee pips (4 bytes). The sqrt ratios are reduced
+//into [MIN_SQRT_RATIO, MAX_SQRT_RATIO] and the fee into [0, MAX_SWAP_FEE) so that every input is a valid step
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct SwapStepInput {
+    pub sqrt_ratio_current_x_96: SqrtPriceX96,
+    pub sqrt_ratio_target_x_96: SqrtPriceX96,
+    pub liquidity: u128,
+    pub amount_remaining: I256,
+    pub fee_pips: u32,
+}
+
+impl SwapStepInput {
+    pub const LEN: usize = 116;
+
+    pub fn decode(data: &[u8]) -> Self {
+        let sqrt_ratio = |offset| {
+            SqrtPriceX96(
+                MIN_SQRT_RATIO
+                    + u256_from_bytes(field(data, offset, 32))
+                        % (MAX_SQRT_RATIO - MIN_SQRT_RATIO + U256::from(1)),
+            )
+        };
+        let mut liquidity = [0; 16];
+        let bytes = field(data, 64, 16);
+        liquidity[..bytes.len()].copy_from_slice(bytes);
+        let mut fee_pips = [0; 4];
+        let bytes = field(data, 112, 4);
+        fee_pips[..bytes.len()].copy_from_slice(bytes);
+
+        Self {
+            sqrt_ratio_current_x_96: sqrt_ratio(0),
+            sqrt_ratio_target_x_96: sqrt_ratio(32),
+            liquidity: u128::from_be_bytes(liquidity),
+            amount_remaining: I256::from_raw(u256_from_bytes(field(data, 80, 32))),
+            fee_pips: u32::from_be_bytes(fee_pips) % MAX_SWAP_FEE,
+        }
+    }
+}
+
+//Inputs of mul_div and mul_div_rounding_up, decoded from 96 bytes as three big endian U256
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct MulDivInput {
+    pub a: U256,
+    pub b: U256,
+    pub denominator: U256,
+}
+
+impl MulDivInput {
+    pub const LEN: usize = 96;
+
+    pub fn decode(data: &[u8]) -> Self {
+        Self {
+            a: u256_from_bytes(field(data, 0, 32)),
+            b: u256_from_bytes(field(data, 32, 32)),
+            denominator: u256_from_bytes(field(data, 64, 32)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use alloy_primitives::{I256, U256};
+
+    use super::{u256_from_bytes, MulDivInput, SwapStepInput};
+    use crate::{
+        fixed_point::SqrtPriceX96,
+        swap_math::MAX_SWAP_FEE,
+        tick_math::{MAX_SQRT_RATIO, MIN_SQRT_RATIO},
+    };
+
+    #[test]
+    fn test_u256_from_bytes() {
+        assert_eq!(u256_from_bytes(&[]), U256::ZERO);
+        assert_eq!(u256_from_bytes(&[0xff; 40]), U256::MAX);
+        assert_eq!(
+            u256_from_bytes(&MAX_SQRT_RATIO.to_be_bytes::<32>()),
+            MAX_SQRT_RATIO
+        );
+        // short inputs are padded on the right
+        assert_eq!(u256_from_bytes(&[1]), U256::from(1) << 248);
+    }
+
+    #[test]
+    fn test_swap_step_input_decode() {
+        assert_eq!(
+            SwapStepInput::decode(&[]),
+            SwapStepInput {
+                sqrt_ratio_current_x_96: SqrtPriceX96(MIN_SQRT_RATIO),
+                sqrt_ratio_target_x_96: SqrtPriceX96(MIN_SQRT_RATIO),
+                liquidity: 0,
+                amount_remaining: I256::ZERO,
+                fee_pips: 0,
+            }
+        );
+
+        let mut data = [0; SwapStepInput::LEN];
+        data[..32].copy_from_slice(&(MAX_SQRT_RATIO - MIN_SQRT_RATIO).to_be_bytes::<32>());
+        data[32..64].copy_from_slice(
+            &(MAX_SQRT_RATIO - MIN_SQRT_RATIO + U256::from(1)).to_be_bytes::<32>(),
+        );
+        data[64..80].copy_from_slice(&u128::MAX.to_be_bytes());
+        data[80..112].copy_from_slice(&I256::MINUS_ONE.into_raw().to_be_bytes::<32>());
+        data[112..].copy_from_slice(&(MAX_SWAP_FEE + 3000).to_be_bytes());
+        assert_eq!(
+            SwapStepInput::decode(&data),
+            SwapStepInput {
+                sqrt_ratio_current_x_96: SqrtPriceX96(MAX_SQRT_RATIO),
+                sqrt_ratio_target_x_96: SqrtPriceX96(MIN_SQRT_RATIO),
+                liquidity: u128::MAX,
+                amount_remaining: I256::MINUS_ONE,
+                fee_pips: 3000,
+            }
+        );
+    }
+
+    #[test]
+    fn test_mul_div_input_decode() {
+        let mut data = [0xff; MulDivInput::LEN + 1];
+        data[95] = 7;
+        assert_eq!(
+            MulDivInput::decode(&data),
+            MulDivInput {
+                a: U256::MAX,
+                b: U256::MAX,
+                denominator: U256::MAX - U256::from(0xf8),
+            }
+        );
+        assert_eq!(MulDivInput::decode(&data[..40]).b, U256::MAX << 192);
+    }
+}
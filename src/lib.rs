@@ -1,43 +1,140 @@
-use alloy::primitives::U256;
+#![cfg_attr(not(any(feature = "std", test)), no_std)]
+
+extern crate alloc;
+
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+use alloy_primitives::{I256, U256};
+
+use crate::{error::UniswapV3MathError, fixed_point::SqrtPriceX96};
+#[cfg(feature = "std")]
+use crate::{pool::PoolState, quoter::QuoteResult, tick::TickInfo, tick_bitmap::TickBitmap};
 
 pub mod bit_math;
 pub mod error;
+#[cfg(feature = "ethers")]
+pub mod ethers;
+pub mod fee_tier;
+pub mod fees;
+pub mod fixed_point;
 pub mod full_math;
+#[cfg(feature = "fuzzing")]
+pub mod fuzz_support;
 pub mod liquidity_math;
+pub mod oracle;
+pub mod pool;
+pub mod pool_address;
+pub mod position;
+pub mod price_format;
+pub mod quoter;
+#[cfg(feature = "serde")]
+pub mod serde_helpers;
+pub mod sqrt;
 pub mod sqrt_price_math;
 pub mod swap_math;
+pub mod swap_simulation;
 pub mod tick;
 pub mod tick_bitmap;
 pub mod tick_math;
+pub mod tick_math_v4;
 pub mod unsafe_math;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
 const U256_1: U256 = U256::from_limbs([1, 0, 0, 0]);
 const U256_2: U256 = U256::from_limbs([2, 0, 0, 0]);
 const U256_3: U256 = U256::from_limbs([3, 0, 0, 0]);
-const U256_4: U256 = U256::from_limbs([4, 0, 0, 0]);
-const U256_5: U256 = U256::from_limbs([5, 0, 0, 0]);
-const U256_6: U256 = U256::from_limbs([6, 0, 0, 0]);
-const U256_7: U256 = U256::from_limbs([7, 0, 0, 0]);
-const U256_8: U256 = U256::from_limbs([8, 0, 0, 0]);
-const U256_15: U256 = U256::from_limbs([15, 0, 0, 0]);
-const U256_16: U256 = U256::from_limbs([16, 0, 0, 0]);
-const U256_32: U256 = U256::from_limbs([32, 0, 0, 0]);
-const U256_64: U256 = U256::from_limbs([64, 0, 0, 0]);
 const U256_127: U256 = U256::from_limbs([127, 0, 0, 0]);
 const U256_128: U256 = U256::from_limbs([128, 0, 0, 0]);
-const U256_255: U256 = U256::from_limbs([255, 0, 0, 0]);
-
-const U256_256: U256 = U256::from_limbs([256, 0, 0, 0]);
-const U256_512: U256 = U256::from_limbs([512, 0, 0, 0]);
-const U256_1024: U256 = U256::from_limbs([1024, 0, 0, 0]);
-const U256_2048: U256 = U256::from_limbs([2048, 0, 0, 0]);
-const U256_4096: U256 = U256::from_limbs([4096, 0, 0, 0]);
-const U256_8192: U256 = U256::from_limbs([8192, 0, 0, 0]);
-const U256_16384: U256 = U256::from_limbs([16384, 0, 0, 0]);
-const U256_32768: U256 = U256::from_limbs([32768, 0, 0, 0]);
-const U256_65536: U256 = U256::from_limbs([65536, 0, 0, 0]);
-const U256_131072: U256 = U256::from_limbs([131072, 0, 0, 0]);
-const U256_262144: U256 = U256::from_limbs([262144, 0, 0, 0]);
-const U256_524288: U256 = U256::from_limbs([524288, 0, 0, 0]);
-
-const U256_MAX_TICK: U256 = U256::from_limbs([887272, 0, 0, 0]);
+
+/// Entry point to the most used quoting functions, each one a thin wrapper over the function of the same name in
+/// its module.
+///
+/// Quoting a swap in a pool built from scratch:
+///
+/// ```
+/// use alloy_primitives::{Address, U256};
+/// use uniswap_v3_math::{
+///     pool::{Pool, PoolState},
+///     UniswapV3QuoterMath,
+/// };
+///
+/// # fn main() -> Result<(), uniswap_v3_math::error::UniswapV3MathError> {
+/// // a 0.3% pool at a price of 1 with full range liquidity
+/// let mut pool = Pool::new(PoolState {
+///     sqrt_price_x_96: UniswapV3QuoterMath::get_sqrt_ratio_at_tick(0)?,
+///     fee: 3000,
+///     tick_spacing: 60,
+///     ..Default::default()
+/// });
+/// pool.mint(Address::ZERO, -887220, 887220, 10_u128.pow(21))?;
+///
+/// let quote = UniswapV3QuoterMath::get_output_amount(
+///     &mut pool.state,
+///     &pool.tick_bitmap,
+///     &pool.ticks,
+///     U256::from(10_u128.pow(18)),
+///     true,
+/// )?;
+/// assert!(quote.amount_out < U256::from(10_u128.pow(18)));
+/// assert_eq!(
+///     UniswapV3QuoterMath::get_tick_at_sqrt_ratio(quote.sqrt_price_after_x_96)?,
+///     quote.tick_after
+/// );
+/// # Ok(())
+/// # }
+/// ```
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct UniswapV3QuoterMath;
+
+impl UniswapV3QuoterMath {
+    pub const fn new() -> Self {
+        Self
+    }
+
+    /// See [`tick_math::get_sqrt_ratio_at_tick`].
+    #[inline(always)]
+    pub const fn get_sqrt_ratio_at_tick(tick: i32) -> Result<SqrtPriceX96, UniswapV3MathError> {
+        tick_math::get_sqrt_ratio_at_tick(tick)
+    }
+
+    /// See [`tick_math::get_tick_at_sqrt_ratio`].
+    #[inline(always)]
+    pub fn get_tick_at_sqrt_ratio(
+        sqrt_price_x_96: SqrtPriceX96,
+    ) -> Result<i32, UniswapV3MathError> {
+        tick_math::get_tick_at_sqrt_ratio(sqrt_price_x_96)
+    }
+
+    /// See [`swap_math::compute_swap_step`].
+    #[inline(always)]
+    pub fn compute_swap_step(
+        sqrt_ratio_current_x_96: SqrtPriceX96,
+        sqrt_ratio_target_x_96: SqrtPriceX96,
+        liquidity: u128,
+        amount_remaining: I256,
+        fee_pips: u32,
+    ) -> Result<(SqrtPriceX96, U256, U256, U256), UniswapV3MathError> {
+        swap_math::compute_swap_step(
+            sqrt_ratio_current_x_96,
+            sqrt_ratio_target_x_96,
+            liquidity,
+            amount_remaining,
+            fee_pips,
+        )
+    }
+
+    /// See [`quoter::get_output_amount`].
+    #[cfg(feature = "std")]
+    #[inline(always)]
+    pub fn get_output_amount(
+        pool_state: &mut PoolState,
+        tick_bitmap: &TickBitmap,
+        ticks: &HashMap<i32, TickInfo>,
+        amount_in: U256,
+        zero_for_one: bool,
+    ) -> Result<QuoteResult, UniswapV3MathError> {
+        quoter::get_output_amount(pool_state, tick_bitmap, ticks, amount_in, zero_for_one)
+    }
+}
@@ -1,29 +1,154 @@
+use alloc::vec::Vec;
+
 use crate::error::UniswapV3MathError;
 
 // returns (uint128 z)
 pub fn add_delta(x: u128, y: i128) -> Result<u128, UniswapV3MathError> {
     if y < 0 {
-        let z = x.overflowing_sub(-y as u128);
+        let z = x.overflowing_sub(y.unsigned_abs());
 
         if z.1 {
-            Err(UniswapV3MathError::LiquiditySub)
+            Err(UniswapV3MathError::LiquidityUnderflow {
+                liquidity: x,
+                delta: y,
+            })
         } else {
             Ok(z.0)
         }
     } else {
         let z = x.overflowing_add(y as u128);
         if z.0 < x {
-            Err(UniswapV3MathError::LiquidityAdd)
+            Err(UniswapV3MathError::LiquidityOverflow {
+                liquidity: x,
+                delta: y,
+            })
         } else {
             Ok(z.0)
         }
     }
 }
 
+// clamps at 0 and u128::MAX instead of erroring, the bool is true if the result was clamped
+pub fn add_delta_saturating(x: u128, y: i128) -> (u128, bool) {
+    if y < 0 {
+        match x.checked_sub(y.unsigned_abs()) {
+            Some(z) => (z, false),
+            None => (0, true),
+        }
+    } else {
+        match x.checked_add(y as u128) {
+            Some(z) => (z, false),
+            None => (u128::MAX, true),
+        }
+    }
+}
+
+// applies the deltas in order, erroring with the index of the first delta that over or underflows
+pub fn apply_deltas(x: u128, deltas: &[i128]) -> Result<u128, UniswapV3MathError> {
+    deltas
+        .iter()
+        .enumerate()
+        .try_fold(x, |liquidity, (index, &delta)| {
+            add_delta(liquidity, delta).map_err(|_| UniswapV3MathError::LiquidityDeltaFailed {
+                index,
+                liquidity,
+                delta,
+            })
+        })
+}
+
+// liquidity after crossing a tick with the given liquidity_net, which is added left to right and subtracted right
+// to left (zero for one). i128::MIN can not be negated, so the subtraction goes through its unsigned magnitude.
+// Errors carry the liquidity_net of the tick as delta
+pub fn cross_tick(
+    current_liquidity: u128,
+    liquidity_net: i128,
+    zero_for_one: bool,
+) -> Result<u128, UniswapV3MathError> {
+    if !zero_for_one {
+        return add_delta(current_liquidity, liquidity_net);
+    }
+
+    let magnitude = liquidity_net.unsigned_abs();
+    let (liquidity, failed) = if liquidity_net < 0 {
+        current_liquidity.overflowing_add(magnitude)
+    } else {
+        current_liquidity.overflowing_sub(magnitude)
+    };
+    match (failed, liquidity_net < 0) {
+        (false, _) => Ok(liquidity),
+        (true, true) => Err(UniswapV3MathError::LiquidityOverflow {
+            liquidity: current_liquidity,
+            delta: liquidity_net,
+        }),
+        (true, false) => Err(UniswapV3MathError::LiquidityUnderflow {
+            liquidity: current_liquidity,
+            delta: liquidity_net,
+        }),
+    }
+}
+
+//Liquidity provided on [lower, upper), e.g. a position or the aggregate of the positions on that range
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct LiquidityRange {
+    pub lower: i32,
+    pub upper: i32,
+    pub liquidity: u128,
+}
+
+// liquidity that is active when the pool is at tick, the sum over the ranges with lower <= tick < upper. The sum
+// saturates at u128::MAX, pools cap the liquidity per tick far below it
+pub fn get_active_liquidity_at_tick(ranges: &[LiquidityRange], tick: i32) -> u128 {
+    ranges
+        .iter()
+        .filter(|range| range.lower <= tick && tick < range.upper)
+        .fold(0u128, |liquidity, range| {
+            liquidity.saturating_add(range.liquidity)
+        })
+}
+
+// (tick, active liquidity) for every multiple of tick_spacing in [from_tick, to_tick], empty if the spacing is
+// not positive or the bounds are reversed
+pub fn build_liquidity_depth_chart(
+    ranges: &[LiquidityRange],
+    from_tick: i32,
+    to_tick: i32,
+    tick_spacing: i32,
+) -> Vec<(i32, u128)> {
+    if tick_spacing <= 0 || from_tick > to_tick {
+        return Vec::new();
+    }
+
+    // first multiple of the spacing at or above from_tick, widened so that rounding up can not overflow
+    let first_tick = (from_tick as i64).div_euclid(tick_spacing as i64) * tick_spacing as i64
+        + if from_tick.rem_euclid(tick_spacing) == 0 {
+            0
+        } else {
+            tick_spacing as i64
+        };
+
+    (first_tick..=to_tick as i64)
+        .step_by(tick_spacing as usize)
+        .map(|tick| {
+            let tick = tick as i32;
+            (tick, get_active_liquidity_at_tick(ranges, tick))
+        })
+        .collect()
+}
+
 #[cfg(test)]
 mod test {
 
-    use crate::liquidity_math::add_delta;
+    use proptest::prelude::*;
+
+    use crate::{
+        error::UniswapV3MathError,
+        liquidity_math::{
+            add_delta, add_delta_saturating, apply_deltas, build_liquidity_depth_chart, cross_tick,
+            get_active_liquidity_at_tick, LiquidityRange,
+        },
+    };
 
     #[test]
     fn test_add_delta() {
@@ -50,5 +175,217 @@ mod test {
         // 3 + -4 underflows
         let result = add_delta(3, -4);
         assert_eq!(result.err().unwrap().to_string(), "Liquidity Sub");
+
+        // errors carry the offending values
+        let result = add_delta(3, -4);
+        assert!(matches!(
+            result,
+            Err(UniswapV3MathError::LiquidityUnderflow {
+                liquidity: 3,
+                delta: -4
+            })
+        ));
+        let result = add_delta(u128::MAX, 1);
+        assert!(matches!(
+            result,
+            Err(UniswapV3MathError::LiquidityOverflow {
+                liquidity: u128::MAX,
+                delta: 1
+            })
+        ));
+
+        // i128::MIN can not be negated in i128
+        let result = add_delta(u128::MAX, i128::MIN);
+        assert_eq!(result.unwrap(), u128::MAX - (1 << 127));
+        let result = add_delta((1 << 127) - 1, i128::MIN);
+        assert!(matches!(
+            result,
+            Err(UniswapV3MathError::LiquidityUnderflow { .. })
+        ));
+    }
+
+    #[test]
+    fn test_add_delta_saturating() {
+        assert_eq!(add_delta_saturating(1, -1), (0, false));
+        assert_eq!(add_delta_saturating(1, 1), (2, false));
+        assert_eq!(add_delta_saturating(3, -4), (0, true));
+        assert_eq!(add_delta_saturating(u128::MAX, 0), (u128::MAX, false));
+        assert_eq!(add_delta_saturating(u128::MAX, 1), (u128::MAX, true));
+        assert_eq!(add_delta_saturating(0, i128::MIN), (0, true));
+        assert_eq!(add_delta_saturating(1 << 127, i128::MIN), (0, false));
+        assert_eq!(
+            add_delta_saturating(u128::MAX, i128::MAX),
+            (u128::MAX, true)
+        );
+    }
+
+    #[test]
+    fn test_apply_deltas() {
+        assert_eq!(apply_deltas(5, &[]).unwrap(), 5);
+        assert_eq!(apply_deltas(5, &[10, -3, -12]).unwrap(), 0);
+
+        // the liquidity carried by the error is the one before the failing delta
+        assert!(matches!(
+            apply_deltas(5, &[10, -3, -13, 4]),
+            Err(UniswapV3MathError::LiquidityDeltaFailed {
+                index: 2,
+                liquidity: 12,
+                delta: -13
+            })
+        ));
+        assert!(matches!(
+            apply_deltas(u128::MAX - 1, &[1, 1]),
+            Err(UniswapV3MathError::LiquidityDeltaFailed { index: 1, .. })
+        ));
+        assert!(matches!(
+            apply_deltas(0, &[i128::MIN]),
+            Err(UniswapV3MathError::LiquidityDeltaFailed { index: 0, .. })
+        ));
+    }
+
+    #[test]
+    fn test_cross_tick() {
+        // left to right adds liquidity_net, right to left subtracts it
+        assert_eq!(cross_tick(10, 4, false).unwrap(), 14);
+        assert_eq!(cross_tick(10, 4, true).unwrap(), 6);
+        assert_eq!(cross_tick(10, -4, false).unwrap(), 6);
+        assert_eq!(cross_tick(10, -4, true).unwrap(), 14);
+
+        // zero liquidity_net leaves the liquidity as is
+        assert_eq!(cross_tick(10, 0, false).unwrap(), 10);
+        assert_eq!(cross_tick(10, 0, true).unwrap(), 10);
+        assert_eq!(cross_tick(0, 0, true).unwrap(), 0);
+
+        // i128::MIN subtracted right to left adds 2**127
+        assert_eq!(cross_tick(0, i128::MIN, true).unwrap(), 1 << 127);
+        assert_eq!(cross_tick(1 << 127, i128::MIN, false).unwrap(), 0);
+        assert!(matches!(
+            cross_tick(1 << 127, i128::MIN, true),
+            Err(UniswapV3MathError::LiquidityOverflow {
+                delta: i128::MIN,
+                ..
+            })
+        ));
+        assert!(matches!(
+            cross_tick(0, i128::MIN, false),
+            Err(UniswapV3MathError::LiquidityUnderflow { .. })
+        ));
+
+        assert!(matches!(
+            cross_tick(3, 4, true),
+            Err(UniswapV3MathError::LiquidityUnderflow {
+                liquidity: 3,
+                delta: 4
+            })
+        ));
+        assert!(matches!(
+            cross_tick(u128::MAX, -1, true),
+            Err(UniswapV3MathError::LiquidityOverflow { .. })
+        ));
+    }
+
+    #[test]
+    fn test_liquidity_depth_chart() {
+        let ranges = [
+            LiquidityRange {
+                lower: -120,
+                upper: 120,
+                liquidity: 100,
+            },
+            LiquidityRange {
+                lower: -60,
+                upper: 60,
+                liquidity: 20,
+            },
+            LiquidityRange {
+                lower: 0,
+                upper: 180,
+                liquidity: 3,
+            },
+            LiquidityRange {
+                lower: 0,
+                upper: 60,
+                liquidity: 4000,
+            },
+        ];
+
+        // lower bounds are inclusive, upper bounds exclusive
+        assert_eq!(get_active_liquidity_at_tick(&ranges, -121), 0);
+        assert_eq!(get_active_liquidity_at_tick(&ranges, -120), 100);
+        assert_eq!(get_active_liquidity_at_tick(&ranges, -1), 120);
+        assert_eq!(get_active_liquidity_at_tick(&ranges, 0), 4123);
+        assert_eq!(get_active_liquidity_at_tick(&ranges, 59), 4123);
+        assert_eq!(get_active_liquidity_at_tick(&ranges, 60), 103);
+        assert_eq!(get_active_liquidity_at_tick(&ranges, 120), 3);
+        assert_eq!(get_active_liquidity_at_tick(&ranges, 180), 0);
+        assert_eq!(get_active_liquidity_at_tick(&[], 0), 0);
+
+        // from_tick is rounded up to the spacing, to_tick is included
+        assert_eq!(
+            build_liquidity_depth_chart(&ranges, -150, 180, 60),
+            vec![
+                (-120, 100),
+                (-60, 120),
+                (0, 4123),
+                (60, 103),
+                (120, 3),
+                (180, 0)
+            ]
+        );
+        assert_eq!(
+            build_liquidity_depth_chart(&ranges, 1, 60, 60),
+            vec![(60, 103)]
+        );
+
+        // every point of a finer chart is the sum over the ranges containing it
+        for (tick, liquidity) in build_liquidity_depth_chart(&ranges, -200, 200, 10) {
+            let expected: u128 = ranges
+                .iter()
+                .filter(|range| (range.lower..range.upper).contains(&tick))
+                .map(|range| range.liquidity)
+                .sum();
+            assert_eq!(liquidity, expected, "{tick}");
+        }
+
+        assert!(build_liquidity_depth_chart(&ranges, 60, 0, 60).is_empty());
+        assert!(build_liquidity_depth_chart(&ranges, 0, 60, 0).is_empty());
+        assert_eq!(
+            build_liquidity_depth_chart(&ranges, i32::MAX - 5, i32::MAX, 60).len(),
+            0
+        );
+    }
+
+    proptest! {
+        #[test]
+        fn test_apply_deltas_commutative(x in any::<u64>(), deltas in prop::collection::vec(any::<i64>(), 0..16)) {
+            // u64 sized values keep every partial sum within u128, only the sign can go wrong
+            let deltas: Vec<i128> = deltas.into_iter().map(i128::from).collect();
+            let total = deltas.iter().sum::<i128>() + x as i128;
+            let mut reversed = deltas.clone();
+            reversed.reverse();
+
+            let mut sorted = deltas.clone();
+            sorted.sort_unstable_by(|a, b| b.cmp(a));
+            // applying the increases first never underflows when the total is non negative
+            if total >= 0 {
+                prop_assert_eq!(apply_deltas(x as u128, &sorted).unwrap(), total as u128);
+            }
+            if let (Ok(forward), Ok(backward)) = (apply_deltas(x as u128, &deltas), apply_deltas(x as u128, &reversed)) {
+                prop_assert_eq!(forward, backward);
+                prop_assert_eq!(forward, total as u128);
+            }
+        }
+
+        #[test]
+        fn test_add_delta_saturating_matches_add_delta(x in any::<u128>(), y in any::<i128>()) {
+            let (z, clamped) = add_delta_saturating(x, y);
+            match add_delta(x, y) {
+                Ok(expected) => prop_assert_eq!((z, clamped), (expected, false)),
+                Err(_) => {
+                    prop_assert!(clamped);
+                    prop_assert_eq!(z, if y < 0 { 0 } else { u128::MAX });
+                }
+            }
+        }
     }
 }
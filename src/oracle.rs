@@ -0,0 +1,930 @@
+use alloc::vec::Vec;
+
+use alloy_primitives::U256;
+
+use crate::{
+    error::UniswapV3MathError, fixed_point::SqrtPriceX96, sqrt_price_math::MAX_U160,
+    tick_math::get_sqrt_ratio_at_tick,
+};
+
+//Oracle.Observation, tick_cumulative is an int56 on chain
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Observation {
+    pub block_timestamp: u32,
+    pub tick_cumulative: i64,
+    pub seconds_per_liquidity_cumulative_x_128: U256,
+    pub initialized: bool,
+}
+
+// returns (Observation memory), the last observation moved forward to block_timestamp
+pub fn transform(
+    last: &Observation,
+    block_timestamp: u32,
+    tick: i32,
+    liquidity: u128,
+) -> Observation {
+    let delta = block_timestamp.wrapping_sub(last.block_timestamp);
+
+    Observation {
+        block_timestamp,
+        tick_cumulative: last
+            .tick_cumulative
+            .wrapping_add(tick as i64 * delta as i64),
+        seconds_per_liquidity_cumulative_x_128: last
+            .seconds_per_liquidity_cumulative_x_128
+            .wrapping_add((U256::from(delta) << 128) / U256::from(liquidity.max(1)))
+            & MAX_U160,
+        initialized: true,
+    }
+}
+
+// returns (Observation memory), the first observation of a pool, written to index 0 with a cardinality of 1
+pub fn initialize(time: u32) -> Observation {
+    Observation {
+        block_timestamp: time,
+        initialized: true,
+        ..Default::default()
+    }
+}
+
+// returns (uint16 indexUpdated, uint16 cardinalityUpdated). Writes at most one observation per block, moving into
+// cardinality_next once the last slot of the current cardinality has been written
+pub fn write(
+    observations: &mut [Observation],
+    index: u16,
+    block_timestamp: u32,
+    tick: i32,
+    liquidity: u128,
+    cardinality: u16,
+    cardinality_next: u16,
+) -> Result<(u16, u16), UniswapV3MathError> {
+    if cardinality == 0 || cardinality_next as usize > observations.len() {
+        return Err(UniswapV3MathError::InvalidObservationCardinality {
+            cardinality: cardinality.max(cardinality_next),
+        });
+    }
+
+    let last = observations[index as usize];
+
+    // early return if we've already written an observation this block
+    if last.block_timestamp == block_timestamp {
+        return Ok((index, cardinality));
+    }
+
+    // if the conditions are right, we can bump the cardinality
+    let cardinality_updated = if cardinality_next > cardinality && index == cardinality - 1 {
+        cardinality_next
+    } else {
+        cardinality
+    };
+
+    let index_updated = (index + 1) % cardinality_updated;
+    observations[index_updated as usize] = transform(&last, block_timestamp, tick, liquidity);
+
+    Ok((index_updated, cardinality_updated))
+}
+
+// returns (uint16), the cardinality_next after preparing the slots up to next. Like the contract the new slots get a
+// non zero timestamp so that the first write into them is not the first one to pay for the storage
+pub fn grow(
+    observations: &mut Vec<Observation>,
+    current: u16,
+    next: u16,
+) -> Result<u16, UniswapV3MathError> {
+    if current == 0 {
+        return Err(UniswapV3MathError::InvalidObservationCardinality { cardinality: 0 });
+    }
+
+    // no-op if the passed next value isn't greater than the current next value
+    if next <= current {
+        return Ok(current);
+    }
+
+    for i in current as usize..next as usize {
+        match observations.get_mut(i) {
+            Some(observation) => observation.block_timestamp = 1,
+            None => observations.push(Observation {
+                block_timestamp: 1,
+                ..Default::default()
+            }),
+        }
+    }
+
+    Ok(next)
+}
+
+//Vec backed observation ring buffer along with the slot0 fields that index it
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct ObservationBuffer {
+    pub observations: Vec<Observation>,
+    pub index: u16,
+    pub cardinality: u16,
+    pub cardinality_next: u16,
+}
+
+impl ObservationBuffer {
+    //Buffer of a pool initialized at time
+    pub fn new(time: u32) -> Self {
+        Self {
+            observations: alloc::vec![initialize(time)],
+            index: 0,
+            cardinality: 1,
+            cardinality_next: 1,
+        }
+    }
+
+    //Writes an observation with the tick and liquidity that were active up to block_timestamp
+    pub fn write(
+        &mut self,
+        block_timestamp: u32,
+        tick: i32,
+        liquidity: u128,
+    ) -> Result<(), UniswapV3MathError> {
+        (self.index, self.cardinality) = write(
+            &mut self.observations,
+            self.index,
+            block_timestamp,
+            tick,
+            liquidity,
+            self.cardinality,
+            self.cardinality_next,
+        )?;
+        Ok(())
+    }
+
+    //Increases cardinality_next, the buffer only grows into the new slots once the current ones have been written
+    pub fn grow(&mut self, cardinality_next: u16) -> Result<(), UniswapV3MathError> {
+        self.cardinality_next = grow(
+            &mut self.observations,
+            self.cardinality_next,
+            cardinality_next,
+        )?;
+        Ok(())
+    }
+
+    // returns (int56[] tickCumulatives, uint160[] secondsPerLiquidityCumulativeX128s) for each of seconds_agos
+    pub fn observe(
+        &self,
+        time: u32,
+        seconds_agos: &[u32],
+        tick: i32,
+        liquidity: u128,
+    ) -> Result<(Vec<i64>, Vec<U256>), UniswapV3MathError> {
+        observe(
+            &self.observations,
+            time,
+            seconds_agos,
+            tick,
+            self.index,
+            liquidity,
+            self.cardinality,
+        )
+    }
+}
+
+// returns (int56[] tickCumulatives, uint160[] secondsPerLiquidityCumulativeX128s) for each of seconds_agos,
+// time is the current block timestamp and index the position of the newest observation
+pub fn observe(
+    observations: &[Observation],
+    time: u32,
+    seconds_agos: &[u32],
+    tick: i32,
+    index: u16,
+    liquidity: u128,
+    cardinality: u16,
+) -> Result<(Vec<i64>, Vec<U256>), UniswapV3MathError> {
+    if cardinality == 0 || cardinality as usize > observations.len() {
+        return Err(UniswapV3MathError::InvalidObservationCardinality { cardinality });
+    }
+
+    let mut tick_cumulatives = Vec::with_capacity(seconds_agos.len());
+    let mut seconds_per_liquidity_cumulative_x_128s = Vec::with_capacity(seconds_agos.len());
+    for &seconds_ago in seconds_agos {
+        let (tick_cumulative, seconds_per_liquidity_cumulative_x_128) = observe_single(
+            observations,
+            time,
+            seconds_ago,
+            tick,
+            index,
+            liquidity,
+            cardinality,
+        )?;
+        tick_cumulatives.push(tick_cumulative);
+        seconds_per_liquidity_cumulative_x_128s.push(seconds_per_liquidity_cumulative_x_128);
+    }
+
+    Ok((tick_cumulatives, seconds_per_liquidity_cumulative_x_128s))
+}
+
+// returns (int56 tickCumulative, uint160 secondsPerLiquidityCumulativeX128) seconds_ago before time
+pub fn observe_single(
+    observations: &[Observation],
+    time: u32,
+    seconds_ago: u32,
+    tick: i32,
+    index: u16,
+    liquidity: u128,
+    cardinality: u16,
+) -> Result<(i64, U256), UniswapV3MathError> {
+    if seconds_ago == 0 {
+        let mut last = observations[index as usize];
+        if last.block_timestamp != time {
+            last = transform(&last, time, tick, liquidity);
+        }
+        return Ok((
+            last.tick_cumulative,
+            last.seconds_per_liquidity_cumulative_x_128,
+        ));
+    }
+
+    let target = time.wrapping_sub(seconds_ago);
+
+    let (before_or_at, at_or_after) = get_surrounding_observations(
+        observations,
+        time,
+        target,
+        tick,
+        index,
+        liquidity,
+        cardinality,
+    )?;
+
+    if target == before_or_at.block_timestamp {
+        // we're at the left boundary
+        Ok((
+            before_or_at.tick_cumulative,
+            before_or_at.seconds_per_liquidity_cumulative_x_128,
+        ))
+    } else if target == at_or_after.block_timestamp {
+        // we're at the right boundary
+        Ok((
+            at_or_after.tick_cumulative,
+            at_or_after.seconds_per_liquidity_cumulative_x_128,
+        ))
+    } else {
+        // we're in the middle
+        let observation_time_delta = at_or_after
+            .block_timestamp
+            .wrapping_sub(before_or_at.block_timestamp) as i64;
+        let target_delta = target.wrapping_sub(before_or_at.block_timestamp) as i64;
+
+        Ok((
+            before_or_at.tick_cumulative
+                + ((at_or_after.tick_cumulative - before_or_at.tick_cumulative)
+                    / observation_time_delta)
+                    * target_delta,
+            before_or_at
+                .seconds_per_liquidity_cumulative_x_128
+                .wrapping_add(
+                    (at_or_after
+                        .seconds_per_liquidity_cumulative_x_128
+                        .wrapping_sub(before_or_at.seconds_per_liquidity_cumulative_x_128)
+                        & MAX_U160)
+                        * U256::from(target_delta)
+                        / U256::from(observation_time_delta),
+                )
+                & MAX_U160,
+        ))
+    }
+}
+
+// returns (int24 arithmeticMeanTick) over the last seconds_ago seconds, rounded towards negative infinity like
+// OracleLibrary.consult
+pub fn get_twap_tick(
+    observations: &[Observation],
+    seconds_ago: u32,
+    time: u32,
+    tick: i32,
+    index: u16,
+    liquidity: u128,
+    cardinality: u16,
+) -> Result<i32, UniswapV3MathError> {
+    if seconds_ago == 0 {
+        return Err(UniswapV3MathError::DenominatorIsZero);
+    }
+
+    let (tick_cumulatives, _) = observe(
+        observations,
+        time,
+        &[seconds_ago, 0],
+        tick,
+        index,
+        liquidity,
+        cardinality,
+    )
+    .map_err(|err| match err {
+        UniswapV3MathError::ObservationTooOld { .. } => {
+            UniswapV3MathError::ObservationCardinalityTooLow {
+                cardinality,
+                seconds_ago,
+            }
+        }
+        err => err,
+    })?;
+
+    arithmetic_mean_tick(tick_cumulatives[0], tick_cumulatives[1], seconds_ago)
+}
+
+// returns (int24 arithmeticMeanTick) between two tick cumulatives elapsed seconds apart, rounded towards negative
+// infinity like OracleLibrary.consult
+pub fn arithmetic_mean_tick(
+    tick_cumulative_start: i64,
+    tick_cumulative_end: i64,
+    elapsed: u32,
+) -> Result<i32, UniswapV3MathError> {
+    if elapsed == 0 {
+        return Err(UniswapV3MathError::DenominatorIsZero);
+    }
+
+    let tick_cumulatives_delta = tick_cumulative_end.wrapping_sub(tick_cumulative_start);
+    let mut arithmetic_mean_tick = (tick_cumulatives_delta / elapsed as i64) as i32;
+    // always round to negative infinity
+    if tick_cumulatives_delta < 0 && tick_cumulatives_delta % elapsed as i64 != 0 {
+        arithmetic_mean_tick -= 1;
+    }
+
+    Ok(arithmetic_mean_tick)
+}
+
+// returns (uint160 sqrtPriceX96) at the arithmetic mean tick between two tick cumulatives
+pub fn mean_sqrt_price_x96(
+    tick_cumulative_start: i64,
+    tick_cumulative_end: i64,
+    elapsed: u32,
+) -> Result<SqrtPriceX96, UniswapV3MathError> {
+    get_sqrt_ratio_at_tick(arithmetic_mean_tick(
+        tick_cumulative_start,
+        tick_cumulative_end,
+        elapsed,
+    )?)
+}
+
+// returns (uint128 harmonicMeanLiquidity) between two seconds per liquidity cumulatives elapsed seconds apart, like
+// OracleLibrary.consult
+pub fn harmonic_mean_liquidity(
+    seconds_per_liquidity_cumulative_start_x_128: U256,
+    seconds_per_liquidity_cumulative_end_x_128: U256,
+    elapsed: u32,
+) -> Result<u128, UniswapV3MathError> {
+    let seconds_per_liquidity_cumulatives_delta = seconds_per_liquidity_cumulative_end_x_128
+        .wrapping_sub(seconds_per_liquidity_cumulative_start_x_128)
+        & MAX_U160;
+    if seconds_per_liquidity_cumulatives_delta.is_zero() {
+        return Err(UniswapV3MathError::DenominatorIsZero);
+    }
+
+    // uint192(secondsAgo) * type(uint160).max / (uint192(delta) << 32), the product fits into 192 bits
+    let seconds_ago_x_160 = U256::from(elapsed) * MAX_U160;
+    let harmonic_mean_liquidity: U256 =
+        seconds_ago_x_160 / (seconds_per_liquidity_cumulatives_delta << 32);
+    Ok(harmonic_mean_liquidity.wrapping_to())
+}
+
+// comparator for 32-bit timestamps, safe for 0 or 1 overflows as a and b are chronologically before or at time
+fn lte(time: u32, a: u32, b: u32) -> bool {
+    // if there hasn't been overflow, no need to adjust
+    if a <= time && b <= time {
+        return a <= b;
+    }
+
+    let a_adjusted = if a > time {
+        a as u64
+    } else {
+        a as u64 + (1 << 32)
+    };
+    let b_adjusted = if b > time {
+        b as u64
+    } else {
+        b as u64 + (1 << 32)
+    };
+
+    a_adjusted <= b_adjusted
+}
+
+// returns (Observation beforeOrAt, Observation atOrAfter) surrounding target, which must lie between the oldest
+// and the newest initialized observation
+fn binary_search(
+    observations: &[Observation],
+    time: u32,
+    target: u32,
+    index: u16,
+    cardinality: u16,
+) -> (Observation, Observation) {
+    let cardinality = cardinality as usize;
+    let mut l = (index as usize + 1) % cardinality; // oldest observation
+    let mut r = l + cardinality - 1; // newest observation
+
+    loop {
+        let i = (l + r) / 2;
+
+        let before_or_at = observations[i % cardinality];
+
+        // we've landed on an uninitialized tick, keep searching higher (more recently)
+        if !before_or_at.initialized {
+            l = i + 1;
+            continue;
+        }
+
+        let at_or_after = observations[(i + 1) % cardinality];
+
+        let target_at_or_after = lte(time, before_or_at.block_timestamp, target);
+
+        // check if we've found the answer!
+        if target_at_or_after && lte(time, target, at_or_after.block_timestamp) {
+            return (before_or_at, at_or_after);
+        }
+
+        if !target_at_or_after {
+            r = i - 1;
+        } else {
+            l = i + 1;
+        }
+    }
+}
+
+// returns (Observation beforeOrAt, Observation atOrAfter), transforming the newest observation when the target
+// is after it
+fn get_surrounding_observations(
+    observations: &[Observation],
+    time: u32,
+    target: u32,
+    tick: i32,
+    index: u16,
+    liquidity: u128,
+    cardinality: u16,
+) -> Result<(Observation, Observation), UniswapV3MathError> {
+    // optimistically set before to the newest observation
+    let before_or_at = observations[index as usize];
+
+    // if the target is chronologically at or after the newest observation, we can early return
+    if lte(time, before_or_at.block_timestamp, target) {
+        if before_or_at.block_timestamp == target {
+            // if newest observation equals target, we're in the same block, so we can ignore atOrAfter
+            return Ok((before_or_at, Observation::default()));
+        } else {
+            // otherwise, we need to transform
+            return Ok((
+                before_or_at,
+                transform(&before_or_at, target, tick, liquidity),
+            ));
+        }
+    }
+
+    // now, set before to the oldest observation
+    let mut before_or_at = observations[(index as usize + 1) % cardinality as usize];
+    if !before_or_at.initialized {
+        before_or_at = observations[0];
+    }
+
+    // ensure that the target is chronologically at or after the oldest observation
+    if !lte(time, before_or_at.block_timestamp, target) {
+        return Err(UniswapV3MathError::ObservationTooOld {
+            target,
+            oldest: before_or_at.block_timestamp,
+        });
+    }
+
+    // if we've reached this point, we have to binary search
+    Ok(binary_search(
+        observations,
+        time,
+        target,
+        index,
+        cardinality,
+    ))
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use alloy_primitives::U256;
+
+    use crate::error::UniswapV3MathError;
+
+    use super::{
+        arithmetic_mean_tick, get_twap_tick, grow, harmonic_mean_liquidity, initialize,
+        mean_sqrt_price_x96, observe, transform, Observation, ObservationBuffer,
+    };
+    use crate::tick_math::get_sqrt_ratio_at_tick;
+
+    // OracleTest, the tick and liquidity given to update take effect after the observation is written
+    struct OracleTest {
+        buffer: ObservationBuffer,
+        time: u32,
+        tick: i32,
+        liquidity: u128,
+    }
+
+    impl OracleTest {
+        fn new() -> Self {
+            Self {
+                buffer: ObservationBuffer::new(0),
+                time: 0,
+                tick: 0,
+                liquidity: 0,
+            }
+        }
+
+        fn update(&mut self, advance_time_by: u32, tick: i32, liquidity: u128) {
+            self.time += advance_time_by;
+            self.buffer
+                .write(self.time, self.tick, self.liquidity)
+                .unwrap();
+            self.tick = tick;
+            self.liquidity = liquidity;
+        }
+    }
+
+    fn observation(
+        block_timestamp: u32,
+        tick_cumulative: i64,
+        seconds_per_liquidity_cumulative_x_128: &str,
+    ) -> Observation {
+        Observation {
+            block_timestamp,
+            tick_cumulative,
+            seconds_per_liquidity_cumulative_x_128: U256::from_str(
+                seconds_per_liquidity_cumulative_x_128,
+            )
+            .unwrap(),
+            initialized: true,
+        }
+    }
+
+    #[test]
+    fn test_initialize_and_grow() {
+        let mut oracle = OracleTest::new();
+        assert_eq!(oracle.buffer.index, 0);
+        assert_eq!(oracle.buffer.cardinality, 1);
+        assert_eq!(oracle.buffer.cardinality_next, 1);
+        assert_eq!(oracle.buffer.observations, vec![initialize(0)]);
+
+        //increases the cardinality next for the first call, without touching the first slot
+        oracle.buffer.grow(5).unwrap();
+        assert_eq!(oracle.buffer.index, 0);
+        assert_eq!(oracle.buffer.cardinality, 1);
+        assert_eq!(oracle.buffer.cardinality_next, 5);
+        assert_eq!(oracle.buffer.observations[0], initialize(0));
+
+        //adds data to all the new slots
+        for observation in &oracle.buffer.observations[1..] {
+            assert_eq!(
+                *observation,
+                Observation {
+                    block_timestamp: 1,
+                    ..Default::default()
+                }
+            );
+        }
+
+        //is a no-op if the cardinality next is already larger
+        oracle.buffer.grow(3).unwrap();
+        assert_eq!(oracle.buffer.cardinality_next, 5);
+        assert_eq!(oracle.buffer.observations.len(), 5);
+
+        //an uninitialized oracle can not grow
+        assert!(matches!(
+            grow(&mut vec![], 0, 3),
+            Err(UniswapV3MathError::InvalidObservationCardinality { cardinality: 0 })
+        ));
+    }
+
+    #[test]
+    fn test_write() {
+        //single element array gets overwritten
+        let mut oracle = OracleTest::new();
+        oracle.update(1, 2, 5);
+        assert_eq!(oracle.buffer.index, 0);
+        assert_eq!(
+            oracle.buffer.observations[0],
+            observation(1, 0, "340282366920938463463374607431768211456")
+        );
+        oracle.update(5, -1, 8);
+        assert_eq!(oracle.buffer.index, 0);
+        assert_eq!(
+            oracle.buffer.observations[0],
+            observation(6, 10, "680564733841876926926749214863536422912")
+        );
+        oracle.update(3, 2, 3);
+        assert_eq!(oracle.buffer.index, 0);
+        assert_eq!(
+            oracle.buffer.observations[0],
+            observation(9, 7, "808170621437228850725514692650449502208")
+        );
+
+        //does nothing if time has not changed
+        let mut oracle = OracleTest::new();
+        oracle.buffer.grow(2).unwrap();
+        oracle.update(1, 3, 2);
+        assert_eq!(oracle.buffer.index, 1);
+        oracle.update(0, -5, 9);
+        assert_eq!(oracle.buffer.index, 1);
+        assert_eq!(
+            oracle.buffer.observations[1],
+            observation(1, 0, "340282366920938463463374607431768211456")
+        );
+
+        //writes an index if time has changed
+        let mut oracle = OracleTest::new();
+        oracle.buffer.grow(3).unwrap();
+        oracle.update(6, 3, 2);
+        assert_eq!(oracle.buffer.index, 1);
+        oracle.update(4, -5, 9);
+        assert_eq!(oracle.buffer.index, 2);
+        assert_eq!(
+            oracle.buffer.observations[1],
+            observation(6, 0, "2041694201525630780780247644590609268736")
+        );
+        assert_eq!(
+            oracle.buffer.observations[2],
+            observation(10, 12, "2722258935367507707706996859454145691648")
+        );
+
+        //grows cardinality when writing past
+        let mut oracle = OracleTest::new();
+        oracle.buffer.grow(2).unwrap();
+        oracle.buffer.grow(4).unwrap();
+        assert_eq!(oracle.buffer.cardinality, 1);
+        oracle.update(3, 5, 6);
+        assert_eq!(oracle.buffer.cardinality, 4);
+        oracle.update(4, 6, 4);
+        assert_eq!(oracle.buffer.cardinality, 4);
+        assert_eq!(oracle.buffer.index, 2);
+        assert_eq!(
+            oracle.buffer.observations[2],
+            observation(7, 20, "1247702012043441032699040227249816775338")
+        );
+
+        //wraps around
+        let mut oracle = OracleTest::new();
+        oracle.buffer.grow(3).unwrap();
+        oracle.update(3, 1, 2);
+        oracle.update(4, 2, 3);
+        oracle.update(5, 3, 4);
+        assert_eq!(oracle.buffer.index, 0);
+        assert_eq!(
+            oracle.buffer.observations[0],
+            observation(12, 14, "2268549112806256423089164049545121409706")
+        );
+
+        //accumulates liquidity
+        let mut oracle = OracleTest::new();
+        oracle.buffer.grow(4).unwrap();
+        oracle.update(3, 3, 2);
+        oracle.update(4, -7, 6);
+        oracle.update(5, -2, 4);
+        assert_eq!(oracle.buffer.index, 3);
+        assert_eq!(
+            oracle.buffer.observations[1],
+            observation(3, 0, "1020847100762815390390123822295304634368")
+        );
+        assert_eq!(
+            oracle.buffer.observations[2],
+            observation(7, 12, "1701411834604692317316873037158841057280")
+        );
+        assert_eq!(
+            oracle.buffer.observations[3],
+            observation(12, -23, "1984980473705474370203018543351981233493")
+        );
+
+        //the written observations can be observed
+        let (tick_cumulatives, _) = oracle.buffer.observe(14, &[14, 9, 5, 2, 0], -2, 4).unwrap();
+        assert_eq!(tick_cumulatives, vec![0, 6, -2, -23, -27]);
+    }
+
+    #[test]
+    fn test_observe_single_observation() {
+        //initialized at time 5 with tick 2 and liquidity 4, observed at time 8
+        let observations = [initialize(5)];
+
+        let (tick_cumulatives, seconds_per_liquidity) =
+            observe(&observations, 5, &[0], 2, 0, 4, 1).unwrap();
+        assert_eq!(tick_cumulatives, vec![0]);
+        assert_eq!(seconds_per_liquidity, vec![U256::ZERO]);
+
+        //at exactly seconds ago
+        let (tick_cumulatives, seconds_per_liquidity) =
+            observe(&observations, 8, &[3], 2, 0, 4, 1).unwrap();
+        assert_eq!(tick_cumulatives, vec![0]);
+        assert_eq!(seconds_per_liquidity, vec![U256::ZERO]);
+
+        //counterfactual in the past and now
+        let (tick_cumulatives, seconds_per_liquidity) =
+            observe(&observations, 8, &[1, 0], 2, 0, 4, 1).unwrap();
+        assert_eq!(tick_cumulatives, vec![4, 6]);
+        assert_eq!(
+            seconds_per_liquidity,
+            vec![
+                U256::from(170141183460469231731687303715884105728_u128),
+                U256::from(255211775190703847597530955573826158592_u128)
+            ]
+        );
+
+        //older than the only observation
+        assert!(matches!(
+            observe(&observations, 8, &[4], 2, 0, 4, 1),
+            Err(UniswapV3MathError::ObservationTooOld {
+                target: 4,
+                oldest: 5
+            })
+        ));
+
+        assert!(matches!(
+            observe(&observations, 8, &[0], 2, 0, 4, 0),
+            Err(UniswapV3MathError::InvalidObservationCardinality { cardinality: 0 })
+        ));
+        assert!(matches!(
+            observe(&observations, 8, &[0], 2, 0, 4, 2),
+            Err(UniswapV3MathError::InvalidObservationCardinality { cardinality: 2 })
+        ));
+    }
+
+    #[test]
+    fn test_observe_interpolation() {
+        //initialized at time 5 with tick -5 and liquidity 5, then tick 1 and liquidity 2 from time 9
+        let first = initialize(5);
+        let second = transform(&first, 9, -5, 5);
+        let observations = [first, second];
+
+        assert_eq!(second.tick_cumulative, -20);
+        assert_eq!(
+            second.seconds_per_liquidity_cumulative_x_128,
+            U256::from(272225893536750770770699685945414569164_u128)
+        );
+
+        //exactly on each observation and halfway in between
+        let (tick_cumulatives, seconds_per_liquidity) =
+            observe(&observations, 9, &[4, 2, 0], 1, 1, 2, 2).unwrap();
+        assert_eq!(tick_cumulatives, vec![0, -10, -20]);
+        assert_eq!(
+            seconds_per_liquidity,
+            vec![
+                U256::ZERO,
+                U256::from(136112946768375385385349842972707284582_u128),
+                U256::from(272225893536750770770699685945414569164_u128)
+            ]
+        );
+
+        //the interpolated tick cumulative rounds the per second delta towards zero like int56 division
+        let third = Observation {
+            block_timestamp: 12,
+            tick_cumulative: -27,
+            ..second
+        };
+        let observations = [first, second, third];
+        let (tick_cumulatives, _) = observe(&observations, 12, &[2], 1, 2, 2, 3).unwrap();
+        assert_eq!(tick_cumulatives, vec![-22]);
+
+        //after the newest observation
+        let (tick_cumulatives, _) = observe(&observations, 15, &[1], 1, 2, 2, 3).unwrap();
+        assert_eq!(tick_cumulatives, vec![-25]);
+    }
+
+    #[test]
+    fn test_observe_wraparound() {
+        //the ring buffer wrapped so the oldest observation sits at index 1, and the timestamps overflowed u32
+        let oldest = initialize(u32::MAX - 9);
+        let middle = transform(&oldest, u32::MAX - 1, 10, 1);
+        let newest = transform(&middle, 6, -20, 1);
+        let observations = [newest, oldest, middle];
+
+        assert_eq!(middle.tick_cumulative, 80);
+        assert_eq!(newest.tick_cumulative, -80);
+
+        let (tick_cumulatives, _) =
+            observe(&observations, 10, &[20, 16, 12, 4, 0], 0, 0, 1, 3).unwrap();
+        assert_eq!(tick_cumulatives, vec![0, 40, 80, -80, -80]);
+
+        //halfway between the middle and the newest observation across the overflow
+        let (tick_cumulatives, _) = observe(&observations, 10, &[8], 0, 0, 1, 3).unwrap();
+        assert_eq!(tick_cumulatives, vec![0]);
+
+        assert!(matches!(
+            observe(&observations, 10, &[21], 0, 0, 1, 3),
+            Err(UniswapV3MathError::ObservationTooOld { .. })
+        ));
+    }
+
+    #[test]
+    fn test_get_twap_tick() {
+        //an hour at tick 100, then 20 minutes at tick -300 and 10 minutes at tick -50 up to now
+        let start = 1_000_000;
+        let first = initialize(start);
+        let second = transform(&first, start + 3600, 100, 1);
+        let third = transform(&second, start + 4800, -300, 1);
+        let observations = [first, second, third];
+        let now = start + 5400;
+
+        //(1200 * -300 + 600 * -50) / 1800 = -216.67, rounded down
+        assert_eq!(
+            get_twap_tick(&observations, 1800, now, -50, 2, 1, 3).unwrap(),
+            -217
+        );
+
+        //entirely within the tick 100 period
+        assert_eq!(
+            get_twap_tick(&observations, 1800, start + 2400, 100, 0, 1, 1).unwrap(),
+            100
+        );
+
+        //(1400 * 100 + 400 * -300) / 1800 = 11.11, positive averages are truncated
+        assert_eq!(
+            get_twap_tick(&observations, 1800, start + 4000, -300, 1, 1, 2).unwrap(),
+            11
+        );
+
+        assert!(matches!(
+            get_twap_tick(&observations, 6000, now, -50, 2, 1, 3),
+            Err(UniswapV3MathError::ObservationCardinalityTooLow {
+                cardinality: 3,
+                seconds_ago: 6000
+            })
+        ));
+        assert!(matches!(
+            get_twap_tick(&observations, 0, now, -50, 2, 1, 3),
+            Err(UniswapV3MathError::DenominatorIsZero)
+        ));
+    }
+
+    #[test]
+    fn test_arithmetic_mean_tick() {
+        //positive deltas are truncated
+        assert_eq!(arithmetic_mean_tick(0, 10, 3).unwrap(), 3);
+        assert_eq!(arithmetic_mean_tick(-100, -90, 3).unwrap(), 3);
+
+        //negative deltas that are not divisible round down, where -10 / 3 alone would give -3
+        assert_eq!(arithmetic_mean_tick(0, -10, 3).unwrap(), -4);
+        assert_eq!(arithmetic_mean_tick(100, 90, 3).unwrap(), -4);
+        assert_eq!(arithmetic_mean_tick(0, -1, 1800).unwrap(), -1);
+        //divisible negative deltas are exact
+        assert_eq!(arithmetic_mean_tick(0, -9, 3).unwrap(), -3);
+
+        assert!(matches!(
+            arithmetic_mean_tick(0, -10, 0),
+            Err(UniswapV3MathError::DenominatorIsZero)
+        ));
+
+        assert_eq!(
+            mean_sqrt_price_x96(0, -10, 3).unwrap(),
+            get_sqrt_ratio_at_tick(-4).unwrap()
+        );
+        //a mean tick out of bounds has no sqrt price
+        assert!(matches!(
+            mean_sqrt_price_x96(0, 887273, 1),
+            Err(UniswapV3MathError::TickOutOfBounds { .. })
+        ));
+    }
+
+    #[test]
+    fn test_harmonic_mean_liquidity() {
+        //10 seconds at a liquidity of 1e18
+        let start = initialize(0);
+        let end = transform(&start, 10, 0, 1e18 as u128);
+        assert_eq!(
+            harmonic_mean_liquidity(
+                start.seconds_per_liquidity_cumulative_x_128,
+                end.seconds_per_liquidity_cumulative_x_128,
+                10
+            )
+            .unwrap(),
+            1e18 as u128
+        );
+
+        //5 seconds at 1e18 and 5 seconds at 3e18, 2 / (1 / 1e18 + 1 / 3e18)
+        let middle = transform(&start, 5, 0, 1e18 as u128);
+        let end = transform(&middle, 10, 0, 3e18 as u128);
+        assert_eq!(
+            harmonic_mean_liquidity(
+                start.seconds_per_liquidity_cumulative_x_128,
+                end.seconds_per_liquidity_cumulative_x_128,
+                10
+            )
+            .unwrap(),
+            1.5e18 as u128
+        );
+
+        //the uint160 accumulator wrapped in between
+        let start = Observation {
+            seconds_per_liquidity_cumulative_x_128: U256::MAX >> 96,
+            ..start
+        };
+        let end = transform(&start, 10, 0, 1e18 as u128);
+        assert_eq!(
+            harmonic_mean_liquidity(
+                start.seconds_per_liquidity_cumulative_x_128,
+                end.seconds_per_liquidity_cumulative_x_128,
+                10
+            )
+            .unwrap(),
+            1e18 as u128
+        );
+
+        assert!(matches!(
+            harmonic_mean_liquidity(U256::from(5), U256::from(5), 10),
+            Err(UniswapV3MathError::DenominatorIsZero)
+        ));
+    }
+}
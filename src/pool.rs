@@ -0,0 +1,1365 @@
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+use alloy_primitives::{Bytes, U256};
+
+use crate::{
+    error::UniswapV3MathError,
+    fee_tier::FeeTier,
+    fixed_point::{FeeGrowthX128, SqrtPriceX96},
+    sqrt_price_math::{_get_amount_0_delta, _get_amount_1_delta},
+    tick_math::{get_sqrt_ratio_at_tick, max_tick_for_spacing, min_tick_for_spacing},
+};
+#[cfg(feature = "std")]
+use {
+    crate::{
+        liquidity_math::add_delta,
+        position::{encode_position_key, PositionInfo},
+        sqrt_price_math::{get_amount_0_delta_signed, get_amount_1_delta_signed},
+        swap_simulation::{
+            simulate_swap_crossing_ticks, simulate_swap_with_protocol_fee, HashMapTickDataProvider,
+            SwapResult,
+        },
+        tick::{assert_valid_tick, clear_tick, get_fee_growth_inside, update_tick, TickInfo},
+        tick_bitmap::TickBitmap,
+        tick_math::{tick_spacing_to_max_liquidity_per_tick, MAX_SQRT_RATIO, MIN_SQRT_RATIO},
+    },
+    alloy_primitives::{Address, B256, I256},
+};
+
+pub use crate::swap_simulation::Q128;
+
+//Fields of UniswapV3Pool.slot0, packed into a single storage slot from the lowest bits up
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Slot0 {
+    pub sqrt_price_x_96: SqrtPriceX96,
+    pub tick: i32,
+    pub observation_index: u16,
+    pub observation_cardinality: u16,
+    pub observation_cardinality_next: u16,
+    pub fee_protocol: u8,
+    pub unlocked: bool,
+}
+
+impl Slot0 {
+    //Decodes the raw storage word of slot0: sqrtPriceX96 in bits [0, 160), tick in [160, 184), observationIndex in
+    //[184, 200), observationCardinality in [200, 216), observationCardinalityNext in [216, 232), feeProtocol in
+    //[232, 240) and unlocked in [240, 248)
+    pub fn decode(word: &[u8]) -> Result<Self, UniswapV3MathError> {
+        let word = storage_word(word)?;
+        let field = |offset: usize, bits: usize| -> u64 {
+            ((word >> offset) & ((U256::from(1) << bits) - U256::from(1))).to()
+        };
+
+        Ok(Self {
+            sqrt_price_x_96: SqrtPriceX96(word & ((U256::from(1) << 160) - U256::from(1))),
+            // sign extends the int24
+            tick: ((field(160, 24) as i32) << 8) >> 8,
+            observation_index: field(184, 16) as u16,
+            observation_cardinality: field(200, 16) as u16,
+            observation_cardinality_next: field(216, 16) as u16,
+            fee_protocol: field(232, 8) as u8,
+            unlocked: field(240, 8) != 0,
+        })
+    }
+}
+
+// big endian storage word, eth_getStorageAt returns 32 bytes but shorter left trimmed words are accepted too
+fn storage_word(word: &[u8]) -> Result<U256, UniswapV3MathError> {
+    U256::try_from_be_slice(word)
+        .ok_or(UniswapV3MathError::InvalidStorageWord { length: word.len() })
+}
+
+//Slot0, liquidity, fee growth and protocol fees of a pool, enough to replay swaps off-chain. fee_protocol is the
+//packed slot0.feeProtocol, the token0 denominator in the lower and the token1 denominator in the upper 4 bits,
+//and protocol_fees_token_0/1 the protocolFees owed to the factory owner
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PoolState {
+    pub sqrt_price_x_96: SqrtPriceX96,
+    pub liquidity: u128,
+    pub tick: i32,
+    pub fee: u32,
+    pub tick_spacing: i32,
+    pub fee_growth_global_0_x_128: FeeGrowthX128,
+    pub fee_growth_global_1_x_128: FeeGrowthX128,
+    pub fee_protocol: u8,
+    pub protocol_fees_token_0: u128,
+    pub protocol_fees_token_1: u128,
+}
+
+impl PoolState {
+    //Pool state from the raw 32 byte storage words of slot0, liquidity, feeGrowthGlobal0X128 and
+    //feeGrowthGlobal1X128, as returned by eth_getStorageAt for the slots 0, 4, 1 and 2 of a UniswapV3Pool. fee and
+    //tick_spacing are immutables of the pool and not in storage, they are left at 0 to be set with with_fee_tier.
+    //The protocol fees already owed are in slot 3, which is not read, so they start at 0
+    pub fn from_chain_state(
+        slot0: Bytes,
+        liquidity_raw: Bytes,
+        fee_growth_global0_raw: Bytes,
+        fee_growth_global1_raw: Bytes,
+    ) -> Result<Self, UniswapV3MathError> {
+        let slot0 = Slot0::decode(&slot0)?;
+        let liquidity = storage_word(&liquidity_raw)?;
+
+        Ok(Self {
+            sqrt_price_x_96: slot0.sqrt_price_x_96,
+            liquidity: u128::try_from(liquidity)
+                .map_err(|_| UniswapV3MathError::SafeCastToU128Overflow { value: liquidity })?,
+            tick: slot0.tick,
+            fee_protocol: slot0.fee_protocol,
+            fee_growth_global_0_x_128: FeeGrowthX128(storage_word(&fee_growth_global0_raw)?),
+            fee_growth_global_1_x_128: FeeGrowthX128(storage_word(&fee_growth_global1_raw)?),
+            ..Default::default()
+        })
+    }
+
+    //Sets fee and tick_spacing from a validated tier, e.g. from_chain_state(..)?.with_fee_tier(FeeTier::Standard)
+    pub fn with_fee_tier(self, fee_tier: FeeTier) -> Self {
+        Self {
+            fee: fee_tier.fee_pips(),
+            tick_spacing: fee_tier.tick_spacing(),
+            ..self
+        }
+    }
+
+    //Tier of the pool's fee and tick spacing, errors if they are not a pair the factory could have enabled
+    pub fn fee_tier(&self) -> Result<FeeTier, UniswapV3MathError> {
+        FeeTier::new(self.fee, self.tick_spacing)
+    }
+}
+
+#[cfg(feature = "std")]
+impl PoolState {
+    //Runs the UniswapV3Pool.swap step loop against the given ticks, updating the pool state in place.
+    //Returns the signed (amount0, amount1) deltas of the pool, positive amounts are paid into the pool.
+    pub fn simulate_swap(
+        &mut self,
+        tick_bitmap: &TickBitmap,
+        ticks: &HashMap<i32, TickInfo>,
+        zero_for_one: bool,
+        amount_specified: I256,
+        sqrt_price_limit_x_96: SqrtPriceX96,
+    ) -> Result<(I256, I256), UniswapV3MathError> {
+        self.check_swap(zero_for_one, amount_specified, sqrt_price_limit_x_96)?;
+
+        let result = simulate_swap_with_protocol_fee(
+            &HashMapTickDataProvider { tick_bitmap, ticks },
+            self.sqrt_price_x_96,
+            self.tick,
+            self.liquidity,
+            self.tick_spacing,
+            self.fee,
+            self.fee_protocol,
+            amount_specified,
+            sqrt_price_limit_x_96,
+        )?;
+
+        self.apply_swap_result(&result, zero_for_one);
+
+        Ok((result.amount_0, result.amount_1))
+    }
+
+    //Adds amount of liquidity to the position of owner on [tick_lower, tick_upper] like UniswapV3Pool.mint,
+    //updating the boundary ticks, the tick bitmap, the active liquidity and the position. Returns the
+    //(amount0, amount1) owed to the pool, rounded up. The pool does not track the oracle, so newly initialized
+    //ticks start with zeroed seconds and tick cumulatives
+    #[allow(clippy::too_many_arguments)]
+    pub fn simulate_mint(
+        &mut self,
+        owner: Address,
+        tick_lower: i32,
+        tick_upper: i32,
+        amount: u128,
+        tick_bitmap: &mut TickBitmap,
+        ticks: &mut HashMap<i32, TickInfo>,
+        positions: &mut HashMap<B256, PositionInfo>,
+    ) -> Result<(U256, U256), UniswapV3MathError> {
+        if amount == 0 {
+            return Err(UniswapV3MathError::LiquidityIsZero);
+        }
+        let liquidity_delta = i128::try_from(amount)
+            .map_err(|_| UniswapV3MathError::SafeCastToI128Overflow { value: amount })?;
+
+        let (amount_0, amount_1) = self.modify_position(
+            owner,
+            tick_lower,
+            tick_upper,
+            liquidity_delta,
+            tick_bitmap,
+            ticks,
+            positions,
+        )?;
+
+        Ok((amount_0.into_raw(), amount_1.into_raw()))
+    }
+
+    //Removes amount of liquidity from the position of owner like UniswapV3Pool.burn, unflipping ticks that are no
+    //longer referenced. The burned amounts are credited to the tokens owed of the position, and unlike burn the
+    //returned (amount0, amount1) are the resulting tokens owed, i.e. the burned liquidity plus all fees accrued
+    //and not yet collected. Errors with InsufficientPositionLiquidity if the position holds less than amount
+    #[allow(clippy::too_many_arguments)]
+    pub fn simulate_burn(
+        &mut self,
+        owner: Address,
+        tick_lower: i32,
+        tick_upper: i32,
+        amount: u128,
+        tick_bitmap: &mut TickBitmap,
+        ticks: &mut HashMap<i32, TickInfo>,
+        positions: &mut HashMap<B256, PositionInfo>,
+    ) -> Result<(U256, U256), UniswapV3MathError> {
+        let key = encode_position_key(owner, tick_lower, tick_upper);
+        let liquidity = positions.get(&key).map_or(0, |position| position.liquidity);
+        if amount > liquidity {
+            return Err(UniswapV3MathError::InsufficientPositionLiquidity { liquidity, amount });
+        }
+
+        let (amount_0, amount_1) = self.modify_position(
+            owner,
+            tick_lower,
+            tick_upper,
+            -(amount as i128),
+            tick_bitmap,
+            ticks,
+            positions,
+        )?;
+
+        let position = positions.entry(key).or_default();
+        position.tokens_owed_0 = position
+            .tokens_owed_0
+            .wrapping_add(amount_0.unsigned_abs().wrapping_to());
+        position.tokens_owed_1 = position
+            .tokens_owed_1
+            .wrapping_add(amount_1.unsigned_abs().wrapping_to());
+
+        Ok((
+            U256::from(position.tokens_owed_0),
+            U256::from(position.tokens_owed_1),
+        ))
+    }
+
+    // UniswapV3Pool._modifyPosition without the position and oracle bookkeeping. Returns the signed amounts of the
+    // pool and only touches the pool, the bitmap and the ticks once every check has passed
+    #[allow(clippy::too_many_arguments)]
+    fn modify_position(
+        &mut self,
+        owner: Address,
+        tick_lower: i32,
+        tick_upper: i32,
+        liquidity_delta: i128,
+        tick_bitmap: &mut TickBitmap,
+        ticks: &mut HashMap<i32, TickInfo>,
+        positions: &mut HashMap<B256, PositionInfo>,
+    ) -> Result<(I256, I256), UniswapV3MathError> {
+        if tick_lower >= tick_upper {
+            return Err(UniswapV3MathError::InvalidTickRange {
+                tick_lower,
+                tick_upper,
+            });
+        }
+        assert_valid_tick(tick_lower, self.tick_spacing)?;
+        assert_valid_tick(tick_upper, self.tick_spacing)?;
+
+        let max_liquidity = tick_spacing_to_max_liquidity_per_tick(self.tick_spacing)?;
+        let mut lower = ticks.get(&tick_lower).copied().unwrap_or_default();
+        let mut upper = ticks.get(&tick_upper).copied().unwrap_or_default();
+        let mut flipped_lower = false;
+        let mut flipped_upper = false;
+        if liquidity_delta != 0 {
+            for (info, tick, flipped, is_upper) in [
+                (&mut lower, tick_lower, &mut flipped_lower, false),
+                (&mut upper, tick_upper, &mut flipped_upper, true),
+            ] {
+                *flipped = update_tick(
+                    info,
+                    tick,
+                    self.tick,
+                    liquidity_delta,
+                    self.fee_growth_global_0_x_128,
+                    self.fee_growth_global_1_x_128,
+                    U256::ZERO,
+                    0,
+                    0,
+                    is_upper,
+                    max_liquidity,
+                )?;
+            }
+        }
+
+        let (fee_growth_inside_0_x_128, fee_growth_inside_1_x_128) = get_fee_growth_inside(
+            &lower,
+            &upper,
+            tick_lower,
+            tick_upper,
+            self.tick,
+            self.fee_growth_global_0_x_128,
+            self.fee_growth_global_1_x_128,
+        );
+        let key = encode_position_key(owner, tick_lower, tick_upper);
+        let mut position = positions.get(&key).copied().unwrap_or_default();
+        position.update(
+            liquidity_delta,
+            fee_growth_inside_0_x_128,
+            fee_growth_inside_1_x_128,
+        )?;
+
+        let sqrt_ratio_lower_x_96 = get_sqrt_ratio_at_tick(tick_lower)?;
+        let sqrt_ratio_upper_x_96 = get_sqrt_ratio_at_tick(tick_upper)?;
+        let mut liquidity = self.liquidity;
+        let (amount_0, amount_1) = if self.tick < tick_lower {
+            // current tick is below the passed range; liquidity can only become in range by crossing from left to
+            // right, when we'll need _more_ token0 (it's becoming more valuable) so user must provide it
+            (
+                get_amount_0_delta_signed(
+                    sqrt_ratio_lower_x_96,
+                    sqrt_ratio_upper_x_96,
+                    liquidity_delta,
+                )?,
+                I256::ZERO,
+            )
+        } else if self.tick < tick_upper {
+            // current tick is inside the passed range
+            liquidity = add_delta(liquidity, liquidity_delta)?;
+            (
+                get_amount_0_delta_signed(
+                    self.sqrt_price_x_96,
+                    sqrt_ratio_upper_x_96,
+                    liquidity_delta,
+                )?,
+                get_amount_1_delta_signed(
+                    sqrt_ratio_lower_x_96,
+                    self.sqrt_price_x_96,
+                    liquidity_delta,
+                )?,
+            )
+        } else {
+            // current tick is above the passed range; liquidity can only become in range by crossing from right to
+            // left, when we'll need _more_ token1 (it's becoming more valuable) so user must provide it
+            (
+                I256::ZERO,
+                get_amount_1_delta_signed(
+                    sqrt_ratio_lower_x_96,
+                    sqrt_ratio_upper_x_96,
+                    liquidity_delta,
+                )?,
+            )
+        };
+
+        for (tick, info, flipped) in [
+            (tick_lower, lower, flipped_lower),
+            (tick_upper, upper, flipped_upper),
+        ] {
+            if flipped {
+                tick_bitmap.flip_tick(tick, self.tick_spacing)?;
+            }
+            // clear any tick data that is no longer needed
+            if flipped && liquidity_delta < 0 {
+                clear_tick(ticks, tick);
+            } else if liquidity_delta != 0 {
+                ticks.insert(tick, info);
+            }
+        }
+        positions.insert(key, position);
+        self.liquidity = liquidity;
+
+        Ok((amount_0, amount_1))
+    }
+
+    fn check_swap(
+        &self,
+        zero_for_one: bool,
+        amount_specified: I256,
+        sqrt_price_limit_x_96: SqrtPriceX96,
+    ) -> Result<(), UniswapV3MathError> {
+        if amount_specified.is_zero() {
+            return Err(UniswapV3MathError::AmountSpecifiedIsZero);
+        }
+
+        // the simulation infers the direction from the limit, so it has to agree with zero_for_one
+        let valid_limit = if zero_for_one {
+            sqrt_price_limit_x_96 < self.sqrt_price_x_96 && *sqrt_price_limit_x_96 > MIN_SQRT_RATIO
+        } else {
+            sqrt_price_limit_x_96 > self.sqrt_price_x_96 && *sqrt_price_limit_x_96 < MAX_SQRT_RATIO
+        };
+        if !valid_limit {
+            return Err(UniswapV3MathError::SqrtPriceLimitOutOfBounds {
+                sqrt_price_x_96: self.sqrt_price_x_96.0,
+                sqrt_price_limit_x_96: sqrt_price_limit_x_96.0,
+            });
+        }
+
+        Ok(())
+    }
+
+    // moves the pool to the state after a simulated swap and accrues its fee growth
+    pub(crate) fn apply_swap_result(&mut self, result: &SwapResult, zero_for_one: bool) {
+        self.sqrt_price_x_96 = result.sqrt_price_x_96;
+        self.tick = result.tick;
+        self.liquidity = result.liquidity;
+        // the protocol fees are uint128 and wrap like protocolFees in UniswapV3Pool.swap
+        if zero_for_one {
+            self.fee_growth_global_0_x_128 = self
+                .fee_growth_global_0_x_128
+                .wrapping_add(result.fee_growth_x_128);
+            self.protocol_fees_token_0 =
+                self.protocol_fees_token_0.wrapping_add(result.protocol_fee);
+        } else {
+            self.fee_growth_global_1_x_128 = self
+                .fee_growth_global_1_x_128
+                .wrapping_add(result.fee_growth_x_128);
+            self.protocol_fees_token_1 =
+                self.protocol_fees_token_1.wrapping_add(result.protocol_fee);
+        }
+    }
+}
+
+//Amount of the input token needed to move the price of pool to the next tick boundary in the swap direction, the
+//nearest multiple of tick_spacing strictly below the current sqrt price for zero_for_one and strictly above it
+//otherwise. Returns (amount_in, next_tick). The amount is rounded up like in SwapMath.computeSwapStep and excludes
+//the swap fee, and the liquidity is taken as constant up to next_tick. Errors with TickOutOfBounds if there is no
+//usable tick left in the swap direction
+pub fn get_amount_to_next_tick(
+    pool: &PoolState,
+    zero_for_one: bool,
+) -> Result<(U256, i32), UniswapV3MathError> {
+    let tick_spacing = pool.tick_spacing;
+    let min_tick = min_tick_for_spacing(tick_spacing)?;
+    let max_tick = max_tick_for_spacing(tick_spacing)?;
+
+    // the boundary at or below the current tick, the price is at or above it
+    let mut next_tick = pool.tick.div_euclid(tick_spacing) * tick_spacing;
+    if zero_for_one {
+        if get_sqrt_ratio_at_tick(next_tick)? >= pool.sqrt_price_x_96 {
+            next_tick -= tick_spacing;
+        }
+    } else {
+        next_tick += tick_spacing;
+        // the price sits on the boundary above the current tick after crossing it with a zero_for_one swap
+        if get_sqrt_ratio_at_tick(next_tick)? <= pool.sqrt_price_x_96 {
+            next_tick += tick_spacing;
+        }
+    }
+    if next_tick < min_tick || next_tick > max_tick {
+        return Err(UniswapV3MathError::TickOutOfBounds { tick: next_tick });
+    }
+
+    let sqrt_ratio_next_x_96 = get_sqrt_ratio_at_tick(next_tick)?;
+    let amount_in = if zero_for_one {
+        _get_amount_0_delta(
+            sqrt_ratio_next_x_96,
+            pool.sqrt_price_x_96,
+            pool.liquidity,
+            true,
+        )?
+    } else {
+        _get_amount_1_delta(
+            pool.sqrt_price_x_96,
+            sqrt_ratio_next_x_96,
+            pool.liquidity,
+            true,
+        )?
+    };
+
+    Ok((amount_in, next_tick))
+}
+
+//A pool held fully in memory: its state, initialized ticks and positions. Swaps cross ticks like
+//UniswapV3Pool.swap, flipping their fee growth outside, so that the fees of the positions stay correct across
+//swaps, mints and burns. The oracle is not tracked
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Pool {
+    pub state: PoolState,
+    pub tick_bitmap: TickBitmap,
+    pub ticks: HashMap<i32, TickInfo>,
+    pub positions: HashMap<B256, PositionInfo>,
+}
+
+#[cfg(feature = "std")]
+impl Pool {
+    //Empty pool at the given state, the state liquidity should be 0 as there are no positions yet
+    pub fn new(state: PoolState) -> Self {
+        Self {
+            state,
+            ..Default::default()
+        }
+    }
+
+    //Swaps amount_specified, exact input when positive and exact output when negative, like
+    //UniswapV3Pool.swap. Returns the swap result, its amounts are the signed pool deltas
+    pub fn swap(
+        &mut self,
+        amount_specified: I256,
+        zero_for_one: bool,
+        sqrt_price_limit_x_96: SqrtPriceX96,
+    ) -> Result<SwapResult, UniswapV3MathError> {
+        self.state
+            .check_swap(zero_for_one, amount_specified, sqrt_price_limit_x_96)?;
+
+        let result = simulate_swap_crossing_ticks(
+            &self.tick_bitmap,
+            &mut self.ticks,
+            self.state.sqrt_price_x_96,
+            self.state.tick,
+            self.state.liquidity,
+            self.state.tick_spacing,
+            self.state.fee,
+            self.state.fee_protocol,
+            amount_specified,
+            sqrt_price_limit_x_96,
+            self.state.fee_growth_global_0_x_128,
+            self.state.fee_growth_global_1_x_128,
+        )?;
+
+        self.state.apply_swap_result(&result, zero_for_one);
+
+        Ok(result)
+    }
+
+    //Same as PoolState::simulate_mint on the pool's ticks and positions
+    pub fn mint(
+        &mut self,
+        owner: Address,
+        tick_lower: i32,
+        tick_upper: i32,
+        amount: u128,
+    ) -> Result<(U256, U256), UniswapV3MathError> {
+        self.state.simulate_mint(
+            owner,
+            tick_lower,
+            tick_upper,
+            amount,
+            &mut self.tick_bitmap,
+            &mut self.ticks,
+            &mut self.positions,
+        )
+    }
+
+    //Same as PoolState::simulate_burn on the pool's ticks and positions
+    pub fn burn(
+        &mut self,
+        owner: Address,
+        tick_lower: i32,
+        tick_upper: i32,
+        amount: u128,
+    ) -> Result<(U256, U256), UniswapV3MathError> {
+        self.state.simulate_burn(
+            owner,
+            tick_lower,
+            tick_upper,
+            amount,
+            &mut self.tick_bitmap,
+            &mut self.ticks,
+            &mut self.positions,
+        )
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::HashMap, str::FromStr};
+
+    use alloy_primitives::{address, hex, Address, Bytes, I256, U256};
+
+    use crate::{
+        error::UniswapV3MathError,
+        fee_tier::FeeTier,
+        fixed_point::{FeeGrowthX128, SqrtPriceX96},
+        position::{encode_position_key, get_position_fees},
+        sqrt_price_math::get_amounts_for_liquidity,
+        swap_math::compute_swap_step,
+        tick::TickInfo,
+        tick_bitmap::TickBitmap,
+        tick_math::{
+            get_sqrt_ratio_at_tick, get_tick_at_sqrt_ratio, MAX_SQRT_RATIO, MIN_SQRT_RATIO,
+        },
+    };
+
+    use super::{get_amount_to_next_tick, Pool, PoolState, Slot0};
+
+    const OWNER: Address = address!("C36442b4a4522E871399CD717aBDD847Ab11FE88");
+
+    // Pool at price 1 with liquidity positions on [-887220, 887220] and [-600, 600]
+    fn init_test_pool() -> eyre::Result<(PoolState, TickBitmap, HashMap<i32, TickInfo>)> {
+        let mut tick_bitmap = TickBitmap::new();
+        let mut ticks = HashMap::new();
+        for (tick_lower, tick_upper, liquidity) in
+            [(-887220, 887220, 1e18 as i128), (-600, 600, 2e18 as i128)]
+        {
+            for (tick, liquidity_net) in [(tick_lower, liquidity), (tick_upper, -liquidity)] {
+                tick_bitmap.flip_tick(tick, 60)?;
+                ticks.insert(
+                    tick,
+                    TickInfo {
+                        liquidity_gross: liquidity as u128,
+                        liquidity_net,
+                        initialized: true,
+                        ..Default::default()
+                    },
+                );
+            }
+        }
+
+        let pool = PoolState {
+            sqrt_price_x_96: SqrtPriceX96(U256::from_str("79228162514264337593543950336")?),
+            liquidity: 3e18 as u128,
+            tick: 0,
+            fee: 3000,
+            tick_spacing: 60,
+            ..Default::default()
+        };
+
+        Ok((pool, tick_bitmap, ticks))
+    }
+
+    #[test]
+    fn test_simulate_swap_within_range() -> eyre::Result<()> {
+        let (mut pool, tick_bitmap, ticks) = init_test_pool()?;
+        let amount = I256::from_str("1000000000000000")?;
+
+        let (sqrt_price, amount_in, amount_out, fee_amount) = compute_swap_step(
+            pool.sqrt_price_x_96,
+            get_sqrt_ratio_at_tick(-60)?,
+            pool.liquidity,
+            amount,
+            pool.fee,
+        )?;
+
+        //exact input zero for one that stays within the first word
+        let (amount_0, amount_1) = pool.simulate_swap(
+            &tick_bitmap,
+            &ticks,
+            true,
+            amount,
+            SqrtPriceX96(MIN_SQRT_RATIO + U256::from(1)),
+        )?;
+
+        assert_eq!(amount_0, amount);
+        assert_eq!(amount_0.into_raw(), amount_in + fee_amount);
+        assert_eq!(-amount_1, I256::from_raw(amount_out));
+        assert_eq!(pool.sqrt_price_x_96, sqrt_price);
+        assert_eq!(pool.tick, get_tick_at_sqrt_ratio(sqrt_price)?);
+        assert_eq!(pool.liquidity, 3e18 as u128);
+        assert!(pool.fee_growth_global_0_x_128 > U256::ZERO);
+        assert_eq!(pool.fee_growth_global_1_x_128, U256::ZERO);
+        Ok(())
+    }
+
+    #[test]
+    fn test_simulate_swap_crosses_ticks() -> eyre::Result<()> {
+        let (mut pool, tick_bitmap, ticks) = init_test_pool()?;
+
+        //exact input one for zero that crosses the upper tick of the inner position
+        let (amount_0, amount_1) = pool.simulate_swap(
+            &tick_bitmap,
+            &ticks,
+            false,
+            I256::from_str("1000000000000000000")?,
+            SqrtPriceX96(MAX_SQRT_RATIO - U256::from(1)),
+        )?;
+
+        assert!(amount_0 < I256::ZERO);
+        assert_eq!(amount_1, I256::from_str("1000000000000000000")?);
+        assert!(pool.tick >= 600);
+        assert_eq!(pool.liquidity, 1e18 as u128);
+
+        //exact output zero for one back through the inner position
+        let (amount_0, amount_1) = pool.simulate_swap(
+            &tick_bitmap,
+            &ticks,
+            true,
+            I256::from_str("-1000000000000000000")?,
+            SqrtPriceX96(MIN_SQRT_RATIO + U256::from(1)),
+        )?;
+
+        assert!(amount_0 > I256::ZERO);
+        assert_eq!(amount_1, I256::from_str("-1000000000000000000")?);
+        assert!(pool.tick < 0);
+        assert_eq!(pool.liquidity, 3e18 as u128);
+        Ok(())
+    }
+
+    #[test]
+    fn test_simulate_swap_stops_at_price_limit() -> eyre::Result<()> {
+        let (mut pool, tick_bitmap, ticks) = init_test_pool()?;
+        let sqrt_price_limit = get_sqrt_ratio_at_tick(-1200)?;
+
+        let (amount_0, amount_1) = pool.simulate_swap(
+            &tick_bitmap,
+            &ticks,
+            true,
+            I256::from_str("100000000000000000000")?,
+            sqrt_price_limit,
+        )?;
+
+        assert!(amount_0 < I256::from_str("100000000000000000000")?);
+        assert!(amount_1 < I256::ZERO);
+        assert_eq!(pool.sqrt_price_x_96, sqrt_price_limit);
+        assert_eq!(pool.tick, -1200);
+        assert_eq!(pool.liquidity, 1e18 as u128);
+        Ok(())
+    }
+
+    #[test]
+    fn test_simulate_swap_errors() -> eyre::Result<()> {
+        let (mut pool, tick_bitmap, ticks) = init_test_pool()?;
+
+        let result = pool.simulate_swap(
+            &tick_bitmap,
+            &ticks,
+            true,
+            I256::ZERO,
+            SqrtPriceX96(MIN_SQRT_RATIO),
+        );
+        assert_eq!(result.unwrap_err().to_string(), "Amount specified is 0");
+
+        let result = pool.simulate_swap(
+            &tick_bitmap,
+            &ticks,
+            true,
+            I256::from_str("1000")?,
+            SqrtPriceX96(MAX_SQRT_RATIO - U256::from(1)),
+        );
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Sqrt price limit is out of bounds"
+        );
+
+        let result = pool.simulate_swap(
+            &tick_bitmap,
+            &ticks,
+            false,
+            I256::from_str("1000")?,
+            SqrtPriceX96(MAX_SQRT_RATIO),
+        );
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Sqrt price limit is out of bounds"
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_slot0_decode() -> eyre::Result<()> {
+        //a slot0 word packed by hand for 2000 USDC per WETH, observation 42 of 723, unlocked, no protocol fee. It
+        //follows the mainnet storage layout but is not a storage read of the USDC/WETH pool
+        let slot0 = Slot0::decode(&hex!(
+            "00010002d302d3002a030e770000000000005758ae05bbf89b1e32f83635685c"
+        ))?;
+        assert_eq!(
+            slot0,
+            Slot0 {
+                sqrt_price_x_96: SqrtPriceX96(U256::from_str(
+                    "1771595571142957102961017161607260"
+                )?),
+                tick: 200311,
+                observation_index: 42,
+                observation_cardinality: 723,
+                observation_cardinality_next: 723,
+                fee_protocol: 0,
+                unlocked: true,
+            }
+        );
+        assert_eq!(get_tick_at_sqrt_ratio(slot0.sqrt_price_x_96)?, slot0.tick);
+
+        //negative ticks are sign extended, the other fields saturated and locked
+        let slot0 = Slot0::decode(&hex!(
+            "000044fffffffffffff2761800000000000000000000000000000001000276a3"
+        ))?;
+        assert_eq!(
+            slot0,
+            Slot0 {
+                sqrt_price_x_96: SqrtPriceX96(MIN_SQRT_RATIO),
+                tick: -887272,
+                observation_index: u16::MAX,
+                observation_cardinality: u16::MAX,
+                observation_cardinality_next: u16::MAX,
+                fee_protocol: 0x44,
+                unlocked: false,
+            }
+        );
+
+        //left trimmed words decode like their zero padded form
+        assert_eq!(Slot0::decode(&hex!("01"))?.sqrt_price_x_96, U256::from(1));
+        assert_eq!(Slot0::decode(&[])?, Slot0::default());
+        assert!(matches!(
+            Slot0::decode(&[0; 33]),
+            Err(UniswapV3MathError::InvalidStorageWord { length: 33 })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_pool_state_from_chain_state() -> eyre::Result<()> {
+        let fee_growth_global_0_x_128 = U256::from_str("2815129326312409738467497312578384")?;
+        let fee_growth_global_1_x_128 = U256::MAX - U256::from(12345);
+
+        let state = PoolState::from_chain_state(
+            Bytes::from(hex!(
+                "00010002d302d3002a030e770000000000005758ae05bbf89b1e32f83635685c"
+            )),
+            Bytes::from(U256::from(17_000_000_000_000_000_000_u128).to_be_bytes::<32>()),
+            Bytes::from(fee_growth_global_0_x_128.to_be_bytes::<32>()),
+            Bytes::from(fee_growth_global_1_x_128.to_be_bytes::<32>()),
+        )?;
+        assert_eq!(
+            state,
+            PoolState {
+                sqrt_price_x_96: SqrtPriceX96(U256::from_str(
+                    "1771595571142957102961017161607260"
+                )?),
+                liquidity: 17_000_000_000_000_000_000,
+                tick: 200311,
+                fee: 0,
+                tick_spacing: 0,
+                fee_growth_global_0_x_128: FeeGrowthX128(fee_growth_global_0_x_128),
+                fee_growth_global_1_x_128: FeeGrowthX128(fee_growth_global_1_x_128),
+                fee_protocol: 0,
+                protocol_fees_token_0: 0,
+                protocol_fees_token_1: 0,
+            }
+        );
+
+        //the packed feeProtocol is taken over from slot0
+        let state = PoolState::from_chain_state(
+            Bytes::from(hex!(
+                "00016402d302d3002a030e770000000000005758ae05bbf89b1e32f83635685c"
+            )),
+            Bytes::new(),
+            Bytes::new(),
+            Bytes::new(),
+        )?;
+        assert_eq!(state.fee_protocol, (6 << 4) | 4);
+
+        //the liquidity slot holds a uint128
+        assert!(matches!(
+            PoolState::from_chain_state(
+                Bytes::new(),
+                Bytes::from(
+                    U256::from(u128::MAX)
+                        .wrapping_add(U256::from(1))
+                        .to_be_bytes::<32>()
+                ),
+                Bytes::new(),
+                Bytes::new(),
+            ),
+            Err(UniswapV3MathError::SafeCastToU128Overflow { .. })
+        ));
+        assert!(matches!(
+            PoolState::from_chain_state(
+                Bytes::new(),
+                Bytes::new(),
+                Bytes::new(),
+                Bytes::from([0; 40])
+            ),
+            Err(UniswapV3MathError::InvalidStorageWord { length: 40 })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_pool_state_with_fee_tier() -> eyre::Result<()> {
+        let state = PoolState {
+            liquidity: 17_000_000_000_000_000_000,
+            tick: 200311,
+            ..Default::default()
+        }
+        .with_fee_tier(FeeTier::LowVolatility);
+        assert_eq!((state.fee, state.tick_spacing), (500, 10));
+        assert_eq!(state.liquidity, 17_000_000_000_000_000_000);
+        assert_eq!(state.fee_tier()?, FeeTier::LowVolatility);
+
+        let state = state.with_fee_tier(FeeTier::custom(2500, 50)?);
+        assert_eq!((state.fee, state.tick_spacing), (2500, 50));
+        assert_eq!(
+            state.fee_tier()?,
+            FeeTier::Custom {
+                fee: 2500,
+                tick_spacing: 50
+            }
+        );
+
+        //from_chain_state leaves the immutables unset
+        assert!(matches!(
+            PoolState::default().fee_tier(),
+            Err(UniswapV3MathError::InvalidTickSpacing { tick_spacing: 0 })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_amount_to_next_tick() -> eyre::Result<()> {
+        //the hand packed slot0 word of test_slot0_decode at tick 200311, with a liquidity and spacing like the
+        //USDC/WETH 0.05% pool
+        let slot0 = Slot0::decode(&hex!(
+            "00010002d302d3002a030e770000000000005758ae05bbf89b1e32f83635685c"
+        ))?;
+        let pool = PoolState {
+            sqrt_price_x_96: slot0.sqrt_price_x_96,
+            liquidity: 17_000_000_000_000_000_000,
+            tick: slot0.tick,
+            fee: 500,
+            tick_spacing: 10,
+            ..Default::default()
+        };
+
+        for (zero_for_one, expected_next_tick, expected_amount_in) in [
+            (true, 200310, "45670232957"),
+            (false, 200320, "167258631070385606071"),
+        ] {
+            let (amount_in, next_tick) = get_amount_to_next_tick(&pool, zero_for_one)?;
+            assert_eq!(next_tick, expected_next_tick);
+            assert_eq!(amount_in, U256::from_str(expected_amount_in)?);
+
+            //the amount in is exactly what a fee free swap step needs to reach the next tick
+            let sqrt_ratio_next_x_96 = get_sqrt_ratio_at_tick(next_tick)?;
+            let amount_remaining = I256::from_raw(amount_in);
+            let step = |amount_remaining| {
+                compute_swap_step(
+                    pool.sqrt_price_x_96,
+                    sqrt_ratio_next_x_96,
+                    pool.liquidity,
+                    amount_remaining,
+                    0,
+                )
+            };
+            assert_eq!(step(amount_remaining)?.0, sqrt_ratio_next_x_96);
+            assert_ne!(step(amount_remaining - I256::ONE)?.0, sqrt_ratio_next_x_96);
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_amount_to_next_tick_on_boundary() -> eyre::Result<()> {
+        //price 1 sits on tick 0, the next boundary is one tick spacing away in both directions
+        let (mut pool, _, _) = init_test_pool()?;
+        assert_eq!(get_amount_to_next_tick(&pool, true)?.1, -60);
+        assert_eq!(get_amount_to_next_tick(&pool, false)?.1, 60);
+
+        //the same price after a zero_for_one swap crossed tick 0
+        pool.tick = -1;
+        assert_eq!(get_amount_to_next_tick(&pool, true)?.1, -60);
+        assert_eq!(get_amount_to_next_tick(&pool, false)?.1, 60);
+
+        //no liquidity, no amount needed
+        pool.liquidity = 0;
+        assert_eq!(get_amount_to_next_tick(&pool, true)?, (U256::ZERO, -60));
+
+        //at the lowest usable tick there is no boundary left below
+        pool.tick = -887220;
+        pool.sqrt_price_x_96 = get_sqrt_ratio_at_tick(-887220)?;
+        assert!(matches!(
+            get_amount_to_next_tick(&pool, true),
+            Err(UniswapV3MathError::TickOutOfBounds { tick: -887280 })
+        ));
+        assert_eq!(get_amount_to_next_tick(&pool, false)?.1, -887160);
+
+        pool.tick_spacing = 0;
+        assert!(matches!(
+            get_amount_to_next_tick(&pool, true),
+            Err(UniswapV3MathError::InvalidTickSpacing { tick_spacing: 0 })
+        ));
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_pool_state_serde() -> eyre::Result<()> {
+        let (pool, _, _) = init_test_pool()?;
+
+        let json = serde_json::to_string(&pool)?;
+        assert_eq!(serde_json::from_str::<PoolState>(&json)?, pool);
+        Ok(())
+    }
+
+    #[test]
+    fn test_simulate_mint() -> eyre::Result<()> {
+        let (mut pool, mut tick_bitmap, mut ticks) = init_test_pool()?;
+        let mut positions = HashMap::new();
+        let initial = (pool, tick_bitmap.clone(), ticks.clone());
+        let amount = 1e18 as u128;
+
+        //in range, both tokens are owed rounded up and the active liquidity grows
+        let (amount_0, amount_1) = pool.simulate_mint(
+            OWNER,
+            -120,
+            180,
+            amount,
+            &mut tick_bitmap,
+            &mut ticks,
+            &mut positions,
+        )?;
+        let (amount_0_down, amount_1_down) = get_amounts_for_liquidity(
+            pool.sqrt_price_x_96,
+            get_sqrt_ratio_at_tick(-120)?,
+            get_sqrt_ratio_at_tick(180)?,
+            amount,
+        )?;
+        assert_eq!(amount_0, amount_0_down + U256::from(1));
+        assert_eq!(amount_1, amount_1_down + U256::from(1));
+        assert_eq!(pool.liquidity, 4e18 as u128);
+        assert!(tick_bitmap.is_initialized(-120, 60) && tick_bitmap.is_initialized(180, 60));
+        assert_eq!(ticks[&-120].liquidity_net, amount as i128);
+        assert_eq!(ticks[&180].liquidity_net, -(amount as i128));
+        assert_eq!(
+            positions[&encode_position_key(OWNER, -120, 180)].liquidity,
+            amount
+        );
+
+        //the lower tick is at or below the current tick, so it holds all previous fee growth
+        assert_eq!(
+            ticks[&-120].fee_growth_outside_0_x_128,
+            pool.fee_growth_global_0_x_128
+        );
+
+        //burning the same range restores the pool, the bitmap and the ticks
+        let (burned_0, burned_1) = pool.simulate_burn(
+            OWNER,
+            -120,
+            180,
+            amount,
+            &mut tick_bitmap,
+            &mut ticks,
+            &mut positions,
+        )?;
+        assert_eq!((burned_0, burned_1), (amount_0_down, amount_1_down));
+        assert_eq!((pool, tick_bitmap.clone(), ticks.clone()), initial);
+
+        //above the current price only token0 is owed, below only token1, and the active liquidity is unchanged
+        let (amount_0, amount_1) = pool.simulate_mint(
+            OWNER,
+            600,
+            1200,
+            amount,
+            &mut tick_bitmap,
+            &mut ticks,
+            &mut positions,
+        )?;
+        assert!(amount_0 > U256::ZERO && amount_1.is_zero());
+        let (amount_0, amount_1) = pool.simulate_mint(
+            OWNER,
+            -1200,
+            -600,
+            amount,
+            &mut tick_bitmap,
+            &mut ticks,
+            &mut positions,
+        )?;
+        assert!(amount_0.is_zero() && amount_1 > U256::ZERO);
+        assert_eq!(pool.liquidity, 3e18 as u128);
+
+        //600 and -600 were already initialized by the [-600, 600] position and are not flipped again
+        assert_eq!(ticks[&600].liquidity_gross, 3e18 as u128);
+        assert_eq!(ticks[&600].liquidity_net, -1e18 as i128);
+        for (tick_lower, tick_upper) in [(600, 1200), (-1200, -600)] {
+            pool.simulate_burn(
+                OWNER,
+                tick_lower,
+                tick_upper,
+                amount,
+                &mut tick_bitmap,
+                &mut ticks,
+                &mut positions,
+            )?;
+        }
+        assert!(tick_bitmap.is_initialized(600, 60) && tick_bitmap.is_initialized(-600, 60));
+        assert_eq!((pool, tick_bitmap.clone(), ticks.clone()), initial);
+        Ok(())
+    }
+
+    #[test]
+    fn test_simulate_mint_errors() -> eyre::Result<()> {
+        let (mut pool, mut tick_bitmap, mut ticks) = init_test_pool()?;
+        let mut positions = HashMap::new();
+        let initial = (pool, tick_bitmap.clone(), ticks.clone());
+
+        for (tick_lower, tick_upper, amount) in [
+            (60, -60, 1),
+            (-60, 90, 1),
+            (-60, 60, 0),
+            (-60, 60, u128::MAX),
+            //the upper tick would exceed the max liquidity per tick
+            (-60, 600, 11505743598341114571880798222544994),
+        ] {
+            let result = pool.simulate_mint(
+                OWNER,
+                tick_lower,
+                tick_upper,
+                amount,
+                &mut tick_bitmap,
+                &mut ticks,
+                &mut positions,
+            );
+            assert!(
+                matches!(
+                    (amount, result),
+                    (1, Err(UniswapV3MathError::InvalidTickRange { .. }))
+                        | (1, Err(UniswapV3MathError::InvalidTick { tick: 90, .. }))
+                        | (0, Err(UniswapV3MathError::LiquidityIsZero))
+                        | (
+                            u128::MAX,
+                            Err(UniswapV3MathError::SafeCastToI128Overflow { .. })
+                        )
+                        | (_, Err(UniswapV3MathError::TickLiquidityOverflow { .. }))
+                ),
+                "{tick_lower} {tick_upper} {amount}"
+            );
+        }
+
+        //nothing is written on errors
+        assert_eq!((pool, tick_bitmap, ticks), initial);
+        assert!(positions.is_empty());
+        Ok(())
+    }
+
+    #[test]
+    fn test_simulate_burn_with_fees() -> eyre::Result<()> {
+        let (mut pool, mut tick_bitmap, mut ticks) = init_test_pool()?;
+        let mut positions = HashMap::new();
+        let amount = 1e18 as u128;
+
+        pool.simulate_mint(
+            OWNER,
+            -120,
+            120,
+            amount,
+            &mut tick_bitmap,
+            &mut ticks,
+            &mut positions,
+        )?;
+
+        //burning more than the position holds
+        assert!(matches!(
+            pool.simulate_burn(
+                OWNER,
+                -120,
+                120,
+                amount + 1,
+                &mut tick_bitmap,
+                &mut ticks,
+                &mut positions,
+            ),
+            Err(UniswapV3MathError::InsufficientPositionLiquidity { liquidity, .. }) if liquidity == amount
+        ));
+        assert!(matches!(
+            pool.simulate_burn(
+                Address::ZERO,
+                -120,
+                120,
+                1,
+                &mut tick_bitmap,
+                &mut ticks,
+                &mut positions,
+            ),
+            Err(UniswapV3MathError::InsufficientPositionLiquidity { liquidity: 0, .. })
+        ));
+
+        //swaps back and forth within the range accrue fees in both tokens
+        pool.simulate_swap(
+            &tick_bitmap,
+            &ticks,
+            true,
+            I256::from_str("10000000000000000")?,
+            get_sqrt_ratio_at_tick(-120)?,
+        )?;
+        pool.simulate_swap(
+            &tick_bitmap,
+            &ticks,
+            false,
+            I256::from_str("10000000000000000")?,
+            get_sqrt_ratio_at_tick(120)?,
+        )?;
+        assert!(pool.tick > -120 && pool.tick < 120);
+
+        let key = encode_position_key(OWNER, -120, 120);
+        let (fees_0, fees_1) = get_position_fees(
+            &positions[&key],
+            &ticks[&-120],
+            &ticks[&120],
+            -120,
+            120,
+            pool.tick,
+            pool.fee_growth_global_0_x_128,
+            pool.fee_growth_global_1_x_128,
+        );
+        assert!(fees_0 > U256::ZERO && fees_1 > U256::ZERO);
+
+        //the position takes a quarter of the fees, its share of the 4e18 active liquidity
+        assert_eq!(
+            fees_0,
+            (*pool.fee_growth_global_0_x_128 * U256::from(amount)) >> 128
+        );
+
+        let (amount_0, amount_1) = get_amounts_for_liquidity(
+            pool.sqrt_price_x_96,
+            get_sqrt_ratio_at_tick(-120)?,
+            get_sqrt_ratio_at_tick(120)?,
+            amount,
+        )?;
+        let (owed_0, owed_1) = pool.simulate_burn(
+            OWNER,
+            -120,
+            120,
+            amount,
+            &mut tick_bitmap,
+            &mut ticks,
+            &mut positions,
+        )?;
+        assert_eq!(owed_0, amount_0 + fees_0);
+        assert_eq!(owed_1, amount_1 + fees_1);
+
+        //the emptied position keeps its tokens owed, the ticks are gone
+        assert_eq!(positions[&key].liquidity, 0);
+        assert_eq!(U256::from(positions[&key].tokens_owed_0), owed_0);
+        assert!(!tick_bitmap.is_initialized(-120, 60) && !ticks.contains_key(&120));
+        assert_eq!(pool.liquidity, 3e18 as u128);
+        Ok(())
+    }
+
+    #[test]
+    fn test_pool_swap_crosses_ticks() -> eyre::Result<()> {
+        let (state, _, _) = init_test_pool()?;
+        let mut pool = Pool::new(PoolState {
+            liquidity: 0,
+            ..state
+        });
+        pool.mint(OWNER, -887220, 887220, 1e18 as u128)?;
+        pool.mint(OWNER, -600, 600, 2e18 as u128)?;
+        assert_eq!(pool.state.liquidity, 3e18 as u128);
+
+        //the swap amounts match the simulation on the same ticks
+        let mut state = pool.state;
+        let amount = I256::from_str("1000000000000000000")?;
+        let expected = state.simulate_swap(
+            &pool.tick_bitmap,
+            &pool.ticks,
+            true,
+            amount,
+            SqrtPriceX96(MIN_SQRT_RATIO + U256::from(1)),
+        )?;
+        let result = pool.swap(amount, true, SqrtPriceX96(MIN_SQRT_RATIO + U256::from(1)))?;
+        assert_eq!((result.amount_0, result.amount_1), expected);
+        assert_eq!(pool.state, state);
+        assert_eq!(result.ticks_crossed, 1);
+
+        //the crossed tick holds the fee growth accrued up to the crossing, the other ticks are untouched
+        let crossed = pool.ticks[&-600];
+        assert!(crossed.fee_growth_outside_0_x_128 > U256::ZERO);
+        assert!(crossed.fee_growth_outside_0_x_128 < pool.state.fee_growth_global_0_x_128);
+        assert_eq!(crossed.fee_growth_outside_1_x_128, U256::ZERO);
+        assert_eq!(pool.ticks[&600].fee_growth_outside_0_x_128, U256::ZERO);
+
+        //errors leave the pool untouched
+        let before = pool.clone();
+        assert!(matches!(
+            pool.swap(
+                I256::ZERO,
+                true,
+                SqrtPriceX96(MIN_SQRT_RATIO + U256::from(1))
+            ),
+            Err(UniswapV3MathError::AmountSpecifiedIsZero)
+        ));
+        assert!(matches!(
+            pool.swap(amount, true, SqrtPriceX96(MAX_SQRT_RATIO - U256::from(1))),
+            Err(UniswapV3MathError::SqrtPriceLimitOutOfBounds { .. })
+        ));
+        //crossing back into a range whose liquidity net is off errors after the first tick was crossed
+        pool.ticks.get_mut(&600).unwrap().liquidity_net = -(4e18 as i128);
+        let corrupted = pool.clone();
+        assert!(pool
+            .swap(
+                I256::from_str("100000000000000000000")?,
+                false,
+                SqrtPriceX96(MAX_SQRT_RATIO - U256::from(1))
+            )
+            .is_err());
+        assert_eq!(pool, corrupted);
+        assert_ne!(pool, before);
+        Ok(())
+    }
+
+    #[test]
+    fn test_pool_fees_across_swaps() -> eyre::Result<()> {
+        //without a protocol fee and with 1/4 of the token0 and 1/6 of the token1 fees going to the protocol
+        for fee_protocol in [0, (6 << 4) | 4] {
+            let (state, _, _) = init_test_pool()?;
+            let mut pool = Pool::new(PoolState {
+                liquidity: 0,
+                fee_protocol,
+                ..state
+            });
+            let positions = [
+                (-887220, 887220, 1e18 as u128),
+                (-600, 600, 2e18 as u128),
+                (-120, 60, 5e17 as u128),
+                (300, 900, 1e18 as u128),
+            ];
+
+            //token balances of the pool, what goes in is positive
+            let mut balance_0 = I256::ZERO;
+            let mut balance_1 = I256::ZERO;
+            for (tick_lower, tick_upper, amount) in positions {
+                let (amount_0, amount_1) = pool.mint(OWNER, tick_lower, tick_upper, amount)?;
+                balance_0 += I256::from_raw(amount_0);
+                balance_1 += I256::from_raw(amount_1);
+            }
+
+            //swaps back and forth through the ranges, each crossing several ticks
+            let amount = I256::from_str("200000000000000000")?;
+            let mut protocol_fees = (0, 0);
+            for (zero_for_one, limit_tick) in [
+                (true, -900),
+                (false, 1200),
+                (true, -60),
+                (false, 400),
+                (true, 30),
+            ] {
+                let result =
+                    pool.swap(amount, zero_for_one, get_sqrt_ratio_at_tick(limit_tick)?)?;
+                assert!(result.ticks_crossed > 0 || zero_for_one && limit_tick == 30);
+                assert_eq!(result.protocol_fee > 0, fee_protocol > 0);
+                if zero_for_one {
+                    protocol_fees.0 += result.protocol_fee;
+                } else {
+                    protocol_fees.1 += result.protocol_fee;
+                }
+                balance_0 += result.amount_0;
+                balance_1 += result.amount_1;
+            }
+            assert_eq!(
+                (
+                    pool.state.protocol_fees_token_0,
+                    pool.state.protocol_fees_token_1
+                ),
+                protocol_fees
+            );
+
+            //every position earned fees, burning everything pays out the principal and fees
+            for (tick_lower, tick_upper, amount) in positions {
+                let (owed_0, owed_1) = pool.burn(OWNER, tick_lower, tick_upper, amount)?;
+                let key = encode_position_key(OWNER, tick_lower, tick_upper);
+                assert!(
+                    pool.positions[&key].tokens_owed_0 > 0
+                        || pool.positions[&key].tokens_owed_1 > 0
+                );
+                balance_0 -= I256::from_raw(owed_0);
+                balance_1 -= I256::from_raw(owed_1);
+            }
+
+            //only the protocol fees and rounding dust are left in the pool, a fee growth outside that was not
+            //flipped on a crossing would pay out fees that were never collected or leave fees behind
+            assert_eq!(pool.state.liquidity, 0);
+            assert!(pool.ticks.is_empty());
+            for (balance, protocol_fee) in
+                [(balance_0, protocol_fees.0), (balance_1, protocol_fees.1)]
+            {
+                let dust = balance - I256::try_from(protocol_fee)?;
+                assert!(dust >= I256::ZERO && dust < I256::try_from(20)?, "{dust}");
+            }
+        }
+        Ok(())
+    }
+}
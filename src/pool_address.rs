@@ -0,0 +1,71 @@
+use alloy_primitives::{address, b256, keccak256, Address, B256};
+
+pub const UNISWAP_V3_FACTORY: Address = address!("1F98431c8aD98523631AE4a59f267346ea31F984");
+pub const POOL_INIT_CODE_HASH: B256 =
+    b256!("e34f199b19b2b4f47f68442619d555527d244f78a3297ea89325f843f87b8b54");
+
+// returns (address pool), the CREATE2 address of the pool as computed by PoolAddress.computeAddress
+// the tokens are sorted first so the result does not depend on the order they are passed in
+pub fn compute_pool_address(
+    factory: Address,
+    token_a: Address,
+    token_b: Address,
+    fee: u32,
+    init_code_hash: B256,
+) -> Address {
+    let (token_0, token_1) = if token_a < token_b {
+        (token_a, token_b)
+    } else {
+        (token_b, token_a)
+    };
+
+    // abi.encode(token0, token1, fee), every argument is left padded to 32 bytes
+    let mut encoded = [0_u8; 96];
+    encoded[12..32].copy_from_slice(token_0.as_slice());
+    encoded[44..64].copy_from_slice(token_1.as_slice());
+    encoded[92..96].copy_from_slice(&fee.to_be_bytes());
+
+    factory.create2(keccak256(encoded), init_code_hash)
+}
+
+#[cfg(test)]
+mod test {
+    use alloy_primitives::address;
+
+    use super::{compute_pool_address, POOL_INIT_CODE_HASH, UNISWAP_V3_FACTORY};
+
+    #[test]
+    fn test_compute_pool_address() {
+        let usdc = address!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48");
+        let weth = address!("C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2");
+
+        // USDC/ETH 0.3% pool on mainnet
+        assert_eq!(
+            compute_pool_address(UNISWAP_V3_FACTORY, usdc, weth, 3000, POOL_INIT_CODE_HASH),
+            address!("8ad599c3A0ff1De082011EFDDc58f1908eb6e6D8")
+        );
+
+        // USDC/ETH 0.05% pool on mainnet
+        assert_eq!(
+            compute_pool_address(UNISWAP_V3_FACTORY, usdc, weth, 500, POOL_INIT_CODE_HASH),
+            address!("88e6A0c2dDD26FEEb64F039a2c41296FcB3f5640")
+        );
+    }
+
+    #[test]
+    fn test_compute_pool_address_token_order() {
+        let usdc = address!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48");
+        let weth = address!("C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2");
+
+        assert_eq!(
+            compute_pool_address(UNISWAP_V3_FACTORY, usdc, weth, 3000, POOL_INIT_CODE_HASH),
+            compute_pool_address(UNISWAP_V3_FACTORY, weth, usdc, 3000, POOL_INIT_CODE_HASH)
+        );
+
+        // a different fee tier yields a different pool
+        assert_ne!(
+            compute_pool_address(UNISWAP_V3_FACTORY, usdc, weth, 3000, POOL_INIT_CODE_HASH),
+            compute_pool_address(UNISWAP_V3_FACTORY, usdc, weth, 10000, POOL_INIT_CODE_HASH)
+        );
+    }
+}
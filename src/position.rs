@@ -0,0 +1,650 @@
+use alloy_primitives::{keccak256, Address, B256, U256, U512};
+
+use crate::{
+    error::UniswapV3MathError,
+    fees::compute_uncollected_fees,
+    fixed_point::{FeeGrowthX128, SqrtPriceX96},
+    full_math::mul_div,
+    liquidity_math::add_delta,
+    sqrt_price_math::{get_amounts_for_liquidity, Q96},
+    tick::{get_fee_growth_inside, TickInfo},
+    tick_math::get_sqrt_ratio_at_tick,
+};
+
+//Key of the positions mapping of a pool
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PositionKey {
+    pub owner: Address,
+    pub tick_lower: i32,
+    pub tick_upper: i32,
+}
+
+impl PositionKey {
+    pub fn new(owner: Address, tick_lower: i32, tick_upper: i32) -> Self {
+        Self {
+            owner,
+            tick_lower,
+            tick_upper,
+        }
+    }
+
+    // returns (bytes32) = keccak256(abi.encodePacked(owner, tickLower, tickUpper))
+    pub fn encode(&self) -> B256 {
+        encode_position_key(self.owner, self.tick_lower, self.tick_upper)
+    }
+}
+
+// returns (bytes32) = keccak256(abi.encodePacked(owner, tickLower, tickUpper)), ticks are packed as 3 byte int24s
+pub fn encode_position_key(owner: Address, tick_lower: i32, tick_upper: i32) -> B256 {
+    let mut packed = [0_u8; 26];
+    packed[..20].copy_from_slice(owner.as_slice());
+    packed[20..23].copy_from_slice(&tick_lower.to_be_bytes()[1..]);
+    packed[23..].copy_from_slice(&tick_upper.to_be_bytes()[1..]);
+
+    keccak256(packed)
+}
+
+//Value of the positions mapping of a pool
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PositionInfo {
+    pub liquidity: u128,
+    pub fee_growth_inside_0_last_x_128: FeeGrowthX128,
+    pub fee_growth_inside_1_last_x_128: FeeGrowthX128,
+    pub tokens_owed_0: u128,
+    pub tokens_owed_1: u128,
+}
+
+impl PositionInfo {
+    //Position.update, credits the fees accrued since the last update to the tokens owed and applies the liquidity
+    //delta. A zero delta pokes the position, which errors with LiquidityIsZero ("NP") for an empty position.
+    //Like the contract the accrued fees are truncated to uint128 and the tokens owed overflow silently
+    pub fn update(
+        &mut self,
+        liquidity_delta: i128,
+        fee_growth_inside_0_x_128: FeeGrowthX128,
+        fee_growth_inside_1_x_128: FeeGrowthX128,
+    ) -> Result<(), UniswapV3MathError> {
+        self.update_inner(
+            liquidity_delta,
+            fee_growth_inside_0_x_128,
+            fee_growth_inside_1_x_128,
+            false,
+        )
+    }
+
+    //Same as update, but errors with TokensOwedOverflow and leaves the position untouched if the accrued fees do not
+    //fit into uint128 or the tokens owed overflow
+    pub fn update_checked(
+        &mut self,
+        liquidity_delta: i128,
+        fee_growth_inside_0_x_128: FeeGrowthX128,
+        fee_growth_inside_1_x_128: FeeGrowthX128,
+    ) -> Result<(), UniswapV3MathError> {
+        self.update_inner(
+            liquidity_delta,
+            fee_growth_inside_0_x_128,
+            fee_growth_inside_1_x_128,
+            true,
+        )
+    }
+
+    fn update_inner(
+        &mut self,
+        liquidity_delta: i128,
+        fee_growth_inside_0_x_128: FeeGrowthX128,
+        fee_growth_inside_1_x_128: FeeGrowthX128,
+        checked: bool,
+    ) -> Result<(), UniswapV3MathError> {
+        let liquidity_next = if liquidity_delta == 0 {
+            // disallow pokes for 0 liquidity positions
+            if self.liquidity == 0 {
+                return Err(UniswapV3MathError::LiquidityIsZero);
+            }
+            self.liquidity
+        } else {
+            add_delta(self.liquidity, liquidity_delta)?
+        };
+
+        let tokens_owed_0 = credit_fees(
+            self.tokens_owed_0,
+            *fee_growth_inside_0_x_128.wrapping_sub(self.fee_growth_inside_0_last_x_128),
+            self.liquidity,
+            checked,
+        )?;
+        let tokens_owed_1 = credit_fees(
+            self.tokens_owed_1,
+            *fee_growth_inside_1_x_128.wrapping_sub(self.fee_growth_inside_1_last_x_128),
+            self.liquidity,
+            checked,
+        )?;
+
+        self.liquidity = liquidity_next;
+        self.fee_growth_inside_0_last_x_128 = fee_growth_inside_0_x_128;
+        self.fee_growth_inside_1_last_x_128 = fee_growth_inside_1_x_128;
+        self.tokens_owed_0 = tokens_owed_0;
+        self.tokens_owed_1 = tokens_owed_1;
+
+        Ok(())
+    }
+}
+
+// returns (uint256 fees0, uint256 fees1), the tokens owed of the position plus the fees accrued since its last
+// update, i.e. what collect would return after a poke. Like Position.update the accrued fees are truncated to
+// uint128 and added to the tokens owed with overflow
+#[allow(clippy::too_many_arguments)]
+pub fn get_position_fees(
+    position: &PositionInfo,
+    lower_tick: &TickInfo,
+    upper_tick: &TickInfo,
+    tick_lower: i32,
+    tick_upper: i32,
+    tick_current: i32,
+    fee_growth_global_0_x_128: FeeGrowthX128,
+    fee_growth_global_1_x_128: FeeGrowthX128,
+) -> (U256, U256) {
+    let (fee_growth_inside_0_x_128, fee_growth_inside_1_x_128) = get_fee_growth_inside(
+        lower_tick,
+        upper_tick,
+        tick_lower,
+        tick_upper,
+        tick_current,
+        fee_growth_global_0_x_128,
+        fee_growth_global_1_x_128,
+    );
+
+    let tokens_owed_0 = position.tokens_owed_0.wrapping_add(
+        compute_uncollected_fees(
+            fee_growth_inside_0_x_128,
+            position.fee_growth_inside_0_last_x_128,
+            position.liquidity,
+        )
+        .wrapping_to(),
+    );
+    let tokens_owed_1 = position.tokens_owed_1.wrapping_add(
+        compute_uncollected_fees(
+            fee_growth_inside_1_x_128,
+            position.fee_growth_inside_1_last_x_128,
+            position.liquidity,
+        )
+        .wrapping_to(),
+    );
+
+    (U256::from(tokens_owed_0), U256::from(tokens_owed_1))
+}
+
+//Where the current tick is relative to a position, only in range positions earn fees
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum PositionStatus {
+    BelowRange,
+    InRange,
+    AboveRange,
+}
+
+//In range is tick_lower <= current_tick < tick_upper as in UniswapV3Pool._modifyPosition
+pub fn position_in_range(
+    tick_lower: i32,
+    tick_upper: i32,
+    current_tick: i32,
+) -> Result<PositionStatus, UniswapV3MathError> {
+    if tick_lower >= tick_upper {
+        return Err(UniswapV3MathError::InvalidTickRange {
+            tick_lower,
+            tick_upper,
+        });
+    }
+
+    Ok(if current_tick < tick_lower {
+        PositionStatus::BelowRange
+    } else if current_tick < tick_upper {
+        PositionStatus::InRange
+    } else {
+        PositionStatus::AboveRange
+    })
+}
+
+//Amount0 plus amount1 of the position at the current sqrt price, with amount1 converted to token0 at that price.
+//Rounds down
+pub fn compute_position_value_in_token0(
+    liquidity: u128,
+    tick_lower: i32,
+    tick_upper: i32,
+    current_sqrt_price_x_96: SqrtPriceX96,
+) -> Result<U256, UniswapV3MathError> {
+    let (amount_0, amount_1) =
+        position_amounts(liquidity, tick_lower, tick_upper, current_sqrt_price_x_96)?;
+
+    // amount1 / (sqrtPrice / 2^96)^2, divided in two steps as the squared sqrt price can exceed 256 bits
+    let amount_1_in_token_0 = mul_div(
+        mul_div(amount_1, Q96, current_sqrt_price_x_96.0)?,
+        Q96,
+        current_sqrt_price_x_96.0,
+    )?;
+
+    Ok(amount_0 + amount_1_in_token_0)
+}
+
+//Amount0 plus amount1 of the position at the current sqrt price, with amount0 converted to token1 at that price.
+//Rounds down
+pub fn compute_position_value_in_token1(
+    liquidity: u128,
+    tick_lower: i32,
+    tick_upper: i32,
+    current_sqrt_price_x_96: SqrtPriceX96,
+) -> Result<U256, UniswapV3MathError> {
+    let (amount_0, amount_1) =
+        position_amounts(liquidity, tick_lower, tick_upper, current_sqrt_price_x_96)?;
+
+    let amount_0_in_token_1 = mul_div(
+        mul_div(amount_0, current_sqrt_price_x_96.0, Q96)?,
+        current_sqrt_price_x_96.0,
+        Q96,
+    )?;
+
+    Ok(amount_1 + amount_0_in_token_1)
+}
+
+fn position_amounts(
+    liquidity: u128,
+    tick_lower: i32,
+    tick_upper: i32,
+    current_sqrt_price_x_96: SqrtPriceX96,
+) -> Result<(U256, U256), UniswapV3MathError> {
+    if tick_lower >= tick_upper {
+        return Err(UniswapV3MathError::InvalidTickRange {
+            tick_lower,
+            tick_upper,
+        });
+    }
+    if current_sqrt_price_x_96.is_zero() {
+        return Err(UniswapV3MathError::SqrtPriceIsZero);
+    }
+
+    get_amounts_for_liquidity(
+        current_sqrt_price_x_96,
+        get_sqrt_ratio_at_tick(tick_lower)?,
+        get_sqrt_ratio_at_tick(tick_upper)?,
+        liquidity,
+    )
+}
+
+// uint128(FullMath.mulDiv(feeGrowthDeltaX128, liquidity, FixedPoint128.Q128)), the product can not overflow 512 bits
+fn accrued_fees(fee_growth_delta_x_128: U256, liquidity: u128) -> u128 {
+    let fees: U512 = (U512::from(fee_growth_delta_x_128) * U512::from(liquidity)) >> 128;
+    fees.wrapping_to()
+}
+
+// tokens_owed + accrued_fees, wrapping like the contract unless checked
+fn credit_fees(
+    tokens_owed: u128,
+    fee_growth_delta_x_128: U256,
+    liquidity: u128,
+    checked: bool,
+) -> Result<u128, UniswapV3MathError> {
+    if !checked {
+        return Ok(tokens_owed.wrapping_add(accrued_fees(fee_growth_delta_x_128, liquidity)));
+    }
+
+    let fees: U512 = (U512::from(fee_growth_delta_x_128) * U512::from(liquidity)) >> 128;
+    u128::try_from(fees)
+        .ok()
+        .and_then(|fees| tokens_owed.checked_add(fees))
+        .ok_or(UniswapV3MathError::TokensOwedOverflow {
+            tokens_owed,
+            fee_growth_delta_x_128,
+        })
+}
+
+#[cfg(test)]
+mod test {
+    use alloy_primitives::{address, hex, keccak256, U256};
+
+    use crate::{
+        error::UniswapV3MathError,
+        fixed_point::{FeeGrowthX128, SqrtPriceX96},
+        sqrt_price_math::get_amounts_for_liquidity,
+        swap_simulation::Q128,
+        tick::TickInfo,
+        tick_math::get_sqrt_ratio_at_tick,
+    };
+
+    use super::{
+        compute_position_value_in_token0, compute_position_value_in_token1, encode_position_key,
+        get_position_fees, position_in_range, PositionInfo, PositionKey, PositionStatus,
+    };
+
+    #[test]
+    fn test_encode_position_key() {
+        // the expected keys hash hand-packed abi.encodePacked(owner, tickLower, tickUpper) bytes, they are not
+        // storage keys read from a pool. The owner is the NonfungiblePositionManager
+        let owner = address!("C36442b4a4522E871399CD717aBDD847Ab11FE88");
+
+        let key = PositionKey::new(owner, -887220, 887220);
+        assert_eq!(
+            key.encode(),
+            keccak256(hex!(
+                "c36442b4a4522e871399cd717abdd847ab11fe88" "f2764c" "0d89b4"
+            ))
+        );
+        assert_eq!(key.encode(), encode_position_key(owner, -887220, 887220));
+
+        // int24 packing keeps the two's complement of negative ticks
+        assert_eq!(
+            encode_position_key(owner, -1, 0),
+            keccak256(hex!(
+                "c36442b4a4522e871399cd717abdd847ab11fe88" "ffffff" "000000"
+            ))
+        );
+        assert_eq!(
+            encode_position_key(owner, 195540, 198720),
+            keccak256(hex!(
+                "c36442b4a4522e871399cd717abdd847ab11fe88" "02fbd4" "030840"
+            ))
+        );
+
+        assert_ne!(
+            encode_position_key(owner, -60, 60),
+            encode_position_key(owner, 60, -60)
+        );
+    }
+
+    #[test]
+    fn test_get_position_fees() {
+        let position = PositionInfo {
+            liquidity: 100,
+            tokens_owed_1: 7,
+            ..Default::default()
+        };
+        // the lower tick was initialized while the price was above it, so it holds all previous growth
+        let lower = TickInfo {
+            fee_growth_outside_0_x_128: FeeGrowthX128(Q128 * U256::from(5)),
+            fee_growth_outside_1_x_128: FeeGrowthX128(U256::MAX),
+            initialized: true,
+            ..Default::default()
+        };
+        let upper = TickInfo {
+            initialized: true,
+            ..Default::default()
+        };
+
+        //nothing accrued before any swap
+        assert_eq!(
+            get_position_fees(
+                &position,
+                &lower,
+                &upper,
+                -60,
+                60,
+                0,
+                FeeGrowthX128(Q128 * U256::from(5)),
+                FeeGrowthX128(U256::MAX)
+            ),
+            (U256::ZERO, U256::from(7))
+        );
+
+        //first swap accrues 3 token0 per unit of liquidity inside the range
+        let fee_growth_global_0 = Q128 * U256::from(8);
+        assert_eq!(
+            get_position_fees(
+                &position,
+                &lower,
+                &upper,
+                -60,
+                60,
+                0,
+                FeeGrowthX128(fee_growth_global_0),
+                FeeGrowthX128(U256::MAX)
+            ),
+            (U256::from(300), U256::from(7))
+        );
+
+        //second swap accrues 2.5 token1 per unit of liquidity, with the global fee growth wrapping around
+        let fee_growth_global_1 = U256::MAX.wrapping_add(Q128 * U256::from(5) / U256::from(2));
+        assert_eq!(
+            get_position_fees(
+                &position,
+                &lower,
+                &upper,
+                -60,
+                60,
+                0,
+                FeeGrowthX128(fee_growth_global_0),
+                FeeGrowthX128(fee_growth_global_1)
+            ),
+            (U256::from(300), U256::from(257))
+        );
+
+        //after the price left the range through the upper tick, growth outside of the range is not counted
+        let upper = TickInfo {
+            fee_growth_outside_0_x_128: FeeGrowthX128(fee_growth_global_0),
+            fee_growth_outside_1_x_128: FeeGrowthX128(fee_growth_global_1),
+            ..upper
+        };
+        assert_eq!(
+            get_position_fees(
+                &position,
+                &lower,
+                &upper,
+                -60,
+                60,
+                120,
+                FeeGrowthX128(fee_growth_global_0 + Q128),
+                FeeGrowthX128(fee_growth_global_1 + Q128)
+            ),
+            (U256::from(300), U256::from(257))
+        );
+    }
+
+    #[test]
+    fn test_position_update() -> eyre::Result<()> {
+        let mut position = PositionInfo::default();
+
+        //poking an empty position is rejected
+        assert!(matches!(
+            position.update(0, FeeGrowthX128(U256::ZERO), FeeGrowthX128(U256::ZERO)),
+            Err(UniswapV3MathError::LiquidityIsZero)
+        ));
+
+        //minting at the first checkpoint only records the fee growth inside
+        position.update(
+            100,
+            FeeGrowthX128(Q128 * U256::from(2)),
+            FeeGrowthX128(Q128),
+        )?;
+        assert_eq!(
+            position,
+            PositionInfo {
+                liquidity: 100,
+                fee_growth_inside_0_last_x_128: FeeGrowthX128(Q128 * U256::from(2)),
+                fee_growth_inside_1_last_x_128: FeeGrowthX128(Q128),
+                tokens_owed_0: 0,
+                tokens_owed_1: 0,
+            }
+        );
+
+        //a poke at the second checkpoint credits the fees accrued in between
+        position.update(
+            0,
+            FeeGrowthX128(Q128 * U256::from(5)),
+            FeeGrowthX128(Q128 + Q128 / U256::from(4)),
+        )?;
+        assert_eq!(position.liquidity, 100);
+        assert_eq!(position.tokens_owed_0, 300);
+        assert_eq!(position.tokens_owed_1, 25);
+
+        //burning credits the fees with the liquidity before the burn and matches get_position_fees
+        let fee_growth_inside_0 = FeeGrowthX128(Q128 * U256::from(6));
+        let (fees_0, fees_1) = get_position_fees(
+            &position,
+            &TickInfo::default(),
+            &TickInfo::default(),
+            -60,
+            60,
+            0,
+            fee_growth_inside_0,
+            FeeGrowthX128(Q128 + Q128 / U256::from(4)),
+        );
+        position.update(
+            -40,
+            fee_growth_inside_0,
+            FeeGrowthX128(Q128 + Q128 / U256::from(4)),
+        )?;
+        assert_eq!(position.liquidity, 60);
+        assert_eq!(U256::from(position.tokens_owed_0), fees_0);
+        assert_eq!(U256::from(position.tokens_owed_1), fees_1);
+        assert_eq!(position.tokens_owed_0, 400);
+
+        //burning more than the position holds fails without touching the position
+        let before = position;
+        assert!(matches!(
+            position.update(
+                -61,
+                FeeGrowthX128(Q128 * U256::from(7)),
+                FeeGrowthX128(Q128)
+            ),
+            Err(UniswapV3MathError::LiquidityUnderflow { .. })
+        ));
+        assert_eq!(position, before);
+        Ok(())
+    }
+
+    #[test]
+    fn test_position_update_overflow() -> eyre::Result<()> {
+        let position = PositionInfo {
+            liquidity: 1,
+            tokens_owed_0: u128::MAX,
+            ..Default::default()
+        };
+
+        //the contract lets the tokens owed overflow
+        let mut unchecked = position;
+        unchecked.update(
+            0,
+            FeeGrowthX128(Q128 * U256::from(2)),
+            FeeGrowthX128(U256::ZERO),
+        )?;
+        assert_eq!(unchecked.tokens_owed_0, 1);
+
+        //the checked mode errors instead
+        let mut checked = position;
+        assert!(matches!(
+            checked.update_checked(
+                0,
+                FeeGrowthX128(Q128 * U256::from(2)),
+                FeeGrowthX128(U256::ZERO)
+            ),
+            Err(UniswapV3MathError::TokensOwedOverflow {
+                tokens_owed: u128::MAX,
+                ..
+            })
+        ));
+        assert_eq!(checked, position);
+
+        //accrued fees above uint128 are truncated, or rejected in checked mode
+        let position = PositionInfo {
+            liquidity: u128::MAX,
+            ..Default::default()
+        };
+        let mut unchecked = position;
+        unchecked.update(
+            0,
+            FeeGrowthX128(U256::ZERO),
+            FeeGrowthX128(Q128 * U256::from(3)),
+        )?;
+        assert_eq!(unchecked.tokens_owed_1, u128::MAX - 2);
+        let mut checked = position;
+        assert!(checked
+            .update_checked(
+                0,
+                FeeGrowthX128(U256::ZERO),
+                FeeGrowthX128(Q128 * U256::from(3))
+            )
+            .is_err());
+        Ok(())
+    }
+
+    #[test]
+    fn test_compute_position_value() -> eyre::Result<()> {
+        let liquidity = 1e18 as u128;
+
+        //at price 1 both tokens are worth the same
+        let sqrt_price = get_sqrt_ratio_at_tick(0)?;
+        let (amount_0, amount_1) = get_amounts_for_liquidity(
+            sqrt_price,
+            get_sqrt_ratio_at_tick(-600)?,
+            get_sqrt_ratio_at_tick(600)?,
+            liquidity,
+        )?;
+        assert!(amount_0 > U256::ZERO && amount_1 > U256::ZERO);
+        assert_eq!(
+            compute_position_value_in_token0(liquidity, -600, 600, sqrt_price)?,
+            amount_0 + amount_1
+        );
+        assert_eq!(
+            compute_position_value_in_token1(liquidity, -600, 600, sqrt_price)?,
+            amount_0 + amount_1
+        );
+
+        //below the range the position only holds token0
+        let sqrt_price = get_sqrt_ratio_at_tick(-1200)?;
+        let (amount_0, amount_1) = get_amounts_for_liquidity(
+            sqrt_price,
+            get_sqrt_ratio_at_tick(-600)?,
+            get_sqrt_ratio_at_tick(600)?,
+            liquidity,
+        )?;
+        assert_eq!(amount_1, U256::ZERO);
+        assert_eq!(
+            compute_position_value_in_token0(liquidity, -600, 600, sqrt_price)?,
+            amount_0
+        );
+        //token0 is worth 1.0001^-1200 ~ 0.887 token1
+        let value_1 = compute_position_value_in_token1(liquidity, -600, 600, sqrt_price)?;
+        assert_eq!(value_1 * U256::from(1000) / amount_0, U256::from(886));
+
+        //above the range the position only holds token1, worth 1 / 1.0001^1200 ~ 0.887 token0
+        let sqrt_price = get_sqrt_ratio_at_tick(1200)?;
+        let value_0 = compute_position_value_in_token0(liquidity, -600, 600, sqrt_price)?;
+        let value_1 = compute_position_value_in_token1(liquidity, -600, 600, sqrt_price)?;
+        assert_eq!(value_0 * U256::from(1000) / value_1, U256::from(886));
+
+        //straddling, converting the value in token0 to token1 gives the value in token1 up to rounding
+        let sqrt_price = get_sqrt_ratio_at_tick(300)?;
+        let value_0 = compute_position_value_in_token0(liquidity, -600, 600, sqrt_price)?;
+        let value_1 = compute_position_value_in_token1(liquidity, -600, 600, sqrt_price)?;
+        let price_x_192 = *sqrt_price * *sqrt_price;
+        let value_0_in_token_1 = (value_0 * price_x_192) >> 192;
+        assert!(value_1 >= value_0_in_token_1 && value_1 - value_0_in_token_1 <= U256::from(2));
+
+        assert!(matches!(
+            compute_position_value_in_token0(liquidity, 600, -600, sqrt_price),
+            Err(UniswapV3MathError::InvalidTickRange { .. })
+        ));
+        assert!(matches!(
+            compute_position_value_in_token1(liquidity, -600, 600, SqrtPriceX96(U256::ZERO)),
+            Err(UniswapV3MathError::SqrtPriceIsZero)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_position_in_range() {
+        let status = |current_tick| position_in_range(-60, 60, current_tick).unwrap();
+        assert_eq!(status(-61), PositionStatus::BelowRange);
+        // the lower tick is in range, the upper tick is not
+        assert_eq!(status(-60), PositionStatus::InRange);
+        assert_eq!(status(0), PositionStatus::InRange);
+        assert_eq!(status(59), PositionStatus::InRange);
+        assert_eq!(status(60), PositionStatus::AboveRange);
+        assert_eq!(status(i32::MAX), PositionStatus::AboveRange);
+
+        for (tick_lower, tick_upper) in [(60, 60), (60, -60)] {
+            assert!(matches!(
+                position_in_range(tick_lower, tick_upper, 0),
+                Err(UniswapV3MathError::InvalidTickRange { .. })
+            ));
+        }
+    }
+}
@@ -0,0 +1,261 @@
+use alloc::{string::String, vec::Vec};
+
+use alloy_primitives::{U256, U512};
+
+use crate::{
+    error::UniswapV3MathError,
+    tick_math::{MAX_SQRT_RATIO, MIN_SQRT_RATIO},
+};
+
+//Exact decimal price of the base token in the quote token, truncated to significant_digits significant digits.
+//The price is sqrt_price_x_96^2 / 2^192 * 10^(decimals0 - decimals1) with token0 as base and its inverse with
+//token1 as base. The ratio is computed over 512 bits and its digits by long division, the decimals only move the
+//decimal point, so no precision is lost at any price in [MIN_SQRT_RATIO, MAX_SQRT_RATIO]. Trailing zeros after the
+//decimal point are dropped, e.g. "2000" or "0.0005"
+pub fn sqrt_price_x96_to_decimal_string(
+    sqrt_price_x_96: U256,
+    decimals0: u8,
+    decimals1: u8,
+    base_is_token0: bool,
+    significant_digits: usize,
+) -> Result<String, UniswapV3MathError> {
+    if !(MIN_SQRT_RATIO..=MAX_SQRT_RATIO).contains(&sqrt_price_x_96) {
+        return Err(UniswapV3MathError::SqrtPriceOutOfBounds { sqrt_price_x_96 });
+    }
+    if significant_digits == 0 {
+        return Err(UniswapV3MathError::InvalidSignificantDigits { significant_digits });
+    }
+
+    let price_x_192 = U512::from(sqrt_price_x_96) * U512::from(sqrt_price_x_96);
+    let q192 = U512::from(1) << 192;
+    let (numerator, denominator, decimal_shift) = if base_is_token0 {
+        (price_x_192, q192, decimals0 as i32 - decimals1 as i32)
+    } else {
+        (q192, price_x_192, decimals1 as i32 - decimals0 as i32)
+    };
+
+    let (integer, mut remainder) = numerator.div_rem(denominator);
+    let mut digits = integer_digits(integer);
+    // digits before the decimal point of the raw ratio
+    let integer_len = digits.len();
+
+    if digits.len() > significant_digits {
+        digits[significant_digits..].fill(0);
+    }
+    // leading zeros after the decimal point do not count as significant
+    let mut significant = digits.len().min(significant_digits);
+    let ten = U512::from(10);
+    while significant < significant_digits && !remainder.is_zero() {
+        let (digit, rest) = (remainder * ten).div_rem(denominator);
+        remainder = rest;
+        let digit = digit.to::<u8>();
+        if significant > 0 || digit != 0 {
+            significant += 1;
+        }
+        digits.push(digit);
+    }
+
+    Ok(format_digits(
+        &digits,
+        integer_len as i64 + decimal_shift as i64,
+    ))
+}
+
+// decimal digits of value, most significant first, empty for 0
+fn integer_digits(mut value: U512) -> Vec<u8> {
+    let ten = U512::from(10);
+    let mut digits = Vec::new();
+    while !value.is_zero() {
+        let (quotient, digit) = value.div_rem(ten);
+        digits.push(digit.to::<u8>());
+        value = quotient;
+    }
+    digits.reverse();
+    digits
+}
+
+// places the decimal point after point digits, padding with zeros on either side, and trims leading zeros of
+// the integer part and trailing zeros of the fraction
+fn format_digits(digits: &[u8], point: i64) -> String {
+    let (integer, fraction): (Vec<u8>, Vec<u8>) = if point <= 0 {
+        let mut fraction = alloc::vec![0; point.unsigned_abs() as usize];
+        fraction.extend_from_slice(digits);
+        (Vec::new(), fraction)
+    } else if point as usize >= digits.len() {
+        let mut integer = digits.to_vec();
+        integer.resize(point as usize, 0);
+        (integer, Vec::new())
+    } else {
+        let (integer, fraction) = digits.split_at(point as usize);
+        (integer.to_vec(), fraction.to_vec())
+    };
+
+    let integer = match integer.iter().position(|&digit| digit != 0) {
+        Some(first) => &integer[first..],
+        None => &[0][..],
+    };
+    let fraction = match fraction.iter().rposition(|&digit| digit != 0) {
+        Some(last) => &fraction[..=last],
+        None => &[][..],
+    };
+
+    let mut formatted: String = integer
+        .iter()
+        .map(|&digit| (b'0' + digit) as char)
+        .collect();
+    if !fraction.is_empty() {
+        formatted.push('.');
+        formatted.extend(fraction.iter().map(|&digit| (b'0' + digit) as char));
+    }
+    formatted
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use alloy_primitives::U256;
+
+    use super::sqrt_price_x96_to_decimal_string;
+    use crate::{
+        error::UniswapV3MathError,
+        tick_math::{MAX_SQRT_RATIO, MIN_SQRT_RATIO},
+    };
+
+    #[test]
+    fn test_sqrt_price_x96_to_decimal_string() {
+        // expected values are the exact rationals truncated with python's fractions. The sqrt prices are
+        // constructed for round prices and decimal pairs, they are not slot0 reads of real pools
+        let cases = [
+            // USDC / WETH at 2000 USDC per ETH, the price of USDC is just below 0.0005 after flooring the sqrt
+            (
+                U256::from_str("1771595571142957102961017161607260").unwrap(),
+                6,
+                18,
+                true,
+                18,
+                "0.000499999999999999999",
+            ),
+            (
+                U256::from_str("1771595571142957102961017161607260").unwrap(),
+                6,
+                18,
+                false,
+                18,
+                "2000",
+            ),
+            (
+                U256::from_str("1362369434556658843362446487138593").unwrap(),
+                6,
+                18,
+                false,
+                30,
+                "3381.96714789245892341139255863",
+            ),
+            (
+                U256::from_str("1362369434556658843362446487138593").unwrap(),
+                6,
+                18,
+                true,
+                12,
+                "0.000295685900031",
+            ),
+            (
+                U256::from_str("31741532771011633093148117264281").unwrap(),
+                8,
+                18,
+                true,
+                20,
+                "0.000016050797726813329967",
+            ),
+            (
+                U256::from_str("79224201403219477170569").unwrap(),
+                18,
+                6,
+                true,
+                25,
+                "0.9999000099990000999899772",
+            ),
+            (
+                U256::from_str("79228162514264337593543950336").unwrap(),
+                18,
+                18,
+                true,
+                5,
+                "1",
+            ),
+            (
+                MIN_SQRT_RATIO,
+                18,
+                18,
+                true,
+                10,
+                "0.000000000000000000000000000000000000002938956808",
+            ),
+            (
+                MIN_SQRT_RATIO,
+                18,
+                18,
+                false,
+                10,
+                "340256786600000000000000000000000000000",
+            ),
+            (
+                MAX_SQRT_RATIO,
+                18,
+                18,
+                true,
+                10,
+                "340256786800000000000000000000000000000",
+            ),
+            (
+                U256::from_str("75541088972021052632782079082").unwrap(),
+                18,
+                18,
+                true,
+                4,
+                "0.909",
+            ),
+        ];
+        for (sqrt_price_x_96, decimals0, decimals1, base_is_token0, significant_digits, expected) in
+            cases
+        {
+            assert_eq!(
+                sqrt_price_x96_to_decimal_string(
+                    sqrt_price_x_96,
+                    decimals0,
+                    decimals1,
+                    base_is_token0,
+                    significant_digits
+                )
+                .unwrap(),
+                expected,
+                "{sqrt_price_x_96} {decimals0} {decimals1} {base_is_token0}"
+            );
+        }
+
+        // the widest decimal gaps only move the decimal point
+        let result = sqrt_price_x96_to_decimal_string(MAX_SQRT_RATIO, 0, 255, true, 3).unwrap();
+        assert_eq!(result, format!("0.{}34", "0".repeat(216)));
+        let result = sqrt_price_x96_to_decimal_string(MAX_SQRT_RATIO, 255, 0, true, 3).unwrap();
+        assert_eq!(result, format!("34{}", "0".repeat(292)));
+    }
+
+    #[test]
+    fn test_sqrt_price_x96_to_decimal_string_errors() {
+        assert!(matches!(
+            sqrt_price_x96_to_decimal_string(MIN_SQRT_RATIO - U256::from(1), 18, 18, true, 10),
+            Err(UniswapV3MathError::SqrtPriceOutOfBounds { .. })
+        ));
+        assert!(matches!(
+            sqrt_price_x96_to_decimal_string(MAX_SQRT_RATIO + U256::from(1), 18, 18, true, 10),
+            Err(UniswapV3MathError::SqrtPriceOutOfBounds { .. })
+        ));
+        assert!(matches!(
+            sqrt_price_x96_to_decimal_string(MIN_SQRT_RATIO, 18, 18, true, 0),
+            Err(UniswapV3MathError::InvalidSignificantDigits {
+                significant_digits: 0
+            })
+        ));
+    }
+}
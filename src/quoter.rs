@@ -0,0 +1,589 @@
+#[cfg(feature = "std")]
+use std::{collections::HashMap, vec::Vec};
+
+#[cfg(feature = "std")]
+use alloy_primitives::Address;
+use alloy_primitives::U256;
+
+use crate::{
+    error::UniswapV3MathError,
+    fixed_point::SqrtPriceX96,
+    pool::PoolState,
+    sqrt_price_math::to_int_256,
+    swap_simulation::{simulate_swap_with_protocol_fee, SwapResult, TickDataProvider},
+    tick_math::{MAX_SQRT_RATIO, MIN_SQRT_RATIO},
+};
+#[cfg(feature = "std")]
+use crate::{swap_simulation::HashMapTickDataProvider, tick::TickInfo, tick_bitmap::TickBitmap};
+
+//Rough gas cost of crossing an initialized tick, used for QuoteResult::gas_estimate
+pub const GAS_PER_TICK_CROSSED: u64 = 15_000;
+
+//QuoterV2 return values of a single pool quote
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct Quote {
+    pub amount_in: U256,
+    pub amount_out: U256,
+    pub sqrt_price_after_x_96: SqrtPriceX96,
+    pub tick_after: i32,
+    pub initialized_ticks_crossed: u32,
+}
+
+//Quotes the amount out of swapping amount_in through the pool, like QuoterV2.quoteExactInputSingle.
+//Without a sqrt price limit the swap may run to the min/max sqrt ratio
+pub fn quote_exact_input_single<P: TickDataProvider>(
+    pool_state: &PoolState,
+    tick_provider: &P,
+    amount_in: U256,
+    zero_for_one: bool,
+    sqrt_price_limit_x_96: Option<SqrtPriceX96>,
+) -> Result<Quote, UniswapV3MathError> {
+    let result = simulate_swap_with_protocol_fee(
+        tick_provider,
+        pool_state.sqrt_price_x_96,
+        pool_state.tick,
+        pool_state.liquidity,
+        pool_state.tick_spacing,
+        pool_state.fee,
+        pool_state.fee_protocol,
+        to_int_256(amount_in, false)?,
+        sqrt_price_limit_or_default(sqrt_price_limit_x_96, zero_for_one),
+    )?;
+
+    Ok(to_quote(&result, zero_for_one))
+}
+
+//Quotes the amount in needed to receive amount_out from the pool, like QuoterV2.quoteExactOutputSingle.
+//Without a sqrt price limit the full amount_out has to be available or the quote fails
+pub fn quote_exact_output_single<P: TickDataProvider>(
+    pool_state: &PoolState,
+    tick_provider: &P,
+    amount_out: U256,
+    zero_for_one: bool,
+    sqrt_price_limit_x_96: Option<SqrtPriceX96>,
+) -> Result<Quote, UniswapV3MathError> {
+    let result = simulate_swap_with_protocol_fee(
+        tick_provider,
+        pool_state.sqrt_price_x_96,
+        pool_state.tick,
+        pool_state.liquidity,
+        pool_state.tick_spacing,
+        pool_state.fee,
+        pool_state.fee_protocol,
+        to_int_256(amount_out, true)?,
+        sqrt_price_limit_or_default(sqrt_price_limit_x_96, zero_for_one),
+    )?;
+
+    let quote = to_quote(&result, zero_for_one);
+    if sqrt_price_limit_x_96.is_none() && quote.amount_out != amount_out {
+        return Err(UniswapV3MathError::InsufficientLiquidity {
+            amount_out,
+            amount_out_received: quote.amount_out,
+        });
+    }
+
+    Ok(quote)
+}
+
+//Result of get_output_amount and get_input_amount. gas_estimate only accounts for the initialized ticks crossed,
+//GAS_PER_TICK_CROSSED each
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct QuoteResult {
+    pub amount_in: U256,
+    pub amount_out: U256,
+    pub sqrt_price_after_x_96: SqrtPriceX96,
+    pub tick_after: i32,
+    pub gas_estimate: u64,
+}
+
+//Swaps amount_in through the pool without a price limit and moves pool_state to the state after the swap
+#[cfg(feature = "std")]
+pub fn get_output_amount(
+    pool_state: &mut PoolState,
+    tick_bitmap: &TickBitmap,
+    ticks: &HashMap<i32, TickInfo>,
+    amount_in: U256,
+    zero_for_one: bool,
+) -> Result<QuoteResult, UniswapV3MathError> {
+    let result = simulate_swap_with_protocol_fee(
+        &HashMapTickDataProvider { tick_bitmap, ticks },
+        pool_state.sqrt_price_x_96,
+        pool_state.tick,
+        pool_state.liquidity,
+        pool_state.tick_spacing,
+        pool_state.fee,
+        pool_state.fee_protocol,
+        to_int_256(amount_in, false)?,
+        sqrt_price_limit_or_default(None, zero_for_one),
+    )?;
+    pool_state.apply_swap_result(&result, zero_for_one);
+
+    Ok(to_quote_result(&result, zero_for_one))
+}
+
+//Swaps for exactly amount_out and moves pool_state to the state after the swap. Errors with
+//InsufficientLiquidity and leaves pool_state untouched if the pool can not provide amount_out
+#[cfg(feature = "std")]
+pub fn get_input_amount(
+    pool_state: &mut PoolState,
+    tick_bitmap: &TickBitmap,
+    ticks: &HashMap<i32, TickInfo>,
+    amount_out: U256,
+    zero_for_one: bool,
+) -> Result<QuoteResult, UniswapV3MathError> {
+    let result = simulate_swap_with_protocol_fee(
+        &HashMapTickDataProvider { tick_bitmap, ticks },
+        pool_state.sqrt_price_x_96,
+        pool_state.tick,
+        pool_state.liquidity,
+        pool_state.tick_spacing,
+        pool_state.fee,
+        pool_state.fee_protocol,
+        to_int_256(amount_out, true)?,
+        sqrt_price_limit_or_default(None, zero_for_one),
+    )?;
+
+    let quote = to_quote_result(&result, zero_for_one);
+    if quote.amount_out != amount_out {
+        return Err(UniswapV3MathError::InsufficientLiquidity {
+            amount_out,
+            amount_out_received: quote.amount_out,
+        });
+    }
+    pool_state.apply_swap_result(&result, zero_for_one);
+
+    Ok(quote)
+}
+
+//Result of get_output_amount_multi_hop. intermediate_amounts holds the amount out of every hop but the last,
+//which is the amount in of the following hop
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MultiHopQuoteResult {
+    pub amount_out: U256,
+    pub intermediate_amounts: Vec<U256>,
+    pub sqrt_price_after_x_96: SqrtPriceX96,
+}
+
+//Swaps amount_in through the pools in order, path[i] -> path[i + 1] through pools[i]. The swap direction of each
+//hop follows from the token order, token0 being the lower address. The pools are only moved to the state after
+//the swap if every hop succeeds
+#[cfg(feature = "std")]
+pub fn get_output_amount_multi_hop(
+    pools: &mut [(&mut PoolState, &TickBitmap, &HashMap<i32, TickInfo>)],
+    amount_in: U256,
+    path: &[Address],
+) -> Result<MultiHopQuoteResult, UniswapV3MathError> {
+    if pools.is_empty() || path.len() != pools.len() + 1 {
+        return Err(UniswapV3MathError::InvalidPathLength {
+            path_length: path.len(),
+            pools: pools.len(),
+        });
+    }
+
+    let mut pool_states = Vec::with_capacity(pools.len());
+    let mut intermediate_amounts = Vec::with_capacity(pools.len() - 1);
+    let mut amount = amount_in;
+    for ((pool_state, tick_bitmap, ticks), tokens) in pools.iter().zip(path.windows(2)) {
+        let mut pool_state = **pool_state;
+        let quote = get_output_amount(
+            &mut pool_state,
+            tick_bitmap,
+            ticks,
+            amount,
+            tokens[0] < tokens[1],
+        )?;
+
+        pool_states.push(pool_state);
+        intermediate_amounts.push(quote.amount_out);
+        amount = quote.amount_out;
+    }
+    intermediate_amounts.pop();
+
+    for ((pool_state, _, _), state_after) in pools.iter_mut().zip(&pool_states) {
+        **pool_state = *state_after;
+    }
+
+    Ok(MultiHopQuoteResult {
+        amount_out: amount,
+        intermediate_amounts,
+        sqrt_price_after_x_96: pool_states[pool_states.len() - 1].sqrt_price_x_96,
+    })
+}
+
+fn sqrt_price_limit_or_default(
+    sqrt_price_limit_x_96: Option<SqrtPriceX96>,
+    zero_for_one: bool,
+) -> SqrtPriceX96 {
+    sqrt_price_limit_x_96.unwrap_or(SqrtPriceX96(if zero_for_one {
+        MIN_SQRT_RATIO + U256::from(1)
+    } else {
+        MAX_SQRT_RATIO - U256::from(1)
+    }))
+}
+
+fn to_quote(result: &SwapResult, zero_for_one: bool) -> Quote {
+    let (amount_in, amount_out) = if zero_for_one {
+        (result.amount_0, result.amount_1)
+    } else {
+        (result.amount_1, result.amount_0)
+    };
+
+    Quote {
+        amount_in: amount_in.into_raw(),
+        amount_out: amount_out.unsigned_abs(),
+        sqrt_price_after_x_96: result.sqrt_price_x_96,
+        tick_after: result.tick,
+        initialized_ticks_crossed: result.ticks_crossed,
+    }
+}
+
+#[cfg(feature = "std")]
+fn to_quote_result(result: &SwapResult, zero_for_one: bool) -> QuoteResult {
+    let quote = to_quote(result, zero_for_one);
+
+    QuoteResult {
+        amount_in: quote.amount_in,
+        amount_out: quote.amount_out,
+        sqrt_price_after_x_96: quote.sqrt_price_after_x_96,
+        tick_after: quote.tick_after,
+        gas_estimate: quote.initialized_ticks_crossed as u64 * GAS_PER_TICK_CROSSED,
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::HashMap, str::FromStr};
+
+    use alloy_primitives::{address, Address, I256, U256};
+
+    use crate::{
+        error::UniswapV3MathError,
+        fixed_point::SqrtPriceX96,
+        pool::PoolState,
+        swap_math::compute_swap_step,
+        swap_simulation::HashMapTickDataProvider,
+        tick::TickInfo,
+        tick_bitmap::TickBitmap,
+        tick_math::{get_sqrt_ratio_at_tick, MIN_SQRT_RATIO},
+    };
+
+    use super::{
+        get_input_amount, get_output_amount, get_output_amount_multi_hop, quote_exact_input_single,
+        quote_exact_output_single, GAS_PER_TICK_CROSSED,
+    };
+
+    const USDC: Address = address!("A0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48");
+    const WETH: Address = address!("C02aaA39b223FE8D0A0e5C4F27eAD9083C756Cc2");
+    const WBTC: Address = address!("2260FAC5E5542a773Aa44fBCeD7C16DC9631F9fF");
+    const DAI: Address = address!("6B175474E89094C44Da98b954EedeAC495271d0F");
+
+    // Pool at price 1 with the given (tick_lower, tick_upper, liquidity) positions and a tick spacing of 60
+    fn init_test_pool(
+        positions: &[(i32, i32, i128)],
+    ) -> eyre::Result<(PoolState, TickBitmap, HashMap<i32, TickInfo>)> {
+        let mut tick_bitmap = TickBitmap::new();
+        let mut ticks = HashMap::new();
+        let mut liquidity = 0;
+        for &(tick_lower, tick_upper, position_liquidity) in positions {
+            for (tick, liquidity_net) in [
+                (tick_lower, position_liquidity),
+                (tick_upper, -position_liquidity),
+            ] {
+                tick_bitmap.flip_tick(tick, 60)?;
+                ticks.insert(
+                    tick,
+                    TickInfo {
+                        liquidity_gross: position_liquidity as u128,
+                        liquidity_net,
+                        initialized: true,
+                        ..Default::default()
+                    },
+                );
+            }
+            liquidity += position_liquidity as u128;
+        }
+
+        let pool = PoolState {
+            sqrt_price_x_96: get_sqrt_ratio_at_tick(0)?,
+            liquidity,
+            tick: 0,
+            fee: 3000,
+            tick_spacing: 60,
+            ..Default::default()
+        };
+
+        Ok((pool, tick_bitmap, ticks))
+    }
+
+    #[test]
+    fn test_quote_exact_input_single() -> eyre::Result<()> {
+        let (pool, tick_bitmap, ticks) =
+            init_test_pool(&[(-887220, 887220, 1e18 as i128), (-600, 600, 2e18 as i128)])?;
+        let provider = HashMapTickDataProvider {
+            tick_bitmap: &tick_bitmap,
+            ticks: &ticks,
+        };
+        let amount_in = U256::from_str("1000000000000000000")?;
+
+        let quote = quote_exact_input_single(&pool, &provider, amount_in, false, None)?;
+
+        //the pool state is not touched, simulating the same swap on it gives the same amounts
+        let mut swapped = pool;
+        let (amount_0, amount_1) = swapped.simulate_swap(
+            &tick_bitmap,
+            &ticks,
+            false,
+            I256::from_raw(amount_in),
+            get_sqrt_ratio_at_tick(887271)?,
+        )?;
+        assert_eq!(quote.amount_in, amount_1.into_raw());
+        assert_eq!(quote.amount_out, amount_0.unsigned_abs());
+        assert_eq!(quote.sqrt_price_after_x_96, swapped.sqrt_price_x_96);
+        assert_eq!(quote.tick_after, swapped.tick);
+        assert_eq!(quote.initialized_ticks_crossed, 1);
+
+        //stops at the limit with part of the input left
+        let sqrt_price_limit = get_sqrt_ratio_at_tick(300)?;
+        let quote =
+            quote_exact_input_single(&pool, &provider, amount_in, false, Some(sqrt_price_limit))?;
+        assert!(quote.amount_in < amount_in);
+        assert_eq!(quote.sqrt_price_after_x_96, sqrt_price_limit);
+        assert_eq!(quote.tick_after, 300);
+        assert_eq!(quote.initialized_ticks_crossed, 0);
+        Ok(())
+    }
+
+    #[test]
+    fn test_quote_exact_output_single() -> eyre::Result<()> {
+        let (pool, tick_bitmap, ticks) =
+            init_test_pool(&[(-887220, 887220, 1e18 as i128), (-600, 600, 2e18 as i128)])?;
+        let provider = HashMapTickDataProvider {
+            tick_bitmap: &tick_bitmap,
+            ticks: &ticks,
+        };
+        let amount_in = U256::from_str("1000000000000000000")?;
+
+        //asking for the output of an exact input quote needs at most the same input
+        let exact_in = quote_exact_input_single(&pool, &provider, amount_in, true, None)?;
+        let exact_out =
+            quote_exact_output_single(&pool, &provider, exact_in.amount_out, true, None)?;
+
+        assert_eq!(exact_out.amount_out, exact_in.amount_out);
+        assert!(exact_out.amount_in <= amount_in);
+        assert_eq!(exact_out.tick_after, exact_in.tick_after);
+        assert_eq!(exact_out.initialized_ticks_crossed, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_quote_exact_output_single_insufficient_liquidity() -> eyre::Result<()> {
+        let (pool, tick_bitmap, ticks) = init_test_pool(&[(-600, 600, 1e18 as i128)])?;
+        let provider = HashMapTickDataProvider {
+            tick_bitmap: &tick_bitmap,
+            ticks: &ticks,
+        };
+        let amount_out = U256::from_str("1000000000000000000")?;
+
+        //the position only holds about 0.03e18 of token0
+        let result = quote_exact_output_single(&pool, &provider, amount_out, false, None);
+        assert!(matches!(
+            result,
+            Err(UniswapV3MathError::InsufficientLiquidity { amount_out_received, .. })
+                if amount_out_received < amount_out
+        ));
+
+        //with an explicit limit the partial output is returned
+        let quote = quote_exact_output_single(
+            &pool,
+            &provider,
+            amount_out,
+            false,
+            Some(get_sqrt_ratio_at_tick(1200)?),
+        )?;
+        assert!(quote.amount_out < amount_out);
+        assert_eq!(quote.tick_after, 1200);
+        assert_eq!(quote.initialized_ticks_crossed, 1);
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_output_amount() -> eyre::Result<()> {
+        let (mut pool, tick_bitmap, ticks) = init_test_pool(&[(-887220, 887220, 1e18 as i128)])?;
+        let amount_in = U256::from(1e15);
+
+        //a swap within a single range is one swap step
+        let (sqrt_price_next, _, amount_out, _) = compute_swap_step(
+            pool.sqrt_price_x_96,
+            SqrtPriceX96(MIN_SQRT_RATIO + U256::from(1)),
+            pool.liquidity,
+            I256::from_raw(amount_in),
+            pool.fee,
+        )?;
+        let initial = pool;
+        let quote = get_output_amount(&mut pool, &tick_bitmap, &ticks, amount_in, true)?;
+        assert_eq!(quote.amount_in, amount_in);
+        assert_eq!(quote.amount_out, amount_out);
+        assert_eq!(quote.sqrt_price_after_x_96, sqrt_price_next);
+        assert_eq!(quote.gas_estimate, 0);
+
+        //the pool moved to the state after the swap
+        assert_eq!(pool.sqrt_price_x_96, quote.sqrt_price_after_x_96);
+        assert_eq!(pool.tick, quote.tick_after);
+        assert!(pool.fee_growth_global_0_x_128 > initial.fee_growth_global_0_x_128);
+        assert_eq!(
+            pool.fee_growth_global_1_x_128,
+            initial.fee_growth_global_1_x_128
+        );
+
+        //crossing the concentrated position adds the gas of one tick
+        let (mut pool, tick_bitmap, ticks) =
+            init_test_pool(&[(-887220, 887220, 1e18 as i128), (-600, 600, 2e18 as i128)])?;
+        let provider = HashMapTickDataProvider {
+            tick_bitmap: &tick_bitmap,
+            ticks: &ticks,
+        };
+        let expected = quote_exact_input_single(&pool, &provider, U256::from(1e18), true, None)?;
+        let quote = get_output_amount(&mut pool, &tick_bitmap, &ticks, U256::from(1e18), true)?;
+        assert_eq!(quote.amount_out, expected.amount_out);
+        assert_eq!(quote.tick_after, expected.tick_after);
+        assert_eq!(quote.gas_estimate, GAS_PER_TICK_CROSSED);
+        assert_eq!(pool.liquidity, 1e18 as u128);
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_input_amount() -> eyre::Result<()> {
+        let (mut pool, tick_bitmap, ticks) =
+            init_test_pool(&[(-887220, 887220, 1e18 as i128), (-600, 600, 2e18 as i128)])?;
+        let amount_out = U256::from(1e18);
+
+        //swapping the quoted input gives at least the requested output
+        let mut exact_in_pool = pool;
+        let quote = get_input_amount(&mut pool, &tick_bitmap, &ticks, amount_out, false)?;
+        assert_eq!(quote.amount_out, amount_out);
+        assert_eq!(quote.gas_estimate, GAS_PER_TICK_CROSSED);
+        let exact_in = get_output_amount(
+            &mut exact_in_pool,
+            &tick_bitmap,
+            &ticks,
+            quote.amount_in,
+            false,
+        )?;
+        assert!(exact_in.amount_out >= amount_out);
+        assert_eq!(pool.tick, quote.tick_after);
+
+        //the pool is left untouched when it can not provide the output
+        let (mut pool, tick_bitmap, ticks) = init_test_pool(&[(-600, 600, 2e18 as i128)])?;
+        let initial = pool;
+        assert!(matches!(
+            get_input_amount(&mut pool, &tick_bitmap, &ticks, amount_out, false),
+            Err(UniswapV3MathError::InsufficientLiquidity { .. })
+        ));
+        assert_eq!(pool, initial);
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_output_amount_multi_hop() -> eyre::Result<()> {
+        let (mut usdc_weth, usdc_weth_bitmap, usdc_weth_ticks) =
+            init_test_pool(&[(-887220, 887220, 1e18 as i128), (-600, 600, 2e18 as i128)])?;
+        let (mut wbtc_weth, wbtc_weth_bitmap, wbtc_weth_ticks) =
+            init_test_pool(&[(-887220, 887220, 5e17 as i128)])?;
+        let (mut wbtc_dai, wbtc_dai_bitmap, wbtc_dai_ticks) =
+            init_test_pool(&[(-1200, 1200, 3e18 as i128)])?;
+        let amount_in = U256::from(1e17);
+
+        //USDC -> WETH and WBTC -> DAI swap token0 for token1, WETH -> WBTC swaps token1 for token0
+        let (mut first, mut second, mut third) = (usdc_weth, wbtc_weth, wbtc_dai);
+        let hop_0 = get_output_amount(
+            &mut first,
+            &usdc_weth_bitmap,
+            &usdc_weth_ticks,
+            amount_in,
+            true,
+        )?;
+        let hop_1 = get_output_amount(
+            &mut second,
+            &wbtc_weth_bitmap,
+            &wbtc_weth_ticks,
+            hop_0.amount_out,
+            false,
+        )?;
+        let hop_2 = get_output_amount(
+            &mut third,
+            &wbtc_dai_bitmap,
+            &wbtc_dai_ticks,
+            hop_1.amount_out,
+            true,
+        )?;
+
+        let result = get_output_amount_multi_hop(
+            &mut [
+                (&mut usdc_weth, &usdc_weth_bitmap, &usdc_weth_ticks),
+                (&mut wbtc_weth, &wbtc_weth_bitmap, &wbtc_weth_ticks),
+                (&mut wbtc_dai, &wbtc_dai_bitmap, &wbtc_dai_ticks),
+            ],
+            amount_in,
+            &[USDC, WETH, WBTC, DAI],
+        )?;
+        assert_eq!(
+            result.intermediate_amounts,
+            [hop_0.amount_out, hop_1.amount_out]
+        );
+        assert_eq!(result.amount_out, hop_2.amount_out);
+        assert_eq!(result.sqrt_price_after_x_96, hop_2.sqrt_price_after_x_96);
+        assert!(result.amount_out < amount_in);
+
+        //every pool moved to the state after its hop
+        assert_eq!(usdc_weth, first);
+        assert_eq!(wbtc_weth, second);
+        assert_eq!(wbtc_dai, third);
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_output_amount_multi_hop_errors() -> eyre::Result<()> {
+        let (mut pool, tick_bitmap, ticks) = init_test_pool(&[(-887220, 887220, 1e18 as i128)])?;
+        let amount_in = U256::from(1e17);
+
+        assert!(matches!(
+            get_output_amount_multi_hop(
+                &mut [(&mut pool, &tick_bitmap, &ticks)],
+                amount_in,
+                &[USDC, WETH, DAI],
+            ),
+            Err(UniswapV3MathError::InvalidPathLength {
+                path_length: 3,
+                pools: 1
+            })
+        ));
+        assert!(matches!(
+            get_output_amount_multi_hop(&mut [], amount_in, &[USDC]),
+            Err(UniswapV3MathError::InvalidPathLength { .. })
+        ));
+
+        //the empty second pool has nothing to pay out, so the third hop has no input and the whole route fails
+        let (mut empty, empty_bitmap, empty_ticks) = init_test_pool(&[])?;
+        let (mut last, last_bitmap, last_ticks) = init_test_pool(&[(-600, 600, 1e18 as i128)])?;
+        let initial = pool;
+        let result = get_output_amount_multi_hop(
+            &mut [
+                (&mut pool, &tick_bitmap, &ticks),
+                (&mut empty, &empty_bitmap, &empty_ticks),
+                (&mut last, &last_bitmap, &last_ticks),
+            ],
+            amount_in,
+            &[USDC, WETH, WBTC, DAI],
+        );
+        assert!(matches!(
+            result,
+            Err(UniswapV3MathError::AmountSpecifiedIsZero)
+        ));
+        assert_eq!(pool, initial);
+        Ok(())
+    }
+}
@@ -0,0 +1,25 @@
+//Serde helpers for fields whose default representation does not match the rest of the crate
+//I256 as a sign prefixed 0x hex string for human readable formats, like U256 is, and as the raw two's
+//complement word for binary formats. Use with #[serde(with = "crate::serde_helpers::i256_hex")]
+pub mod i256_hex {
+    use alloc::string::String;
+
+    use alloy_primitives::{I256, U256};
+    use serde::{de::Error, Deserialize, Deserializer, Serialize, Serializer};
+
+    pub fn serialize<S: Serializer>(value: &I256, serializer: S) -> Result<S::Ok, S::Error> {
+        if serializer.is_human_readable() {
+            serializer.serialize_str(&value.to_hex_string())
+        } else {
+            value.into_raw().serialize(serializer)
+        }
+    }
+
+    pub fn deserialize<'de, D: Deserializer<'de>>(deserializer: D) -> Result<I256, D::Error> {
+        if deserializer.is_human_readable() {
+            I256::from_hex_str(&String::deserialize(deserializer)?).map_err(D::Error::custom)
+        } else {
+            U256::deserialize(deserializer).map(I256::from_raw)
+        }
+    }
+}
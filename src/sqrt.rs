@@ -0,0 +1,73 @@
+use alloy_primitives::{ruint::Uint, U256};
+
+// returns (uint128 z) = floor(sqrt(x))
+pub fn uint128_sqrt(x: U256) -> u128 {
+    // sqrt(2**256 - 1) < 2**128, so the result always fits into 128 bits
+    floor_sqrt(x).to::<u128>()
+}
+
+// returns floor(sqrt(x)) for any width, only uses integer ops so it stays available without std
+pub(crate) fn floor_sqrt<const BITS: usize, const LIMBS: usize>(
+    x: Uint<BITS, LIMBS>,
+) -> Uint<BITS, LIMBS> {
+    if x.is_zero() {
+        return x;
+    }
+
+    // seed with a power of two that is greater than or equal to sqrt(x)
+    let mut z = Uint::<BITS, LIMBS>::from(1) << x.bit_len().div_ceil(2);
+
+    // Newton-Raphson iterations decrease monotonically towards floor(sqrt(x))
+    loop {
+        let y = (z + x / z) >> 1;
+        if y >= z {
+            break;
+        }
+        z = y;
+    }
+
+    debug_assert!(z * z <= x);
+
+    z
+}
+
+#[cfg(test)]
+mod test {
+    use alloy_primitives::{keccak256, U256, U512};
+
+    use super::uint128_sqrt;
+
+    fn assert_floor_sqrt(x: U256) {
+        let result = U512::from(uint128_sqrt(x));
+        let x = U512::from(x);
+        assert!(result * result <= x);
+        assert!(x < (result + U512::from(1)) * (result + U512::from(1)));
+    }
+
+    #[test]
+    fn test_uint128_sqrt() {
+        assert_eq!(uint128_sqrt(U256::ZERO), 0);
+        assert_eq!(uint128_sqrt(U256::from(1)), 1);
+        assert_eq!(uint128_sqrt(U256::from(3)), 1);
+        assert_eq!(uint128_sqrt(U256::from(4)), 2);
+        assert_eq!(uint128_sqrt(U256::MAX), u128::MAX);
+        assert_eq!(uint128_sqrt(U256::from(u128::MAX)), u64::MAX as u128);
+
+        // perfect squares and one-off values
+        for root in [2_u128, 7, 1 << 64, 1e18 as u128, u128::MAX] {
+            let square = U256::from(root) * U256::from(root);
+            assert_eq!(uint128_sqrt(square), root);
+            assert_eq!(uint128_sqrt(square - U256::from(1)), root - 1);
+            if root < u128::MAX {
+                assert_eq!(uint128_sqrt(square + U256::from(1)), root);
+            }
+        }
+
+        assert_floor_sqrt(U256::MAX);
+
+        // pseudo random values over the whole range
+        for i in 0..1000_u32 {
+            assert_floor_sqrt(U256::from_be_bytes(keccak256(i.to_be_bytes()).0) >> (i % 256));
+        }
+    }
+}
@@ -1,23 +1,102 @@
-use alloy::primitives::{I256, U256};
+use alloy_primitives::{ruint::Uint, I256, U256, U512};
 
+pub use crate::full_math::Rounding;
 use crate::{
     error::UniswapV3MathError,
+    fixed_point::{SqrtPriceX96, RESOLUTION},
     full_math::{mul_div, mul_div_rounding_up},
+    sqrt::floor_sqrt,
+    tick_math::{get_sqrt_ratio_at_tick, MAX_SQRT_RATIO, MIN_SQRT_RATIO},
     unsafe_math::div_rounding_up,
 };
 
 pub const MAX_U160: U256 =
     U256::from_limbs([18446744073709551615, 18446744073709551615, 4294967295, 0]);
-pub const Q96: U256 = U256::from_limbs([0, 4294967296, 0, 0]);
-pub const FIXED_POINT_96_RESOLUTION: U256 = U256::from_limbs([96, 0, 0, 0]);
+pub use crate::fixed_point::Q96;
+pub const FIXED_POINT_96_RESOLUTION: U256 = U256::from_limbs([RESOLUTION as u64, 0, 0, 0]);
+
+// returns (uint160 sqrtPriceX96) = sqrt(amount1 / amount0) * 2**96
+pub fn encode_sqrt_ratio_x_96(
+    amount_1: U256,
+    amount_0: U256,
+) -> Result<SqrtPriceX96, UniswapV3MathError> {
+    if amount_0.is_zero() {
+        return Err(UniswapV3MathError::DenominatorIsZero);
+    }
+
+    // ratio in X192 is computed over 512 bits so that large amount1 / amount0 ratios keep full precision
+    let ratio_x_192: U512 = (U512::from(amount_1) << (2 * RESOLUTION)) / U512::from(amount_0);
+    let sqrt_price_x_96 = U256::saturating_from(floor_sqrt(ratio_x_192));
+
+    // MAX_SQRT_RATIO is excluded like in TickMath.getTickAtSqrtRatio, a pool can not be initialized at it
+    if sqrt_price_x_96 < MIN_SQRT_RATIO || sqrt_price_x_96 >= MAX_SQRT_RATIO {
+        return Err(UniswapV3MathError::SqrtPriceOutOfBounds { sqrt_price_x_96 });
+    }
+
+    Ok(SqrtPriceX96(sqrt_price_x_96))
+}
+
+//Human readable price of token0 in token1, adjusted for the decimals of both tokens. The f64 result keeps about
+//15 significant digits, so it is meant for display and not for further pool math
+#[cfg(feature = "std")]
+pub fn sqrt_price_x96_to_price(
+    sqrt_price_x96: SqrtPriceX96,
+    token0_decimals: u8,
+    token1_decimals: u8,
+) -> f64 {
+    let sqrt_price = f64::from(sqrt_price_x96.0) / f64::from(Q96);
+
+    sqrt_price * sqrt_price * 10_f64.powi(token0_decimals as i32 - token1_decimals as i32)
+}
+
+//Inverse of sqrt_price_x96_to_price, the result is only as precise as the f64 price it is computed from
+#[cfg(feature = "std")]
+pub fn price_to_sqrt_price_x96(
+    price: f64,
+    token0_decimals: u8,
+    token1_decimals: u8,
+) -> Result<SqrtPriceX96, UniswapV3MathError> {
+    if !price.is_finite() || price <= 0.0 {
+        return Err(UniswapV3MathError::InvalidPrice { price });
+    }
+
+    let raw_price = price * 10_f64.powi(token1_decimals as i32 - token0_decimals as i32);
+    let sqrt_price_x_96 = U256::try_from(raw_price.sqrt() * f64::from(Q96)).unwrap_or(U256::MAX);
+
+    if sqrt_price_x_96 < MIN_SQRT_RATIO || sqrt_price_x_96 > MAX_SQRT_RATIO {
+        return Err(UniswapV3MathError::SqrtPriceOutOfBounds { sqrt_price_x_96 });
+    }
+
+    Ok(SqrtPriceX96(sqrt_price_x_96))
+}
+
+//Tick of the human readable price of token0 in token1 rounded to a multiple of tick_spacing, together with its
+//exact sqrt price. The price is adjusted for the decimals and passed to tick_math::price_to_tick, so prices beyond
+//the tick bounds error and the multiple is moved inside the bounds
+#[cfg(feature = "std")]
+pub fn snap_price_to_sqrt_price(
+    price: f64,
+    tick_spacing: i32,
+    token0_decimals: u8,
+    token1_decimals: u8,
+) -> Result<(i32, SqrtPriceX96), UniswapV3MathError> {
+    if !price.is_finite() || price <= 0.0 {
+        return Err(UniswapV3MathError::InvalidPrice { price });
+    }
+
+    let raw_price = price * 10_f64.powi(token1_decimals as i32 - token0_decimals as i32);
+    let tick = crate::tick_math::price_to_tick(raw_price, tick_spacing)?;
+
+    Ok((tick, get_sqrt_ratio_at_tick(tick)?))
+}
 
 // returns (sqrtQX96)
 pub fn get_next_sqrt_price_from_input(
-    sqrt_price: U256,
+    sqrt_price: SqrtPriceX96,
     liquidity: u128,
     amount_in: U256,
     zero_for_one: bool,
-) -> Result<U256, UniswapV3MathError> {
+) -> Result<SqrtPriceX96, UniswapV3MathError> {
     if sqrt_price.is_zero() {
         return Err(UniswapV3MathError::SqrtPriceIsZero);
     } else if liquidity == 0 {
@@ -33,11 +112,11 @@ pub fn get_next_sqrt_price_from_input(
 
 // returns (sqrtQX96)
 pub fn get_next_sqrt_price_from_output(
-    sqrt_price: U256,
+    sqrt_price: SqrtPriceX96,
     liquidity: u128,
     amount_out: U256,
     zero_for_one: bool,
-) -> Result<U256, UniswapV3MathError> {
+) -> Result<SqrtPriceX96, UniswapV3MathError> {
     if sqrt_price.is_zero() {
         return Err(UniswapV3MathError::SqrtPriceIsZero);
     } else if liquidity == 0 {
@@ -51,18 +130,102 @@ pub fn get_next_sqrt_price_from_output(
     }
 }
 
+// returns (sqrtQX96, amountIn) where sqrtQX96 never crosses sqrt_price_limit and amountIn is the input
+// actually consumed, rounded up like compute_swap_step
+pub fn get_next_sqrt_price_from_input_capped(
+    sqrt_price: SqrtPriceX96,
+    liquidity: u128,
+    amount_in: U256,
+    zero_for_one: bool,
+    sqrt_price_limit: SqrtPriceX96,
+) -> Result<(SqrtPriceX96, U256), UniswapV3MathError> {
+    check_sqrt_price_limit(sqrt_price, zero_for_one, sqrt_price_limit)?;
+
+    let amount_in_to_limit = if zero_for_one {
+        _get_amount_0_delta(sqrt_price_limit, sqrt_price, liquidity, true)?
+    } else {
+        _get_amount_1_delta(sqrt_price, sqrt_price_limit, liquidity, true)?
+    };
+
+    if amount_in >= amount_in_to_limit {
+        return Ok((sqrt_price_limit, amount_in_to_limit));
+    }
+
+    let next_sqrt_price =
+        get_next_sqrt_price_from_input(sqrt_price, liquidity, amount_in, zero_for_one)?;
+
+    let amount_in = if zero_for_one {
+        _get_amount_0_delta(next_sqrt_price, sqrt_price, liquidity, true)?
+    } else {
+        _get_amount_1_delta(sqrt_price, next_sqrt_price, liquidity, true)?
+    };
+
+    Ok((next_sqrt_price, amount_in))
+}
+
+// returns (sqrtQX96, amountOut) where sqrtQX96 never crosses sqrt_price_limit and amountOut is the output
+// actually received, rounded down like compute_swap_step
+pub fn get_next_sqrt_price_from_output_capped(
+    sqrt_price: SqrtPriceX96,
+    liquidity: u128,
+    amount_out: U256,
+    zero_for_one: bool,
+    sqrt_price_limit: SqrtPriceX96,
+) -> Result<(SqrtPriceX96, U256), UniswapV3MathError> {
+    check_sqrt_price_limit(sqrt_price, zero_for_one, sqrt_price_limit)?;
+
+    let amount_out_to_limit = if zero_for_one {
+        _get_amount_1_delta(sqrt_price_limit, sqrt_price, liquidity, false)?
+    } else {
+        _get_amount_0_delta(sqrt_price, sqrt_price_limit, liquidity, false)?
+    };
+
+    if amount_out >= amount_out_to_limit {
+        return Ok((sqrt_price_limit, amount_out_to_limit));
+    }
+
+    let next_sqrt_price =
+        get_next_sqrt_price_from_output(sqrt_price, liquidity, amount_out, zero_for_one)?;
+
+    let amount_out_received = if zero_for_one {
+        _get_amount_1_delta(next_sqrt_price, sqrt_price, liquidity, false)?
+    } else {
+        _get_amount_0_delta(sqrt_price, next_sqrt_price, liquidity, false)?
+    };
+
+    Ok((next_sqrt_price, amount_out_received.min(amount_out)))
+}
+
+fn check_sqrt_price_limit(
+    sqrt_price: SqrtPriceX96,
+    zero_for_one: bool,
+    sqrt_price_limit: SqrtPriceX96,
+) -> Result<(), UniswapV3MathError> {
+    if (zero_for_one && sqrt_price_limit > sqrt_price)
+        || (!zero_for_one && sqrt_price_limit < sqrt_price)
+    {
+        return Err(UniswapV3MathError::SqrtPriceLimitOutOfBounds {
+            sqrt_price_x_96: sqrt_price.0,
+            sqrt_price_limit_x_96: sqrt_price_limit.0,
+        });
+    }
+
+    Ok(())
+}
+
 // returns (uint160 sqrtQX96)
 pub fn get_next_sqrt_price_from_amount_0_rounding_up(
-    sqrt_price_x_96: U256,
+    sqrt_price_x_96: SqrtPriceX96,
     liquidity: u128,
     amount: U256,
     add: bool,
-) -> Result<U256, UniswapV3MathError> {
+) -> Result<SqrtPriceX96, UniswapV3MathError> {
     if amount.is_zero() {
         return Ok(sqrt_price_x_96);
     }
+    let sqrt_price_x_96 = sqrt_price_x_96.0;
 
-    let numerator_1: U256 = U256::from(liquidity) << 96;
+    let numerator_1: U256 = U256::from(liquidity) << RESOLUTION;
 
     if add {
         let product = amount.wrapping_mul(sqrt_price_x_96);
@@ -71,38 +234,44 @@ pub fn get_next_sqrt_price_from_amount_0_rounding_up(
             let denominator = numerator_1.wrapping_add(product);
 
             if denominator >= numerator_1 {
-                return mul_div_rounding_up(numerator_1, sqrt_price_x_96, denominator);
+                return mul_div_rounding_up(numerator_1, sqrt_price_x_96, denominator)
+                    .map(SqrtPriceX96);
             }
         }
 
-        Ok(div_rounding_up(
+        Ok(SqrtPriceX96(div_rounding_up(
             numerator_1,
             (numerator_1.wrapping_div(sqrt_price_x_96)).wrapping_add(amount),
-        ))
+        )))
     } else {
         let product = amount.wrapping_mul(sqrt_price_x_96);
         if product.wrapping_div(amount) == sqrt_price_x_96 && numerator_1 > product {
             let denominator = numerator_1.wrapping_sub(product);
 
-            mul_div_rounding_up(numerator_1, sqrt_price_x_96, denominator)
+            mul_div_rounding_up(numerator_1, sqrt_price_x_96, denominator).map(SqrtPriceX96)
         } else {
-            Err(UniswapV3MathError::ProductDivAmount)
+            Err(UniswapV3MathError::ProductDivAmount {
+                sqrt_price_x_96,
+                liquidity,
+                amount,
+            })
         }
     }
 }
 
 // returns (uint160 sqrtQX96)
 pub fn get_next_sqrt_price_from_amount_1_rounding_down(
-    sqrt_price_x_96: U256,
+    sqrt_price_x_96: SqrtPriceX96,
     liquidity: u128,
     amount: U256,
     add: bool,
-) -> Result<U256, UniswapV3MathError> {
+) -> Result<SqrtPriceX96, UniswapV3MathError> {
+    let sqrt_price_x_96 = sqrt_price_x_96.0;
     let liquidity = U256::from(liquidity);
 
     if add {
         let quotient = if amount <= MAX_U160 {
-            (amount << FIXED_POINT_96_RESOLUTION) / liquidity
+            (amount << RESOLUTION) / liquidity
         } else {
             mul_div(amount, Q96, liquidity)?
         };
@@ -110,121 +279,411 @@ pub fn get_next_sqrt_price_from_amount_1_rounding_down(
         let next_sqrt_price = sqrt_price_x_96 + quotient;
 
         if next_sqrt_price > MAX_U160 {
-            Err(UniswapV3MathError::SafeCastToU160Overflow)
+            Err(UniswapV3MathError::SafeCastToU160Overflow {
+                value: next_sqrt_price,
+            })
         } else {
-            Ok(next_sqrt_price)
+            Ok(SqrtPriceX96(next_sqrt_price))
         }
     } else {
         let quotient = if amount <= MAX_U160 {
-            div_rounding_up(amount << FIXED_POINT_96_RESOLUTION, liquidity)
+            div_rounding_up(amount << RESOLUTION, liquidity)
         } else {
             mul_div_rounding_up(amount, Q96, liquidity)?
         };
 
         //require(sqrtPX96 > quotient);
         if sqrt_price_x_96 <= quotient {
-            return Err(UniswapV3MathError::SqrtPriceIsLteQuotient);
+            return Err(UniswapV3MathError::SqrtPriceIsLteQuotient {
+                sqrt_price_x_96,
+                quotient,
+            });
         }
 
-        Ok(sqrt_price_x_96 - quotient)
+        Ok(SqrtPriceX96(sqrt_price_x_96 - quotient))
     }
 }
 
 // returns (uint256 amount0)
-pub fn _get_amount_0_delta(
-    mut sqrt_ratio_a_x_96: U256,
-    mut sqrt_ratio_b_x_96: U256,
+pub fn get_amount_0_delta_rounded(
+    sqrt_ratio_a_x_96: SqrtPriceX96,
+    sqrt_ratio_b_x_96: SqrtPriceX96,
     liquidity: u128,
-    round_up: bool,
+    rounding: Rounding,
 ) -> Result<U256, UniswapV3MathError> {
+    let (mut sqrt_ratio_a_x_96, mut sqrt_ratio_b_x_96) = (sqrt_ratio_a_x_96.0, sqrt_ratio_b_x_96.0);
     if sqrt_ratio_a_x_96 > sqrt_ratio_b_x_96 {
         (sqrt_ratio_a_x_96, sqrt_ratio_b_x_96) = (sqrt_ratio_b_x_96, sqrt_ratio_a_x_96)
     };
 
-    let numerator_1 = U256::from(liquidity) << 96;
+    let numerator_1 = U256::from(liquidity) << RESOLUTION;
     let numerator_2 = sqrt_ratio_b_x_96 - sqrt_ratio_a_x_96;
 
     if sqrt_ratio_a_x_96.is_zero() {
         return Err(UniswapV3MathError::SqrtPriceIsZero);
     }
 
-    if round_up {
-        let numerator_partial = mul_div_rounding_up(numerator_1, numerator_2, sqrt_ratio_b_x_96)?;
-        Ok(div_rounding_up(numerator_partial, sqrt_ratio_a_x_96))
-    } else {
-        Ok(mul_div(numerator_1, numerator_2, sqrt_ratio_b_x_96)? / sqrt_ratio_a_x_96)
+    match rounding {
+        Rounding::Up => {
+            let numerator_partial =
+                mul_div_rounding_up(numerator_1, numerator_2, sqrt_ratio_b_x_96)?;
+            Ok(div_rounding_up(numerator_partial, sqrt_ratio_a_x_96))
+        }
+        Rounding::Down | Rounding::Floor => {
+            Ok(mul_div(numerator_1, numerator_2, sqrt_ratio_b_x_96)? / sqrt_ratio_a_x_96)
+        }
     }
 }
 
 // returns (uint256 amount1)
-pub fn _get_amount_1_delta(
-    mut sqrt_ratio_a_x_96: U256,
-    mut sqrt_ratio_b_x_96: U256,
+pub fn get_amount_1_delta_rounded(
+    sqrt_ratio_a_x_96: SqrtPriceX96,
+    sqrt_ratio_b_x_96: SqrtPriceX96,
     liquidity: u128,
-    round_up: bool,
+    rounding: Rounding,
 ) -> Result<U256, UniswapV3MathError> {
+    let (mut sqrt_ratio_a_x_96, mut sqrt_ratio_b_x_96) = (sqrt_ratio_a_x_96.0, sqrt_ratio_b_x_96.0);
     if sqrt_ratio_a_x_96 > sqrt_ratio_b_x_96 {
         (sqrt_ratio_a_x_96, sqrt_ratio_b_x_96) = (sqrt_ratio_b_x_96, sqrt_ratio_a_x_96)
     };
 
-    let denominator = U256::from_limbs([0, 4294967296, 0, 0]);
-
-    if round_up {
-        mul_div_rounding_up(
+    match rounding {
+        Rounding::Up => mul_div_rounding_up(
             U256::from(liquidity),
             sqrt_ratio_b_x_96 - sqrt_ratio_a_x_96,
-            denominator,
-        )
-    } else {
-        mul_div(
+            Q96,
+        ),
+        Rounding::Down | Rounding::Floor => mul_div(
             U256::from(liquidity),
             sqrt_ratio_b_x_96 - sqrt_ratio_a_x_96,
-            denominator,
-        )
+            Q96,
+        ),
     }
 }
 
+// returns (uint256 amount0)
+pub fn _get_amount_0_delta(
+    sqrt_ratio_a_x_96: SqrtPriceX96,
+    sqrt_ratio_b_x_96: SqrtPriceX96,
+    liquidity: u128,
+    round_up: bool,
+) -> Result<U256, UniswapV3MathError> {
+    get_amount_0_delta_rounded(
+        sqrt_ratio_a_x_96,
+        sqrt_ratio_b_x_96,
+        liquidity,
+        if round_up {
+            Rounding::Up
+        } else {
+            Rounding::Down
+        },
+    )
+}
+
+// returns (uint256 amount1)
+pub fn _get_amount_1_delta(
+    sqrt_ratio_a_x_96: SqrtPriceX96,
+    sqrt_ratio_b_x_96: SqrtPriceX96,
+    liquidity: u128,
+    round_up: bool,
+) -> Result<U256, UniswapV3MathError> {
+    get_amount_1_delta_rounded(
+        sqrt_ratio_a_x_96,
+        sqrt_ratio_b_x_96,
+        liquidity,
+        if round_up {
+            Rounding::Up
+        } else {
+            Rounding::Down
+        },
+    )
+}
+
+// returns (int256 amount0), rounded down for negative liquidity and up for positive liquidity
+pub fn get_amount_0_delta_signed(
+    sqrt_ratio_a_x_96: SqrtPriceX96,
+    sqrt_ratio_b_x_96: SqrtPriceX96,
+    liquidity: i128,
+) -> Result<I256, UniswapV3MathError> {
+    let amount_0 = _get_amount_0_delta(
+        sqrt_ratio_a_x_96,
+        sqrt_ratio_b_x_96,
+        liquidity.unsigned_abs(),
+        liquidity >= 0,
+    )?;
+
+    to_int_256(amount_0, liquidity < 0)
+}
+
+// returns (int256 amount1), rounded down for negative liquidity and up for positive liquidity
+pub fn get_amount_1_delta_signed(
+    sqrt_ratio_a_x_96: SqrtPriceX96,
+    sqrt_ratio_b_x_96: SqrtPriceX96,
+    liquidity: i128,
+) -> Result<I256, UniswapV3MathError> {
+    let amount_1 = _get_amount_1_delta(
+        sqrt_ratio_a_x_96,
+        sqrt_ratio_b_x_96,
+        liquidity.unsigned_abs(),
+        liquidity >= 0,
+    )?;
+
+    to_int_256(amount_1, liquidity < 0)
+}
+
+// SafeCast.toInt256, negated if requested
+pub(crate) fn to_int_256(value: U256, negate: bool) -> Result<I256, UniswapV3MathError> {
+    let value = I256::try_from(value).map_err(|_| UniswapV3MathError::ResultOverflowsI256)?;
+
+    Ok(if negate { -value } else { value })
+}
+
 pub fn get_amount_0_delta(
-    sqrt_ratio_a_x_96: U256,
-    sqrt_ratio_b_x_96: U256,
+    sqrt_ratio_a_x_96: SqrtPriceX96,
+    sqrt_ratio_b_x_96: SqrtPriceX96,
     liquidity: i128,
 ) -> Result<I256, UniswapV3MathError> {
-    if liquidity < 0 {
-        Ok(-I256::from_raw(_get_amount_0_delta(
-            sqrt_ratio_a_x_96,
-            sqrt_ratio_b_x_96,
-            -liquidity as u128,
-            false,
-        )?))
-    } else {
-        Ok(I256::from_raw(_get_amount_0_delta(
-            sqrt_ratio_a_x_96,
-            sqrt_ratio_b_x_96,
-            liquidity as u128,
-            true,
-        )?))
-    }
+    get_amount_0_delta_signed(sqrt_ratio_a_x_96, sqrt_ratio_b_x_96, liquidity)
 }
 
 pub fn get_amount_1_delta(
-    sqrt_ratio_a_x_96: U256,
-    sqrt_ratio_b_x_96: U256,
+    sqrt_ratio_a_x_96: SqrtPriceX96,
+    sqrt_ratio_b_x_96: SqrtPriceX96,
     liquidity: i128,
 ) -> Result<I256, UniswapV3MathError> {
-    if liquidity < 0 {
-        Ok(-I256::from_raw(_get_amount_1_delta(
-            sqrt_ratio_a_x_96,
-            sqrt_ratio_b_x_96,
-            -liquidity as u128,
-            false,
-        )?))
+    get_amount_1_delta_signed(sqrt_ratio_a_x_96, sqrt_ratio_b_x_96, liquidity)
+}
+
+// returns (sqrtRatioLowerX96, sqrtRatioUpperX96) for a valid tick range
+fn get_sqrt_ratios_for_ticks(
+    tick_lower: i32,
+    tick_upper: i32,
+) -> Result<(SqrtPriceX96, SqrtPriceX96), UniswapV3MathError> {
+    if tick_lower >= tick_upper {
+        return Err(UniswapV3MathError::InvalidTickRange {
+            tick_lower,
+            tick_upper,
+        });
+    }
+
+    Ok((
+        get_sqrt_ratio_at_tick(tick_lower)?,
+        get_sqrt_ratio_at_tick(tick_upper)?,
+    ))
+}
+
+// returns (uint256 amount0) between the sqrt ratios of tick_lower and tick_upper
+pub fn get_amount_0_delta_for_ticks(
+    tick_lower: i32,
+    tick_upper: i32,
+    liquidity: u128,
+    round_up: bool,
+) -> Result<U256, UniswapV3MathError> {
+    let (sqrt_ratio_a_x_96, sqrt_ratio_b_x_96) = get_sqrt_ratios_for_ticks(tick_lower, tick_upper)?;
+
+    _get_amount_0_delta(sqrt_ratio_a_x_96, sqrt_ratio_b_x_96, liquidity, round_up)
+}
+
+// returns (uint256 amount1) between the sqrt ratios of tick_lower and tick_upper
+pub fn get_amount_1_delta_for_ticks(
+    tick_lower: i32,
+    tick_upper: i32,
+    liquidity: u128,
+    round_up: bool,
+) -> Result<U256, UniswapV3MathError> {
+    let (sqrt_ratio_a_x_96, sqrt_ratio_b_x_96) = get_sqrt_ratios_for_ticks(tick_lower, tick_upper)?;
+
+    _get_amount_1_delta(sqrt_ratio_a_x_96, sqrt_ratio_b_x_96, liquidity, round_up)
+}
+
+// returns (uint256 amount0, uint256 amount1) held by liquidity on [tick_lower, tick_upper) at the current sqrt price
+pub fn get_token_amounts_for_ticks(
+    sqrt_price_x_96: SqrtPriceX96,
+    tick_lower: i32,
+    tick_upper: i32,
+    liquidity: u128,
+) -> Result<(U256, U256), UniswapV3MathError> {
+    let (sqrt_ratio_a_x_96, sqrt_ratio_b_x_96) = get_sqrt_ratios_for_ticks(tick_lower, tick_upper)?;
+
+    get_amounts_for_liquidity(
+        sqrt_price_x_96,
+        sqrt_ratio_a_x_96,
+        sqrt_ratio_b_x_96,
+        liquidity,
+    )
+}
+
+// returns (uint128 liquidity)
+pub fn get_liquidity_for_amount_0(
+    sqrt_ratio_a_x_96: SqrtPriceX96,
+    sqrt_ratio_b_x_96: SqrtPriceX96,
+    amount_0: U256,
+) -> Result<u128, UniswapV3MathError> {
+    let (mut sqrt_ratio_a_x_96, mut sqrt_ratio_b_x_96) = (sqrt_ratio_a_x_96.0, sqrt_ratio_b_x_96.0);
+    if sqrt_ratio_a_x_96 > sqrt_ratio_b_x_96 {
+        (sqrt_ratio_a_x_96, sqrt_ratio_b_x_96) = (sqrt_ratio_b_x_96, sqrt_ratio_a_x_96)
+    };
+
+    let intermediate = mul_div(sqrt_ratio_a_x_96, sqrt_ratio_b_x_96, Q96)?;
+    let liquidity = mul_div(
+        amount_0,
+        intermediate,
+        sqrt_ratio_b_x_96 - sqrt_ratio_a_x_96,
+    )?;
+
+    u128::try_from(liquidity)
+        .map_err(|_| UniswapV3MathError::SafeCastToU128Overflow { value: liquidity })
+}
+
+// returns (uint128 liquidity)
+pub fn get_liquidity_for_amount_1(
+    sqrt_ratio_a_x_96: SqrtPriceX96,
+    sqrt_ratio_b_x_96: SqrtPriceX96,
+    amount_1: U256,
+) -> Result<u128, UniswapV3MathError> {
+    let (mut sqrt_ratio_a_x_96, mut sqrt_ratio_b_x_96) = (sqrt_ratio_a_x_96.0, sqrt_ratio_b_x_96.0);
+    if sqrt_ratio_a_x_96 > sqrt_ratio_b_x_96 {
+        (sqrt_ratio_a_x_96, sqrt_ratio_b_x_96) = (sqrt_ratio_b_x_96, sqrt_ratio_a_x_96)
+    };
+
+    let liquidity = mul_div(amount_1, Q96, sqrt_ratio_b_x_96 - sqrt_ratio_a_x_96)?;
+
+    u128::try_from(liquidity)
+        .map_err(|_| UniswapV3MathError::SafeCastToU128Overflow { value: liquidity })
+}
+
+// Max liquidity that can be minted with only token0, as in the TS SDK maxLiquidityForAmount0Imprecise/Precise.
+// The imprecise variant truncates sqrt(a) * sqrt(b) / 2**96 before dividing, the precise one keeps the full product.
+pub fn max_liquidity_for_amount_0(
+    sqrt_ratio_a_x_96: SqrtPriceX96,
+    sqrt_ratio_b_x_96: SqrtPriceX96,
+    amount_0: U256,
+    precise: bool,
+) -> Result<u128, UniswapV3MathError> {
+    if !precise {
+        return get_liquidity_for_amount_0(sqrt_ratio_a_x_96, sqrt_ratio_b_x_96, amount_0);
+    }
+
+    let (mut sqrt_ratio_a_x_96, mut sqrt_ratio_b_x_96) = (sqrt_ratio_a_x_96.0, sqrt_ratio_b_x_96.0);
+    if sqrt_ratio_a_x_96 > sqrt_ratio_b_x_96 {
+        (sqrt_ratio_a_x_96, sqrt_ratio_b_x_96) = (sqrt_ratio_b_x_96, sqrt_ratio_a_x_96)
+    };
+
+    let denominator = sqrt_ratio_b_x_96 - sqrt_ratio_a_x_96;
+    if denominator.is_zero() {
+        return Err(UniswapV3MathError::DenominatorIsZero);
+    }
+
+    // amount0 * sqrtA * sqrtB can take up to 576 bits
+    let numerator = Uint::<768, 12>::from(amount_0)
+        * Uint::<768, 12>::from(sqrt_ratio_a_x_96)
+        * Uint::<768, 12>::from(sqrt_ratio_b_x_96);
+    let liquidity = numerator / (Uint::<768, 12>::from(Q96) * Uint::<768, 12>::from(denominator));
+
+    u128::try_from(liquidity).map_err(|_| UniswapV3MathError::SafeCastToU128Overflow {
+        value: U256::saturating_from(liquidity),
+    })
+}
+
+// Max liquidity that can be minted with only token1, the SDK uses the same math for both precisions
+pub fn max_liquidity_for_amount_1(
+    sqrt_ratio_a_x_96: SqrtPriceX96,
+    sqrt_ratio_b_x_96: SqrtPriceX96,
+    amount_1: U256,
+) -> Result<u128, UniswapV3MathError> {
+    get_liquidity_for_amount_1(sqrt_ratio_a_x_96, sqrt_ratio_b_x_96, amount_1)
+}
+
+// returns (uint128 liquidity)
+pub fn get_liquidity_for_amounts(
+    sqrt_ratio_x_96: SqrtPriceX96,
+    mut sqrt_ratio_a_x_96: SqrtPriceX96,
+    mut sqrt_ratio_b_x_96: SqrtPriceX96,
+    amount_0: U256,
+    amount_1: U256,
+) -> Result<u128, UniswapV3MathError> {
+    if sqrt_ratio_a_x_96 > sqrt_ratio_b_x_96 {
+        (sqrt_ratio_a_x_96, sqrt_ratio_b_x_96) = (sqrt_ratio_b_x_96, sqrt_ratio_a_x_96)
+    };
+
+    if sqrt_ratio_x_96 <= sqrt_ratio_a_x_96 {
+        get_liquidity_for_amount_0(sqrt_ratio_a_x_96, sqrt_ratio_b_x_96, amount_0)
+    } else if sqrt_ratio_x_96 < sqrt_ratio_b_x_96 {
+        let liquidity_0 = get_liquidity_for_amount_0(sqrt_ratio_x_96, sqrt_ratio_b_x_96, amount_0)?;
+        let liquidity_1 = get_liquidity_for_amount_1(sqrt_ratio_a_x_96, sqrt_ratio_x_96, amount_1)?;
+
+        Ok(liquidity_0.min(liquidity_1))
     } else {
-        Ok(I256::from_raw(_get_amount_1_delta(
-            sqrt_ratio_a_x_96,
-            sqrt_ratio_b_x_96,
-            liquidity as u128,
-            true,
-        )?))
+        get_liquidity_for_amount_1(sqrt_ratio_a_x_96, sqrt_ratio_b_x_96, amount_1)
+    }
+}
+
+// returns (uint256 amount0)
+fn get_amount_0_for_liquidity(
+    sqrt_ratio_a_x_96: SqrtPriceX96,
+    sqrt_ratio_b_x_96: SqrtPriceX96,
+    liquidity: u128,
+) -> Result<U256, UniswapV3MathError> {
+    let (mut sqrt_ratio_a_x_96, mut sqrt_ratio_b_x_96) = (sqrt_ratio_a_x_96.0, sqrt_ratio_b_x_96.0);
+    if sqrt_ratio_a_x_96 > sqrt_ratio_b_x_96 {
+        (sqrt_ratio_a_x_96, sqrt_ratio_b_x_96) = (sqrt_ratio_b_x_96, sqrt_ratio_a_x_96)
+    };
+
+    if sqrt_ratio_a_x_96.is_zero() {
+        return Err(UniswapV3MathError::SqrtPriceIsZero);
+    }
+
+    Ok(mul_div(
+        U256::from(liquidity) << RESOLUTION,
+        sqrt_ratio_b_x_96 - sqrt_ratio_a_x_96,
+        sqrt_ratio_b_x_96,
+    )? / sqrt_ratio_a_x_96)
+}
+
+// returns (uint256 amount1)
+fn get_amount_1_for_liquidity(
+    sqrt_ratio_a_x_96: SqrtPriceX96,
+    sqrt_ratio_b_x_96: SqrtPriceX96,
+    liquidity: u128,
+) -> Result<U256, UniswapV3MathError> {
+    let (mut sqrt_ratio_a_x_96, mut sqrt_ratio_b_x_96) = (sqrt_ratio_a_x_96.0, sqrt_ratio_b_x_96.0);
+    if sqrt_ratio_a_x_96 > sqrt_ratio_b_x_96 {
+        (sqrt_ratio_a_x_96, sqrt_ratio_b_x_96) = (sqrt_ratio_b_x_96, sqrt_ratio_a_x_96)
+    };
+
+    mul_div(
+        U256::from(liquidity),
+        sqrt_ratio_b_x_96 - sqrt_ratio_a_x_96,
+        Q96,
+    )
+}
+
+// returns (uint256 amount0, uint256 amount1)
+pub fn get_amounts_for_liquidity(
+    sqrt_ratio_x_96: SqrtPriceX96,
+    mut sqrt_ratio_a_x_96: SqrtPriceX96,
+    mut sqrt_ratio_b_x_96: SqrtPriceX96,
+    liquidity: u128,
+) -> Result<(U256, U256), UniswapV3MathError> {
+    if sqrt_ratio_a_x_96 > sqrt_ratio_b_x_96 {
+        (sqrt_ratio_a_x_96, sqrt_ratio_b_x_96) = (sqrt_ratio_b_x_96, sqrt_ratio_a_x_96)
+    };
+
+    if sqrt_ratio_x_96 <= sqrt_ratio_a_x_96 {
+        Ok((
+            get_amount_0_for_liquidity(sqrt_ratio_a_x_96, sqrt_ratio_b_x_96, liquidity)?,
+            U256::ZERO,
+        ))
+    } else if sqrt_ratio_x_96 < sqrt_ratio_b_x_96 {
+        Ok((
+            get_amount_0_for_liquidity(sqrt_ratio_x_96, sqrt_ratio_b_x_96, liquidity)?,
+            get_amount_1_for_liquidity(sqrt_ratio_a_x_96, sqrt_ratio_x_96, liquidity)?,
+        ))
+    } else {
+        Ok((
+            U256::ZERO,
+            get_amount_1_for_liquidity(sqrt_ratio_a_x_96, sqrt_ratio_b_x_96, liquidity)?,
+        ))
     }
 }
 
@@ -235,20 +694,35 @@ mod test {
         str::FromStr,
     };
 
-    use alloy::primitives::U256;
+    use alloy_primitives::{I256, U256, U512};
+    use proptest::prelude::*;
 
     use crate::{
+        error::UniswapV3MathError,
+        fee_tier::FeeTier,
+        fixed_point::SqrtPriceX96,
         sqrt_price_math::{_get_amount_1_delta, get_next_sqrt_price_from_output, MAX_U160},
+        swap_math::compute_swap_step,
+        tick_math::{get_sqrt_ratio_at_tick, tick_to_price, MAX_SQRT_RATIO, MAX_TICK, MIN_TICK},
         U256_1, U256_2,
     };
 
-    use super::{_get_amount_0_delta, get_next_sqrt_price_from_input};
+    use super::{
+        _get_amount_0_delta, encode_sqrt_ratio_x_96, get_amount_0_delta_for_ticks,
+        get_amount_0_delta_rounded, get_amount_0_delta_signed, get_amount_1_delta_for_ticks,
+        get_amount_1_delta_rounded, get_amount_1_delta_signed, get_amounts_for_liquidity,
+        get_liquidity_for_amount_0, get_liquidity_for_amount_1, get_liquidity_for_amounts,
+        get_next_sqrt_price_from_input, get_next_sqrt_price_from_input_capped,
+        get_next_sqrt_price_from_output_capped, get_token_amounts_for_ticks,
+        max_liquidity_for_amount_0, max_liquidity_for_amount_1, price_to_sqrt_price_x96,
+        snap_price_to_sqrt_price, sqrt_price_x96_to_price, Rounding, Q96,
+    };
 
     #[test]
     fn test_get_next_sqrt_price_from_input() {
         //Fails if price is zero
         let result = get_next_sqrt_price_from_input(
-            U256::ZERO,
+            SqrtPriceX96(U256::ZERO),
             0,
             U256::from(100000000000000000_u128),
             false,
@@ -256,12 +730,17 @@ mod test {
         assert_eq!(result.unwrap_err().to_string(), "Sqrt price is 0");
 
         //Fails if liquidity is zero
-        let result =
-            get_next_sqrt_price_from_input(U256_1, 0, U256::from(100000000000000000_u128), true);
+        let result = get_next_sqrt_price_from_input(
+            SqrtPriceX96(U256_1),
+            0,
+            U256::from(100000000000000000_u128),
+            true,
+        );
         assert_eq!(result.unwrap_err().to_string(), "Liquidity is 0");
 
         //fails if input amount overflows the price
-        let result = get_next_sqrt_price_from_input(MAX_U160, 1024, U256::from(1024), false);
+        let result =
+            get_next_sqrt_price_from_input(SqrtPriceX96(MAX_U160), 1024, U256::from(1024), false);
         assert_eq!(
             result.unwrap_err().to_string(),
             "Overflow when casting to U160"
@@ -269,7 +748,7 @@ mod test {
 
         //any input amount cannot underflow the price
         let result = get_next_sqrt_price_from_input(
-            U256_1,
+            SqrtPriceX96(U256_1),
             1,
             U256::from_str(
                 "57896044618658097711785492504343953926634992332820282019728792003956564819968",
@@ -282,7 +761,7 @@ mod test {
 
         //returns input price if amount in is zero and zeroForOne = true
         let result = get_next_sqrt_price_from_input(
-            U256::from_str("79228162514264337593543950336").unwrap(),
+            SqrtPriceX96(U256::from_str("79228162514264337593543950336").unwrap()),
             1e17 as u128,
             U256::ZERO,
             true,
@@ -295,7 +774,7 @@ mod test {
 
         //returns input price if amount in is zero and zeroForOne = false
         let result = get_next_sqrt_price_from_input(
-            U256::from_str("79228162514264337593543950336").unwrap(),
+            SqrtPriceX96(U256::from_str("79228162514264337593543950336").unwrap()),
             1e17 as u128,
             U256::ZERO,
             true,
@@ -311,13 +790,17 @@ mod test {
         let sqrt_price = MAX_U160;
         let liquidity = u128::MAX;
         let max_amount_no_overflow = U256::MAX - ((U256::from(liquidity) << 96) / sqrt_price);
-        let result =
-            get_next_sqrt_price_from_input(sqrt_price, liquidity, max_amount_no_overflow, true);
+        let result = get_next_sqrt_price_from_input(
+            SqrtPriceX96(sqrt_price),
+            liquidity,
+            max_amount_no_overflow,
+            true,
+        );
         assert_eq!(result.unwrap(), U256_1);
 
         //input amount of 0.1 token1
         let result = get_next_sqrt_price_from_input(
-            U256::from_str("79228162514264337593543950336").unwrap(),
+            SqrtPriceX96(U256::from_str("79228162514264337593543950336").unwrap()),
             1e18 as u128,
             U256::from_str("100000000000000000").unwrap(),
             false,
@@ -330,7 +813,7 @@ mod test {
 
         //input amount of 0.1 token0
         let result = get_next_sqrt_price_from_input(
-            U256::from_str("79228162514264337593543950336").unwrap(),
+            SqrtPriceX96(U256::from_str("79228162514264337593543950336").unwrap()),
             1e18 as u128,
             U256::from_str("100000000000000000").unwrap(),
             true,
@@ -343,7 +826,7 @@ mod test {
 
         //amountIn > type(uint96).max and zeroForOne = true
         let result = get_next_sqrt_price_from_input(
-            U256::from_str("79228162514264337593543950336").unwrap(),
+            SqrtPriceX96(U256::from_str("79228162514264337593543950336").unwrap()),
             1e19 as u128,
             U256::from_str("1267650600228229401496703205376").unwrap(),
             true,
@@ -357,7 +840,7 @@ mod test {
 
         //can return 1 with enough amountIn and zeroForOne = true
         let result = get_next_sqrt_price_from_input(
-            U256::from_str("79228162514264337593543950336").unwrap(),
+            SqrtPriceX96(U256::from_str("79228162514264337593543950336").unwrap()),
             1,
             U256::MAX / U256_2,
             true,
@@ -369,16 +852,22 @@ mod test {
     #[test]
     fn test_get_next_sqrt_price_from_output() {
         //fails if price is zero
-        let result = get_next_sqrt_price_from_output(U256::ZERO, 0, U256::from(1000000000), false);
+        let result = get_next_sqrt_price_from_output(
+            SqrtPriceX96(U256::ZERO),
+            0,
+            U256::from(1000000000),
+            false,
+        );
         assert_eq!(result.unwrap_err().to_string(), "Sqrt price is 0");
 
         //fails if liquidity is zero
-        let result = get_next_sqrt_price_from_output(U256_1, 0, U256::from(1000000000), false);
+        let result =
+            get_next_sqrt_price_from_output(SqrtPriceX96(U256_1), 0, U256::from(1000000000), false);
         assert_eq!(result.unwrap_err().to_string(), "Liquidity is 0");
 
         //fails if output amount is exactly the virtual reserves of token0
         let result = get_next_sqrt_price_from_output(
-            U256::from_str("20282409603651670423947251286016").unwrap(),
+            SqrtPriceX96(U256::from_str("20282409603651670423947251286016").unwrap()),
             1024,
             U256::from(4),
             false,
@@ -390,7 +879,7 @@ mod test {
 
         //fails if output amount is greater than virtual reserves of token0
         let result = get_next_sqrt_price_from_output(
-            U256::from_str("20282409603651670423947251286016").unwrap(),
+            SqrtPriceX96(U256::from_str("20282409603651670423947251286016").unwrap()),
             1024,
             U256::from(5),
             false,
@@ -402,7 +891,7 @@ mod test {
 
         //fails if output amount is greater than virtual reserves of token1
         let result = get_next_sqrt_price_from_output(
-            U256::from_str("20282409603651670423947251286016").unwrap(),
+            SqrtPriceX96(U256::from_str("20282409603651670423947251286016").unwrap()),
             1024,
             U256::from(262145),
             true,
@@ -414,7 +903,7 @@ mod test {
 
         //fails if output amount is exactly the virtual reserves of token1
         let result = get_next_sqrt_price_from_output(
-            U256::from_str("20282409603651670423947251286016").unwrap(),
+            SqrtPriceX96(U256::from_str("20282409603651670423947251286016").unwrap()),
             1024,
             U256::from(262144),
             true,
@@ -426,7 +915,7 @@ mod test {
 
         //succeeds if output amount is just less than the virtual
         let result = get_next_sqrt_price_from_output(
-            U256::from_str("20282409603651670423947251286016").unwrap(),
+            SqrtPriceX96(U256::from_str("20282409603651670423947251286016").unwrap()),
             1024,
             U256::from(262143),
             true,
@@ -438,7 +927,7 @@ mod test {
 
         //puzzling echidna test
         let result = get_next_sqrt_price_from_output(
-            U256::from_str("20282409603651670423947251286016").unwrap(),
+            SqrtPriceX96(U256::from_str("20282409603651670423947251286016").unwrap()),
             1024,
             U256::from(4),
             false,
@@ -450,7 +939,7 @@ mod test {
 
         //returns input price if amount in is zero and zeroForOne = true
         let result = get_next_sqrt_price_from_output(
-            U256::from_str("79228162514264337593543950336").unwrap(),
+            SqrtPriceX96(U256::from_str("79228162514264337593543950336").unwrap()),
             1e17 as u128,
             U256::ZERO,
             true,
@@ -462,7 +951,7 @@ mod test {
 
         //returns input price if amount in is zero and zeroForOne = false
         let result = get_next_sqrt_price_from_output(
-            U256::from_str("79228162514264337593543950336").unwrap(),
+            SqrtPriceX96(U256::from_str("79228162514264337593543950336").unwrap()),
             1e17 as u128,
             U256::ZERO,
             false,
@@ -474,7 +963,7 @@ mod test {
 
         //output amount of 0.1 token1
         let result = get_next_sqrt_price_from_output(
-            U256::from_str("79228162514264337593543950336").unwrap(),
+            SqrtPriceX96(U256::from_str("79228162514264337593543950336").unwrap()),
             1e18 as u128,
             U256::from(1e17 as u128),
             false,
@@ -486,7 +975,7 @@ mod test {
 
         //output amount of 0.1 token1
         let result = get_next_sqrt_price_from_output(
-            U256::from_str("79228162514264337593543950336").unwrap(),
+            SqrtPriceX96(U256::from_str("79228162514264337593543950336").unwrap()),
             1e18 as u128,
             U256::from(1e17 as u128),
             true,
@@ -498,7 +987,7 @@ mod test {
 
         //reverts if amountOut is impossible in zero for one direction
         let result = get_next_sqrt_price_from_output(
-            U256::from_str("79228162514264337593543950336").unwrap(),
+            SqrtPriceX96(U256::from_str("79228162514264337593543950336").unwrap()),
             1,
             U256::MAX,
             true,
@@ -510,7 +999,7 @@ mod test {
 
         //reverts if amountOut is impossible in one for zero direction
         let result = get_next_sqrt_price_from_output(
-            U256::from_str("79228162514264337593543950336").unwrap(),
+            SqrtPriceX96(U256::from_str("79228162514264337593543950336").unwrap()),
             1,
             U256::MAX,
             false,
@@ -525,8 +1014,8 @@ mod test {
     fn test_get_amount_0_delta() {
         // returns 0 if liquidity is 0
         let amount_0 = _get_amount_0_delta(
-            U256::from_str("79228162514264337593543950336").unwrap(),
-            U256::from_str("79228162514264337593543950336").unwrap(),
+            SqrtPriceX96(U256::from_str("79228162514264337593543950336").unwrap()),
+            SqrtPriceX96(U256::from_str("79228162514264337593543950336").unwrap()),
             0,
             true,
         );
@@ -535,8 +1024,8 @@ mod test {
 
         // returns 0 if prices are equal
         let amount_0 = _get_amount_0_delta(
-            U256::from_str("79228162514264337593543950336").unwrap(),
-            U256::from_str("87150978765690771352898345369").unwrap(),
+            SqrtPriceX96(U256::from_str("79228162514264337593543950336").unwrap()),
+            SqrtPriceX96(U256::from_str("87150978765690771352898345369").unwrap()),
             0,
             true,
         );
@@ -545,8 +1034,8 @@ mod test {
 
         // returns 0.1 amount1 for price of 1 to 1.21
         let amount_0 = _get_amount_0_delta(
-            U256::from_str("79228162514264337593543950336").unwrap(),
-            U256::from_str("87150978765690771352898345369").unwrap(),
+            SqrtPriceX96(U256::from_str("79228162514264337593543950336").unwrap()),
+            SqrtPriceX96(U256::from_str("87150978765690771352898345369").unwrap()),
             1e18 as u128,
             true,
         )
@@ -558,8 +1047,8 @@ mod test {
         );
 
         let amount_0_rounded_down = _get_amount_0_delta(
-            U256::from_str("79228162514264337593543950336").unwrap(),
-            U256::from_str("87150978765690771352898345369").unwrap(),
+            SqrtPriceX96(U256::from_str("79228162514264337593543950336").unwrap()),
+            SqrtPriceX96(U256::from_str("87150978765690771352898345369").unwrap()),
             1e18 as u128,
             false,
         );
@@ -568,16 +1057,16 @@ mod test {
 
         // works for prices that overflow
         let amount_0_up = _get_amount_0_delta(
-            U256::from_str("2787593149816327892691964784081045188247552").unwrap(),
-            U256::from_str("22300745198530623141535718272648361505980416").unwrap(),
+            SqrtPriceX96(U256::from_str("2787593149816327892691964784081045188247552").unwrap()),
+            SqrtPriceX96(U256::from_str("22300745198530623141535718272648361505980416").unwrap()),
             1e18 as u128,
             true,
         )
         .unwrap();
 
         let amount_0_down = _get_amount_0_delta(
-            U256::from_str("2787593149816327892691964784081045188247552").unwrap(),
-            U256::from_str("22300745198530623141535718272648361505980416").unwrap(),
+            SqrtPriceX96(U256::from_str("2787593149816327892691964784081045188247552").unwrap()),
+            SqrtPriceX96(U256::from_str("22300745198530623141535718272648361505980416").unwrap()),
             1e18 as u128,
             false,
         )
@@ -590,8 +1079,8 @@ mod test {
     fn test_get_amount_1_delta() {
         // returns 0 if liquidity is 0
         let amount_1 = _get_amount_1_delta(
-            U256::from_str("79228162514264337593543950336").unwrap(),
-            U256::from_str("79228162514264337593543950336").unwrap(),
+            SqrtPriceX96(U256::from_str("79228162514264337593543950336").unwrap()),
+            SqrtPriceX96(U256::from_str("79228162514264337593543950336").unwrap()),
             0,
             true,
         );
@@ -600,8 +1089,8 @@ mod test {
 
         // returns 0 if prices are equal
         let amount_1 = _get_amount_1_delta(
-            U256::from_str("79228162514264337593543950336").unwrap(),
-            U256::from_str("87150978765690771352898345369").unwrap(),
+            SqrtPriceX96(U256::from_str("79228162514264337593543950336").unwrap()),
+            SqrtPriceX96(U256::from_str("87150978765690771352898345369").unwrap()),
             0,
             true,
         );
@@ -610,8 +1099,8 @@ mod test {
 
         // returns 0.1 amount1 for price of 1 to 1.21
         let amount_1 = _get_amount_1_delta(
-            U256::from_str("79228162514264337593543950336").unwrap(),
-            U256::from_str("87150978765690771352898345369").unwrap(),
+            SqrtPriceX96(U256::from_str("79228162514264337593543950336").unwrap()),
+            SqrtPriceX96(U256::from_str("87150978765690771352898345369").unwrap()),
             1e18 as u128,
             true,
         )
@@ -623,8 +1112,8 @@ mod test {
         );
 
         let amount_1_rounded_down = _get_amount_1_delta(
-            U256::from_str("79228162514264337593543950336").unwrap(),
-            U256::from_str("87150978765690771352898345369").unwrap(),
+            SqrtPriceX96(U256::from_str("79228162514264337593543950336").unwrap()),
+            SqrtPriceX96(U256::from_str("87150978765690771352898345369").unwrap()),
             1e18 as u128,
             false,
         );
@@ -634,8 +1123,9 @@ mod test {
 
     #[test]
     fn test_swap_computation() {
-        let sqrt_price =
-            U256::from_str("1025574284609383690408304870162715216695788925244").unwrap();
+        let sqrt_price = SqrtPriceX96(
+            U256::from_str("1025574284609383690408304870162715216695788925244").unwrap(),
+        );
         let liquidity = 50015962439936049619261659728067971248;
         let zero_for_one = true;
         let amount_in = U256::from(406);
@@ -652,4 +1142,801 @@ mod test {
 
         assert_eq!(amount_0_delta, U256::from(406));
     }
+
+    #[test]
+    fn test_get_liquidity_for_amounts() {
+        // encodePriceSqrt(100, 110) and encodePriceSqrt(110, 100)
+        let sqrt_ratio_a = SqrtPriceX96(U256::from_str("75541088972021052632782079082").unwrap());
+        let sqrt_ratio_b = SqrtPriceX96(U256::from_str("83095197869223157896060286990").unwrap());
+
+        // amounts for price inside
+        let liquidity = get_liquidity_for_amounts(
+            SqrtPriceX96(U256::from_str("79228162514264337593543950336").unwrap()),
+            sqrt_ratio_a,
+            sqrt_ratio_b,
+            U256::from(100),
+            U256::from(200),
+        )
+        .unwrap();
+        assert_eq!(liquidity, 2148);
+
+        // amounts for price below
+        let liquidity = get_liquidity_for_amounts(
+            SqrtPriceX96(U256::from_str("75162434512514379355924140470").unwrap()),
+            sqrt_ratio_a,
+            sqrt_ratio_b,
+            U256::from(100),
+            U256::from(200),
+        )
+        .unwrap();
+        assert_eq!(liquidity, 1048);
+
+        // amounts for price above
+        let liquidity = get_liquidity_for_amounts(
+            SqrtPriceX96(U256::from_str("83472048772503575395058907992").unwrap()),
+            sqrt_ratio_a,
+            sqrt_ratio_b,
+            U256::from(100),
+            U256::from(200),
+        )
+        .unwrap();
+        assert_eq!(liquidity, 2097);
+
+        // amounts for price equal to lower boundary
+        let liquidity = get_liquidity_for_amounts(
+            sqrt_ratio_a,
+            sqrt_ratio_a,
+            sqrt_ratio_b,
+            U256::from(100),
+            U256::from(200),
+        )
+        .unwrap();
+        assert_eq!(liquidity, 1048);
+
+        // amounts for price equal to upper boundary
+        let liquidity = get_liquidity_for_amounts(
+            sqrt_ratio_b,
+            sqrt_ratio_a,
+            sqrt_ratio_b,
+            U256::from(100),
+            U256::from(200),
+        )
+        .unwrap();
+        assert_eq!(liquidity, 2097);
+    }
+
+    #[test]
+    fn test_get_liquidity_for_amount_0_and_1() {
+        // encodePriceSqrt(100, 110) and encodePriceSqrt(110, 100)
+        let sqrt_ratio_a = SqrtPriceX96(U256::from_str("75541088972021052632782079082").unwrap());
+        let sqrt_ratio_b = SqrtPriceX96(U256::from_str("83095197869223157896060286990").unwrap());
+
+        let liquidity = get_liquidity_for_amount_0(sqrt_ratio_a, sqrt_ratio_b, U256::from(100));
+        assert_eq!(liquidity.unwrap(), 1048);
+
+        let liquidity = get_liquidity_for_amount_1(sqrt_ratio_a, sqrt_ratio_b, U256::from(200));
+        assert_eq!(liquidity.unwrap(), 2097);
+
+        // unsorted bounds are swapped
+        let liquidity = get_liquidity_for_amount_0(sqrt_ratio_b, sqrt_ratio_a, U256::from(100));
+        assert_eq!(liquidity.unwrap(), 1048);
+
+        let liquidity = get_liquidity_for_amount_1(sqrt_ratio_b, sqrt_ratio_a, U256::from(200));
+        assert_eq!(liquidity.unwrap(), 2097);
+
+        // liquidity exceeding uint128 errors instead of truncating
+        let liquidity =
+            get_liquidity_for_amount_1(sqrt_ratio_a, sqrt_ratio_b, U256::from(u128::MAX));
+        assert_eq!(
+            liquidity.unwrap_err().to_string(),
+            "Overflow when casting to U128"
+        );
+
+        let liquidity = get_liquidity_for_amounts(
+            sqrt_ratio_b,
+            sqrt_ratio_a,
+            sqrt_ratio_b,
+            U256::ZERO,
+            U256::from(u128::MAX),
+        );
+        assert_eq!(
+            liquidity.unwrap_err().to_string(),
+            "Overflow when casting to U128"
+        );
+    }
+
+    #[test]
+    fn test_get_amounts_for_liquidity() {
+        // encodePriceSqrt(100, 110) and encodePriceSqrt(110, 100)
+        let sqrt_ratio_a = SqrtPriceX96(U256::from_str("75541088972021052632782079082").unwrap());
+        let sqrt_ratio_b = SqrtPriceX96(U256::from_str("83095197869223157896060286990").unwrap());
+
+        // amounts for price inside
+        let (amount_0, amount_1) = get_amounts_for_liquidity(
+            SqrtPriceX96(U256::from_str("79228162514264337593543950336").unwrap()),
+            sqrt_ratio_a,
+            sqrt_ratio_b,
+            2148,
+        )
+        .unwrap();
+        assert_eq!(amount_0, U256::from(99));
+        assert_eq!(amount_1, U256::from(99));
+
+        // amounts for price below
+        let (amount_0, amount_1) = get_amounts_for_liquidity(
+            SqrtPriceX96(U256::from_str("75162434512514379355924140470").unwrap()),
+            sqrt_ratio_a,
+            sqrt_ratio_b,
+            1048,
+        )
+        .unwrap();
+        assert_eq!(amount_0, U256::from(99));
+        assert_eq!(amount_1, U256::ZERO);
+
+        // amounts for price above
+        let (amount_0, amount_1) = get_amounts_for_liquidity(
+            SqrtPriceX96(U256::from_str("83472048772503575395058907992").unwrap()),
+            sqrt_ratio_a,
+            sqrt_ratio_b,
+            2097,
+        )
+        .unwrap();
+        assert_eq!(amount_0, U256::ZERO);
+        assert_eq!(amount_1, U256::from(199));
+
+        // unsorted range bounds are swapped
+        let (amount_0, amount_1) = get_amounts_for_liquidity(
+            SqrtPriceX96(U256::from_str("79228162514264337593543950336").unwrap()),
+            sqrt_ratio_b,
+            sqrt_ratio_a,
+            2148,
+        )
+        .unwrap();
+        assert_eq!(amount_0, U256::from(99));
+        assert_eq!(amount_1, U256::from(99));
+
+        // zero liquidity
+        let (amount_0, amount_1) = get_amounts_for_liquidity(
+            SqrtPriceX96(U256::from_str("79228162514264337593543950336").unwrap()),
+            sqrt_ratio_a,
+            sqrt_ratio_b,
+            0,
+        )
+        .unwrap();
+        assert_eq!(amount_0, U256::ZERO);
+        assert_eq!(amount_1, U256::ZERO);
+
+        // range of width one tick, [0, 1]
+        let sqrt_ratio_tick_0 = get_sqrt_ratio_at_tick(0).unwrap();
+        let sqrt_ratio_tick_1 = get_sqrt_ratio_at_tick(1).unwrap();
+        let (amount_0_below, amount_1_below) = get_amounts_for_liquidity(
+            get_sqrt_ratio_at_tick(-1).unwrap(),
+            sqrt_ratio_tick_0,
+            sqrt_ratio_tick_1,
+            1e18 as u128,
+        )
+        .unwrap();
+        assert_eq!(
+            amount_0_below,
+            _get_amount_0_delta(sqrt_ratio_tick_0, sqrt_ratio_tick_1, 1e18 as u128, false).unwrap()
+        );
+        assert_eq!(amount_1_below, U256::ZERO);
+
+        let (amount_0_above, amount_1_above) = get_amounts_for_liquidity(
+            get_sqrt_ratio_at_tick(2).unwrap(),
+            sqrt_ratio_tick_0,
+            sqrt_ratio_tick_1,
+            1e18 as u128,
+        )
+        .unwrap();
+        assert_eq!(amount_0_above, U256::ZERO);
+        assert_eq!(
+            amount_1_above,
+            _get_amount_1_delta(sqrt_ratio_tick_0, sqrt_ratio_tick_1, 1e18 as u128, false).unwrap()
+        );
+
+        // price inside the tick holds part of each token
+        let (amount_0_inside, amount_1_inside) = get_amounts_for_liquidity(
+            SqrtPriceX96((*sqrt_ratio_tick_0 + *sqrt_ratio_tick_1) / U256_2),
+            sqrt_ratio_tick_0,
+            sqrt_ratio_tick_1,
+            1e18 as u128,
+        )
+        .unwrap();
+        assert!(amount_0_inside > U256::ZERO && amount_0_inside < amount_0_below);
+        assert!(amount_1_inside > U256::ZERO && amount_1_inside < amount_1_above);
+
+        // amounts round trip through liquidity within rounding
+        let sqrt_price = SqrtPriceX96(U256::from_str("79228162514264337593543950336").unwrap());
+        let amount_0 = U256::from(1e18 as u128);
+        let amount_1 = U256::from(3e18 as u128);
+        let liquidity =
+            get_liquidity_for_amounts(sqrt_price, sqrt_ratio_a, sqrt_ratio_b, amount_0, amount_1)
+                .unwrap();
+        let (amount_0_out, amount_1_out) =
+            get_amounts_for_liquidity(sqrt_price, sqrt_ratio_a, sqrt_ratio_b, liquidity).unwrap();
+
+        // token0 is the binding amount, token1 is only partially used
+        assert!(amount_0_out <= amount_0 && amount_0 - amount_0_out <= U256_2);
+        assert!(amount_1_out <= amount_1);
+    }
+
+    #[test]
+    fn test_encode_sqrt_ratio_x_96() {
+        // 1:1 is exactly 2**96
+        let result = encode_sqrt_ratio_x_96(U256::from(1e18 as u128), U256::from(1e18 as u128));
+        assert_eq!(
+            result.unwrap(),
+            U256::from_str("79228162514264337593543950336").unwrap()
+        );
+
+        // encodePriceSqrt(100, 110)
+        let result = encode_sqrt_ratio_x_96(U256::from(100), U256::from(110));
+        assert_eq!(
+            result.unwrap(),
+            U256::from_str("75541088972021052632782079082").unwrap()
+        );
+
+        // USDC (token0, 6 decimals) / WETH (token1, 18 decimals) at 2000 USDC per ETH
+        let result = encode_sqrt_ratio_x_96(U256::from(1e18 as u128), U256::from(2000e6 as u128));
+        assert_eq!(
+            result.unwrap(),
+            U256::from_str("1771595571142957102961017161607260").unwrap()
+        );
+
+        // encodeSqrtRatioX96(100, 1) and encodeSqrtRatioX96(1, 100) of the SDK
+        let result = encode_sqrt_ratio_x_96(U256::from(100), U256_1);
+        assert_eq!(
+            result.unwrap(),
+            U256::from_str("792281625142643375935439503360").unwrap()
+        );
+        let result = encode_sqrt_ratio_x_96(U256_1, U256::from(100));
+        assert_eq!(
+            result.unwrap(),
+            U256::from_str("7922816251426433759354395033").unwrap()
+        );
+
+        // 18 decimal amounts are rounded down
+        let result = encode_sqrt_ratio_x_96(
+            U256::from_str("1234567890123456789012").unwrap(),
+            U256::from_str("987654321098765432109").unwrap(),
+        );
+        assert_eq!(
+            result.unwrap(),
+            U256::from_str("88579778153556238182685589891").unwrap()
+        );
+
+        // ratios that overflow 256 bits in X192 are still encoded precisely
+        let result = encode_sqrt_ratio_x_96(U256::from(1) << 126, U256_1);
+        assert_eq!(result.unwrap(), U256::from(1) << 159);
+
+        // fails if the denominator is zero
+        let result = encode_sqrt_ratio_x_96(U256_1, U256::ZERO);
+        assert_eq!(result.unwrap_err().to_string(), "Denominator is 0");
+
+        // fails if the price is below the min sqrt ratio
+        let result = encode_sqrt_ratio_x_96(U256_1, U256::from(1) << 128);
+        assert_eq!(result.unwrap_err().to_string(), "Second inequality must be < because the price can never reach the price at the max tick");
+
+        // fails if the price is above the max sqrt ratio
+        let result = encode_sqrt_ratio_x_96(U256::from(1) << 128, U256_1);
+        assert_eq!(result.unwrap_err().to_string(), "Second inequality must be < because the price can never reach the price at the max tick");
+
+        // the max sqrt ratio itself is out of bounds, one below is not
+        let amount_1 =
+            U256::from_str("6276629866113596247980114726598279378674362140634661003678").unwrap();
+        let result = encode_sqrt_ratio_x_96(amount_1, U256::from(1) << 64);
+        assert!(matches!(
+            result,
+            Err(UniswapV3MathError::SqrtPriceOutOfBounds { sqrt_price_x_96 }) if sqrt_price_x_96 == MAX_SQRT_RATIO
+        ));
+        let result = encode_sqrt_ratio_x_96(amount_1 - U256_1, U256::from(1) << 64);
+        assert_eq!(result.unwrap(), MAX_SQRT_RATIO - U256_1);
+    }
+
+    #[test]
+    fn test_get_amount_delta_signed() {
+        let sqrt_ratio_a = SqrtPriceX96(U256::from_str("79228162514264337593543950336").unwrap());
+        let sqrt_ratio_b = SqrtPriceX96(U256::from_str("87150978765690771352898345369").unwrap());
+
+        // positive liquidity rounds up, negative liquidity rounds down and negates
+        let amount_0 = get_amount_0_delta_signed(sqrt_ratio_a, sqrt_ratio_b, 1e18 as i128).unwrap();
+        assert_eq!(
+            amount_0.into_raw(),
+            _get_amount_0_delta(sqrt_ratio_a, sqrt_ratio_b, 1e18 as u128, true).unwrap()
+        );
+        let amount_0 =
+            get_amount_0_delta_signed(sqrt_ratio_a, sqrt_ratio_b, -1e18 as i128).unwrap();
+        assert_eq!(
+            (-amount_0).into_raw(),
+            _get_amount_0_delta(sqrt_ratio_a, sqrt_ratio_b, 1e18 as u128, false).unwrap()
+        );
+
+        let amount_1 = get_amount_1_delta_signed(sqrt_ratio_a, sqrt_ratio_b, 1e18 as i128).unwrap();
+        assert_eq!(
+            amount_1.into_raw(),
+            _get_amount_1_delta(sqrt_ratio_a, sqrt_ratio_b, 1e18 as u128, true).unwrap()
+        );
+        let amount_1 =
+            get_amount_1_delta_signed(sqrt_ratio_a, sqrt_ratio_b, -1e18 as i128).unwrap();
+        assert_eq!(
+            (-amount_1).into_raw(),
+            _get_amount_1_delta(sqrt_ratio_a, sqrt_ratio_b, 1e18 as u128, false).unwrap()
+        );
+
+        // zero liquidity
+        let amount_0 = get_amount_0_delta_signed(sqrt_ratio_a, sqrt_ratio_b, 0).unwrap();
+        assert_eq!(amount_0, I256::ZERO);
+
+        // i128::MIN does not overflow when negated
+        let amount_0 = get_amount_0_delta_signed(sqrt_ratio_a, sqrt_ratio_b, i128::MIN).unwrap();
+        assert_eq!(
+            (-amount_0).into_raw(),
+            _get_amount_0_delta(sqrt_ratio_a, sqrt_ratio_b, 1 << 127, false).unwrap()
+        );
+        let amount_1 = get_amount_1_delta_signed(sqrt_ratio_a, sqrt_ratio_b, i128::MIN).unwrap();
+        assert_eq!(
+            (-amount_1).into_raw(),
+            _get_amount_1_delta(sqrt_ratio_a, sqrt_ratio_b, 1 << 127, false).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_max_liquidity_for_amount_0_and_1() {
+        // encodePriceSqrt(100, 110) and encodePriceSqrt(110, 100)
+        let sqrt_ratio_a = U256::from_str("75541088972021052632782079082").unwrap();
+        let sqrt_ratio_b = U256::from_str("83095197869223157896060286990").unwrap();
+
+        for precise in [false, true] {
+            let liquidity = max_liquidity_for_amount_0(
+                SqrtPriceX96(sqrt_ratio_a),
+                SqrtPriceX96(sqrt_ratio_b),
+                U256::from(100),
+                precise,
+            );
+            assert_eq!(liquidity.unwrap(), 1048);
+        }
+
+        let liquidity = max_liquidity_for_amount_1(
+            SqrtPriceX96(sqrt_ratio_b),
+            SqrtPriceX96(sqrt_ratio_a),
+            U256::from(200),
+        );
+        assert_eq!(liquidity.unwrap(), 2097);
+
+        // for amount0 = MaxUint256 the imprecise math overflows uint256 in mul_div, the precise math
+        // computes the SDK value which does not fit into uint128
+        let liquidity = max_liquidity_for_amount_0(
+            SqrtPriceX96(sqrt_ratio_a),
+            SqrtPriceX96(sqrt_ratio_b),
+            U256::MAX,
+            false,
+        );
+        assert_eq!(
+            liquidity.unwrap_err().to_string(),
+            "Denominator is less than or equal to prod_1"
+        );
+        let liquidity = max_liquidity_for_amount_0(
+            SqrtPriceX96(sqrt_ratio_a),
+            SqrtPriceX96(sqrt_ratio_b),
+            U256::MAX,
+            true,
+        );
+        assert_eq!(
+            liquidity.unwrap_err().to_string(),
+            "Overflow when casting to U128"
+        );
+
+        // near the min tick sqrt(a) * sqrt(b) / 2**96 truncates to zero in the imprecise math
+        let sqrt_ratio_a = U256::from(4295128739_u64);
+        let sqrt_ratio_b = U256::from(4295343490_u64);
+        let amount_0 = U256::from(1e18 as u128);
+
+        let liquidity = max_liquidity_for_amount_0(
+            SqrtPriceX96(sqrt_ratio_a),
+            SqrtPriceX96(sqrt_ratio_b),
+            amount_0,
+            false,
+        );
+        assert_eq!(liquidity.unwrap(), 0);
+
+        let liquidity = max_liquidity_for_amount_0(
+            SqrtPriceX96(sqrt_ratio_a),
+            SqrtPriceX96(sqrt_ratio_b),
+            amount_0,
+            true,
+        );
+        assert_eq!(liquidity.unwrap(), 1084);
+
+        // near the max tick both variants agree
+        let sqrt_ratio_a =
+            U256::from_str("1461373636630004318706518188784493106690254656249").unwrap();
+        let sqrt_ratio_b =
+            U256::from_str("1461446703485210103287273052203988822378723970342").unwrap();
+
+        let liquidity = max_liquidity_for_amount_0(
+            SqrtPriceX96(sqrt_ratio_a),
+            SqrtPriceX96(sqrt_ratio_b),
+            U256::from(1),
+            true,
+        );
+        assert_eq!(
+            liquidity.unwrap(),
+            max_liquidity_for_amount_0(
+                SqrtPriceX96(sqrt_ratio_a),
+                SqrtPriceX96(sqrt_ratio_b),
+                U256::from(1),
+                false
+            )
+            .unwrap()
+        );
+
+        // amount0 * sqrtA * sqrtB exceeds 512 bits
+        let liquidity = max_liquidity_for_amount_0(
+            SqrtPriceX96(sqrt_ratio_a),
+            SqrtPriceX96(sqrt_ratio_b),
+            U256::MAX,
+            true,
+        );
+        assert_eq!(
+            liquidity.unwrap_err().to_string(),
+            "Overflow when casting to U128"
+        );
+
+        // equal bounds
+        let liquidity = max_liquidity_for_amount_0(
+            SqrtPriceX96(sqrt_ratio_a),
+            SqrtPriceX96(sqrt_ratio_a),
+            U256::from(1),
+            true,
+        );
+        assert_eq!(liquidity.unwrap_err().to_string(), "Denominator is 0");
+    }
+
+    #[test]
+    fn test_get_amount_delta_rounded() {
+        let q96 = U256::from_str("79228162514264337593543950336").unwrap();
+
+        // inexact divisions differ by exactly one unit
+        let sqrt_ratio_a = SqrtPriceX96(q96);
+        let sqrt_ratio_b = U256::from_str("87150978765690771352898345369").unwrap();
+
+        let amount_0_up = get_amount_0_delta_rounded(
+            sqrt_ratio_a,
+            SqrtPriceX96(sqrt_ratio_b),
+            1e18 as u128,
+            Rounding::Up,
+        )
+        .unwrap();
+        let amount_0_down = get_amount_0_delta_rounded(
+            sqrt_ratio_a,
+            SqrtPriceX96(sqrt_ratio_b),
+            1e18 as u128,
+            Rounding::Down,
+        )
+        .unwrap();
+        assert_eq!(amount_0_up, amount_0_down + U256_1);
+        assert_eq!(
+            amount_0_up,
+            _get_amount_0_delta(sqrt_ratio_a, SqrtPriceX96(sqrt_ratio_b), 1e18 as u128, true)
+                .unwrap()
+        );
+
+        let amount_1_up = get_amount_1_delta_rounded(
+            sqrt_ratio_a,
+            SqrtPriceX96(sqrt_ratio_b),
+            1e18 as u128,
+            Rounding::Up,
+        )
+        .unwrap();
+        let amount_1_down = get_amount_1_delta_rounded(
+            sqrt_ratio_a,
+            SqrtPriceX96(sqrt_ratio_b),
+            1e18 as u128,
+            Rounding::Down,
+        )
+        .unwrap();
+        assert_eq!(amount_1_up, amount_1_down + U256_1);
+        assert_eq!(
+            amount_1_down,
+            _get_amount_1_delta(
+                sqrt_ratio_a,
+                SqrtPriceX96(sqrt_ratio_b),
+                1e18 as u128,
+                false
+            )
+            .unwrap()
+        );
+
+        // exact divisions are equal, price of 1 to 4
+        let sqrt_ratio_b = q96 * U256_2;
+
+        let amount_0_up =
+            get_amount_0_delta_rounded(sqrt_ratio_a, SqrtPriceX96(sqrt_ratio_b), 2, Rounding::Up)
+                .unwrap();
+        let amount_0_down =
+            get_amount_0_delta_rounded(sqrt_ratio_a, SqrtPriceX96(sqrt_ratio_b), 2, Rounding::Down)
+                .unwrap();
+        assert_eq!(amount_0_up, U256_1);
+        assert_eq!(amount_0_up, amount_0_down);
+
+        let amount_1_up =
+            get_amount_1_delta_rounded(sqrt_ratio_a, SqrtPriceX96(sqrt_ratio_b), 1, Rounding::Up)
+                .unwrap();
+        let amount_1_down =
+            get_amount_1_delta_rounded(sqrt_ratio_a, SqrtPriceX96(sqrt_ratio_b), 1, Rounding::Down)
+                .unwrap();
+        assert_eq!(amount_1_up, U256_1);
+        assert_eq!(amount_1_up, amount_1_down);
+    }
+
+    #[test]
+    fn test_get_next_sqrt_price_capped() {
+        let sqrt_price = SqrtPriceX96(U256::from_str("79228162514264337593543950336").unwrap());
+
+        // agrees with a fee-less compute_swap_step over a grid of inputs
+        for liquidity in [1e15 as u128, 2e18 as u128, 3e24 as u128] {
+            for amount in [
+                U256::from(1000),
+                U256::from(1e16 as u128),
+                U256::from(1e20 as u128),
+            ] {
+                for (zero_for_one, sqrt_price_limit) in [
+                    (true, get_sqrt_ratio_at_tick(-10).unwrap()),
+                    (true, get_sqrt_ratio_at_tick(-10000).unwrap()),
+                    (false, get_sqrt_ratio_at_tick(10).unwrap()),
+                    (false, get_sqrt_ratio_at_tick(10000).unwrap()),
+                ] {
+                    let (sqrt_next, amount_in, _, _) = compute_swap_step(
+                        sqrt_price,
+                        sqrt_price_limit,
+                        liquidity,
+                        I256::from_raw(amount),
+                        0,
+                    )
+                    .unwrap();
+                    let result = get_next_sqrt_price_from_input_capped(
+                        sqrt_price,
+                        liquidity,
+                        amount,
+                        zero_for_one,
+                        sqrt_price_limit,
+                    )
+                    .unwrap();
+                    assert_eq!(result, (sqrt_next, amount_in));
+
+                    let Ok((sqrt_next, _, amount_out, _)) = compute_swap_step(
+                        sqrt_price,
+                        sqrt_price_limit,
+                        liquidity,
+                        -I256::from_raw(amount),
+                        0,
+                    ) else {
+                        // the output exceeds the virtual reserves, the capped variant must agree
+                        assert!(get_next_sqrt_price_from_output_capped(
+                            sqrt_price,
+                            liquidity,
+                            amount,
+                            zero_for_one,
+                            sqrt_price_limit,
+                        )
+                        .is_err());
+                        continue;
+                    };
+                    let result = get_next_sqrt_price_from_output_capped(
+                        sqrt_price,
+                        liquidity,
+                        amount,
+                        zero_for_one,
+                        sqrt_price_limit,
+                    )
+                    .unwrap();
+                    assert_eq!(result, (sqrt_next, amount_out));
+                }
+            }
+        }
+
+        // limit on the wrong side of the price
+        let result = get_next_sqrt_price_from_input_capped(
+            sqrt_price,
+            1e18 as u128,
+            U256::from(1000),
+            true,
+            get_sqrt_ratio_at_tick(10).unwrap(),
+        );
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Sqrt price limit is out of bounds"
+        );
+    }
+
+    #[test]
+    fn test_amount_deltas_for_ticks() {
+        let liquidity = 2e18 as u128;
+        let sqrt_ratio_lower = get_sqrt_ratio_at_tick(-600).unwrap();
+        let sqrt_ratio_upper = get_sqrt_ratio_at_tick(1200).unwrap();
+
+        for round_up in [true, false] {
+            assert_eq!(
+                get_amount_0_delta_for_ticks(-600, 1200, liquidity, round_up).unwrap(),
+                _get_amount_0_delta(sqrt_ratio_lower, sqrt_ratio_upper, liquidity, round_up)
+                    .unwrap()
+            );
+            assert_eq!(
+                get_amount_1_delta_for_ticks(-600, 1200, liquidity, round_up).unwrap(),
+                _get_amount_1_delta(sqrt_ratio_lower, sqrt_ratio_upper, liquidity, round_up)
+                    .unwrap()
+            );
+        }
+
+        for sqrt_price in [
+            get_sqrt_ratio_at_tick(-1000).unwrap(),
+            SqrtPriceX96(U256::from_str("79228162514264337593543950336").unwrap()),
+            get_sqrt_ratio_at_tick(5000).unwrap(),
+        ] {
+            assert_eq!(
+                get_token_amounts_for_ticks(sqrt_price, -600, 1200, liquidity).unwrap(),
+                get_amounts_for_liquidity(
+                    sqrt_price,
+                    sqrt_ratio_lower,
+                    sqrt_ratio_upper,
+                    liquidity
+                )
+                .unwrap()
+            );
+        }
+
+        // invalid ranges
+        let result = get_amount_0_delta_for_ticks(60, 60, liquidity, true);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Lower tick must be less than upper tick"
+        );
+        let result = get_amount_1_delta_for_ticks(60, -60, liquidity, true);
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Lower tick must be less than upper tick"
+        );
+        let result = get_token_amounts_for_ticks(
+            SqrtPriceX96(U256::from_str("79228162514264337593543950336").unwrap()),
+            MIN_TICK - 1,
+            MAX_TICK,
+            liquidity,
+        );
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "The given tick must be less than, or equal to, the maximum tick"
+        );
+    }
+
+    #[test]
+    fn test_sqrt_price_x96_to_price() -> eyre::Result<()> {
+        //USDC/WETH with USDC (6 decimals) as token0 at roughly 3000 USDC per WETH
+        let sqrt_price_x_96 = U256::from_str("1446501726624926496477173928747177")?;
+        let price = sqrt_price_x96_to_price(SqrtPriceX96(sqrt_price_x_96), 6, 18);
+        assert!((1.0 / price - 3000.0).abs() < 1.0, "{}", 1.0 / price);
+
+        //1:1 raw price with equal decimals
+        assert_eq!(sqrt_price_x96_to_price(SqrtPriceX96(Q96), 18, 18), 1.0);
+        assert_eq!(sqrt_price_x96_to_price(SqrtPriceX96(Q96), 18, 6), 1e12);
+
+        //round trip within the f64 precision
+        let round_trip = price_to_sqrt_price_x96(price, 6, 18)?;
+        let error = f64::from(round_trip.abs_diff(sqrt_price_x_96)) / f64::from(sqrt_price_x_96);
+        assert!(error < 1e-12, "{error}");
+        assert_eq!(price_to_sqrt_price_x96(1.0, 18, 18)?, Q96);
+
+        for price in [0.0, -1.0, f64::NAN, f64::INFINITY] {
+            assert!(matches!(
+                price_to_sqrt_price_x96(price, 6, 18),
+                Err(UniswapV3MathError::InvalidPrice { .. })
+            ));
+        }
+        assert!(matches!(
+            price_to_sqrt_price_x96(1e300, 0, 18),
+            Err(UniswapV3MathError::SqrtPriceOutOfBounds { .. })
+        ));
+        assert!(matches!(
+            price_to_sqrt_price_x96(1e-300, 18, 0),
+            Err(UniswapV3MathError::SqrtPriceOutOfBounds { .. })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_snap_price_to_sqrt_price() -> eyre::Result<()> {
+        //with a spacing of 1 the snapped price is within one tick, 0.01%, of the input
+        for price in [0.0123, 0.5, 1.0, 7.77, 42.0, 314.159, 2500.0, 99999.0] {
+            let (tick, sqrt_price_x_96) = snap_price_to_sqrt_price(price, 1, 18, 18)?;
+            assert_eq!(sqrt_price_x_96, get_sqrt_ratio_at_tick(tick)?);
+            let round_trip = sqrt_price_x96_to_price(sqrt_price_x_96, 18, 18);
+            assert!(
+                (round_trip / price - 1.0).abs() < 1e-4,
+                "{price} {round_trip}"
+            );
+        }
+
+        //USDC/WETH at 2000 USDC per WETH, the price of USDC is 0.0005 WETH
+        let (tick, sqrt_price_x_96) = snap_price_to_sqrt_price(0.0005, 10, 6, 18)?;
+        assert_eq!(tick, 200310);
+        assert!(
+            (sqrt_price_x96_to_price(sqrt_price_x_96, 6, 18) / 0.0005 - 1.0).abs() < 5e-4,
+            "{tick}"
+        );
+        let (tick, _) = snap_price_to_sqrt_price(0.0005, 60, 6, 18)?;
+        assert_eq!(tick, 200340);
+        let (tick, _) = snap_price_to_sqrt_price(0.0005, 200, 6, 18)?;
+        assert_eq!(tick, 200400);
+
+        //the nearest multiple of the spacing is kept inside the bounds
+        let (tick, _) = snap_price_to_sqrt_price(tick_to_price(MAX_TICK), 200, 18, 18)?;
+        assert_eq!(tick, 887200);
+        let (tick, _) = snap_price_to_sqrt_price(tick_to_price(MIN_TICK + 1), 200, 18, 18)?;
+        assert_eq!(tick, -887200);
+
+        assert!(matches!(
+            snap_price_to_sqrt_price(1e300, 1, 18, 18),
+            Err(UniswapV3MathError::TickOutOfBounds { .. })
+        ));
+        assert!(matches!(
+            snap_price_to_sqrt_price(-1.0, 1, 18, 18),
+            Err(UniswapV3MathError::InvalidPrice { .. })
+        ));
+        assert!(matches!(
+            snap_price_to_sqrt_price(1.0, 0, 18, 18),
+            Err(UniswapV3MathError::InvalidTickSpacing { tick_spacing: 0 })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_liquidity_for_amount_0_and_1_fee_tier_ranges() -> eyre::Result<()> {
+        let amount = U256::from(1e18);
+
+        for fee_tier in [
+            FeeTier::HundredthBip,
+            FeeTier::LowVolatility,
+            FeeTier::Standard,
+            FeeTier::HighVolatility,
+        ] {
+            let tick_spacing = fee_tier.tick_spacing();
+            for (tick_lower, tick_upper) in [
+                (-tick_spacing, tick_spacing),
+                (-100 * tick_spacing, 10 * tick_spacing),
+                (-1000 * tick_spacing, 1000 * tick_spacing),
+            ] {
+                let sqrt_ratio_a = get_sqrt_ratio_at_tick(tick_lower)?;
+                let sqrt_ratio_b = get_sqrt_ratio_at_tick(tick_upper)?;
+
+                // the liquidity never needs more than the given amount and leaves at most a rounding dust
+                let liquidity = get_liquidity_for_amount_0(sqrt_ratio_a, sqrt_ratio_b, amount)?;
+                let amount_0 = _get_amount_0_delta(sqrt_ratio_a, sqrt_ratio_b, liquidity, true)?;
+                assert!(amount_0 <= amount);
+                assert!(amount - amount_0 <= amount / U256::from(1e9));
+
+                let liquidity = get_liquidity_for_amount_1(sqrt_ratio_a, sqrt_ratio_b, amount)?;
+                let amount_1 = _get_amount_1_delta(sqrt_ratio_a, sqrt_ratio_b, liquidity, true)?;
+                assert!(amount_1 <= amount);
+                assert!(amount - amount_1 <= amount / U256::from(1e9));
+            }
+        }
+        Ok(())
+    }
+
+    proptest! {
+        #[test]
+        fn test_encode_sqrt_ratio_x_96_is_floor_sqrt(amount_1 in any::<u128>(), amount_0 in 1..=u128::MAX) {
+            if let Ok(sqrt_price_x_96) = encode_sqrt_ratio_x_96(U256::from(amount_1), U256::from(amount_0)) {
+                // sqrt_price_x_96^2 / 2^192 <= amount1 / amount0 < (sqrt_price_x_96 + 1)^2 / 2^192, computed without
+                // division over 512 bits
+                let sqrt_price_x_96 = U512::from(sqrt_price_x_96.0);
+                let scaled_amount_1 = U512::from(amount_1) << 192;
+                let amount_0 = U512::from(amount_0);
+                prop_assert!(sqrt_price_x_96 * sqrt_price_x_96 * amount_0 <= scaled_amount_1);
+                let next = sqrt_price_x_96 + U512::from(1);
+                // the floor of the X192 ratio is what is square rooted, so the upper bound holds up to one amount0
+                prop_assert!(next * next * amount_0 > scaled_amount_1 - scaled_amount_1 % amount_0);
+            }
+        }
+    }
 }
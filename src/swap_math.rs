@@ -1,14 +1,56 @@
-use alloy::primitives::{I256, U256};
+use alloc::vec::Vec;
+
+use alloy_primitives::{ruint::Uint, I256, U256};
 
 use crate::{
     error::UniswapV3MathError,
+    fixed_point::{FeeGrowthX128, SqrtPriceX96, Q128, RESOLUTION},
     full_math::{mul_div, mul_div_rounding_up},
+    liquidity_math::cross_tick,
+    pool::PoolState,
+    quoter::quote_exact_input_single,
     sqrt_price_math::{
         _get_amount_0_delta, _get_amount_1_delta, get_next_sqrt_price_from_input,
         get_next_sqrt_price_from_output,
     },
+    swap_simulation::TickDataProvider,
+    tick_math::{get_sqrt_ratio_at_tick, get_tick_at_sqrt_ratio, MAX_TICK, MIN_TICK},
+};
+#[cfg(feature = "std")]
+use {
+    crate::{
+        sqrt_price_math::sqrt_price_x96_to_price, swap_simulation::HashMapTickDataProvider,
+        tick::TickInfo, tick_bitmap::TickBitmap,
+    },
+    std::collections::HashMap,
 };
 
+//Fee pips of 1e6 is a 100% fee, which V4 pools allow for exact input swaps
+pub const MAX_SWAP_FEE: u32 = 1_000_000;
+
+//Result of a single swap step, reached_target_price is set when the step was limited by the
+//target price rather than by the remaining amount
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SwapStep {
+    pub sqrt_ratio_next_x_96: SqrtPriceX96,
+    pub amount_in: U256,
+    pub amount_out: U256,
+    pub fee_amount: U256,
+    pub reached_target_price: bool,
+    pub exact_in: bool,
+}
+
+//Name of the step struct in UniswapV3Pool.swap
+pub type StepComputations = SwapStep;
+
+impl SwapStep {
+    //Input paid by the swapper for the step, fee included
+    pub fn total_cost(&self) -> U256 {
+        self.amount_in + self.fee_amount
+    }
+}
+
 // //returns (
 //         uint160 sqrtRatioNextX96,
 //         uint256 amountIn,
@@ -16,24 +58,51 @@ use crate::{
 //         uint256 feeAmount
 //     )
 pub fn compute_swap_step(
-    sqrt_ratio_current_x_96: U256,
-    sqrt_ratio_target_x_96: U256,
+    sqrt_ratio_current_x_96: SqrtPriceX96,
+    sqrt_ratio_target_x_96: SqrtPriceX96,
     liquidity: u128,
     amount_remaining: I256,
     fee_pips: u32,
-) -> Result<(U256, U256, U256, U256), UniswapV3MathError> {
+) -> Result<(SqrtPriceX96, U256, U256, U256), UniswapV3MathError> {
+    let step = compute_swap_step_struct(
+        sqrt_ratio_current_x_96,
+        sqrt_ratio_target_x_96,
+        liquidity,
+        amount_remaining,
+        fee_pips,
+    )?;
+
+    Ok((
+        step.sqrt_ratio_next_x_96,
+        step.amount_in,
+        step.amount_out,
+        step.fee_amount,
+    ))
+}
+
+pub fn compute_swap_step_struct(
+    sqrt_ratio_current_x_96: SqrtPriceX96,
+    sqrt_ratio_target_x_96: SqrtPriceX96,
+    liquidity: u128,
+    amount_remaining: I256,
+    fee_pips: u32,
+) -> Result<SwapStep, UniswapV3MathError> {
     let zero_for_one = sqrt_ratio_current_x_96 >= sqrt_ratio_target_x_96;
     let exact_in = amount_remaining >= I256::ZERO;
 
-    let sqrt_ratio_next_x_96: U256;
+    if fee_pips > MAX_SWAP_FEE || (fee_pips == MAX_SWAP_FEE && !exact_in) {
+        return Err(UniswapV3MathError::InvalidFeePips { fee_pips });
+    }
+
+    let sqrt_ratio_next_x_96: SqrtPriceX96;
     let mut amount_in = U256::ZERO;
     let mut amount_out = U256::ZERO;
 
     if exact_in {
         let amount_remaining_less_fee = mul_div(
             amount_remaining.into_raw(),
-            U256::from(1e6 as u32 - fee_pips),    //1e6 - fee_pips
-            U256::from_limbs([1000000, 0, 0, 0]), //1e6
+            U256::from(MAX_SWAP_FEE - fee_pips),
+            U256::from(MAX_SWAP_FEE),
         )?;
 
         amount_in = if zero_for_one {
@@ -79,13 +148,13 @@ pub fn compute_swap_step(
             )?
         };
 
-        sqrt_ratio_next_x_96 = if (-amount_remaining).into_raw() >= amount_out {
+        sqrt_ratio_next_x_96 = if amount_remaining.unsigned_abs() >= amount_out {
             sqrt_ratio_target_x_96
         } else {
             get_next_sqrt_price_from_output(
                 sqrt_ratio_current_x_96,
                 liquidity,
-                (-amount_remaining).into_raw(),
+                amount_remaining.unsigned_abs(),
                 zero_for_one,
             )?
         };
@@ -131,31 +200,442 @@ pub fn compute_swap_step(
         }
     }
 
-    if !exact_in && amount_out > (-amount_remaining).into_raw() {
-        amount_out = (-amount_remaining).into_raw();
+    if !exact_in && amount_out > amount_remaining.unsigned_abs() {
+        amount_out = amount_remaining.unsigned_abs();
     }
 
-    if exact_in && sqrt_ratio_next_x_96 != sqrt_ratio_target_x_96 {
-        let fee_amount = amount_remaining.into_raw() - amount_in;
-        Ok((sqrt_ratio_next_x_96, amount_in, amount_out, fee_amount))
+    let fee_amount = if exact_in && !max {
+        amount_remaining.into_raw() - amount_in
+    } else if fee_pips == MAX_SWAP_FEE {
+        // as in V4's SwapMath, a 100% fee equals the amount in rather than dividing by zero
+        amount_in
     } else {
-        let fee_amount = mul_div_rounding_up(
+        mul_div_rounding_up(
             amount_in,
             U256::from(fee_pips),
-            U256::from(1e6 as u32 - fee_pips),
+            U256::from(MAX_SWAP_FEE - fee_pips),
+        )?
+    };
+
+    Ok(SwapStep {
+        sqrt_ratio_next_x_96,
+        amount_in,
+        amount_out,
+        fee_amount,
+        reached_target_price: max,
+        exact_in,
+    })
+}
+
+//Swap step with its fee split between the protocol and the LPs
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SwapStepWithProtocolFee {
+    pub step: SwapStep,
+    pub protocol_fee: U256,
+    pub lp_fee: U256,
+}
+
+//fee_protocol is the denominator of the input token, i.e. slot0.feeProtocol % 16 for zero_for_one and
+//slot0.feeProtocol >> 4 otherwise. The protocol takes fee_amount / fee_protocol rounded down and the
+//remainder stays with the LPs, 0 disables the protocol fee like in UniswapV3Pool.swap. Any denominator other
+//than 0 or [4, 10] errors with InvalidFeeProtocol, see get_protocol_fee_amount
+pub fn compute_swap_step_with_protocol_fee(
+    sqrt_ratio_current_x_96: SqrtPriceX96,
+    sqrt_ratio_target_x_96: SqrtPriceX96,
+    liquidity: u128,
+    amount_remaining: I256,
+    fee_pips: u32,
+    fee_protocol: u8,
+) -> Result<SwapStepWithProtocolFee, UniswapV3MathError> {
+    let step = compute_swap_step_struct(
+        sqrt_ratio_current_x_96,
+        sqrt_ratio_target_x_96,
+        liquidity,
+        amount_remaining,
+        fee_pips,
+    )?;
+
+    Ok(SwapStepWithProtocolFee {
+        step,
+        protocol_fee: get_protocol_fee_amount(step.fee_amount, fee_protocol)?,
+        lp_fee: get_lp_fee_amount(step.fee_amount, fee_protocol)?,
+    })
+}
+
+//Protocol share of a swap fee, swap_fee / protocol_fee_numerator rounded down. The numerator is 0 (disabled) or in
+//[4, 10] as enforced by UniswapV3Pool.setFeeProtocol, anything else errors with InvalidFeeProtocol
+pub fn get_protocol_fee_amount(
+    swap_fee: U256,
+    protocol_fee_numerator: u8,
+) -> Result<U256, UniswapV3MathError> {
+    match protocol_fee_numerator {
+        0 => Ok(U256::ZERO),
+        4..=10 => Ok(swap_fee / U256::from(protocol_fee_numerator)),
+        _ => Err(UniswapV3MathError::InvalidFeeProtocol {
+            fee_protocol: protocol_fee_numerator,
+        }),
+    }
+}
+
+//LP share of a swap fee, the complement of get_protocol_fee_amount including its rounding dust
+pub fn get_lp_fee_amount(
+    swap_fee: U256,
+    protocol_fee_numerator: u8,
+) -> Result<U256, UniswapV3MathError> {
+    Ok(swap_fee - get_protocol_fee_amount(swap_fee, protocol_fee_numerator)?)
+}
+
+//fee_growth_global_x_128 + fee_amount * 2^128 / liquidity, wrapping like the fee growth globals of the pool. The
+//division rounds down, so accumulating fees step by step trails a single aggregated computation by less than one
+//unit per step
+pub fn accumulate_fee_growth(
+    fee_growth_global_x_128: FeeGrowthX128,
+    fee_amount: U256,
+    liquidity: u128,
+) -> Result<FeeGrowthX128, UniswapV3MathError> {
+    if liquidity == 0 {
+        return Err(UniswapV3MathError::LiquidityIsZero);
+    }
+
+    Ok(fee_growth_global_x_128.wrapping_add(FeeGrowthX128(mul_div(
+        fee_amount,
+        Q128,
+        U256::from(liquidity),
+    )?)))
+}
+
+// returns (uint256 amountIn, uint256 feeAmount), the fee inclusive input that moves the price from
+// sqrt_price_current_x_96 to exactly sqrt_price_target_x_96 without crossing a tick, and the fee part of it.
+// Both are rounded up so that compute_swap_step with amount_in as exact input reaches the target
+pub fn amount_in_to_reach_sqrt_price(
+    sqrt_price_current_x_96: SqrtPriceX96,
+    sqrt_price_target_x_96: SqrtPriceX96,
+    liquidity: u128,
+    fee_pips: u32,
+    zero_for_one: bool,
+) -> Result<(U256, U256), UniswapV3MathError> {
+    if (zero_for_one && sqrt_price_target_x_96 > sqrt_price_current_x_96)
+        || (!zero_for_one && sqrt_price_target_x_96 < sqrt_price_current_x_96)
+    {
+        return Err(UniswapV3MathError::InvalidSqrtPriceTarget {
+            sqrt_price_x_96: sqrt_price_current_x_96.0,
+            sqrt_price_target_x_96: sqrt_price_target_x_96.0,
+        });
+    }
+
+    // with a 100% fee no input ever reaches the pool
+    if fee_pips >= MAX_SWAP_FEE {
+        return Err(UniswapV3MathError::InvalidFeePips { fee_pips });
+    }
+
+    let amount_in_less_fee = if zero_for_one {
+        _get_amount_0_delta(
+            sqrt_price_target_x_96,
+            sqrt_price_current_x_96,
+            liquidity,
+            true,
+        )?
+    } else {
+        _get_amount_1_delta(
+            sqrt_price_current_x_96,
+            sqrt_price_target_x_96,
+            liquidity,
+            true,
+        )?
+    };
+
+    // smallest amount_in with amount_in * (1e6 - fee_pips) / 1e6 >= amount_in_less_fee
+    let amount_in = mul_div_rounding_up(
+        amount_in_less_fee,
+        U256::from(MAX_SWAP_FEE),
+        U256::from(MAX_SWAP_FEE - fee_pips),
+    )?;
+
+    Ok((amount_in, amount_in - amount_in_less_fee))
+}
+
+//One step of amount_to_move_ticks, the swap between two initialized ticks or word boundaries at constant
+//liquidity. tick is the current tick of the pool after the step
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MoveStep {
+    pub sqrt_price_start_x_96: SqrtPriceX96,
+    pub sqrt_price_end_x_96: SqrtPriceX96,
+    pub tick: i32,
+    pub liquidity: u128,
+    pub amount_in: U256,
+    pub amount_out: U256,
+    pub fee_amount: U256,
+}
+
+//Totals of amount_to_move_ticks, amount_in is fee inclusive
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct MoveResult {
+    pub amount_in: U256,
+    pub amount_out: U256,
+    pub fee_amount: U256,
+    pub sqrt_price_x_96: SqrtPriceX96,
+    pub tick: i32,
+    pub liquidity: u128,
+    pub steps: Vec<MoveStep>,
+}
+
+//Walks the swap loop until the price reaches get_sqrt_ratio_at_tick(current_tick + tick_delta), crossing the
+//initialized ticks on the way. A negative tick_delta moves the price down, i.e. zero for one. Errors with
+//LiquidityIsZero if the price would have to pass through a range without liquidity to reach the target
+#[allow(clippy::too_many_arguments)]
+pub fn amount_to_move_ticks<P: TickDataProvider>(
+    sqrt_price_x_96: SqrtPriceX96,
+    current_tick: i32,
+    liquidity: u128,
+    tick_spacing: i32,
+    fee_pips: u32,
+    tick_delta: i32,
+    tick_provider: &P,
+) -> Result<MoveResult, UniswapV3MathError> {
+    let target_tick = current_tick.saturating_add(tick_delta);
+    if !(MIN_TICK..=MAX_TICK).contains(&target_tick) {
+        return Err(UniswapV3MathError::TickOutOfBounds { tick: target_tick });
+    }
+    let sqrt_price_target_x_96 = get_sqrt_ratio_at_tick(target_tick)?;
+    let zero_for_one = tick_delta < 0;
+
+    let mut result = MoveResult {
+        sqrt_price_x_96,
+        tick: current_tick,
+        liquidity,
+        ..Default::default()
+    };
+
+    while result.sqrt_price_x_96 != sqrt_price_target_x_96 {
+        let (tick_next, initialized) = tick_provider.next_initialized_tick_within_one_word(
+            result.tick,
+            tick_spacing,
+            zero_for_one,
+        )?;
+
+        // ensure that we do not overshoot the min/max tick, as the tick bitmap is not aware of these bounds
+        let tick_next = tick_next.clamp(MIN_TICK, MAX_TICK);
+        let sqrt_price_next_x_96 = get_sqrt_ratio_at_tick(tick_next)?;
+
+        let step_target_x_96 = if (zero_for_one && sqrt_price_next_x_96 < sqrt_price_target_x_96)
+            || (!zero_for_one && sqrt_price_next_x_96 > sqrt_price_target_x_96)
+        {
+            sqrt_price_target_x_96
+        } else {
+            sqrt_price_next_x_96
+        };
+
+        // without liquidity the price only moves for free up to the next initialized tick, there is none left
+        // before the target
+        if result.liquidity == 0
+            && step_target_x_96 == sqrt_price_target_x_96
+            && !(initialized && sqrt_price_next_x_96 == sqrt_price_target_x_96)
+        {
+            return Err(UniswapV3MathError::LiquidityIsZero);
+        }
+
+        // the remaining amount is unbounded, so every step reaches its target price
+        let step = compute_swap_step_struct(
+            result.sqrt_price_x_96,
+            step_target_x_96,
+            result.liquidity,
+            I256::MAX,
+            fee_pips,
         )?;
 
-        Ok((sqrt_ratio_next_x_96, amount_in, amount_out, fee_amount))
+        let liquidity_step = result.liquidity;
+        let sqrt_price_start_x_96 = result.sqrt_price_x_96;
+        result.sqrt_price_x_96 = step.sqrt_ratio_next_x_96;
+
+        if result.sqrt_price_x_96 == sqrt_price_next_x_96 {
+            if initialized {
+                let liquidity_net = tick_provider.liquidity_net(tick_next)?;
+                result.liquidity = cross_tick(result.liquidity, liquidity_net, zero_for_one)?;
+            }
+            result.tick = if zero_for_one {
+                tick_next - 1
+            } else {
+                tick_next
+            };
+        } else if result.sqrt_price_x_96 != sqrt_price_start_x_96 {
+            result.tick = get_tick_at_sqrt_ratio(result.sqrt_price_x_96)?;
+        }
+
+        // steps that start on the boundary they target do not move the price and are left out of the breakdown
+        if result.sqrt_price_x_96 == sqrt_price_start_x_96 {
+            continue;
+        }
+
+        result.amount_in += step.total_cost();
+        result.amount_out += step.amount_out;
+        result.fee_amount += step.fee_amount;
+        result.steps.push(MoveStep {
+            sqrt_price_start_x_96,
+            sqrt_price_end_x_96: result.sqrt_price_x_96,
+            tick: result.tick,
+            liquidity: liquidity_step,
+            amount_in: step.total_cost(),
+            amount_out: step.amount_out,
+            fee_amount: step.fee_amount,
+        });
+    }
+
+    Ok(result)
+}
+
+const BPS: u64 = 10_000;
+
+// wide enough for an amount times a squared sqrt price
+type U1024 = Uint<1024, 16>;
+
+//Price impact of an exact input swap in basis points. price_impact_bps compares the execution price, fees
+//included, with the mid price before the swap and price_movement_bps is the move of the pool price
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct PriceImpact {
+    pub amount_in: U256,
+    pub amount_out: U256,
+    pub sqrt_price_before_x_96: SqrtPriceX96,
+    pub sqrt_price_after_x_96: SqrtPriceX96,
+    pub price_impact_bps: u32,
+    pub price_movement_bps: u32,
+}
+
+//Relative move of the price, i.e. the squared sqrt price, from sqrt_price_before_x_96 to sqrt_price_after_x_96
+//in basis points. Rounded up so that any movement is at least 1 bps, saturates at u32::MAX
+pub fn price_impact_bps(
+    sqrt_price_before_x_96: SqrtPriceX96,
+    sqrt_price_after_x_96: SqrtPriceX96,
+) -> Result<u32, UniswapV3MathError> {
+    if sqrt_price_before_x_96.is_zero() {
+        return Err(UniswapV3MathError::SqrtPriceIsZero);
+    }
+
+    let price_before = U1024::from(sqrt_price_before_x_96.0).pow(U1024::from(2));
+    let price_after = U1024::from(sqrt_price_after_x_96.0).pow(U1024::from(2));
+
+    Ok(bps_rounding_up(
+        price_before.abs_diff(price_after),
+        price_before,
+    ))
+}
+
+//Simulates swapping amount_in through the pool without a price limit and reports its price impact, the
+//execution price is rounded down so that the impact is pessimistic. Errors with LiquidityIsZero if the pool
+//can not take any of the input
+pub fn simulate_price_impact<P: TickDataProvider>(
+    pool_state: &PoolState,
+    tick_provider: &P,
+    amount_in: U256,
+    zero_for_one: bool,
+) -> Result<PriceImpact, UniswapV3MathError> {
+    let quote = quote_exact_input_single(pool_state, tick_provider, amount_in, zero_for_one, None)?;
+    if quote.amount_in.is_zero() {
+        return Err(UniswapV3MathError::LiquidityIsZero);
+    }
+
+    // value of both sides in units of the input token times 2^192 at the mid price
+    let price_before = U1024::from(pool_state.sqrt_price_x_96.0).pow(U1024::from(2));
+    let (value_out, value_in) = if zero_for_one {
+        (
+            U1024::from(quote.amount_out) << (2 * RESOLUTION),
+            U1024::from(quote.amount_in) * price_before,
+        )
+    } else {
+        (
+            U1024::from(quote.amount_out) * price_before,
+            U1024::from(quote.amount_in) << (2 * RESOLUTION),
+        )
+    };
+
+    Ok(PriceImpact {
+        amount_in: quote.amount_in,
+        amount_out: quote.amount_out,
+        sqrt_price_before_x_96: pool_state.sqrt_price_x_96,
+        sqrt_price_after_x_96: quote.sqrt_price_after_x_96,
+        price_impact_bps: bps_rounding_up(value_in.saturating_sub(value_out), value_in),
+        price_movement_bps: price_impact_bps(
+            pool_state.sqrt_price_x_96,
+            quote.sqrt_price_after_x_96,
+        )?,
+    })
+}
+
+fn bps_rounding_up(numerator: U1024, denominator: U1024) -> u32 {
+    (numerator * U1024::from(BPS))
+        .div_ceil(denominator)
+        .saturating_to()
+}
+
+//Price impact above which a trade is commonly flagged to the user, 1% as a fraction
+pub const PRICE_IMPACT_WARNING_THRESHOLD: f64 = 0.01;
+
+//Price impact of swapping amount_in through the pool as a fraction, 1 - effective price / spot price. The effective
+//price is amount_out / amount_in of the simulated swap, fees included, and the spot price the price of the input
+//token before the swap. Computed in f64 for display, see simulate_price_impact for exact basis points. Errors
+//with LiquidityIsZero if the pool can not take any of the input
+#[cfg(feature = "std")]
+pub fn get_price_impact(
+    pool: &PoolState,
+    tick_bitmap: &TickBitmap,
+    ticks: &HashMap<i32, TickInfo>,
+    amount_in: U256,
+    zero_for_one: bool,
+) -> Result<f64, UniswapV3MathError> {
+    let quote = quote_exact_input_single(
+        pool,
+        &HashMapTickDataProvider { tick_bitmap, ticks },
+        amount_in,
+        zero_for_one,
+        None,
+    )?;
+    if quote.amount_in.is_zero() {
+        return Err(UniswapV3MathError::LiquidityIsZero);
     }
+
+    // raw price of token0 in token1, the decimals cancel out against the raw amounts
+    let price = sqrt_price_x96_to_price(pool.sqrt_price_x_96, 0, 0);
+    let spot_price = if zero_for_one { price } else { 1.0 / price };
+    let effective_price = f64::from(quote.amount_out) / f64::from(quote.amount_in);
+
+    Ok(1.0 - effective_price / spot_price)
+}
+
+//Whether price_impact, as returned by get_price_impact, reaches warning_threshold, e.g.
+//PRICE_IMPACT_WARNING_THRESHOLD
+pub fn exceeds_price_impact_warning(price_impact: f64, warning_threshold: f64) -> bool {
+    price_impact >= warning_threshold
 }
 
 #[cfg(test)]
 mod test {
 
-    use crate::sqrt_price_math::{get_next_sqrt_price_from_input, get_next_sqrt_price_from_output};
-    use crate::swap_math::compute_swap_step;
+    use crate::error::UniswapV3MathError;
+    use crate::fixed_point::{FeeGrowthX128, SqrtPriceX96};
+    use crate::full_math::mul_div_rounding_up;
+    use crate::pool::PoolState;
+    use crate::sqrt_price_math::{
+        _get_amount_0_delta, _get_amount_1_delta, get_next_sqrt_price_from_input,
+        get_next_sqrt_price_from_output,
+    };
+    use crate::swap_math::{
+        accumulate_fee_growth, amount_in_to_reach_sqrt_price, amount_to_move_ticks,
+        compute_swap_step, compute_swap_step_struct, compute_swap_step_with_protocol_fee,
+        exceeds_price_impact_warning, get_lp_fee_amount, get_price_impact, get_protocol_fee_amount,
+        price_impact_bps, simulate_price_impact, SwapStep, MAX_SWAP_FEE,
+        PRICE_IMPACT_WARNING_THRESHOLD,
+    };
+    use crate::swap_simulation::HashMapTickDataProvider;
+    use crate::tick::TickInfo;
+    use crate::tick_bitmap::TickBitmap;
+    use crate::tick_math::{get_sqrt_ratio_at_tick, MAX_SQRT_RATIO, MIN_SQRT_RATIO};
     use crate::U256_1;
-    use alloy::primitives::{I256, U256};
+    use alloy_primitives::{I256, U256};
+    use proptest::prelude::*;
+    use std::collections::HashMap;
     use std::str::FromStr;
 
     #[allow(unused)]
@@ -171,8 +651,14 @@ mod test {
         let fee = 600;
         let zero_for_one = false;
 
-        let (sqrt_p, amount_in, amount_out, fee_amount) =
-            compute_swap_step(price, price_target, liquidity, amount, fee).unwrap();
+        let (sqrt_p, amount_in, amount_out, fee_amount) = compute_swap_step(
+            SqrtPriceX96(price),
+            SqrtPriceX96(price_target),
+            liquidity,
+            amount,
+            fee,
+        )
+        .unwrap();
 
         assert_eq!(
             sqrt_p,
@@ -186,7 +672,8 @@ mod test {
         assert!(amount_in + fee_amount < U256::from_limbs(*amount.as_limbs()));
 
         let price_after_whole_input_amount =
-            get_next_sqrt_price_from_input(price, liquidity, amount_in, zero_for_one).unwrap();
+            get_next_sqrt_price_from_input(SqrtPriceX96(price), liquidity, amount_in, zero_for_one)
+                .unwrap();
 
         assert_eq!(sqrt_p, price_target);
         assert!(sqrt_p < price_after_whole_input_amount);
@@ -201,8 +688,14 @@ mod test {
         let fee = 600;
         let zero_for_one = false;
 
-        let (sqrt_p, amount_in, amount_out, fee_amount) =
-            compute_swap_step(price, price_target, liquidity, amount, fee).unwrap();
+        let (sqrt_p, amount_in, amount_out, fee_amount) = compute_swap_step(
+            SqrtPriceX96(price),
+            SqrtPriceX96(price_target),
+            liquidity,
+            amount,
+            fee,
+        )
+        .unwrap();
 
         assert_eq!(amount_in, U256::from_str("9975124224178055").unwrap());
         assert_eq!(fee_amount, U256::from_str("5988667735148").unwrap());
@@ -212,7 +705,7 @@ mod test {
         assert!(amount_in + fee_amount < U256::from_limbs(*amount.as_limbs()));
 
         let price_after_whole_output_amount = get_next_sqrt_price_from_output(
-            price,
+            SqrtPriceX96(price),
             liquidity,
             (amount * -I256::ONE).into_raw(),
             zero_for_one,
@@ -232,8 +725,14 @@ mod test {
         let fee = 600;
         let zero_for_one = false;
 
-        let (sqrt_p, amount_in, amount_out, fee_amount) =
-            compute_swap_step(price, price_target, liquidity, amount, fee).unwrap();
+        let (sqrt_p, amount_in, amount_out, fee_amount) = compute_swap_step(
+            SqrtPriceX96(price),
+            SqrtPriceX96(price_target),
+            liquidity,
+            amount,
+            fee,
+        )
+        .unwrap();
 
         assert_eq!(amount_in, U256::from_str("999400000000000000").unwrap());
         assert_eq!(fee_amount, U256::from_str("600000000000000").unwrap());
@@ -241,7 +740,7 @@ mod test {
         assert_eq!(amount_in + fee_amount, amount.into_raw());
 
         let price_after_whole_input_amount_less_fee = get_next_sqrt_price_from_input(
-            price,
+            SqrtPriceX96(price),
             liquidity,
             (amount - I256::from_raw(fee_amount)).into_raw(),
             zero_for_one,
@@ -261,38 +760,36 @@ mod test {
         let fee = 600;
         let zero_for_one = false;
 
-        let (sqrt_p, amount_in, amount_out, fee_amount) =
-            compute_swap_step(price, price_target, liquidity, amount, fee).unwrap();
+        let (sqrt_p, amount_in, amount_out, fee_amount) = compute_swap_step(
+            SqrtPriceX96(price),
+            SqrtPriceX96(price_target),
+            liquidity,
+            amount,
+            fee,
+        )
+        .unwrap();
 
         assert_eq!(amount_in, U256::from_str("2000000000000000000").unwrap());
         assert_eq!(fee_amount, U256::from_str("1200720432259356").unwrap());
         assert_eq!(amount_out, (amount * -I256::ONE).into_raw());
 
         let price_after_whole_output_amount = get_next_sqrt_price_from_output(
-            price,
+            SqrtPriceX96(price),
             liquidity,
             (amount * -I256::ONE).into_raw(),
             zero_for_one,
         )
         .unwrap();
-        //sqrtPrice 158456325028528675187087900672
-        //price_after_whole_output_amount Should be: 158456325028528675187087900672
-        // sqrtp: 158456325028528675187087900672, price_after_whole output amount: 118842243771396506390315925504
 
         assert!(sqrt_p < price_target);
-        //TODO:FIXME: failing
-        println!(
-            "sqrtp: {:?}, price_after_whole output amount: {:?}",
-            sqrt_p, price_after_whole_output_amount
-        );
         assert_eq!(sqrt_p, price_after_whole_output_amount);
 
         //------------------------------------------------------------
 
         //amount out is capped at the desired amount out
         let (sqrt_p, amount_in, amount_out, fee_amount) = compute_swap_step(
-            U256::from_str("417332158212080721273783715441582").unwrap(),
-            U256::from_str("1452870262520218020823638996").unwrap(),
+            SqrtPriceX96(U256::from_str("417332158212080721273783715441582").unwrap()),
+            SqrtPriceX96(U256::from_str("1452870262520218020823638996").unwrap()),
             159344665391607089467575320103_u128,
             I256::from_str("-1").unwrap(),
             1,
@@ -311,8 +808,8 @@ mod test {
 
         //target price of 1 uses partial input amount
         let (sqrt_p, amount_in, amount_out, fee_amount) = compute_swap_step(
-            U256::from_str("2").unwrap(),
-            U256::from_str("1").unwrap(),
+            SqrtPriceX96(U256::from_str("2").unwrap()),
+            SqrtPriceX96(U256::from_str("1").unwrap()),
             1_u128,
             I256::from_str("3915081100057732413702495386755767").unwrap(),
             1,
@@ -338,8 +835,8 @@ mod test {
 
         //entire input amount taken as fee
         let (sqrt_p, amount_in, amount_out, fee_amount) = compute_swap_step(
-            U256::from_str("2413").unwrap(),
-            U256::from_str("79887613182836312").unwrap(),
+            SqrtPriceX96(U256::from_str("2413").unwrap()),
+            SqrtPriceX96(U256::from_str("79887613182836312").unwrap()),
             1985041575832132834610021537970_u128,
             I256::from_str("10").unwrap(),
             1872,
@@ -363,8 +860,14 @@ mod test {
         let amount_remaining = -I256::from_limbs(*U256::from(4).as_limbs());
         let fee = 3000;
 
-        let (sqrt_p, amount_in, amount_out, fee_amount) =
-            compute_swap_step(price, price_target, liquidity, amount_remaining, fee).unwrap();
+        let (sqrt_p, amount_in, amount_out, fee_amount) = compute_swap_step(
+            SqrtPriceX96(price),
+            SqrtPriceX96(price_target),
+            liquidity,
+            amount_remaining,
+            fee,
+        )
+        .unwrap();
 
         assert_eq!(amount_out, U256::ZERO);
         assert_eq!(sqrt_p, price_target);
@@ -384,12 +887,935 @@ mod test {
         let amount_remaining = -I256::from_limbs(*U256::from(263000).as_limbs());
         let fee = 3000;
 
-        let (sqrt_p, amount_in, amount_out, fee_amount) =
-            compute_swap_step(price, price_target, liquidity, amount_remaining, fee).unwrap();
+        let (sqrt_p, amount_in, amount_out, fee_amount) = compute_swap_step(
+            SqrtPriceX96(price),
+            SqrtPriceX96(price_target),
+            liquidity,
+            amount_remaining,
+            fee,
+        )
+        .unwrap();
 
         assert_eq!(amount_out, U256::from(26214));
         assert_eq!(sqrt_p, price_target);
         assert_eq!(amount_in, U256_1);
         assert_eq!(fee_amount, U256_1);
     }
+
+    #[test]
+    fn test_compute_swap_step_exact_output() {
+        let price = U256::from_str("79228162514264337593543950336").unwrap();
+        let liquidity = 1e18 as u128;
+        let amount_out_requested = U256::from(1e16 as u128);
+        let fee = 3000;
+
+        //zero for one exact output that can not be received before the first target
+        let price_target = get_sqrt_ratio_at_tick(-60).unwrap();
+        let step = compute_swap_step_struct(
+            SqrtPriceX96(price),
+            price_target,
+            liquidity,
+            -I256::from_raw(amount_out_requested),
+            fee,
+        )
+        .unwrap();
+        assert!(step.reached_target_price && !step.exact_in);
+        assert_eq!(step.sqrt_ratio_next_x_96, price_target);
+        assert!(step.amount_out < amount_out_requested);
+        assert_eq!(
+            step.amount_out,
+            _get_amount_1_delta(price_target, SqrtPriceX96(price), liquidity, false).unwrap()
+        );
+        assert_eq!(
+            step.amount_in,
+            _get_amount_0_delta(price_target, SqrtPriceX96(price), liquidity, true).unwrap()
+        );
+        //the fee is charged on top of the amount in, rounded up
+        assert_eq!(
+            step.fee_amount,
+            mul_div_rounding_up(
+                step.amount_in,
+                U256::from(fee),
+                U256::from(MAX_SWAP_FEE - fee)
+            )
+            .unwrap()
+        );
+
+        //the next step receives the rest of the amount out without reaching its target
+        let remaining = amount_out_requested - step.amount_out;
+        let next = compute_swap_step_struct(
+            step.sqrt_ratio_next_x_96,
+            get_sqrt_ratio_at_tick(-600).unwrap(),
+            liquidity,
+            -I256::from_raw(remaining),
+            fee,
+        )
+        .unwrap();
+        assert!(!next.reached_target_price);
+        assert_eq!(next.amount_out, remaining);
+        assert_eq!(
+            next.sqrt_ratio_next_x_96,
+            get_next_sqrt_price_from_output(step.sqrt_ratio_next_x_96, liquidity, remaining, true)
+                .unwrap()
+        );
+        assert_eq!(
+            next.amount_in,
+            _get_amount_0_delta(
+                next.sqrt_ratio_next_x_96,
+                step.sqrt_ratio_next_x_96,
+                liquidity,
+                true
+            )
+            .unwrap()
+        );
+        assert_eq!(
+            next.fee_amount,
+            mul_div_rounding_up(
+                next.amount_in,
+                U256::from(fee),
+                U256::from(MAX_SWAP_FEE - fee)
+            )
+            .unwrap()
+        );
+
+        //splitting the output over two steps costs at least as much as receiving it in one step
+        let single = compute_swap_step_struct(
+            SqrtPriceX96(price),
+            get_sqrt_ratio_at_tick(-600).unwrap(),
+            liquidity,
+            -I256::from_raw(amount_out_requested),
+            fee,
+        )
+        .unwrap();
+        assert_eq!(single.amount_out, amount_out_requested);
+        //the amount out of the first step is rounded down, so the two steps end at a price at most as high
+        assert!(next.sqrt_ratio_next_x_96 <= single.sqrt_ratio_next_x_96);
+        assert!(step.total_cost() + next.total_cost() >= single.total_cost());
+    }
+
+    #[test]
+    fn test_compute_swap_step_struct() {
+        let price = U256::from_str("79228162514264337593543950336").unwrap();
+        let price_target = U256::from_str("79623317895830914510639640423").unwrap();
+        let liquidity = 2e18 as u128;
+
+        //exact in capped at the target price
+        let step = compute_swap_step_struct(
+            SqrtPriceX96(price),
+            SqrtPriceX96(price_target),
+            liquidity,
+            I256::from_str("1000000000000000000").unwrap(),
+            600,
+        )
+        .unwrap();
+
+        assert_eq!(
+            step,
+            SwapStep {
+                sqrt_ratio_next_x_96: SqrtPriceX96(price_target),
+                amount_in: U256::from_str("9975124224178055").unwrap(),
+                amount_out: U256::from_str("9925619580021728").unwrap(),
+                fee_amount: U256::from_str("5988667735148").unwrap(),
+                reached_target_price: true,
+                exact_in: true,
+            }
+        );
+
+        //exact in capped by the amount
+        let step = compute_swap_step_struct(
+            SqrtPriceX96(price),
+            SqrtPriceX96(price_target),
+            liquidity,
+            I256::from_str("1000000000000").unwrap(),
+            600,
+        )
+        .unwrap();
+
+        assert!(!step.reached_target_price);
+        assert!(step.exact_in);
+        assert!(step.sqrt_ratio_next_x_96 < price_target);
+        assert_eq!(step.total_cost(), U256::from_str("1000000000000").unwrap());
+
+        //exact out capped at the target price
+        let step = compute_swap_step_struct(
+            SqrtPriceX96(price),
+            SqrtPriceX96(price_target),
+            liquidity,
+            I256::from_str("-1000000000000000000").unwrap(),
+            600,
+        )
+        .unwrap();
+
+        assert!(step.reached_target_price);
+        assert!(!step.exact_in);
+        assert_eq!(step.sqrt_ratio_next_x_96, price_target);
+        assert_eq!(step.amount_out, U256::from_str("9925619580021728").unwrap());
+
+        //exact out capped by the amount
+        let step = compute_swap_step_struct(
+            SqrtPriceX96(price),
+            SqrtPriceX96(price_target),
+            liquidity,
+            I256::from_str("-1000000000000").unwrap(),
+            600,
+        )
+        .unwrap();
+
+        assert!(!step.reached_target_price);
+        assert!(!step.exact_in);
+        assert!(step.sqrt_ratio_next_x_96 < price_target);
+        assert_eq!(step.amount_out, U256::from_str("1000000000000").unwrap());
+
+        //zero liquidity moves straight to the target without any amounts
+        for amount in [
+            I256::from_str("1000").unwrap(),
+            I256::from_str("-1000").unwrap(),
+        ] {
+            let step = compute_swap_step_struct(
+                SqrtPriceX96(price),
+                SqrtPriceX96(price_target),
+                0,
+                amount,
+                600,
+            )
+            .unwrap();
+
+            assert!(step.reached_target_price);
+            assert_eq!(step.sqrt_ratio_next_x_96, price_target);
+            assert_eq!(step.amount_in, U256::ZERO);
+            assert_eq!(step.amount_out, U256::ZERO);
+            assert_eq!(step.fee_amount, U256::ZERO);
+        }
+
+        //the tuple version returns the same values
+        let amount = I256::from_str("-1000000000000").unwrap();
+        let step = compute_swap_step_struct(
+            SqrtPriceX96(price),
+            SqrtPriceX96(price_target),
+            liquidity,
+            amount,
+            600,
+        )
+        .unwrap();
+        assert_eq!(
+            compute_swap_step(
+                SqrtPriceX96(price),
+                SqrtPriceX96(price_target),
+                liquidity,
+                amount,
+                600
+            )
+            .unwrap(),
+            (
+                step.sqrt_ratio_next_x_96,
+                step.amount_in,
+                step.amount_out,
+                step.fee_amount
+            )
+        );
+    }
+
+    #[test]
+    fn test_compute_swap_step_with_protocol_fee() {
+        let price = SqrtPriceX96(U256::from_str("79228162514264337593543950336").unwrap());
+        let price_target = SqrtPriceX96(U256::from_str("79623317895830914510639640423").unwrap());
+        let liquidity = 2e18 as u128;
+        let amount = I256::from_str("1000000000000000000").unwrap();
+
+        let step = compute_swap_step_struct(price, price_target, liquidity, amount, 600).unwrap();
+        // 5988667735148 is not a multiple of any of the denominators below
+        assert_eq!(step.fee_amount, U256::from_str("5988667735148").unwrap());
+
+        for (fee_protocol, protocol_fee, dust) in [
+            (5, "1197733547029", 3),
+            (6, "998111289191", 2),
+            (8, "748583466893", 4),
+            (10, "598866773514", 8),
+        ] {
+            let result = compute_swap_step_with_protocol_fee(
+                price,
+                price_target,
+                liquidity,
+                amount,
+                600,
+                fee_protocol,
+            )
+            .unwrap();
+
+            assert_eq!(result.step, step);
+            assert_eq!(result.protocol_fee, U256::from_str(protocol_fee).unwrap());
+            // the protocol fee is rounded down, the dust stays with the LPs
+            assert_eq!(result.protocol_fee + result.lp_fee, step.fee_amount);
+            assert_eq!(
+                result.lp_fee,
+                result.protocol_fee * U256::from(fee_protocol - 1) + U256::from(dust)
+            );
+        }
+
+        let result =
+            compute_swap_step_with_protocol_fee(price, price_target, liquidity, amount, 600, 0)
+                .unwrap();
+        assert_eq!(result.protocol_fee, U256::ZERO);
+        assert_eq!(result.lp_fee, step.fee_amount);
+
+        //a fee amount below the denominator goes entirely to the LPs
+        let result = compute_swap_step_with_protocol_fee(
+            SqrtPriceX96(U256::from_str("2413").unwrap()),
+            SqrtPriceX96(U256::from_str("79887613182836312").unwrap()),
+            1985041575832132834610021537970_u128,
+            I256::from_str("3").unwrap(),
+            1872,
+            4,
+        )
+        .unwrap();
+        assert_eq!(result.step.fee_amount, U256::from(3));
+        assert_eq!(result.protocol_fee, U256::ZERO);
+        assert_eq!(result.lp_fee, U256::from(3));
+
+        //denominators that UniswapV3Pool.setFeeProtocol rejects error instead of splitting the fee
+        for fee_protocol in [1, 2, 3, 11, 15, u8::MAX] {
+            assert!(matches!(
+                compute_swap_step_with_protocol_fee(
+                    price,
+                    price_target,
+                    liquidity,
+                    amount,
+                    600,
+                    fee_protocol
+                ),
+                Err(UniswapV3MathError::InvalidFeeProtocol { fee_protocol: value }) if value == fee_protocol
+            ));
+        }
+    }
+
+    #[test]
+    fn test_get_protocol_fee_amount() {
+        for swap_fee in [0_u64, 3, 99, 5988667735148, u64::MAX] {
+            let swap_fee = U256::from(swap_fee);
+            for protocol_fee_numerator in [0, 4, 5, 6, 7, 8, 9, 10] {
+                let protocol_fee =
+                    get_protocol_fee_amount(swap_fee, protocol_fee_numerator).unwrap();
+                let lp_fee = get_lp_fee_amount(swap_fee, protocol_fee_numerator).unwrap();
+                assert_eq!(protocol_fee + lp_fee, swap_fee);
+            }
+        }
+
+        let swap_fee = U256::from_str("5988667735148").unwrap();
+        assert_eq!(get_protocol_fee_amount(swap_fee, 0).unwrap(), U256::ZERO);
+        assert_eq!(
+            get_protocol_fee_amount(swap_fee, 6).unwrap(),
+            U256::from_str("998111289191").unwrap()
+        );
+        assert_eq!(
+            get_lp_fee_amount(swap_fee, 6).unwrap(),
+            U256::from_str("4990556445957").unwrap()
+        );
+
+        for protocol_fee_numerator in [1, 3, 11, 255] {
+            assert!(matches!(
+                get_protocol_fee_amount(swap_fee, protocol_fee_numerator),
+                Err(UniswapV3MathError::InvalidFeeProtocol { fee_protocol })
+                    if fee_protocol == protocol_fee_numerator
+            ));
+            assert!(get_lp_fee_amount(swap_fee, protocol_fee_numerator).is_err());
+        }
+    }
+
+    #[test]
+    fn test_compute_swap_step_fee_bounds() {
+        let price = U256::from_str("79228162514264337593543950336").unwrap();
+        let price_target = SqrtPriceX96(U256::from_str("79623317895830914510639640423").unwrap());
+        let liquidity = 2e18 as u128;
+        let exact_in = I256::from_str("1000000000000").unwrap();
+        let exact_out = -exact_in;
+
+        //no fee
+        let step =
+            compute_swap_step_struct(SqrtPriceX96(price), price_target, liquidity, exact_in, 0)
+                .unwrap();
+        assert_eq!(step.fee_amount, U256::ZERO);
+        assert_eq!(step.amount_in, exact_in.into_raw());
+
+        let step =
+            compute_swap_step_struct(SqrtPriceX96(price), price_target, liquidity, exact_out, 0)
+                .unwrap();
+        assert_eq!(step.fee_amount, U256::ZERO);
+        assert_eq!(step.amount_out, exact_out.unsigned_abs());
+
+        //99.9999% fee
+        let step = compute_swap_step_struct(
+            SqrtPriceX96(price),
+            price_target,
+            liquidity,
+            exact_in,
+            999_999,
+        )
+        .unwrap();
+        assert_eq!(step.amount_in, U256::from(1000000));
+        assert_eq!(step.fee_amount, U256::from(999999000000_u64));
+        assert!(!step.reached_target_price);
+
+        let step = compute_swap_step_struct(
+            SqrtPriceX96(price),
+            price_target,
+            liquidity,
+            exact_out,
+            999_999,
+        )
+        .unwrap();
+        assert_eq!(step.amount_out, exact_out.unsigned_abs());
+        assert_eq!(step.fee_amount, step.amount_in * U256::from(999_999));
+
+        //100% fee consumes the whole input as fee without moving the price
+        let step = compute_swap_step_struct(
+            SqrtPriceX96(price),
+            price_target,
+            liquidity,
+            exact_in,
+            MAX_SWAP_FEE,
+        )
+        .unwrap();
+        assert_eq!(step.sqrt_ratio_next_x_96, price);
+        assert_eq!(step.amount_in, U256::ZERO);
+        assert_eq!(step.amount_out, U256::ZERO);
+        assert_eq!(step.fee_amount, exact_in.into_raw());
+
+        //100% fee with the target already reached
+        let step = compute_swap_step_struct(
+            SqrtPriceX96(price),
+            SqrtPriceX96(price),
+            liquidity,
+            exact_in,
+            MAX_SWAP_FEE,
+        )
+        .unwrap();
+        assert!(step.reached_target_price);
+        assert_eq!(step.amount_in, U256::ZERO);
+        assert_eq!(step.fee_amount, U256::ZERO);
+
+        assert!(matches!(
+            compute_swap_step_struct(
+                SqrtPriceX96(price),
+                price_target,
+                liquidity,
+                exact_out,
+                MAX_SWAP_FEE
+            ),
+            Err(UniswapV3MathError::InvalidFeePips {
+                fee_pips: MAX_SWAP_FEE
+            })
+        ));
+        assert!(matches!(
+            compute_swap_step_struct(
+                SqrtPriceX96(price),
+                price_target,
+                liquidity,
+                exact_in,
+                MAX_SWAP_FEE + 1
+            ),
+            Err(UniswapV3MathError::InvalidFeePips { .. })
+        ));
+    }
+
+    #[test]
+    fn test_compute_swap_step_min_amount_remaining() {
+        //int256 min is an exact output of 2^255, as uint256(-amountRemaining) wraps in SwapMath
+        let price = SqrtPriceX96(U256::from_str("79228162514264337593543950336").unwrap());
+        let price_target = SqrtPriceX96(U256::from_str("79623317895830914510639640423").unwrap());
+        let liquidity = 2e18 as u128;
+
+        let step =
+            compute_swap_step_struct(price, price_target, liquidity, I256::MIN, 3000).unwrap();
+        assert!(step.reached_target_price);
+        assert_eq!(
+            step.amount_out,
+            _get_amount_0_delta(price, price_target, liquidity, false).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_amount_in_to_reach_sqrt_price() {
+        let price = U256::from_str("79228162514264337593543950336").unwrap();
+
+        for (target, liquidity, fee, zero_for_one) in [
+            (
+                U256::from_str("79623317895830914510639640423").unwrap(),
+                2e18 as u128,
+                600,
+                false,
+            ),
+            (
+                U256::from_str("78833030112140176575862854579").unwrap(),
+                2e18 as u128,
+                3000,
+                true,
+            ),
+            (
+                U256::from_str("79228162514264337593543950337").unwrap(),
+                1_u128,
+                10000,
+                false,
+            ),
+            (
+                U256::from_str("1461446703485210103287273052203988822378723970341").unwrap(),
+                159344665391607089467575320103_u128,
+                0,
+                false,
+            ),
+            (
+                U256::from_str("4295128740").unwrap(),
+                1e24 as u128,
+                500,
+                true,
+            ),
+        ] {
+            let (amount_in, fee_amount) = amount_in_to_reach_sqrt_price(
+                SqrtPriceX96(price),
+                SqrtPriceX96(target),
+                liquidity,
+                fee,
+                zero_for_one,
+            )
+            .unwrap();
+
+            //the returned amount reaches the target and consumes exactly the fee inclusive amount
+            let step = compute_swap_step_struct(
+                SqrtPriceX96(price),
+                SqrtPriceX96(target),
+                liquidity,
+                I256::from_raw(amount_in),
+                fee,
+            )
+            .unwrap();
+            assert!(step.reached_target_price);
+            assert_eq!(step.amount_in + step.fee_amount, amount_in);
+            assert_eq!(step.fee_amount, fee_amount);
+
+            //one less falls short of the target
+            let step = compute_swap_step_struct(
+                SqrtPriceX96(price),
+                SqrtPriceX96(target),
+                liquidity,
+                I256::from_raw(amount_in - U256_1),
+                fee,
+            )
+            .unwrap();
+            assert!(!step.reached_target_price);
+        }
+
+        //no input is needed when already at the target
+        assert_eq!(
+            amount_in_to_reach_sqrt_price(
+                SqrtPriceX96(price),
+                SqrtPriceX96(price),
+                2e18 as u128,
+                3000,
+                true
+            )
+            .unwrap(),
+            (U256::ZERO, U256::ZERO)
+        );
+
+        assert!(matches!(
+            amount_in_to_reach_sqrt_price(
+                SqrtPriceX96(price),
+                SqrtPriceX96(price + U256_1),
+                2e18 as u128,
+                3000,
+                true
+            ),
+            Err(UniswapV3MathError::InvalidSqrtPriceTarget { .. })
+        ));
+        assert!(matches!(
+            amount_in_to_reach_sqrt_price(
+                SqrtPriceX96(price),
+                SqrtPriceX96(price - U256_1),
+                2e18 as u128,
+                3000,
+                false
+            ),
+            Err(UniswapV3MathError::InvalidSqrtPriceTarget { .. })
+        ));
+        assert!(matches!(
+            amount_in_to_reach_sqrt_price(
+                SqrtPriceX96(price),
+                SqrtPriceX96(price - U256_1),
+                2e18 as u128,
+                MAX_SWAP_FEE,
+                true
+            ),
+            Err(UniswapV3MathError::InvalidFeePips { .. })
+        ));
+    }
+
+    fn init_test_ticks() -> eyre::Result<(TickBitmap, HashMap<i32, TickInfo>)> {
+        let mut tick_bitmap = TickBitmap::new();
+        let mut ticks = HashMap::new();
+        for (tick, liquidity_net) in [(-600, 2e18 as i128), (600, -2e18 as i128)] {
+            tick_bitmap.flip_tick(tick, 60)?;
+            ticks.insert(
+                tick,
+                TickInfo {
+                    liquidity_gross: liquidity_net.unsigned_abs(),
+                    liquidity_net,
+                    initialized: true,
+                    ..Default::default()
+                },
+            );
+        }
+
+        Ok((tick_bitmap, ticks))
+    }
+
+    #[test]
+    fn test_amount_to_move_ticks() -> eyre::Result<()> {
+        let (tick_bitmap, ticks) = init_test_ticks()?;
+        let provider = HashMapTickDataProvider {
+            tick_bitmap: &tick_bitmap,
+            ticks: &ticks,
+        };
+        let price = get_sqrt_ratio_at_tick(0)?;
+
+        //moving within the range is a single step
+        let result = amount_to_move_ticks(price, 0, 2e18 as u128, 60, 3000, -50, &provider)?;
+        let target = get_sqrt_ratio_at_tick(-50)?;
+        let (amount_in, fee_amount) =
+            amount_in_to_reach_sqrt_price(price, target, 2e18 as u128, 3000, true)?;
+        assert_eq!(result.sqrt_price_x_96, target);
+        assert_eq!(result.tick, -50);
+        assert_eq!(result.steps.len(), 1);
+        assert_eq!(result.amount_in, amount_in);
+        assert_eq!(result.fee_amount, fee_amount);
+        assert_eq!(
+            result.amount_out,
+            compute_swap_step(price, target, 2e18 as u128, I256::MAX, 3000)?.2
+        );
+
+        //swapping the returned amount in reaches the target price
+        let step = compute_swap_step_struct(
+            price,
+            target,
+            2e18 as u128,
+            I256::from_raw(result.amount_in),
+            3000,
+        )?;
+        assert_eq!(step.sqrt_ratio_next_x_96, target);
+        assert!(step.reached_target_price);
+
+        //moving to the edge of the range crosses it and leaves the pool without liquidity
+        let result = amount_to_move_ticks(price, 0, 2e18 as u128, 60, 3000, 600, &provider)?;
+        assert_eq!(result.sqrt_price_x_96, get_sqrt_ratio_at_tick(600)?);
+        assert_eq!(result.tick, 600);
+        assert_eq!(result.liquidity, 0);
+        assert!(result
+            .steps
+            .iter()
+            .all(|step| step.liquidity == 2e18 as u128));
+        assert_eq!(
+            result.amount_in,
+            result.steps.iter().map(|step| step.amount_in).sum::<U256>()
+        );
+
+        //no move
+        let result = amount_to_move_ticks(price, 0, 2e18 as u128, 60, 3000, 0, &provider)?;
+        assert!(result.steps.is_empty());
+        assert_eq!(result.amount_in, U256::ZERO);
+
+        //beyond the range there is no liquidity left
+        assert!(matches!(
+            amount_to_move_ticks(price, 0, 2e18 as u128, 60, 3000, -1000, &provider),
+            Err(UniswapV3MathError::LiquidityIsZero)
+        ));
+
+        //target tick out of bounds
+        assert!(matches!(
+            amount_to_move_ticks(price, 0, 2e18 as u128, 60, 3000, 887273, &provider),
+            Err(UniswapV3MathError::TickOutOfBounds { tick: 887273 })
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_price_impact_bps() -> eyre::Result<()> {
+        let price = SqrtPriceX96(U256::from(100) << 96);
+
+        assert_eq!(price_impact_bps(price, price)?, 0);
+        //any movement is rounded up to 1 bps
+        assert_eq!(price_impact_bps(price, SqrtPriceX96(*price - U256_1))?, 1);
+        assert_eq!(price_impact_bps(price, SqrtPriceX96(*price + U256_1))?, 1);
+        //the price moves with the square of the sqrt price
+        assert_eq!(
+            price_impact_bps(
+                price,
+                SqrtPriceX96(*price * U256::from(101) / U256::from(100))
+            )?,
+            201
+        );
+        assert_eq!(
+            price_impact_bps(
+                price,
+                SqrtPriceX96(*price * U256::from(99) / U256::from(100))
+            )?,
+            199
+        );
+        assert_eq!(price_impact_bps(price, SqrtPriceX96(U256::MAX))?, u32::MAX);
+        assert!(matches!(
+            price_impact_bps(SqrtPriceX96(U256::ZERO), price),
+            Err(UniswapV3MathError::SqrtPriceIsZero)
+        ));
+
+        Ok(())
+    }
+
+    #[test]
+    fn test_simulate_price_impact() -> eyre::Result<()> {
+        let mut tick_bitmap = TickBitmap::new();
+        let mut ticks = HashMap::new();
+        for (tick_lower, tick_upper, liquidity) in
+            [(-887220, 887220, 1e18 as i128), (-600, 600, 2e18 as i128)]
+        {
+            for (tick, liquidity_net) in [(tick_lower, liquidity), (tick_upper, -liquidity)] {
+                tick_bitmap.flip_tick(tick, 60)?;
+                ticks.insert(
+                    tick,
+                    TickInfo {
+                        liquidity_gross: liquidity as u128,
+                        liquidity_net,
+                        initialized: true,
+                        ..Default::default()
+                    },
+                );
+            }
+        }
+        let provider = HashMapTickDataProvider {
+            tick_bitmap: &tick_bitmap,
+            ticks: &ticks,
+        };
+        let pool_state = PoolState {
+            sqrt_price_x_96: get_sqrt_ratio_at_tick(0)?,
+            liquidity: 3e18 as u128,
+            tick: 0,
+            fee: 500,
+            tick_spacing: 60,
+            ..Default::default()
+        };
+
+        //a trade that is taken entirely as fee does not move the price
+        let impact = simulate_price_impact(&pool_state, &provider, U256_1, true)?;
+        assert_eq!(impact.amount_out, U256::ZERO);
+        assert_eq!(impact.price_movement_bps, 0);
+        assert_eq!(impact.price_impact_bps, 10_000);
+
+        //a tiny trade pays the fee and rounds the slippage up to the next bps
+        let impact = simulate_price_impact(&pool_state, &provider, U256::from(1e6), false)?;
+        assert_eq!(impact.amount_in, U256::from(1e6));
+        assert_eq!(impact.price_movement_bps, 1);
+        assert_eq!(impact.price_impact_bps, 6);
+
+        //crossing the lower tick of the concentrated position moves the price past tick -600
+        let impact = simulate_price_impact(&pool_state, &provider, U256::from(1e18), true)?;
+        assert!(impact.sqrt_price_after_x_96 < get_sqrt_ratio_at_tick(-600)?);
+        assert!(
+            impact.price_movement_bps
+                > price_impact_bps(pool_state.sqrt_price_x_96, get_sqrt_ratio_at_tick(-600)?)?
+        );
+        //the execution price lies between the mid prices before and after the swap
+        assert!(impact.price_impact_bps > 5);
+        assert!(impact.price_impact_bps < impact.price_movement_bps);
+
+        //no liquidity anywhere
+        let empty_bitmap = TickBitmap::new();
+        let empty_ticks = HashMap::new();
+        let empty_provider = HashMapTickDataProvider {
+            tick_bitmap: &empty_bitmap,
+            ticks: &empty_ticks,
+        };
+        let empty_pool = PoolState {
+            liquidity: 0,
+            ..pool_state
+        };
+        assert!(matches!(
+            simulate_price_impact(&empty_pool, &empty_provider, U256::from(1e18), true),
+            Err(UniswapV3MathError::LiquidityIsZero)
+        ));
+
+        Ok(())
+    }
+
+    // full range pool at price 1 holding liquidity
+    fn full_range_pool(
+        liquidity: u128,
+        fee: u32,
+    ) -> eyre::Result<(PoolState, TickBitmap, HashMap<i32, TickInfo>)> {
+        let mut tick_bitmap = TickBitmap::new();
+        let mut ticks = HashMap::new();
+        for (tick, liquidity_net) in [(-887220, liquidity as i128), (887220, -(liquidity as i128))]
+        {
+            tick_bitmap.flip_tick(tick, 60)?;
+            ticks.insert(
+                tick,
+                TickInfo {
+                    liquidity_gross: liquidity,
+                    liquidity_net,
+                    initialized: true,
+                    ..Default::default()
+                },
+            );
+        }
+        let pool_state = PoolState {
+            sqrt_price_x_96: get_sqrt_ratio_at_tick(0)?,
+            liquidity,
+            tick: 0,
+            fee,
+            tick_spacing: 60,
+            ..Default::default()
+        };
+
+        Ok((pool_state, tick_bitmap, ticks))
+    }
+
+    #[test]
+    fn test_get_price_impact() -> eyre::Result<()> {
+        let liquidity = 1e21 as u128;
+
+        //a small trade in a fee free pool has next to no impact, in a 0.05% pool about the fee
+        let (pool, tick_bitmap, ticks) = full_range_pool(liquidity, 0)?;
+        for zero_for_one in [true, false] {
+            let impact =
+                get_price_impact(&pool, &tick_bitmap, &ticks, U256::from(1e12), zero_for_one)?;
+            assert!(impact.abs() < 1e-8);
+        }
+        let (pool, tick_bitmap, ticks) = full_range_pool(liquidity, 500)?;
+        let impact = get_price_impact(&pool, &tick_bitmap, &ticks, U256::from(1e12), true)?;
+        assert!((impact - 0.0005).abs() < 1e-8);
+        assert!(!exceeds_price_impact_warning(
+            impact,
+            PRICE_IMPACT_WARNING_THRESHOLD
+        ));
+
+        //a full range pool quotes x / (L + x) for x of the input at price 1, a tenth of the liquidity costs 1/11
+        let (pool, tick_bitmap, ticks) = full_range_pool(liquidity, 0)?;
+        let impact = get_price_impact(&pool, &tick_bitmap, &ticks, U256::from(1e20), true)?;
+        assert!((impact - 1.0 / 11.0).abs() < 1e-9);
+        assert!(exceeds_price_impact_warning(
+            impact,
+            PRICE_IMPACT_WARNING_THRESHOLD
+        ));
+
+        //the impact depends on the trade size relative to the pool depth
+        let (deep_pool, deep_tick_bitmap, deep_ticks) = full_range_pool(10 * liquidity, 0)?;
+        let deep_impact = get_price_impact(
+            &deep_pool,
+            &deep_tick_bitmap,
+            &deep_ticks,
+            U256::from(1e20),
+            true,
+        )?;
+        assert!(deep_impact < impact / 9.0);
+        let scaled_impact = get_price_impact(
+            &deep_pool,
+            &deep_tick_bitmap,
+            &deep_ticks,
+            U256::from(1e21),
+            true,
+        )?;
+        assert!((scaled_impact - impact).abs() < 1e-9);
+
+        let empty_pool = PoolState {
+            liquidity: 0,
+            ..pool
+        };
+        assert!(matches!(
+            get_price_impact(
+                &empty_pool,
+                &TickBitmap::new(),
+                &HashMap::new(),
+                U256::from(1e18),
+                true
+            ),
+            Err(UniswapV3MathError::LiquidityIsZero)
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_accumulate_fee_growth() {
+        let q128 = U256_1 << 128;
+        assert_eq!(
+            accumulate_fee_growth(FeeGrowthX128(U256::ZERO), U256::from(6), 3).unwrap(),
+            q128 * U256::from(2)
+        );
+        // wraps on overflow like the pool
+        assert_eq!(
+            accumulate_fee_growth(FeeGrowthX128(U256::MAX), U256::from(1), 1).unwrap(),
+            q128 - U256_1
+        );
+        assert!(matches!(
+            accumulate_fee_growth(FeeGrowthX128(U256::ZERO), U256::from(1), 0),
+            Err(UniswapV3MathError::LiquidityIsZero)
+        ));
+
+        // many small steps trail the aggregated fee growth by less than one unit per step
+        let liquidity = 3e18 as u128;
+        let steps = 1000;
+        let mut fee_growth = U256::MAX - U256::from(10);
+        let mut total_fees = U256::ZERO;
+        for i in 0..steps {
+            let fee_amount = U256::from(1_000_003 + i * 17);
+            fee_growth =
+                *accumulate_fee_growth(FeeGrowthX128(fee_growth), fee_amount, liquidity).unwrap();
+            total_fees += fee_amount;
+        }
+        let aggregated = accumulate_fee_growth(
+            FeeGrowthX128(U256::MAX - U256::from(10)),
+            total_fees,
+            liquidity,
+        )
+        .unwrap();
+        let loss = aggregated.wrapping_sub(FeeGrowthX128(fee_growth));
+        assert!(loss < U256::from(steps), "{loss}");
+        assert!(loss > U256::ZERO);
+    }
+
+    // sqrt ratios in [MIN_SQRT_RATIO, MAX_SQRT_RATIO) with a uniformly drawn bit length, as in the tick math tests
+    fn sqrt_ratio_strategy() -> impl Strategy<Value = U256> {
+        (33..=160_usize, any::<[u64; 3]>())
+            .prop_map(|(bits, limbs)| {
+                let low_bits = U256::from_limbs([limbs[0], limbs[1], limbs[2], 0]) >> (193 - bits);
+                (U256_1 << (bits - 1)) | low_bits
+            })
+            .prop_filter("outside the sqrt ratio range", |sqrt_ratio| {
+                (MIN_SQRT_RATIO..MAX_SQRT_RATIO).contains(sqrt_ratio)
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn test_compute_swap_step_exact_in_within_amount_remaining(
+            sqrt_ratio_current_x_96 in sqrt_ratio_strategy(),
+            sqrt_ratio_target_x_96 in sqrt_ratio_strategy(),
+            liquidity in 1..=u128::MAX,
+            amount_remaining in 1..=u128::MAX,
+            fee_pips in 0..MAX_SWAP_FEE,
+        ) {
+            let amount_remaining = U256::from(amount_remaining);
+            let (sqrt_ratio_next_x_96, amount_in, _, fee_amount) = compute_swap_step(
+                SqrtPriceX96(sqrt_ratio_current_x_96),
+                SqrtPriceX96(sqrt_ratio_target_x_96),
+                liquidity,
+                I256::from_raw(amount_remaining),
+                fee_pips,
+            )
+            .unwrap();
+
+            prop_assert!(amount_in + fee_amount <= amount_remaining);
+            // the price moves towards the target without passing it
+            if sqrt_ratio_current_x_96 >= sqrt_ratio_target_x_96 {
+                prop_assert!((sqrt_ratio_target_x_96..=sqrt_ratio_current_x_96).contains(&sqrt_ratio_next_x_96));
+            } else {
+                prop_assert!((sqrt_ratio_current_x_96..=sqrt_ratio_target_x_96).contains(&sqrt_ratio_next_x_96));
+            }
+        }
+    }
 }
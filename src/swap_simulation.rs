@@ -0,0 +1,864 @@
+#[cfg(feature = "std")]
+use std::collections::HashMap;
+
+use alloy_primitives::I256;
+#[cfg(any(feature = "std", feature = "async"))]
+use alloy_primitives::U256;
+
+#[cfg(any(feature = "std", feature = "async"))]
+use crate::tick::TickInfo;
+#[cfg(feature = "async")]
+use crate::tick_bitmap::{next_initialized_tick_within_word, next_initialized_tick_word_pos};
+use crate::{
+    error::UniswapV3MathError,
+    fixed_point::{FeeGrowthX128, SqrtPriceX96},
+    liquidity_math,
+    swap_math::{accumulate_fee_growth, compute_swap_step_with_protocol_fee},
+    tick_math::{
+        get_sqrt_ratio_at_tick, get_tick_at_sqrt_ratio, MAX_SQRT_RATIO, MAX_TICK, MIN_SQRT_RATIO,
+        MIN_TICK,
+    },
+};
+#[cfg(feature = "std")]
+use crate::{tick::cross_tick, tick_bitmap::TickBitmap};
+
+pub use crate::fixed_point::Q128;
+
+//Source of the initialized ticks a swap walks through
+pub trait TickDataProvider {
+    //Same semantics as tick_bitmap::next_initialized_tick_within_one_word
+    fn next_initialized_tick_within_one_word(
+        &self,
+        tick: i32,
+        tick_spacing: i32,
+        lte: bool,
+    ) -> Result<(i32, bool), UniswapV3MathError>;
+
+    //Net liquidity added when the tick is crossed left to right, 0 for uninitialized ticks
+    fn liquidity_net(&self, tick: i32) -> Result<i128, UniswapV3MathError>;
+}
+
+//Tick data that is fetched lazily, e.g. over RPC. word returns the tick bitmap word at word_pos and tick the
+//info of an initialized tick
+#[cfg(feature = "async")]
+pub trait AsyncTickDataProvider {
+    fn word(
+        &self,
+        word_pos: i16,
+    ) -> impl core::future::Future<Output = Result<U256, UniswapV3MathError>> + Send;
+
+    fn tick(
+        &self,
+        tick: i32,
+    ) -> impl core::future::Future<Output = Result<TickInfo, UniswapV3MathError>> + Send;
+}
+
+//Tick data held in memory as a tick bitmap and a map of tick infos
+#[cfg(feature = "std")]
+#[derive(Debug, Clone, Copy)]
+pub struct HashMapTickDataProvider<'a> {
+    pub tick_bitmap: &'a TickBitmap,
+    pub ticks: &'a HashMap<i32, TickInfo>,
+}
+
+#[cfg(feature = "std")]
+impl TickDataProvider for HashMapTickDataProvider<'_> {
+    fn next_initialized_tick_within_one_word(
+        &self,
+        tick: i32,
+        tick_spacing: i32,
+        lte: bool,
+    ) -> Result<(i32, bool), UniswapV3MathError> {
+        self.tick_bitmap
+            .next_initialized_tick_within_one_word(tick, tick_spacing, lte)
+    }
+
+    fn liquidity_net(&self, tick: i32) -> Result<i128, UniswapV3MathError> {
+        Ok(self
+            .ticks
+            .get(&tick)
+            .map(|info| info.liquidity_net)
+            .unwrap_or_default())
+    }
+}
+
+//Outcome of a simulated swap, amounts are the signed pool deltas where positive amounts are paid into the pool.
+//fee_growth_x_128 is the fee growth per unit of liquidity accrued in the input token, to be wrapping added to
+//the fee growth global of that token, and protocol_fee the protocol share of the input token fees
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SwapResult {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::i256_hex"))]
+    pub amount_0: I256,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::i256_hex"))]
+    pub amount_1: I256,
+    pub sqrt_price_x_96: SqrtPriceX96,
+    pub tick: i32,
+    pub liquidity: u128,
+    pub ticks_crossed: u32,
+    pub fee_growth_x_128: FeeGrowthX128,
+    pub protocol_fee: u128,
+}
+
+//Runs the UniswapV3Pool.swap step loop from the given pool state. The direction is zero for one when the
+//sqrt price limit is below the current sqrt price
+#[allow(clippy::too_many_arguments)]
+pub fn simulate_swap<P: TickDataProvider>(
+    provider: &P,
+    sqrt_price_x_96: SqrtPriceX96,
+    tick: i32,
+    liquidity: u128,
+    tick_spacing: i32,
+    fee_pips: u32,
+    amount_specified: I256,
+    sqrt_price_limit_x_96: SqrtPriceX96,
+) -> Result<SwapResult, UniswapV3MathError> {
+    simulate_swap_with_protocol_fee(
+        provider,
+        sqrt_price_x_96,
+        tick,
+        liquidity,
+        tick_spacing,
+        fee_pips,
+        0,
+        amount_specified,
+        sqrt_price_limit_x_96,
+    )
+}
+
+//Same as simulate_swap for a pool with a protocol fee. fee_protocol is the packed slot0.feeProtocol, the token0
+//denominator in the lower and the token1 denominator in the upper 4 bits. Every step fee is split with
+//compute_swap_step_with_protocol_fee, the protocol share is summed up in SwapResult::protocol_fee and only the
+//LP share accrues to fee_growth_x_128
+#[allow(clippy::too_many_arguments)]
+pub fn simulate_swap_with_protocol_fee<P: TickDataProvider>(
+    provider: &P,
+    sqrt_price_x_96: SqrtPriceX96,
+    tick: i32,
+    liquidity: u128,
+    tick_spacing: i32,
+    fee_pips: u32,
+    fee_protocol: u8,
+    amount_specified: I256,
+    sqrt_price_limit_x_96: SqrtPriceX96,
+) -> Result<SwapResult, UniswapV3MathError> {
+    let mut swap = SwapLoop::new(
+        sqrt_price_x_96,
+        tick,
+        liquidity,
+        amount_specified,
+        sqrt_price_limit_x_96,
+        fee_protocol,
+    )?;
+
+    while !swap.is_done() {
+        let (tick_next, initialized) = provider.next_initialized_tick_within_one_word(
+            swap.state.tick,
+            tick_spacing,
+            swap.zero_for_one,
+        )?;
+
+        if swap.step(tick_next, fee_pips)? && initialized {
+            swap.cross(provider.liquidity_net(tick_next)?)?;
+        }
+    }
+
+    Ok(swap.result())
+}
+
+// same as simulate_swap_with_protocol_fee, but flips the fee growth outside of every initialized tick crossed like
+// UniswapV3Pool.swap does. The fee growth globals are the pool's before the swap, the oracle values of the
+// crossed ticks are left at 0 as the pool state does not track them. The crossed ticks are restored on errors
+#[cfg(feature = "std")]
+#[allow(clippy::too_many_arguments)]
+pub(crate) fn simulate_swap_crossing_ticks(
+    tick_bitmap: &TickBitmap,
+    ticks: &mut HashMap<i32, TickInfo>,
+    sqrt_price_x_96: SqrtPriceX96,
+    tick: i32,
+    liquidity: u128,
+    tick_spacing: i32,
+    fee_pips: u32,
+    fee_protocol: u8,
+    amount_specified: I256,
+    sqrt_price_limit_x_96: SqrtPriceX96,
+    fee_growth_global_0_x_128: FeeGrowthX128,
+    fee_growth_global_1_x_128: FeeGrowthX128,
+) -> Result<SwapResult, UniswapV3MathError> {
+    let mut swap = SwapLoop::new(
+        sqrt_price_x_96,
+        tick,
+        liquidity,
+        amount_specified,
+        sqrt_price_limit_x_96,
+        fee_protocol,
+    )?;
+    let mut crossed = Vec::new();
+
+    let mut run = || -> Result<(), UniswapV3MathError> {
+        while !swap.is_done() {
+            let (tick_next, initialized) = tick_bitmap.next_initialized_tick_within_one_word(
+                swap.state.tick,
+                tick_spacing,
+                swap.zero_for_one,
+            )?;
+
+            if swap.step(tick_next, fee_pips)? && initialized {
+                // the swap accrues fees in the input token only
+                let (fee_growth_0, fee_growth_1) = if swap.zero_for_one {
+                    (
+                        fee_growth_global_0_x_128.wrapping_add(swap.state.fee_growth_global_x_128),
+                        fee_growth_global_1_x_128,
+                    )
+                } else {
+                    (
+                        fee_growth_global_0_x_128,
+                        fee_growth_global_1_x_128.wrapping_add(swap.state.fee_growth_global_x_128),
+                    )
+                };
+                let liquidity_net = match ticks.get_mut(&tick_next) {
+                    Some(info) => {
+                        crossed.push((tick_next, *info));
+                        cross_tick(info, fee_growth_0, fee_growth_1, U256::ZERO, 0, 0)
+                    }
+                    None => 0,
+                };
+                swap.cross(liquidity_net)?;
+            }
+        }
+        Ok(())
+    };
+
+    match run() {
+        Ok(()) => Ok(swap.result()),
+        Err(error) => {
+            for (tick, info) in crossed.into_iter().rev() {
+                ticks.insert(tick, info);
+            }
+            Err(error)
+        }
+    }
+}
+
+//Same as simulate_swap, the bitmap word is only fetched when the loop moves to a new word and tick infos only
+//when an initialized tick is crossed
+#[cfg(feature = "async")]
+#[allow(clippy::too_many_arguments)]
+pub async fn simulate_swap_async<P: AsyncTickDataProvider>(
+    provider: &P,
+    sqrt_price_x_96: SqrtPriceX96,
+    tick: i32,
+    liquidity: u128,
+    tick_spacing: i32,
+    fee_pips: u32,
+    amount_specified: I256,
+    sqrt_price_limit_x_96: SqrtPriceX96,
+) -> Result<SwapResult, UniswapV3MathError> {
+    let mut swap = SwapLoop::new(
+        sqrt_price_x_96,
+        tick,
+        liquidity,
+        amount_specified,
+        sqrt_price_limit_x_96,
+        0,
+    )?;
+    let mut current_word: Option<(i16, U256)> = None;
+
+    while !swap.is_done() {
+        let word_pos =
+            next_initialized_tick_word_pos(swap.state.tick, tick_spacing, swap.zero_for_one)?;
+        let word = match current_word {
+            Some((current_word_pos, word)) if current_word_pos == word_pos => word,
+            _ => {
+                let word = provider.word(word_pos).await?;
+                current_word = Some((word_pos, word));
+                word
+            }
+        };
+
+        let (tick_next, initialized) = next_initialized_tick_within_word(
+            word,
+            swap.state.tick,
+            tick_spacing,
+            swap.zero_for_one,
+        )?;
+
+        if swap.step(tick_next, fee_pips)? && initialized {
+            swap.cross(provider.tick(tick_next).await?.liquidity_net)?;
+        }
+    }
+
+    Ok(swap.result())
+}
+
+//Top level state of the swap loop, as SwapState in UniswapV3Pool.swap. The simulations start
+//fee_growth_global_x_128 at 0 so that it holds the fee growth of the swap alone, protocol_fee is the protocol
+//share of the input token fees taken before the fee growth is accumulated
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct SwapState {
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::i256_hex"))]
+    pub amount_specified_remaining: I256,
+    #[cfg_attr(feature = "serde", serde(with = "crate::serde_helpers::i256_hex"))]
+    pub amount_calculated: I256,
+    pub sqrt_price_x_96: SqrtPriceX96,
+    pub tick: i32,
+    pub fee_growth_global_x_128: FeeGrowthX128,
+    pub protocol_fee: u128,
+    pub liquidity: u128,
+}
+
+// state carried between the steps of the swap loop, shared by the sync and async simulations. fee_protocol is
+// the protocol fee denominator of the input token, cache.feeProtocol in UniswapV3Pool.swap
+struct SwapLoop {
+    zero_for_one: bool,
+    exact_input: bool,
+    fee_protocol: u8,
+    amount_specified: I256,
+    sqrt_price_limit_x_96: SqrtPriceX96,
+    ticks_crossed: u32,
+    state: SwapState,
+}
+
+impl SwapLoop {
+    // slot0_fee_protocol is the packed slot0.feeProtocol, the token0 denominator in the lower and the token1
+    // denominator in the upper 4 bits
+    fn new(
+        sqrt_price_x_96: SqrtPriceX96,
+        tick: i32,
+        liquidity: u128,
+        amount_specified: I256,
+        sqrt_price_limit_x_96: SqrtPriceX96,
+        slot0_fee_protocol: u8,
+    ) -> Result<Self, UniswapV3MathError> {
+        if amount_specified.is_zero() {
+            return Err(UniswapV3MathError::AmountSpecifiedIsZero);
+        }
+
+        let zero_for_one = sqrt_price_limit_x_96 < sqrt_price_x_96;
+        let valid_limit = if zero_for_one {
+            *sqrt_price_limit_x_96 > MIN_SQRT_RATIO
+        } else {
+            sqrt_price_limit_x_96 > sqrt_price_x_96 && *sqrt_price_limit_x_96 < MAX_SQRT_RATIO
+        };
+        if !valid_limit {
+            return Err(UniswapV3MathError::SqrtPriceLimitOutOfBounds {
+                sqrt_price_x_96: sqrt_price_x_96.0,
+                sqrt_price_limit_x_96: sqrt_price_limit_x_96.0,
+            });
+        }
+
+        Ok(Self {
+            zero_for_one,
+            exact_input: amount_specified > I256::ZERO,
+            fee_protocol: if zero_for_one {
+                slot0_fee_protocol % 16
+            } else {
+                slot0_fee_protocol >> 4
+            },
+            amount_specified,
+            sqrt_price_limit_x_96,
+            ticks_crossed: 0,
+            state: SwapState {
+                amount_specified_remaining: amount_specified,
+                amount_calculated: I256::ZERO,
+                sqrt_price_x_96,
+                tick,
+                fee_growth_global_x_128: FeeGrowthX128::default(),
+                protocol_fee: 0,
+                liquidity,
+            },
+        })
+    }
+
+    fn is_done(&self) -> bool {
+        self.state.amount_specified_remaining.is_zero()
+            || self.state.sqrt_price_x_96 == self.sqrt_price_limit_x_96
+    }
+
+    // swaps towards tick_next, returns true if the price reached it and its liquidity has to be crossed
+    fn step(&mut self, tick_next: i32, fee_pips: u32) -> Result<bool, UniswapV3MathError> {
+        let sqrt_price_start_x_96 = self.state.sqrt_price_x_96;
+
+        // ensure that we do not overshoot the min/max tick, as the tick bitmap is not aware of these bounds
+        let tick_next = tick_next.clamp(MIN_TICK, MAX_TICK);
+
+        let sqrt_price_next_x_96 = get_sqrt_ratio_at_tick(tick_next)?;
+
+        let sqrt_price_target_x_96 = if (self.zero_for_one
+            && sqrt_price_next_x_96 < self.sqrt_price_limit_x_96)
+            || (!self.zero_for_one && sqrt_price_next_x_96 > self.sqrt_price_limit_x_96)
+        {
+            self.sqrt_price_limit_x_96
+        } else {
+            sqrt_price_next_x_96
+        };
+
+        let split = compute_swap_step_with_protocol_fee(
+            self.state.sqrt_price_x_96,
+            sqrt_price_target_x_96,
+            self.state.liquidity,
+            self.state.amount_specified_remaining,
+            fee_pips,
+            self.fee_protocol,
+        )?;
+        let step = split.step;
+        self.state.sqrt_price_x_96 = step.sqrt_ratio_next_x_96;
+
+        if self.exact_input {
+            self.state.amount_specified_remaining -= I256::from_raw(step.total_cost());
+            self.state.amount_calculated -= I256::from_raw(step.amount_out);
+        } else {
+            self.state.amount_specified_remaining += I256::from_raw(step.amount_out);
+            self.state.amount_calculated += I256::from_raw(step.total_cost());
+        }
+
+        // the protocol takes its share before the rest is credited to the in range liquidity, uint128 wrapping
+        // like state.protocolFee
+        self.state.protocol_fee = self
+            .state
+            .protocol_fee
+            .wrapping_add(split.protocol_fee.wrapping_to::<u128>());
+
+        if self.state.liquidity > 0 {
+            self.state.fee_growth_global_x_128 = accumulate_fee_growth(
+                self.state.fee_growth_global_x_128,
+                split.lp_fee,
+                self.state.liquidity,
+            )?;
+        }
+
+        if self.state.sqrt_price_x_96 == sqrt_price_next_x_96 {
+            self.state.tick = if self.zero_for_one {
+                tick_next - 1
+            } else {
+                tick_next
+            };
+
+            Ok(true)
+        } else {
+            if self.state.sqrt_price_x_96 != sqrt_price_start_x_96 {
+                // recompute unless we're on a lower tick boundary (i.e. already transitioned ticks), and haven't moved
+                self.state.tick = get_tick_at_sqrt_ratio(self.state.sqrt_price_x_96)?;
+            }
+
+            Ok(false)
+        }
+    }
+
+    // runs the tick transition of an initialized tick
+    fn cross(&mut self, liquidity_net: i128) -> Result<(), UniswapV3MathError> {
+        self.state.liquidity =
+            liquidity_math::cross_tick(self.state.liquidity, liquidity_net, self.zero_for_one)?;
+        self.ticks_crossed += 1;
+
+        Ok(())
+    }
+
+    fn result(&self) -> SwapResult {
+        let (amount_0, amount_1) = if self.zero_for_one == self.exact_input {
+            (
+                self.amount_specified - self.state.amount_specified_remaining,
+                self.state.amount_calculated,
+            )
+        } else {
+            (
+                self.state.amount_calculated,
+                self.amount_specified - self.state.amount_specified_remaining,
+            )
+        };
+
+        SwapResult {
+            amount_0,
+            amount_1,
+            sqrt_price_x_96: self.state.sqrt_price_x_96,
+            tick: self.state.tick,
+            liquidity: self.state.liquidity,
+            ticks_crossed: self.ticks_crossed,
+            fee_growth_x_128: self.state.fee_growth_global_x_128,
+            protocol_fee: self.state.protocol_fee,
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use std::{collections::HashMap, str::FromStr};
+
+    use alloy_primitives::{I256, U256};
+
+    use crate::{
+        error::UniswapV3MathError,
+        fixed_point::{SqrtPriceX96, Q96},
+        full_math::mul_div,
+        swap_math::compute_swap_step,
+        tick::TickInfo,
+        tick_bitmap::TickBitmap,
+        tick_math::{
+            get_sqrt_ratio_at_tick, get_tick_at_sqrt_ratio, MAX_SQRT_RATIO, MIN_SQRT_RATIO,
+        },
+    };
+
+    use super::{
+        simulate_swap, simulate_swap_with_protocol_fee, HashMapTickDataProvider, SwapResult, Q128,
+    };
+
+    // Liquidity positions on [-887220, 887220] and [-600, 600] with a tick spacing of 60
+    fn init_test_ticks() -> eyre::Result<(TickBitmap, HashMap<i32, TickInfo>)> {
+        let mut tick_bitmap = TickBitmap::new();
+        let mut ticks = HashMap::new();
+        for (tick_lower, tick_upper, liquidity) in
+            [(-887220, 887220, 1e18 as i128), (-600, 600, 2e18 as i128)]
+        {
+            for (tick, liquidity_net) in [(tick_lower, liquidity), (tick_upper, -liquidity)] {
+                tick_bitmap.flip_tick(tick, 60)?;
+                ticks.insert(
+                    tick,
+                    TickInfo {
+                        liquidity_gross: liquidity as u128,
+                        liquidity_net,
+                        initialized: true,
+                        ..Default::default()
+                    },
+                );
+            }
+        }
+
+        Ok((tick_bitmap, ticks))
+    }
+
+    #[test]
+    fn test_simulate_swap_crossing_tick() -> eyre::Result<()> {
+        let (tick_bitmap, ticks) = init_test_ticks()?;
+        let provider = HashMapTickDataProvider {
+            tick_bitmap: &tick_bitmap,
+            ticks: &ticks,
+        };
+        let price = SqrtPriceX96(U256::from_str("79228162514264337593543950336")?);
+        let amount = I256::from_str("1000000000000000000")?;
+
+        let result = simulate_swap(
+            &provider,
+            price,
+            0,
+            3e18 as u128,
+            60,
+            3000,
+            amount,
+            SqrtPriceX96(MAX_SQRT_RATIO - U256::from(1)),
+        )?;
+
+        //first leg up to the upper tick of the inner position, second leg with the outer position only
+        let sqrt_price_600 = get_sqrt_ratio_at_tick(600)?;
+        let (sqrt_price, amount_in_0, amount_out_0, fee_amount_0) =
+            compute_swap_step(price, sqrt_price_600, 3e18 as u128, amount, 3000)?;
+        assert_eq!(sqrt_price, sqrt_price_600);
+
+        let remaining = amount - I256::from_raw(amount_in_0 + fee_amount_0);
+        let (sqrt_price, amount_in_1, amount_out_1, fee_amount_1) = compute_swap_step(
+            sqrt_price_600,
+            get_sqrt_ratio_at_tick(15300)?,
+            1e18 as u128,
+            remaining,
+            3000,
+        )?;
+        assert_eq!(remaining.into_raw(), amount_in_1 + fee_amount_1);
+
+        assert_eq!(result.amount_1, amount);
+        assert_eq!(
+            -result.amount_0,
+            I256::from_raw(amount_out_0 + amount_out_1)
+        );
+        assert_eq!(result.sqrt_price_x_96, sqrt_price);
+        assert_eq!(result.tick, get_tick_at_sqrt_ratio(sqrt_price)?);
+        assert_eq!(result.liquidity, 1e18 as u128);
+        assert_eq!(result.ticks_crossed, 1);
+        assert!(result.fee_growth_x_128 > U256::ZERO);
+        Ok(())
+    }
+
+    #[test]
+    fn test_simulate_swap_direction_from_limit() -> eyre::Result<()> {
+        let (tick_bitmap, ticks) = init_test_ticks()?;
+        let provider = HashMapTickDataProvider {
+            tick_bitmap: &tick_bitmap,
+            ticks: &ticks,
+        };
+        let price = U256::from_str("79228162514264337593543950336")?;
+
+        //exact output zero for one down to the limit, crossing the lower tick of the inner position
+        let result = simulate_swap(
+            &provider,
+            SqrtPriceX96(price),
+            0,
+            3e18 as u128,
+            60,
+            3000,
+            I256::from_str("-100000000000000000000")?,
+            get_sqrt_ratio_at_tick(-1200)?,
+        )?;
+
+        assert!(result.amount_0 > I256::ZERO);
+        assert!(result.amount_1 < I256::ZERO);
+        assert_eq!(result.sqrt_price_x_96, get_sqrt_ratio_at_tick(-1200)?);
+        assert_eq!(result.tick, -1200);
+        assert_eq!(result.liquidity, 1e18 as u128);
+        assert_eq!(result.ticks_crossed, 1);
+
+        let result = simulate_swap(
+            &provider,
+            SqrtPriceX96(price),
+            0,
+            3e18 as u128,
+            60,
+            3000,
+            I256::from_str("1000")?,
+            SqrtPriceX96(price),
+        );
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Sqrt price limit is out of bounds"
+        );
+
+        let result = simulate_swap(
+            &provider,
+            SqrtPriceX96(price),
+            0,
+            3e18 as u128,
+            60,
+            3000,
+            I256::from_str("1000")?,
+            SqrtPriceX96(MIN_SQRT_RATIO),
+        );
+        assert_eq!(
+            result.unwrap_err().to_string(),
+            "Sqrt price limit is out of bounds"
+        );
+        Ok(())
+    }
+
+    // simulate_swap_with_protocol_fee from price 1 on the test ticks
+    fn simulate_swap_with_fee_protocol(
+        provider: &HashMapTickDataProvider,
+        amount_specified: I256,
+        sqrt_price_limit_x_96: SqrtPriceX96,
+        fee_protocol: u8,
+    ) -> Result<SwapResult, UniswapV3MathError> {
+        simulate_swap_with_protocol_fee(
+            provider,
+            SqrtPriceX96(Q96),
+            0,
+            3e18 as u128,
+            60,
+            3000,
+            fee_protocol,
+            amount_specified,
+            sqrt_price_limit_x_96,
+        )
+    }
+
+    #[test]
+    fn test_simulate_swap_protocol_fee() -> eyre::Result<()> {
+        let (tick_bitmap, ticks) = init_test_ticks()?;
+        let provider = HashMapTickDataProvider {
+            tick_bitmap: &tick_bitmap,
+            ticks: &ticks,
+        };
+        let amount = I256::from_str("1000000000000000000")?;
+        let up = SqrtPriceX96(MAX_SQRT_RATIO - U256::from(1));
+        //1/4 of the token0 fees and 1/6 of the token1 fees go to the protocol
+        let slot0_fee_protocol = (6 << 4) | 4;
+
+        //same two steps as test_simulate_swap_crossing_tick
+        let sqrt_price_600 = get_sqrt_ratio_at_tick(600)?;
+        let (_, amount_in_0, _, fee_amount_0) = compute_swap_step(
+            SqrtPriceX96(Q96),
+            sqrt_price_600,
+            3e18 as u128,
+            amount,
+            3000,
+        )?;
+        let remaining = amount - I256::from_raw(amount_in_0 + fee_amount_0);
+        let (_, _, _, fee_amount_1) = compute_swap_step(
+            sqrt_price_600,
+            get_sqrt_ratio_at_tick(15300)?,
+            1e18 as u128,
+            remaining,
+            3000,
+        )?;
+
+        let without_protocol_fee = simulate_swap_with_fee_protocol(&provider, amount, up, 0)?;
+        let result = simulate_swap_with_fee_protocol(&provider, amount, up, slot0_fee_protocol)?;
+
+        //the protocol share is taken per step and rounded down, the LPs accrue the rest
+        let (protocol_fee_0, protocol_fee_1) =
+            (fee_amount_0 / U256::from(6), fee_amount_1 / U256::from(6));
+        assert_eq!(
+            U256::from(result.protocol_fee),
+            protocol_fee_0 + protocol_fee_1
+        );
+        let fee_growth_0 = mul_div(
+            fee_amount_0 - protocol_fee_0,
+            Q128,
+            U256::from(3e18 as u128),
+        )?;
+        let fee_growth_1 = mul_div(
+            fee_amount_1 - protocol_fee_1,
+            Q128,
+            U256::from(1e18 as u128),
+        )?;
+        assert_eq!(result.fee_growth_x_128, fee_growth_0 + fee_growth_1);
+        assert!(result.fee_growth_x_128 < without_protocol_fee.fee_growth_x_128);
+        assert_eq!(without_protocol_fee.protocol_fee, 0);
+
+        //the swap itself does not change
+        assert_eq!(
+            SwapResult {
+                fee_growth_x_128: without_protocol_fee.fee_growth_x_128,
+                protocol_fee: 0,
+                ..result
+            },
+            without_protocol_fee
+        );
+
+        //zero for one takes the token0 denominator from the lower 4 bits
+        let limit = get_sqrt_ratio_at_tick(-60)?;
+        let result = simulate_swap_with_fee_protocol(&provider, amount, limit, slot0_fee_protocol)?;
+        let (_, _, _, fee_amount) =
+            compute_swap_step(SqrtPriceX96(Q96), limit, 3e18 as u128, amount, 3000)?;
+        assert_eq!(U256::from(result.protocol_fee), fee_amount / U256::from(4));
+
+        //a denominator setFeeProtocol would reject errors, the other token's is not looked at
+        assert!(matches!(
+            simulate_swap_with_fee_protocol(&provider, amount, up, 3 << 4),
+            Err(UniswapV3MathError::InvalidFeeProtocol { fee_protocol: 3 })
+        ));
+        assert_eq!(
+            simulate_swap_with_fee_protocol(&provider, amount, limit, 3 << 4)?.protocol_fee,
+            0
+        );
+        Ok(())
+    }
+
+    #[cfg(feature = "async")]
+    mod async_provider {
+        use std::{
+            collections::{HashMap, HashSet},
+            str::FromStr,
+            sync::Mutex,
+        };
+
+        use alloy_primitives::{I256, U256};
+
+        use crate::{
+            error::UniswapV3MathError,
+            fixed_point::SqrtPriceX96,
+            swap_simulation::{
+                simulate_swap, simulate_swap_async, AsyncTickDataProvider, HashMapTickDataProvider,
+            },
+            tick::TickInfo,
+            tick_bitmap::TickBitmap,
+            tick_math::{get_sqrt_ratio_at_tick, MAX_SQRT_RATIO},
+        };
+
+        use super::init_test_ticks;
+
+        // serves the test ticks and records every word and tick that was fetched
+        struct CountingProvider {
+            tick_bitmap: TickBitmap,
+            ticks: HashMap<i32, TickInfo>,
+            words_fetched: Mutex<Vec<i16>>,
+            ticks_fetched: Mutex<Vec<i32>>,
+        }
+
+        impl AsyncTickDataProvider for CountingProvider {
+            async fn word(&self, word_pos: i16) -> Result<U256, UniswapV3MathError> {
+                self.words_fetched.lock().unwrap().push(word_pos);
+                Ok(*self.tick_bitmap.0.get(&word_pos).unwrap_or(&U256::ZERO))
+            }
+
+            async fn tick(&self, tick: i32) -> Result<TickInfo, UniswapV3MathError> {
+                self.ticks_fetched.lock().unwrap().push(tick);
+                Ok(self.ticks.get(&tick).copied().unwrap_or_default())
+            }
+        }
+
+        #[test]
+        fn test_simulate_swap_async() -> eyre::Result<()> {
+            let (tick_bitmap, ticks) = init_test_ticks()?;
+            let provider = CountingProvider {
+                tick_bitmap: tick_bitmap.clone(),
+                ticks: ticks.clone(),
+                words_fetched: Mutex::new(vec![]),
+                ticks_fetched: Mutex::new(vec![]),
+            };
+            let price = get_sqrt_ratio_at_tick(0)?;
+            let amount = I256::from_str("1000000000000000000")?;
+
+            let result = futures::executor::block_on(simulate_swap_async(
+                &provider,
+                price,
+                0,
+                3e18 as u128,
+                60,
+                3000,
+                amount,
+                SqrtPriceX96(MAX_SQRT_RATIO - U256::from(1)),
+            ))?;
+
+            let expected = simulate_swap(
+                &HashMapTickDataProvider {
+                    tick_bitmap: &tick_bitmap,
+                    ticks: &ticks,
+                },
+                price,
+                0,
+                3e18 as u128,
+                60,
+                3000,
+                amount,
+                SqrtPriceX96(MAX_SQRT_RATIO - U256::from(1)),
+            )?;
+            assert_eq!(result, expected);
+
+            //both legs stay within word 0, so it is fetched once and only the crossed tick is loaded
+            assert_eq!(*provider.words_fetched.lock().unwrap(), vec![0]);
+            assert_eq!(*provider.ticks_fetched.lock().unwrap(), vec![600]);
+            Ok(())
+        }
+
+        #[test]
+        fn test_simulate_swap_async_fetches_each_word_once() -> eyre::Result<()> {
+            let (tick_bitmap, ticks) = init_test_ticks()?;
+            let provider = CountingProvider {
+                tick_bitmap,
+                ticks,
+                words_fetched: Mutex::new(vec![]),
+                ticks_fetched: Mutex::new(vec![]),
+            };
+
+            //zero for one across several words down to tick -50000
+            let result = futures::executor::block_on(simulate_swap_async(
+                &provider,
+                get_sqrt_ratio_at_tick(0)?,
+                0,
+                3e18 as u128,
+                60,
+                3000,
+                I256::from_str("100000000000000000000")?,
+                get_sqrt_ratio_at_tick(-50000)?,
+            ))?;
+            assert_eq!(result.tick, -50000);
+            assert_eq!(result.ticks_crossed, 1);
+
+            let words_fetched = provider.words_fetched.lock().unwrap();
+            let unique: HashSet<_> = words_fetched.iter().collect();
+            assert_eq!(unique.len(), words_fetched.len());
+            assert!(words_fetched
+                .iter()
+                .all(|word_pos| (-4..=0).contains(word_pos)));
+            assert_eq!(*provider.ticks_fetched.lock().unwrap(), vec![-600]);
+            Ok(())
+        }
+    }
+}
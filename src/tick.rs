@@ -1,12 +1,849 @@
-use alloy::primitives::U256;
+#[cfg(feature = "std")]
+use std::collections::HashMap;
 
-pub struct Tick {
+use alloy_primitives::U256;
+
+use crate::{
+    error::UniswapV3MathError,
+    fixed_point::FeeGrowthX128,
+    liquidity_math::add_delta,
+    tick_math::{MAX_TICK, MIN_TICK},
+};
+
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TickInfo {
     pub liquidity_gross: u128,
     pub liquidity_net: i128,
-    pub fee_growth_outside_0_x_128: U256,
-    pub fee_growth_outside_1_x_128: U256,
-    pub tick_cumulative_outside: U256,
+    pub fee_growth_outside_0_x_128: FeeGrowthX128,
+    pub fee_growth_outside_1_x_128: FeeGrowthX128,
+    pub tick_cumulative_outside: i64,
     pub seconds_per_liquidity_outside_x_128: U256,
     pub seconds_outside: u32,
     pub initialized: bool,
 }
+
+impl TickInfo {
+    // returns (bool flipped), Tick.update on this tick, see update_tick
+    #[allow(clippy::too_many_arguments)]
+    pub fn update(
+        &mut self,
+        tick: i32,
+        tick_current: i32,
+        liquidity_delta: i128,
+        fee_growth_global_0_x_128: FeeGrowthX128,
+        fee_growth_global_1_x_128: FeeGrowthX128,
+        seconds_per_liquidity_cumulative_x_128: U256,
+        tick_cumulative: i64,
+        time: u32,
+        upper: bool,
+        max_liquidity: u128,
+    ) -> Result<bool, UniswapV3MathError> {
+        update_tick(
+            self,
+            tick,
+            tick_current,
+            liquidity_delta,
+            fee_growth_global_0_x_128,
+            fee_growth_global_1_x_128,
+            seconds_per_liquidity_cumulative_x_128,
+            tick_cumulative,
+            time,
+            upper,
+            max_liquidity,
+        )
+    }
+}
+
+//Returns true if the tick is within [MIN_TICK, MAX_TICK] and a multiple of a positive tick spacing
+pub fn is_valid_tick_for_spacing(tick: i32, tick_spacing: i32) -> bool {
+    tick_spacing > 0 && (MIN_TICK..=MAX_TICK).contains(&tick) && tick % tick_spacing == 0
+}
+
+//Errors with InvalidTick unless is_valid_tick_for_spacing holds
+pub fn assert_valid_tick(tick: i32, tick_spacing: i32) -> Result<(), UniswapV3MathError> {
+    if !is_valid_tick_for_spacing(tick, tick_spacing) {
+        return Err(UniswapV3MathError::InvalidTick { tick, tick_spacing });
+    }
+
+    Ok(())
+}
+
+//How two tick ranges a and b relate. Ranges are [lower, upper) as for positions, ranges that only share a
+//boundary tick are disjoint. Containment includes ranges sharing one boundary
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub enum TickRangeRelation {
+    Disjoint,
+    Overlapping {
+        overlap_lower: i32,
+        overlap_upper: i32,
+    },
+    AContainsB,
+    BContainsA,
+    Identical,
+}
+
+//Errors with InvalidTickRange unless both lowers are below their uppers, tick spacings are not checked
+pub fn tick_range_overlap(
+    a_lower: i32,
+    a_upper: i32,
+    b_lower: i32,
+    b_upper: i32,
+) -> Result<TickRangeRelation, UniswapV3MathError> {
+    for (tick_lower, tick_upper) in [(a_lower, a_upper), (b_lower, b_upper)] {
+        if tick_lower >= tick_upper {
+            return Err(UniswapV3MathError::InvalidTickRange {
+                tick_lower,
+                tick_upper,
+            });
+        }
+    }
+
+    Ok(if a_lower == b_lower && a_upper == b_upper {
+        TickRangeRelation::Identical
+    } else if a_upper <= b_lower || b_upper <= a_lower {
+        TickRangeRelation::Disjoint
+    } else if a_lower <= b_lower && b_upper <= a_upper {
+        TickRangeRelation::AContainsB
+    } else if b_lower <= a_lower && a_upper <= b_upper {
+        TickRangeRelation::BContainsA
+    } else {
+        TickRangeRelation::Overlapping {
+            overlap_lower: a_lower.max(b_lower),
+            overlap_upper: a_upper.min(b_upper),
+        }
+    })
+}
+
+// returns (uint256 feeGrowthInside0X128, uint256 feeGrowthInside1X128)
+pub fn get_fee_growth_inside(
+    lower: &TickInfo,
+    upper: &TickInfo,
+    tick_lower: i32,
+    tick_upper: i32,
+    tick_current: i32,
+    fee_growth_global_0_x_128: FeeGrowthX128,
+    fee_growth_global_1_x_128: FeeGrowthX128,
+) -> (FeeGrowthX128, FeeGrowthX128) {
+    // calculate fee growth below
+    let (fee_growth_below_0_x_128, fee_growth_below_1_x_128) = if tick_current >= tick_lower {
+        (
+            lower.fee_growth_outside_0_x_128,
+            lower.fee_growth_outside_1_x_128,
+        )
+    } else {
+        (
+            fee_growth_global_0_x_128.wrapping_sub(lower.fee_growth_outside_0_x_128),
+            fee_growth_global_1_x_128.wrapping_sub(lower.fee_growth_outside_1_x_128),
+        )
+    };
+
+    // calculate fee growth above
+    let (fee_growth_above_0_x_128, fee_growth_above_1_x_128) = if tick_current < tick_upper {
+        (
+            upper.fee_growth_outside_0_x_128,
+            upper.fee_growth_outside_1_x_128,
+        )
+    } else {
+        (
+            fee_growth_global_0_x_128.wrapping_sub(upper.fee_growth_outside_0_x_128),
+            fee_growth_global_1_x_128.wrapping_sub(upper.fee_growth_outside_1_x_128),
+        )
+    };
+
+    (
+        fee_growth_global_0_x_128
+            .wrapping_sub(fee_growth_below_0_x_128)
+            .wrapping_sub(fee_growth_above_0_x_128),
+        fee_growth_global_1_x_128
+            .wrapping_sub(fee_growth_below_1_x_128)
+            .wrapping_sub(fee_growth_above_1_x_128),
+    )
+}
+
+// returns (bool flipped), true if the tick went from uninitialized to initialized or vice versa.
+// Growth is assumed to happen below a tick that is initialized at or below the current tick
+#[allow(clippy::too_many_arguments)]
+pub fn update_tick(
+    info: &mut TickInfo,
+    tick: i32,
+    tick_current: i32,
+    liquidity_delta: i128,
+    fee_growth_global_0_x_128: FeeGrowthX128,
+    fee_growth_global_1_x_128: FeeGrowthX128,
+    seconds_per_liquidity_cumulative_x_128: U256,
+    tick_cumulative: i64,
+    time: u32,
+    upper: bool,
+    max_liquidity: u128,
+) -> Result<bool, UniswapV3MathError> {
+    let liquidity_gross_before = info.liquidity_gross;
+    let liquidity_gross_after = add_delta(liquidity_gross_before, liquidity_delta)?;
+
+    if liquidity_gross_after > max_liquidity {
+        return Err(UniswapV3MathError::TickLiquidityOverflow {
+            liquidity_gross: liquidity_gross_after,
+            max_liquidity,
+        });
+    }
+
+    // when the lower (upper) tick is crossed left to right (right to left), liquidity must be added (removed)
+    let liquidity_net = if upper {
+        info.liquidity_net.checked_sub(liquidity_delta)
+    } else {
+        info.liquidity_net.checked_add(liquidity_delta)
+    }
+    .ok_or(UniswapV3MathError::LiquidityNetOverflow {
+        liquidity_net: info.liquidity_net,
+        delta: liquidity_delta,
+    })?;
+
+    let flipped = (liquidity_gross_after == 0) != (liquidity_gross_before == 0);
+
+    if liquidity_gross_before == 0 && tick <= tick_current {
+        info.fee_growth_outside_0_x_128 = fee_growth_global_0_x_128;
+        info.fee_growth_outside_1_x_128 = fee_growth_global_1_x_128;
+        info.seconds_per_liquidity_outside_x_128 = seconds_per_liquidity_cumulative_x_128;
+        info.tick_cumulative_outside = tick_cumulative;
+        info.seconds_outside = time;
+    }
+
+    // a tick without gross liquidity is not referenced by any position and counts as uninitialized
+    info.initialized = liquidity_gross_after != 0;
+    info.liquidity_gross = liquidity_gross_after;
+    info.liquidity_net = liquidity_net;
+
+    Ok(flipped)
+}
+
+// deletes the tick data, to be called once the tick is no longer referenced by any position
+#[cfg(feature = "std")]
+pub fn clear_tick(ticks: &mut HashMap<i32, TickInfo>, tick: i32) {
+    ticks.remove(&tick);
+}
+
+// returns (int128 liquidityNet), flipping the outside accumulators of the tick as Tick.cross does
+pub fn cross_tick(
+    tick: &mut TickInfo,
+    fee_growth_global_0_x_128: FeeGrowthX128,
+    fee_growth_global_1_x_128: FeeGrowthX128,
+    seconds_per_liquidity_cumulative_x_128: U256,
+    tick_cumulative: i64,
+    time: u32,
+) -> i128 {
+    tick.fee_growth_outside_0_x_128 =
+        fee_growth_global_0_x_128.wrapping_sub(tick.fee_growth_outside_0_x_128);
+    tick.fee_growth_outside_1_x_128 =
+        fee_growth_global_1_x_128.wrapping_sub(tick.fee_growth_outside_1_x_128);
+    tick.seconds_per_liquidity_outside_x_128 = seconds_per_liquidity_cumulative_x_128
+        .wrapping_sub(tick.seconds_per_liquidity_outside_x_128);
+    tick.tick_cumulative_outside = tick_cumulative.wrapping_sub(tick.tick_cumulative_outside);
+    tick.seconds_outside = time.wrapping_sub(tick.seconds_outside);
+
+    tick.liquidity_net
+}
+
+#[cfg(test)]
+mod test {
+    use alloy_primitives::U256;
+    use proptest::prelude::*;
+
+    use std::collections::HashMap;
+
+    use super::{
+        assert_valid_tick, clear_tick, cross_tick, get_fee_growth_inside,
+        is_valid_tick_for_spacing, tick_range_overlap, update_tick, TickInfo, TickRangeRelation,
+    };
+    use crate::{
+        error::UniswapV3MathError,
+        fixed_point::FeeGrowthX128,
+        tick_math::{MAX_TICK, MIN_TICK},
+    };
+
+    #[test]
+    fn test_get_fee_growth_inside() {
+        //returns all for two uninitialized ticks if tick is inside
+        let (fee_growth_inside_0, fee_growth_inside_1) = get_fee_growth_inside(
+            &TickInfo::default(),
+            &TickInfo::default(),
+            -2,
+            2,
+            0,
+            FeeGrowthX128(U256::from(15)),
+            FeeGrowthX128(U256::from(15)),
+        );
+        assert_eq!(fee_growth_inside_0, U256::from(15));
+        assert_eq!(fee_growth_inside_1, U256::from(15));
+
+        //returns 0 for two uninitialized ticks if tick is above
+        let (fee_growth_inside_0, fee_growth_inside_1) = get_fee_growth_inside(
+            &TickInfo::default(),
+            &TickInfo::default(),
+            -2,
+            2,
+            4,
+            FeeGrowthX128(U256::from(15)),
+            FeeGrowthX128(U256::from(15)),
+        );
+        assert_eq!(fee_growth_inside_0, U256::ZERO);
+        assert_eq!(fee_growth_inside_1, U256::ZERO);
+
+        //returns 0 for two uninitialized ticks if tick is below
+        let (fee_growth_inside_0, fee_growth_inside_1) = get_fee_growth_inside(
+            &TickInfo::default(),
+            &TickInfo::default(),
+            -2,
+            2,
+            -4,
+            FeeGrowthX128(U256::from(15)),
+            FeeGrowthX128(U256::from(15)),
+        );
+        assert_eq!(fee_growth_inside_0, U256::ZERO);
+        assert_eq!(fee_growth_inside_1, U256::ZERO);
+
+        //subtracts upper tick if below
+        let upper = TickInfo {
+            fee_growth_outside_0_x_128: FeeGrowthX128(U256::from(2)),
+            fee_growth_outside_1_x_128: FeeGrowthX128(U256::from(3)),
+            ..Default::default()
+        };
+        let (fee_growth_inside_0, fee_growth_inside_1) = get_fee_growth_inside(
+            &TickInfo::default(),
+            &upper,
+            -2,
+            2,
+            0,
+            FeeGrowthX128(U256::from(15)),
+            FeeGrowthX128(U256::from(15)),
+        );
+        assert_eq!(fee_growth_inside_0, U256::from(13));
+        assert_eq!(fee_growth_inside_1, U256::from(12));
+
+        //subtracts lower tick if above
+        let lower = TickInfo {
+            fee_growth_outside_0_x_128: FeeGrowthX128(U256::from(2)),
+            fee_growth_outside_1_x_128: FeeGrowthX128(U256::from(3)),
+            ..Default::default()
+        };
+        let (fee_growth_inside_0, fee_growth_inside_1) = get_fee_growth_inside(
+            &lower,
+            &TickInfo::default(),
+            -2,
+            2,
+            0,
+            FeeGrowthX128(U256::from(15)),
+            FeeGrowthX128(U256::from(15)),
+        );
+        assert_eq!(fee_growth_inside_0, U256::from(13));
+        assert_eq!(fee_growth_inside_1, U256::from(12));
+
+        //subtracts upper and lower tick if inside
+        let lower = TickInfo {
+            fee_growth_outside_0_x_128: FeeGrowthX128(U256::from(2)),
+            fee_growth_outside_1_x_128: FeeGrowthX128(U256::from(3)),
+            ..Default::default()
+        };
+        let upper = TickInfo {
+            fee_growth_outside_0_x_128: FeeGrowthX128(U256::from(4)),
+            fee_growth_outside_1_x_128: FeeGrowthX128(U256::from(1)),
+            ..Default::default()
+        };
+        let (fee_growth_inside_0, fee_growth_inside_1) = get_fee_growth_inside(
+            &lower,
+            &upper,
+            -2,
+            2,
+            0,
+            FeeGrowthX128(U256::from(15)),
+            FeeGrowthX128(U256::from(15)),
+        );
+        assert_eq!(fee_growth_inside_0, U256::from(9));
+        assert_eq!(fee_growth_inside_1, U256::from(11));
+
+        //works correctly with overflow on inside tick
+        let lower = TickInfo {
+            fee_growth_outside_0_x_128: FeeGrowthX128(U256::MAX - U256::from(3)),
+            fee_growth_outside_1_x_128: FeeGrowthX128(U256::MAX - U256::from(2)),
+            ..Default::default()
+        };
+        let upper = TickInfo {
+            fee_growth_outside_0_x_128: FeeGrowthX128(U256::from(3)),
+            fee_growth_outside_1_x_128: FeeGrowthX128(U256::from(5)),
+            ..Default::default()
+        };
+        let (fee_growth_inside_0, fee_growth_inside_1) = get_fee_growth_inside(
+            &lower,
+            &upper,
+            -2,
+            2,
+            0,
+            FeeGrowthX128(U256::from(15)),
+            FeeGrowthX128(U256::from(15)),
+        );
+        assert_eq!(fee_growth_inside_0, U256::from(16));
+        assert_eq!(fee_growth_inside_1, U256::from(13));
+
+        //the global accumulator wrapped after both ticks were crossed on the way up, the growth inside is still
+        //the growth between the two crossings
+        let lower = TickInfo {
+            fee_growth_outside_0_x_128: FeeGrowthX128(U256::MAX - U256::from(9)),
+            fee_growth_outside_1_x_128: FeeGrowthX128(U256::from(1)),
+            ..Default::default()
+        };
+        let upper = TickInfo {
+            fee_growth_outside_0_x_128: FeeGrowthX128(U256::MAX - U256::from(4)),
+            fee_growth_outside_1_x_128: FeeGrowthX128(U256::from(4)),
+            ..Default::default()
+        };
+        let (fee_growth_inside_0, fee_growth_inside_1) = get_fee_growth_inside(
+            &lower,
+            &upper,
+            -2,
+            2,
+            4,
+            FeeGrowthX128(U256::from(5)),
+            FeeGrowthX128(U256::from(8)),
+        );
+        assert_eq!(fee_growth_inside_0, U256::from(5));
+        assert_eq!(fee_growth_inside_1, U256::from(3));
+
+        //a wrapped upper outside value with the current tick below the range
+        let lower = TickInfo {
+            fee_growth_outside_0_x_128: FeeGrowthX128(U256::from(3)),
+            ..Default::default()
+        };
+        let upper = TickInfo {
+            fee_growth_outside_0_x_128: FeeGrowthX128(U256::MAX - U256::from(4)),
+            ..Default::default()
+        };
+        let (fee_growth_inside_0, _) = get_fee_growth_inside(
+            &lower,
+            &upper,
+            -2,
+            2,
+            -4,
+            FeeGrowthX128(U256::from(5)),
+            FeeGrowthX128(U256::ZERO),
+        );
+        assert_eq!(fee_growth_inside_0, U256::from(8));
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    fn test_tick_info_serde() -> eyre::Result<()> {
+        let tick_info = TickInfo {
+            liquidity_gross: 1e18 as u128,
+            liquidity_net: -1e18 as i128,
+            fee_growth_outside_0_x_128: FeeGrowthX128(U256::MAX),
+            fee_growth_outside_1_x_128: FeeGrowthX128(U256::from(15)),
+            initialized: true,
+            ..Default::default()
+        };
+
+        let json = serde_json::to_string(&tick_info)?;
+        assert_eq!(serde_json::from_str::<TickInfo>(&json)?, tick_info);
+        Ok(())
+    }
+
+    // TickInfo::update with zeroed globals, as most of the Tick.spec update cases use
+    fn update(
+        info: &mut TickInfo,
+        liquidity_delta: i128,
+        upper: bool,
+        max_liquidity: u128,
+    ) -> Result<bool, UniswapV3MathError> {
+        info.update(
+            0,
+            0,
+            liquidity_delta,
+            FeeGrowthX128(U256::ZERO),
+            FeeGrowthX128(U256::ZERO),
+            U256::ZERO,
+            0,
+            0,
+            upper,
+            max_liquidity,
+        )
+    }
+
+    #[test]
+    fn test_update_tick() -> eyre::Result<()> {
+        //flips from zero to nonzero
+        let mut info = TickInfo::default();
+        assert!(update(&mut info, 1, false, 3)?);
+        assert!(info.initialized);
+
+        //does not flip from nonzero to greater nonzero
+        let mut info = TickInfo::default();
+        update(&mut info, 1, false, 3)?;
+        assert!(!update(&mut info, 1, false, 3)?);
+
+        //flips from nonzero to zero
+        let mut info = TickInfo::default();
+        update(&mut info, 1, false, 3)?;
+        assert!(update(&mut info, -1, false, 3)?);
+        assert!(!info.initialized);
+        assert_eq!((info.liquidity_gross, info.liquidity_net), (0, 0));
+
+        //an upper tick flips back once the lower side of its liquidity is removed
+        let mut info = TickInfo::default();
+        assert!(update(&mut info, 5, true, 10)?);
+        assert!(!update(&mut info, 2, false, 10)?);
+        assert!(!update(&mut info, -5, true, 10)?);
+        assert!(info.initialized);
+        assert!(update(&mut info, -2, false, 10)?);
+        assert!(!info.initialized);
+
+        //does not flip from nonzero to lesser nonzero
+        let mut info = TickInfo::default();
+        update(&mut info, 2, false, 3)?;
+        assert!(!update(&mut info, -1, false, 3)?);
+
+        //reverts if total liquidity gross is greater than max
+        let mut info = TickInfo::default();
+        update(&mut info, 2, false, 3)?;
+        update(&mut info, 1, true, 3)?;
+        assert!(matches!(
+            update(&mut info, 1, false, 3),
+            Err(UniswapV3MathError::TickLiquidityOverflow {
+                liquidity_gross: 4,
+                max_liquidity: 3
+            })
+        ));
+        assert_eq!(info.liquidity_gross, 3);
+
+        //nets the liquidity based on upper flag
+        let mut info = TickInfo::default();
+        update(&mut info, 2, false, 10)?;
+        update(&mut info, 1, true, 10)?;
+        update(&mut info, 3, true, 10)?;
+        update(&mut info, 1, false, 10)?;
+        assert_eq!(info.liquidity_gross, 2 + 1 + 3 + 1);
+        assert_eq!(info.liquidity_net, 2 - 1 - 3 + 1);
+
+        //reverts on overflow liquidity net
+        let mut info = TickInfo::default();
+        let delta = (u128::MAX / 2 - 1) as i128;
+        update(&mut info, delta, false, u128::MAX)?;
+        assert!(matches!(
+            update(&mut info, delta, false, u128::MAX),
+            Err(UniswapV3MathError::LiquidityNetOverflow { .. })
+        ));
+
+        //reverts on liquidity gross underflow
+        let mut info = TickInfo::default();
+        assert!(matches!(
+            update(&mut info, -1, false, 3),
+            Err(UniswapV3MathError::LiquidityUnderflow { .. })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_update_tick_growth_fields() -> eyre::Result<()> {
+        let initialized = TickInfo {
+            liquidity_gross: 1,
+            liquidity_net: 1,
+            fee_growth_outside_0_x_128: FeeGrowthX128(U256::from(1)),
+            fee_growth_outside_1_x_128: FeeGrowthX128(U256::from(2)),
+            seconds_per_liquidity_outside_x_128: U256::from(3),
+            tick_cumulative_outside: 4,
+            seconds_outside: 5,
+            initialized: true,
+        };
+
+        //assumes all growth happens below ticks lte current tick
+        let mut info = TickInfo::default();
+        update_tick(
+            &mut info,
+            1,
+            1,
+            1,
+            FeeGrowthX128(U256::from(1)),
+            FeeGrowthX128(U256::from(2)),
+            U256::from(3),
+            4,
+            5,
+            false,
+            u128::MAX,
+        )?;
+        assert_eq!(info, initialized);
+
+        //does not set any growth fields if tick is already initialized
+        update_tick(
+            &mut info,
+            1,
+            1,
+            1,
+            FeeGrowthX128(U256::from(6)),
+            FeeGrowthX128(U256::from(7)),
+            U256::from(8),
+            9,
+            10,
+            false,
+            u128::MAX,
+        )?;
+        assert_eq!(
+            info,
+            TickInfo {
+                liquidity_gross: 2,
+                liquidity_net: 2,
+                ..initialized
+            }
+        );
+
+        //does not set any growth fields for ticks gt current tick
+        let mut info = TickInfo::default();
+        update_tick(
+            &mut info,
+            2,
+            1,
+            1,
+            FeeGrowthX128(U256::from(1)),
+            FeeGrowthX128(U256::from(2)),
+            U256::from(3),
+            4,
+            5,
+            false,
+            u128::MAX,
+        )?;
+        assert_eq!(
+            info,
+            TickInfo {
+                liquidity_gross: 1,
+                liquidity_net: 1,
+                initialized: true,
+                ..Default::default()
+            }
+        );
+
+        //negative tick cumulatives are stored as two's complement
+        let mut info = TickInfo::default();
+        update_tick(
+            &mut info,
+            -1,
+            0,
+            1,
+            FeeGrowthX128(U256::ZERO),
+            FeeGrowthX128(U256::ZERO),
+            U256::ZERO,
+            -4,
+            0,
+            false,
+            u128::MAX,
+        )?;
+        assert_eq!(info.tick_cumulative_outside, -4);
+        Ok(())
+    }
+
+    #[test]
+    fn test_clear_tick() -> eyre::Result<()> {
+        let mut ticks = HashMap::new();
+        let mut info = TickInfo::default();
+        update(&mut info, 1, false, 3)?;
+        ticks.insert(2, info);
+
+        clear_tick(&mut ticks, 2);
+        assert_eq!(
+            ticks.get(&2).copied().unwrap_or_default(),
+            TickInfo::default()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn test_cross_tick() {
+        let initial = TickInfo {
+            liquidity_gross: 3,
+            liquidity_net: 4,
+            fee_growth_outside_0_x_128: FeeGrowthX128(U256::from(1)),
+            fee_growth_outside_1_x_128: FeeGrowthX128(U256::from(2)),
+            tick_cumulative_outside: 6,
+            seconds_per_liquidity_outside_x_128: U256::from(5),
+            seconds_outside: 7,
+            initialized: true,
+        };
+
+        //flips the growth variables
+        let mut tick = initial;
+        let liquidity_net = cross_tick(
+            &mut tick,
+            FeeGrowthX128(U256::from(7)),
+            FeeGrowthX128(U256::from(9)),
+            U256::from(8),
+            15,
+            10,
+        );
+        assert_eq!(liquidity_net, 4);
+        assert_eq!(tick.fee_growth_outside_0_x_128, U256::from(6));
+        assert_eq!(tick.fee_growth_outside_1_x_128, U256::from(7));
+        assert_eq!(tick.seconds_per_liquidity_outside_x_128, U256::from(3));
+        assert_eq!(tick.tick_cumulative_outside, 9);
+        assert_eq!(tick.seconds_outside, 3);
+
+        //two swaps crossing the same tick back and forth with fees accrued in between
+        let mut tick = initial;
+        cross_tick(
+            &mut tick,
+            FeeGrowthX128(U256::from(7)),
+            FeeGrowthX128(U256::from(9)),
+            U256::from(8),
+            15,
+            10,
+        );
+        let liquidity_net = cross_tick(
+            &mut tick,
+            FeeGrowthX128(U256::from(7)),
+            FeeGrowthX128(U256::from(9)),
+            U256::from(8),
+            15,
+            10,
+        );
+        assert_eq!(liquidity_net, 4);
+        assert_eq!(tick, initial);
+
+        //outside values larger than the globals wrap around and still return to the initial state
+        let initial = TickInfo {
+            fee_growth_outside_0_x_128: FeeGrowthX128(U256::MAX),
+            fee_growth_outside_1_x_128: FeeGrowthX128(U256::MAX - U256::from(1)),
+            tick_cumulative_outside: 100,
+            seconds_outside: u32::MAX,
+            ..initial
+        };
+        let mut tick = initial;
+        cross_tick(
+            &mut tick,
+            FeeGrowthX128(U256::from(3)),
+            FeeGrowthX128(U256::from(4)),
+            U256::from(8),
+            -20,
+            5,
+        );
+        assert_eq!(tick.fee_growth_outside_0_x_128, U256::from(4));
+        assert_eq!(tick.fee_growth_outside_1_x_128, U256::from(6));
+        assert_eq!(tick.tick_cumulative_outside, -120);
+        assert_eq!(tick.seconds_outside, 6);
+
+        cross_tick(
+            &mut tick,
+            FeeGrowthX128(U256::from(3)),
+            FeeGrowthX128(U256::from(4)),
+            U256::from(8),
+            -20,
+            5,
+        );
+        assert_eq!(tick, initial);
+    }
+
+    #[test]
+    fn test_is_valid_tick_for_spacing() {
+        assert!(is_valid_tick_for_spacing(0, 1));
+        assert!(is_valid_tick_for_spacing(-887220, 60));
+        assert!(is_valid_tick_for_spacing(887220, 60));
+        assert!(is_valid_tick_for_spacing(MIN_TICK, 1));
+        assert!(is_valid_tick_for_spacing(MAX_TICK, 1));
+
+        //not a multiple of the tick spacing
+        assert!(!is_valid_tick_for_spacing(30, 60));
+        assert!(!is_valid_tick_for_spacing(-30, 60));
+        //out of bounds
+        assert!(!is_valid_tick_for_spacing(MIN_TICK - 1, 1));
+        assert!(!is_valid_tick_for_spacing(887280, 60));
+        //non positive tick spacing
+        assert!(!is_valid_tick_for_spacing(0, 0));
+        assert!(!is_valid_tick_for_spacing(60, -60));
+
+        assert!(assert_valid_tick(-600, 60).is_ok());
+        assert!(matches!(
+            assert_valid_tick(-601, 60),
+            Err(UniswapV3MathError::InvalidTick {
+                tick: -601,
+                tick_spacing: 60
+            })
+        ));
+    }
+
+    proptest! {
+        #[test]
+        fn test_is_valid_tick_for_spacing_no_false_positives(tick in any::<i32>(), tick_spacing in any::<i32>()) {
+            if is_valid_tick_for_spacing(tick, tick_spacing) {
+                prop_assert!(tick_spacing > 0);
+                prop_assert!((MIN_TICK..=MAX_TICK).contains(&tick));
+                prop_assert_eq!(tick % tick_spacing, 0);
+            }
+        }
+
+        #[test]
+        fn test_is_valid_tick_for_spacing_multiples(compressed in -887272..=887272_i32, tick_spacing in 1..=16384_i32) {
+            let tick = compressed / tick_spacing * tick_spacing;
+            prop_assert!(is_valid_tick_for_spacing(tick, tick_spacing));
+            if tick_spacing > 1 {
+                prop_assert!(!is_valid_tick_for_spacing(tick + 1, tick_spacing));
+            }
+        }
+    }
+
+    #[test]
+    fn test_tick_range_overlap() {
+        // disjoint on either side, ranges sharing a boundary tick do not overlap
+        assert_eq!(
+            tick_range_overlap(-120, -60, 0, 60).unwrap(),
+            TickRangeRelation::Disjoint
+        );
+        assert_eq!(
+            tick_range_overlap(0, 60, -120, 0).unwrap(),
+            TickRangeRelation::Disjoint
+        );
+
+        // partial overlap from either side
+        assert_eq!(
+            tick_range_overlap(-120, 60, 0, 120).unwrap(),
+            TickRangeRelation::Overlapping {
+                overlap_lower: 0,
+                overlap_upper: 60
+            }
+        );
+        assert_eq!(
+            tick_range_overlap(0, 120, -120, 60).unwrap(),
+            TickRangeRelation::Overlapping {
+                overlap_lower: 0,
+                overlap_upper: 60
+            }
+        );
+
+        // containment, including a shared boundary
+        assert_eq!(
+            tick_range_overlap(-120, 120, -60, 60).unwrap(),
+            TickRangeRelation::AContainsB
+        );
+        assert_eq!(
+            tick_range_overlap(-120, 120, -120, 60).unwrap(),
+            TickRangeRelation::AContainsB
+        );
+        assert_eq!(
+            tick_range_overlap(0, 60, MIN_TICK, MAX_TICK).unwrap(),
+            TickRangeRelation::BContainsA
+        );
+        assert_eq!(
+            tick_range_overlap(-60, 60, -60, 60).unwrap(),
+            TickRangeRelation::Identical
+        );
+
+        // both ranges are validated
+        assert!(matches!(
+            tick_range_overlap(60, 60, 0, 60),
+            Err(UniswapV3MathError::InvalidTickRange {
+                tick_lower: 60,
+                tick_upper: 60
+            })
+        ));
+        assert!(matches!(
+            tick_range_overlap(0, 60, 60, -60),
+            Err(UniswapV3MathError::InvalidTickRange {
+                tick_lower: 60,
+                tick_upper: -60
+            })
+        ));
+    }
+}
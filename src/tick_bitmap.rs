@@ -1,10 +1,24 @@
+use core::ops::{Bound, RangeBounds};
+
+use crate::tick::assert_valid_tick;
+use crate::tick_math::{MAX_TICK, MIN_TICK};
 use crate::U256_1;
 use crate::{bit_math, error::UniswapV3MathError};
-use alloy::primitives::{Address, BlockNumber, U256};
-use alloy::providers::Provider;
-use alloy::sol;
-use std::{collections::HashMap, sync::Arc};
+use alloy_primitives::U256;
+#[cfg(feature = "std")]
+use {
+    alloy::{providers::Provider, sol},
+    alloy_primitives::{Address, BlockNumber},
+    std::{collections::HashMap, sync::Arc},
+};
+
+//Words of a tick bitmap as taken by the free functions, a BTreeMap when built without std
+#[cfg(feature = "std")]
+pub type TickBitmapWords = HashMap<i16, U256>;
+#[cfg(not(feature = "std"))]
+pub type TickBitmapWords = alloc::collections::BTreeMap<i16, U256>;
 
+#[cfg(feature = "std")]
 sol! {
     #[sol(rpc)]
     interface IUniswapV3Pool {
@@ -12,84 +26,334 @@ sol! {
     }
 }
 
+//Packed tick initialized state, word_pos => word where each bit of a word flags an initialized tick. Words
+//without initialized ticks are not stored
+#[derive(Debug, Clone, Default, PartialEq, Eq)]
+#[cfg_attr(feature = "serde", derive(serde::Serialize, serde::Deserialize))]
+pub struct TickBitmap(pub TickBitmapWords);
+
+impl TickBitmap {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    //Flips the initialized state for a given tick from false to true, or vice versa
+    pub fn flip_tick(&mut self, tick: i32, tick_spacing: i32) -> Result<(), UniswapV3MathError> {
+        flip_tick(&mut self.0, tick, tick_spacing)
+    }
+
+    //Returns true if the given tick is initialized in the bitmap
+    pub fn is_initialized(&self, tick: i32, tick_spacing: i32) -> bool {
+        let Ok((word_pos, bit_pos)) = get_tick_bitmap_position(tick, tick_spacing) else {
+            return false;
+        };
+
+        let word = *self.0.get(&word_pos).unwrap_or(&U256::ZERO);
+        word.bit(bit_pos as usize)
+    }
+
+    //Returns the next initialized tick contained in the same word (or adjacent word) as the tick that is either
+    //to the left (less than or equal to) or right (greater than) of the given tick
+    pub fn next_initialized_tick_within_one_word(
+        &self,
+        tick: i32,
+        tick_spacing: i32,
+        lte: bool,
+    ) -> Result<(i32, bool), UniswapV3MathError> {
+        next_initialized_tick_within_one_word(&self.0, tick, tick_spacing, lte)
+    }
+
+    //Returns the initialized ticks within the range in ascending order. The range is clamped to
+    //[MIN_TICK, MAX_TICK], errors with InvalidTickSpacing if tick_spacing is not positive
+    pub fn iter_initialized(
+        &self,
+        range: impl RangeBounds<i32>,
+        tick_spacing: i32,
+    ) -> Result<InitializedTickIterator<'_, TickBitmapWords>, UniswapV3MathError> {
+        // inclusive bounds, widened so that excluded bounds at i32::MIN/MAX do not overflow
+        let tick_lower = match range.start_bound() {
+            Bound::Included(&tick) => tick as i64,
+            Bound::Excluded(&tick) => tick as i64 + 1,
+            Bound::Unbounded => MIN_TICK as i64,
+        };
+        let tick_upper = match range.end_bound() {
+            Bound::Included(&tick) => tick as i64,
+            Bound::Excluded(&tick) => tick as i64 - 1,
+            Bound::Unbounded => MAX_TICK as i64,
+        };
+
+        InitializedTickIterator::new(&self.0, tick_lower, tick_upper, tick_spacing)
+    }
+}
+
+impl From<TickBitmapWords> for TickBitmap {
+    fn from(tick_bitmap: TickBitmapWords) -> Self {
+        Self(tick_bitmap)
+    }
+}
+
+//Returns the initialized ticks in [tick_lower, tick_upper], ascending or in reverse with .rev(). The range is
+//clamped to [MIN_TICK, MAX_TICK], errors with InvalidTickSpacing if tick_spacing is not positive
+pub fn initialized_ticks_in_range<S: TickBitmapWordSource + ?Sized>(
+    word_source: &S,
+    tick_lower: i32,
+    tick_upper: i32,
+    tick_spacing: i32,
+) -> Result<InitializedTickIterator<'_, S>, UniswapV3MathError> {
+    InitializedTickIterator::new(
+        word_source,
+        tick_lower as i64,
+        tick_upper as i64,
+        tick_spacing,
+    )
+}
+
+//Iterator over the initialized ticks between two compressed ticks. Each call loads the word of the next
+//candidate and jumps to its next set bit, so empty words are skipped in a single step
+#[derive(Debug, Clone)]
+pub struct InitializedTickIterator<'a, S: ?Sized> {
+    word_source: &'a S,
+    tick_spacing: i32,
+    // inclusive compressed bounds of the ticks that are left
+    front: i32,
+    back: i32,
+}
+
+impl<'a, S: TickBitmapWordSource + ?Sized> InitializedTickIterator<'a, S> {
+    fn new(
+        word_source: &'a S,
+        tick_lower: i64,
+        tick_upper: i64,
+        tick_spacing: i32,
+    ) -> Result<Self, UniswapV3MathError> {
+        assert_positive_tick_spacing(tick_spacing)?;
+
+        // clamped one past the bounds at most, so that ranges outside of them stay empty and their compressed
+        // ticks fit a word position
+        let tick_lower = tick_lower.clamp(MIN_TICK as i64, MAX_TICK as i64 + 1) as i32;
+        let tick_upper = tick_upper.clamp(MIN_TICK as i64 - 1, MAX_TICK as i64) as i32;
+
+        // compressed bounds of the range, rounded inwards to the tick spacing
+        Ok(Self {
+            word_source,
+            tick_spacing,
+            front: compress(tick_lower - 1, tick_spacing) + 1,
+            back: compress(tick_upper, tick_spacing),
+        })
+    }
+}
+
+impl<S: TickBitmapWordSource + ?Sized> Iterator for InitializedTickIterator<'_, S> {
+    type Item = i32;
+
+    fn next(&mut self) -> Option<i32> {
+        while self.front <= self.back {
+            let (word_pos, bit_pos) = position(self.front);
+            let (back_word_pos, back_bit_pos) = position(self.back);
+
+            let mut masked = self.word_source.word(word_pos) & (U256::MAX << bit_pos);
+            if word_pos == back_word_pos {
+                masked &= U256::MAX >> (255 - back_bit_pos);
+            }
+
+            if masked.is_zero() {
+                self.front = (word_pos as i32 + 1) << 8;
+                continue;
+            }
+
+            let compressed =
+                ((word_pos as i32) << 8) + bit_math::least_significant_bit(masked).ok()? as i32;
+            self.front = compressed + 1;
+            return Some(compressed * self.tick_spacing);
+        }
+
+        None
+    }
+}
+
+impl<S: TickBitmapWordSource + ?Sized> DoubleEndedIterator for InitializedTickIterator<'_, S> {
+    fn next_back(&mut self) -> Option<i32> {
+        while self.front <= self.back {
+            let (word_pos, bit_pos) = position(self.back);
+            let (front_word_pos, front_bit_pos) = position(self.front);
+
+            let mut masked = self.word_source.word(word_pos) & (U256::MAX >> (255 - bit_pos));
+            if word_pos == front_word_pos {
+                masked &= U256::MAX << front_bit_pos;
+            }
+
+            if masked.is_zero() {
+                self.back = ((word_pos as i32) << 8) - 1;
+                continue;
+            }
+
+            let compressed =
+                ((word_pos as i32) << 8) + bit_math::most_significant_bit(masked).ok()? as i32;
+            self.back = compressed - 1;
+            return Some(compressed * self.tick_spacing);
+        }
+
+        None
+    }
+}
+
 //Flips the initialized state for a given tick from false to true, or vice versa
 pub fn flip_tick(
-    tick_bitmap: &mut HashMap<i16, U256>,
+    tick_bitmap: &mut TickBitmapWords,
     tick: i32,
     tick_spacing: i32,
 ) -> Result<(), UniswapV3MathError> {
-    if (tick % tick_spacing) != 0 {
-        return Err(UniswapV3MathError::TickSpacingError);
-    }
-
-    let (word_pos, bit_pos) = position(tick / tick_spacing);
+    let (word_pos, bit_pos) = get_tick_bitmap_position(tick, tick_spacing)?;
     let mask = U256_1 << bit_pos;
-    let word = *tick_bitmap.get(&word_pos).unwrap_or(&U256::ZERO);
-    tick_bitmap.insert(word_pos, word ^ mask);
+    let word = *tick_bitmap.get(&word_pos).unwrap_or(&U256::ZERO) ^ mask;
+
+    // empty words are dropped so that the map only holds words with initialized ticks
+    if word.is_zero() {
+        tick_bitmap.remove(&word_pos);
+    } else {
+        tick_bitmap.insert(word_pos, word);
+    }
     Ok(())
 }
 
 //Returns the next initialized tick contained in the same word (or adjacent word) as the tick that is either
 //to the left (less than or equal to) or right (greater than) of the given tick
 pub fn next_initialized_tick_within_one_word(
-    tick_bitmap: &HashMap<i16, U256>,
+    tick_bitmap: &TickBitmapWords,
     tick: i32,
     tick_spacing: i32,
     lte: bool,
 ) -> Result<(i32, bool), UniswapV3MathError> {
-    let compressed = if tick < 0 && tick % tick_spacing != 0 {
-        (tick / tick_spacing) - 1
-    } else {
-        tick / tick_spacing
-    };
+    let word_pos = next_initialized_tick_word_pos(tick, tick_spacing, lte)?;
+    let word = *tick_bitmap.get(&word_pos).unwrap_or(&U256::ZERO);
 
-    if lte {
-        let (word_pos, bit_pos) = position(compressed);
+    next_initialized_tick_within_word(word, tick, tick_spacing, lte)
+}
 
-        let mask = (U256_1 << bit_pos) - U256_1 + (U256_1 << bit_pos);
+//Source of tick bitmap words for next_initialized_tick, missing words are empty
+pub trait TickBitmapWordSource {
+    fn word(&self, word_pos: i16) -> U256;
+}
 
-        let masked = *tick_bitmap.get(&word_pos).unwrap_or(&U256::ZERO) & mask;
+impl TickBitmapWordSource for TickBitmapWords {
+    fn word(&self, word_pos: i16) -> U256 {
+        self.get(&word_pos).copied().unwrap_or_default()
+    }
+}
 
-        let initialized = !masked.is_zero();
+impl TickBitmapWordSource for TickBitmap {
+    fn word(&self, word_pos: i16) -> U256 {
+        self.0.word(word_pos)
+    }
+}
 
-        let next = if initialized {
-            (compressed
-                - (bit_pos
-                    .overflowing_sub(bit_math::most_significant_bit(masked)?)
-                    .0) as i32)
-                * tick_spacing
+//Returns the next initialized tick to the left (less than or equal to) or right (greater than) of the given tick,
+//walking as many words as needed. Returns None if no tick is initialized up to and including search_limit_tick,
+//or up to the min/max tick
+pub fn next_initialized_tick<S: TickBitmapWordSource + ?Sized>(
+    word_source: &S,
+    tick: i32,
+    tick_spacing: i32,
+    lte: bool,
+    search_limit_tick: i32,
+) -> Result<Option<i32>, UniswapV3MathError> {
+    let mut tick = tick;
+
+    loop {
+        let word = word_source.word(next_initialized_tick_word_pos(tick, tick_spacing, lte)?);
+        let (next, initialized) = next_initialized_tick_within_word(word, tick, tick_spacing, lte)?;
+
+        let past_limit = if lte {
+            next < search_limit_tick.max(MIN_TICK)
         } else {
-            (compressed - bit_pos as i32) * tick_spacing
+            next > search_limit_tick.min(MAX_TICK)
         };
+        if past_limit {
+            return Ok(None);
+        }
+        if initialized {
+            return Ok(Some(next));
+        }
 
-        Ok((next, initialized))
+        // continue from the boundary of the word that was just searched
+        tick = if lte { next - 1 } else { next };
+    }
+}
+
+//Returns the position of the word that next_initialized_tick_within_one_word searches for the given tick
+pub fn next_initialized_tick_word_pos(
+    tick: i32,
+    tick_spacing: i32,
+    lte: bool,
+) -> Result<i16, UniswapV3MathError> {
+    assert_positive_tick_spacing(tick_spacing)?;
+    let compressed = compress(tick, tick_spacing);
+
+    Ok(if lte {
+        position(compressed).0
     } else {
-        let (word_pos, bit_pos) = position(compressed + 1);
+        position(compressed + 1).0
+    })
+}
 
-        let mask = !((U256_1 << bit_pos) - U256_1);
+//Same as next_initialized_tick_within_one_word, with the word at next_initialized_tick_word_pos already loaded
+pub fn next_initialized_tick_within_word(
+    word: U256,
+    tick: i32,
+    tick_spacing: i32,
+    lte: bool,
+) -> Result<(i32, bool), UniswapV3MathError> {
+    assert_positive_tick_spacing(tick_spacing)?;
+    let compressed = compress(tick, tick_spacing);
 
-        let masked = *tick_bitmap.get(&word_pos).unwrap_or(&U256::ZERO) & mask;
+    if lte {
+        let (_, bit_pos) = position(compressed);
 
-        let initialized = !masked.is_zero();
+        Ok(match bit_math::next_set_bit_lte(word, bit_pos) {
+            Some(next_bit) => (
+                (compressed - (bit_pos - next_bit) as i32) * tick_spacing,
+                true,
+            ),
+            None => ((compressed - bit_pos as i32) * tick_spacing, false),
+        })
+    } else {
+        let (_, bit_pos) = position(compressed + 1);
 
-        let next = if initialized {
-            (compressed
-                + 1
-                + (bit_math::least_significant_bit(masked)?
-                    .overflowing_sub(bit_pos)
-                    .0) as i32)
-                * tick_spacing
-        } else {
-            (compressed + 1 + ((0xFF - bit_pos) as i32)) * tick_spacing
-        };
+        Ok(match bit_math::next_set_bit_gte(word, bit_pos) {
+            Some(next_bit) => (
+                (compressed + 1 + (next_bit - bit_pos) as i32) * tick_spacing,
+                true,
+            ),
+            None => (
+                (compressed + 1 + (0xFF - bit_pos) as i32) * tick_spacing,
+                false,
+            ),
+        })
+    }
+}
 
-        Ok((next, initialized))
+// every lookup divides by the tick spacing
+fn assert_positive_tick_spacing(tick_spacing: i32) -> Result<(), UniswapV3MathError> {
+    if tick_spacing <= 0 {
+        return Err(UniswapV3MathError::InvalidTickSpacing { tick_spacing });
+    }
+
+    Ok(())
+}
+
+// rounds towards negative infinity
+fn compress(tick: i32, tick_spacing: i32) -> i32 {
+    if tick < 0 && tick % tick_spacing != 0 {
+        (tick / tick_spacing) - 1
+    } else {
+        tick / tick_spacing
     }
 }
 
 //Returns next and initialized. This function calls the node to get the word at the word_pos.
 //current_word is the current word in the TickBitmap of the pool based on `tick`. TickBitmap[word_pos] = current_word
 //Where word_pos is the 256 bit offset of the ticks word_pos.. word_pos := tick >> 8
+#[cfg(feature = "std")]
 pub async fn next_initialized_tick_within_one_word_from_provider<P: Provider>(
     tick: i32,
     tick_spacing: i32,
@@ -98,6 +362,7 @@ pub async fn next_initialized_tick_within_one_word_from_provider<P: Provider>(
     block_number: Option<BlockNumber>,
     provider: Arc<P>,
 ) -> Result<(i32, bool), UniswapV3MathError> {
+    assert_positive_tick_spacing(tick_spacing)?;
     let compressed = if tick < 0 && tick % tick_spacing != 0 {
         (tick / tick_spacing) - 1
     } else {
@@ -192,13 +457,37 @@ pub fn position(tick: i32) -> (i16, u8) {
     ((tick >> 8) as i16, (tick % 256) as u8)
 }
 
+//Position of the initialized bit of a tick, erroring with InvalidTick if the tick is out of bounds or not a
+//multiple of tick_spacing
+pub fn get_tick_bitmap_position(
+    tick: i32,
+    tick_spacing: i32,
+) -> Result<(i16, u8), UniswapV3MathError> {
+    assert_valid_tick(tick, tick_spacing)?;
+
+    Ok(position(tick / tick_spacing))
+}
+
+//Inverse of get_tick_bitmap_position
+pub fn tick_from_bitmap_position(word_pos: i16, bit_pos: u8, tick_spacing: i32) -> i32 {
+    (((word_pos as i32) << 8) + bit_pos as i32) * tick_spacing
+}
+
 #[cfg(test)]
+#[allow(clippy::bool_assert_comparison)]
 mod test {
-    use std::{collections::HashMap, vec};
+    use std::{collections::HashMap, ops::Bound, vec};
 
-    use alloy::primitives::U256;
+    use alloy_primitives::{keccak256, U256};
 
-    use super::{flip_tick, next_initialized_tick_within_one_word};
+    use super::{
+        flip_tick, get_tick_bitmap_position, initialized_ticks_in_range, next_initialized_tick,
+        next_initialized_tick_within_one_word, tick_from_bitmap_position, TickBitmap,
+    };
+    use crate::{
+        error::UniswapV3MathError,
+        tick_math::{MAX_TICK, MIN_TICK},
+    };
 
     pub fn init_test_ticks() -> eyre::Result<HashMap<i16, U256>> {
         let test_ticks = vec![-200, -55, -4, 70, 78, 84, 139, 240, 535];
@@ -429,4 +718,436 @@ mod test {
 
         Ok(())
     }
+
+    #[test]
+    pub fn test_tick_bitmap() -> eyre::Result<()> {
+        let mut tick_bitmap = TickBitmap::new();
+        for tick in [-2000, -550, -40, 700, 780, 840, 1390, 2400, 5350] {
+            tick_bitmap.flip_tick(tick, 10)?;
+        }
+        let snapshot = tick_bitmap.clone();
+
+        assert!(tick_bitmap.is_initialized(780, 10));
+        assert!(tick_bitmap.is_initialized(-40, 10));
+        assert!(!tick_bitmap.is_initialized(790, 10));
+        assert!(!tick_bitmap.is_initialized(785, 10));
+
+        //lte returns the same tick if initialized
+        let (next, initialized) =
+            tick_bitmap.next_initialized_tick_within_one_word(780, 10, true)?;
+        assert_eq!(next, 780);
+        assert!(initialized);
+
+        //lte returns the tick directly to the left
+        let (next, initialized) =
+            tick_bitmap.next_initialized_tick_within_one_word(835, 10, true)?;
+        assert_eq!(next, 780);
+        assert!(initialized);
+
+        //gt returns the tick directly to the right
+        let (next, initialized) =
+            tick_bitmap.next_initialized_tick_within_one_word(780, 10, false)?;
+        assert_eq!(next, 840);
+        assert!(initialized);
+
+        //gt returns the tick directly to the right for negative ticks
+        let (next, initialized) =
+            tick_bitmap.next_initialized_tick_within_one_word(-555, 10, false)?;
+        assert_eq!(next, -550);
+        assert!(initialized);
+
+        //gt does not exceed the word boundary
+        let (next, initialized) =
+            tick_bitmap.next_initialized_tick_within_one_word(2400, 10, false)?;
+        assert_eq!(next, 2550);
+        assert!(!initialized);
+
+        //flipping twice restores the original state
+        tick_bitmap.flip_tick(100, 10)?;
+        assert!(tick_bitmap.is_initialized(100, 10));
+        tick_bitmap.flip_tick(100, 10)?;
+        assert!(!tick_bitmap.is_initialized(100, 10));
+        assert_eq!(tick_bitmap, snapshot);
+
+        //empty words are removed
+        tick_bitmap.flip_tick(5350, 10)?;
+        assert!(!tick_bitmap.0.contains_key(&2));
+        tick_bitmap.flip_tick(5350, 10)?;
+        assert_eq!(tick_bitmap, snapshot);
+
+        //rejects ticks not on the tick spacing or out of bounds
+        assert!(matches!(
+            tick_bitmap.flip_tick(15, 10),
+            Err(UniswapV3MathError::InvalidTick { tick: 15, .. })
+        ));
+        assert!(matches!(
+            tick_bitmap.flip_tick(887280, 10),
+            Err(UniswapV3MathError::InvalidTick { tick: 887280, .. })
+        ));
+        assert_eq!(tick_bitmap, snapshot);
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_iter_initialized() -> eyre::Result<()> {
+        let mut tick_bitmap = TickBitmap::new();
+        let ticks = [
+            -887220, -2000, -550, -40, 700, 780, 840, 1390, 2400, 5350, 887220,
+        ];
+        for tick in ticks {
+            tick_bitmap.flip_tick(tick, 10)?;
+        }
+
+        //all ticks in ascending order
+        assert_eq!(
+            tick_bitmap.iter_initialized(.., 10)?.collect::<Vec<_>>(),
+            ticks
+        );
+
+        //inclusive and exclusive bounds
+        assert_eq!(
+            tick_bitmap
+                .iter_initialized(-550..=780, 10)?
+                .collect::<Vec<_>>(),
+            [-550, -40, 700, 780]
+        );
+        assert_eq!(
+            tick_bitmap
+                .iter_initialized(-550..780, 10)?
+                .collect::<Vec<_>>(),
+            [-550, -40, 700]
+        );
+        assert_eq!(
+            tick_bitmap
+                .iter_initialized((Bound::Excluded(-550), Bound::Unbounded), 10)?
+                .take(2)
+                .collect::<Vec<_>>(),
+            [-40, 700]
+        );
+
+        //bounds between multiples of the tick spacing
+        assert_eq!(
+            tick_bitmap
+                .iter_initialized(-555..=-35, 10)?
+                .collect::<Vec<_>>(),
+            [-550, -40]
+        );
+        assert_eq!(tick_bitmap.iter_initialized(-545..=-45, 10)?.count(), 0);
+        assert_eq!(tick_bitmap.iter_initialized(1400..2400, 10)?.count(), 0);
+
+        //bounds beyond the tick range are clamped to it
+        assert_eq!(
+            tick_bitmap
+                .iter_initialized(i32::MIN..=i32::MAX, 10)?
+                .collect::<Vec<_>>(),
+            ticks
+        );
+        assert_eq!(
+            tick_bitmap
+                .iter_initialized((Bound::Excluded(i32::MIN), Bound::Excluded(i32::MAX)), 10)?
+                .collect::<Vec<_>>(),
+            ticks
+        );
+        assert_eq!(
+            tick_bitmap
+                .iter_initialized(MAX_TICK + 1..=i32::MAX, 10)?
+                .count(),
+            0
+        );
+        assert_eq!(tick_bitmap.iter_initialized(..MIN_TICK, 10)?.count(), 0);
+        assert_eq!(
+            initialized_ticks_in_range(&tick_bitmap, i32::MIN, i32::MAX, 10)?
+                .rev()
+                .collect::<Vec<_>>(),
+            ticks.iter().rev().copied().collect::<Vec<_>>()
+        );
+
+        for tick_spacing in [0, -10] {
+            assert!(matches!(
+                tick_bitmap.iter_initialized(.., tick_spacing),
+                Err(UniswapV3MathError::InvalidTickSpacing { .. })
+            ));
+            assert!(matches!(
+                initialized_ticks_in_range(&tick_bitmap, MIN_TICK, MAX_TICK, tick_spacing),
+                Err(UniswapV3MathError::InvalidTickSpacing { .. })
+            ));
+        }
+        Ok(())
+    }
+
+    #[cfg(feature = "serde")]
+    #[test]
+    pub fn test_tick_bitmap_serde() -> eyre::Result<()> {
+        let tick_bitmap = TickBitmap::from(init_test_ticks()?);
+
+        let json = serde_json::to_string(&tick_bitmap)?;
+        assert_eq!(serde_json::from_str::<TickBitmap>(&json)?, tick_bitmap);
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_next_initialized_tick() -> eyre::Result<()> {
+        let tick_bitmap = init_test_ticks()?;
+
+        //returns the tick within the same word
+        assert_eq!(
+            next_initialized_tick(&tick_bitmap, 78, 1, true, MIN_TICK)?,
+            Some(78)
+        );
+        assert_eq!(
+            next_initialized_tick(&tick_bitmap, 78, 1, false, MAX_TICK)?,
+            Some(84)
+        );
+        assert_eq!(
+            next_initialized_tick(&tick_bitmap, -5, 1, true, MIN_TICK)?,
+            Some(-55)
+        );
+        assert_eq!(
+            next_initialized_tick(&tick_bitmap, -1, 1, false, MAX_TICK)?,
+            Some(70)
+        );
+
+        //walks past empty words
+        assert_eq!(
+            next_initialized_tick(&tick_bitmap, 240, 1, false, MAX_TICK)?,
+            Some(535)
+        );
+        assert_eq!(
+            next_initialized_tick(&tick_bitmap, 1000, 1, true, MIN_TICK)?,
+            Some(535)
+        );
+
+        //ticks exactly on word boundaries
+        assert_eq!(
+            next_initialized_tick(&tick_bitmap, 255, 1, true, MIN_TICK)?,
+            Some(240)
+        );
+        assert_eq!(
+            next_initialized_tick(&tick_bitmap, 256, 1, true, MIN_TICK)?,
+            Some(240)
+        );
+        assert_eq!(
+            next_initialized_tick(&tick_bitmap, 255, 1, false, MAX_TICK)?,
+            Some(535)
+        );
+        assert_eq!(
+            next_initialized_tick(&tick_bitmap, -256, 1, true, MIN_TICK)?,
+            None
+        );
+        assert_eq!(
+            next_initialized_tick(&tick_bitmap, -257, 1, false, MAX_TICK)?,
+            Some(-200)
+        );
+
+        //nothing beyond the outermost ticks
+        assert_eq!(
+            next_initialized_tick(&tick_bitmap, 535, 1, false, MAX_TICK)?,
+            None
+        );
+        assert_eq!(
+            next_initialized_tick(&tick_bitmap, -201, 1, true, MIN_TICK)?,
+            None
+        );
+
+        //the search limit is inclusive
+        assert_eq!(
+            next_initialized_tick(&tick_bitmap, 240, 1, false, 535)?,
+            Some(535)
+        );
+        assert_eq!(
+            next_initialized_tick(&tick_bitmap, 240, 1, false, 534)?,
+            None
+        );
+        assert_eq!(
+            next_initialized_tick(&tick_bitmap, 69, 1, true, -4)?,
+            Some(-4)
+        );
+        assert_eq!(next_initialized_tick(&tick_bitmap, 69, 1, true, -3)?, None);
+
+        //completely empty bitmaps
+        let empty = TickBitmap::new();
+        assert_eq!(next_initialized_tick(&empty, 0, 1, true, MIN_TICK)?, None);
+        assert_eq!(next_initialized_tick(&empty, 0, 1, false, MAX_TICK)?, None);
+        assert_eq!(
+            next_initialized_tick(&empty.0, 0, 60, true, MIN_TICK)?,
+            None
+        );
+        assert_eq!(
+            next_initialized_tick(&empty.0, 0, 60, false, MAX_TICK)?,
+            None
+        );
+
+        //negative ticks that are not multiples of the tick spacing are compressed towards negative infinity
+        let mut tick_bitmap = TickBitmap::new();
+        for tick in [-887220, -15360, -60] {
+            tick_bitmap.flip_tick(tick, 60)?;
+        }
+        assert_eq!(
+            next_initialized_tick(&tick_bitmap, -1, 60, true, MIN_TICK)?,
+            Some(-60)
+        );
+        assert_eq!(
+            next_initialized_tick(&tick_bitmap, -61, 60, true, MIN_TICK)?,
+            Some(-15360)
+        );
+        assert_eq!(
+            next_initialized_tick(&tick_bitmap, -15361, 60, true, MIN_TICK)?,
+            Some(-887220)
+        );
+        assert_eq!(
+            next_initialized_tick(&tick_bitmap, -15361, 60, false, MAX_TICK)?,
+            Some(-15360)
+        );
+        assert_eq!(
+            next_initialized_tick(&tick_bitmap, -15360, 60, false, MAX_TICK)?,
+            Some(-60)
+        );
+        assert_eq!(
+            next_initialized_tick(&tick_bitmap, MIN_TICK, 60, false, MAX_TICK)?,
+            Some(-887220)
+        );
+        assert_eq!(
+            next_initialized_tick(&tick_bitmap, -887221, 60, true, MIN_TICK)?,
+            None
+        );
+        assert_eq!(
+            next_initialized_tick(&tick_bitmap, -60, 60, false, MAX_TICK)?,
+            None
+        );
+
+        //the tick spacing is validated before dividing by it
+        for tick_spacing in [0, -60] {
+            assert!(matches!(
+                next_initialized_tick(&tick_bitmap, 0, tick_spacing, true, MIN_TICK),
+                Err(UniswapV3MathError::InvalidTickSpacing { .. })
+            ));
+            assert!(matches!(
+                next_initialized_tick_within_one_word(&tick_bitmap.0, 0, tick_spacing, false),
+                Err(UniswapV3MathError::InvalidTickSpacing { .. })
+            ));
+        }
+        Ok(())
+    }
+
+    #[test]
+    pub fn test_initialized_ticks_in_range() -> eyre::Result<()> {
+        let tick_bitmap = init_test_ticks()?;
+        assert_eq!(
+            initialized_ticks_in_range(&tick_bitmap, -200, 240, 1)?.collect::<Vec<_>>(),
+            [-200, -55, -4, 70, 78, 84, 139, 240]
+        );
+        assert_eq!(
+            initialized_ticks_in_range(&tick_bitmap, -199, 239, 1)?
+                .rev()
+                .collect::<Vec<_>>(),
+            [139, 84, 78, 70, -4, -55]
+        );
+
+        //both ends can be consumed from the same iterator
+        let mut ticks = initialized_ticks_in_range(&tick_bitmap, MIN_TICK, MAX_TICK, 1)?;
+        assert_eq!(ticks.next(), Some(-200));
+        assert_eq!(ticks.next_back(), Some(535));
+        assert_eq!(ticks.next_back(), Some(240));
+        assert_eq!(ticks.next(), Some(-55));
+        assert_eq!(ticks.count(), 5);
+
+        //empty bitmaps and empty ranges
+        assert_eq!(
+            initialized_ticks_in_range(&TickBitmap::new(), MIN_TICK, MAX_TICK, 1)?.count(),
+            0
+        );
+        assert_eq!(
+            initialized_ticks_in_range(&tick_bitmap, 85, 138, 1)?.count(),
+            0
+        );
+        assert_eq!(
+            initialized_ticks_in_range(&tick_bitmap, 240, -200, 1)?.count(),
+            0
+        );
+
+        //randomized bitmaps against a scan over every multiple of the tick spacing
+        for i in 0_u64..32 {
+            let seed = keccak256(i.to_be_bytes());
+            let tick_spacing = [1, 10, 60, 200][seed[0] as usize % 4];
+
+            let mut tick_bitmap = TickBitmap::new();
+            for word_pos in -8_i16..8 {
+                let hash = keccak256([seed.as_slice(), &word_pos.to_be_bytes()].concat());
+                // leave some words empty and keep the others sparse
+                if hash[0] % 3 != 0 {
+                    let word = U256::from_be_bytes(hash.0)
+                        & U256::from_be_bytes(keccak256(hash).0)
+                        & U256::from_be_bytes(keccak256(keccak256(hash)).0);
+                    if !word.is_zero() {
+                        tick_bitmap.0.insert(word_pos, word);
+                    }
+                }
+            }
+
+            let span = 2048 * tick_spacing;
+            let tick_lower = i32::from_be_bytes(seed[4..8].try_into()?).rem_euclid(span) - span;
+            let tick_upper = i32::from_be_bytes(seed[8..12].try_into()?).rem_euclid(span);
+
+            let expected = (tick_lower..=tick_upper)
+                .filter(|tick| tick_bitmap.is_initialized(*tick, tick_spacing))
+                .collect::<Vec<_>>();
+
+            assert_eq!(
+                initialized_ticks_in_range(&tick_bitmap, tick_lower, tick_upper, tick_spacing)?
+                    .collect::<Vec<_>>(),
+                expected
+            );
+            assert_eq!(
+                initialized_ticks_in_range(&tick_bitmap.0, tick_lower, tick_upper, tick_spacing)?
+                    .rev()
+                    .collect::<Vec<_>>(),
+                expected.iter().rev().copied().collect::<Vec<_>>()
+            );
+            assert_eq!(
+                tick_bitmap
+                    .iter_initialized(tick_lower..=tick_upper, tick_spacing)?
+                    .collect::<Vec<_>>(),
+                expected
+            );
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_get_tick_bitmap_position() -> eyre::Result<()> {
+        assert_eq!(get_tick_bitmap_position(0, 1)?, (0, 0));
+        assert_eq!(get_tick_bitmap_position(255, 1)?, (0, 255));
+        assert_eq!(get_tick_bitmap_position(256, 1)?, (1, 0));
+        assert_eq!(get_tick_bitmap_position(-1, 1)?, (-1, 255));
+        assert_eq!(get_tick_bitmap_position(-256, 1)?, (-1, 0));
+        assert_eq!(get_tick_bitmap_position(-257, 1)?, (-2, 255));
+        assert_eq!(get_tick_bitmap_position(-60, 60)?, (-1, 255));
+        assert_eq!(get_tick_bitmap_position(MIN_TICK, 1)?, (-3466, 24));
+        assert_eq!(get_tick_bitmap_position(MAX_TICK, 1)?, (3465, 232));
+
+        for (tick, tick_spacing) in [(30, 60), (MAX_TICK + 1, 1), (0, 0)] {
+            assert!(matches!(
+                get_tick_bitmap_position(tick, tick_spacing),
+                Err(UniswapV3MathError::InvalidTick { .. })
+            ));
+        }
+
+        //round trips for ticks across the whole range
+        for tick_spacing in [1, 10, 60, 200] {
+            let min_compressed = MIN_TICK / tick_spacing;
+            let max_compressed = MAX_TICK / tick_spacing;
+            for compressed in (min_compressed..=max_compressed)
+                .step_by(7)
+                .chain([max_compressed])
+            {
+                let tick = compressed * tick_spacing;
+                let (word_pos, bit_pos) = get_tick_bitmap_position(tick, tick_spacing)?;
+                assert_eq!(
+                    tick_from_bitmap_position(word_pos, bit_pos, tick_spacing),
+                    tick
+                );
+            }
+        }
+        Ok(())
+    }
 }
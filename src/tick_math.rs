@@ -1,12 +1,7 @@
-use alloy::primitives::{I256, U256};
-use std::ops::{BitOr, Neg, Shl, Shr};
+use alloy_primitives::{I256, U256};
+use core::ops::{BitOr, Shl, Shr};
 
-use crate::{
-    error::UniswapV3MathError, U256_1, U256_1024, U256_127, U256_128, U256_131072, U256_15,
-    U256_16, U256_16384, U256_2, U256_2048, U256_255, U256_256, U256_262144, U256_3, U256_32,
-    U256_32768, U256_4, U256_4096, U256_5, U256_512, U256_524288, U256_6, U256_64, U256_65536,
-    U256_7, U256_8, U256_8192, U256_MAX_TICK,
-};
+use crate::{error::UniswapV3MathError, fixed_point::SqrtPriceX96, U256_127, U256_128};
 
 pub const MIN_TICK: i32 = -887272;
 pub const MAX_TICK: i32 = -MIN_TICK;
@@ -29,167 +24,127 @@ pub const TICK_HIGH: I256 = I256::from_raw(U256::from_limbs([
     0,
 ]));
 
-pub fn get_sqrt_ratio_at_tick(tick: i32) -> Result<U256, UniswapV3MathError> {
-    let abs_tick = if tick < 0 {
-        U256::from(tick.neg())
-    } else {
-        U256::from(tick)
-    };
+// ratio multipliers of UniswapV3 TickMath, sqrt(1.0001)^-(2^i) as Q128.128 for the bit i of the absolute tick
+const SQRT_RATIO_MULTIPLIERS: [u128; 20] = [
+    0xfffcb933bd6fad37aa2d162d1a594001,
+    0xfff97272373d413259a46990580e213a,
+    0xfff2e50f5f656932ef12357cf3c7fdcc,
+    0xffe5caca7e10e4e61c3624eaa0941cd0,
+    0xffcb9843d60f6159c9db58835c926644,
+    0xff973b41fa98c081472e6896dfb254c0,
+    0xff2ea16466c96a3843ec78b326b52861,
+    0xfe5dee046a99a2a811c461f1969c3053,
+    0xfcbe86c7900a88aedcffc83b479aa3a4,
+    0xf987a7253ac413176f2b074cf7815e54,
+    0xf3392b0822b70005940c7a398e4b70f3,
+    0xe7159475a2c29b7443b29c7fa6e889d9,
+    0xd097f3bdfd2022b8845ad8f792aa5825,
+    0xa9f746462d870fdf8a65dc1f90e061e5,
+    0x70d869a156d2a1b890bb3df62baf32f7,
+    0x31be135f97d08fd981231505542fcfa6,
+    0x09aa508b5b7a84e1c677de54f3e99bc9,
+    0x005d6af8dedb81196699c329225ee604,
+    0x00002216e584f5fa1ea926041bedfe98,
+    0x00000000048a170391f7dc42444e8fa2,
+];
+
+// (a * b) >> 128 for the 256 bit product of two u128
+const fn mul_shift_128(a: u128, b: u128) -> u128 {
+    const MASK: u128 = u64::MAX as u128;
+    let (a_0, a_1) = (a & MASK, a >> 64);
+    let (b_0, b_1) = (b & MASK, b >> 64);
+    let (mid_0, mid_1) = (a_1 * b_0, a_0 * b_1);
+    let carry = ((a_0 * b_0) >> 64) + (mid_0 & MASK) + (mid_1 & MASK);
+
+    a_1 * b_1 + (mid_0 >> 64) + (mid_1 >> 64) + (carry >> 64)
+}
 
-    if abs_tick > U256_MAX_TICK {
-        return Err(UniswapV3MathError::T);
+// U256::MAX / divisor as little endian limbs, by long division one bit at a time
+const fn div_max_by(divisor: u128) -> [u64; 4] {
+    let mut quotient = [0u64; 4];
+    let mut remainder = 0u128;
+    let mut i = 256;
+    while i > 0 {
+        i -= 1;
+        // every bit of U256::MAX is set
+        let carry = remainder >> 127 == 1;
+        remainder = (remainder << 1) | 1;
+        if carry || remainder >= divisor {
+            remainder = remainder.wrapping_sub(divisor);
+            quotient[i / 64] |= 1 << (i % 64);
+        }
     }
+    quotient
+}
 
-    let mut ratio = if abs_tick & (U256_1) != U256::ZERO {
-        U256::from_limbs([12262481743371124737, 18445821805675392311, 0, 0])
-    } else {
-        U256::from_limbs([0, 0, 1, 0])
-    };
+// returns (uint160 sqrtPriceX96)
+// const so that tick derived prices can be computed at compile time, U256 arithmetic is not const so the ratio
+// is computed on u128 limbs
+pub const fn get_sqrt_ratio_at_tick(tick: i32) -> Result<SqrtPriceX96, UniswapV3MathError> {
+    let abs_tick = tick.unsigned_abs();
 
-    if !(abs_tick & U256_2).is_zero() {
-        ratio = (ratio * U256::from_limbs([6459403834229662010, 18444899583751176498, 0, 0])) >> 128
-    }
-    if !(abs_tick & U256_4).is_zero() {
-        ratio =
-            (ratio * U256::from_limbs([17226890335427755468, 18443055278223354162, 0, 0])) >> 128
-    }
-    if !(abs_tick & U256_8).is_zero() {
-        ratio = (ratio * U256::from_limbs([2032852871939366096, 18439367220385604838, 0, 0])) >> 128
-    }
-    if !(abs_tick & U256_16).is_zero() {
-        ratio =
-            (ratio * U256::from_limbs([14545316742740207172, 18431993317065449817, 0, 0])) >> 128
-    }
-    if !(abs_tick & U256_32).is_zero() {
-        ratio = (ratio * U256::from_limbs([5129152022828963008, 18417254355718160513, 0, 0])) >> 128
-    }
-    if !(abs_tick & U256_64).is_zero() {
-        ratio = (ratio * U256::from_limbs([4894419605888772193, 18387811781193591352, 0, 0])) >> 128
-    }
-    if !(abs_tick & U256_128).is_zero() {
-        ratio = (ratio * U256::from_limbs([1280255884321894483, 18329067761203520168, 0, 0])) >> 128
-    }
-    if !(abs_tick & U256_256).is_zero() {
-        ratio =
-            (ratio * U256::from_limbs([15924666964335305636, 18212142134806087854, 0, 0])) >> 128
-    }
-    if !(abs_tick & U256_512).is_zero() {
-        ratio = (ratio * U256::from_limbs([8010504389359918676, 17980523815641551639, 0, 0])) >> 128
-    }
-    if !(abs_tick & U256_1024).is_zero() {
-        ratio =
-            (ratio * U256::from_limbs([10668036004952895731, 17526086738831147013, 0, 0])) >> 128
-    }
-    if !(abs_tick & U256_2048).is_zero() {
-        ratio = (ratio * U256::from_limbs([4878133418470705625, 16651378430235024244, 0, 0])) >> 128
-    }
-    if !(abs_tick & U256_4096).is_zero() {
-        ratio = (ratio * U256::from_limbs([9537173718739605541, 15030750278693429944, 0, 0])) >> 128
-    }
-    if !(abs_tick & U256_8192).is_zero() {
-        ratio = (ratio * U256::from_limbs([9972618978014552549, 12247334978882834399, 0, 0])) >> 128
-    }
-    if !(abs_tick & U256_16384).is_zero() {
-        ratio = (ratio * U256::from_limbs([10428997489610666743, 8131365268884726200, 0, 0])) >> 128
-    }
-    if !(abs_tick & U256_32768).is_zero() {
-        ratio = (ratio * U256::from_limbs([9305304367709015974, 3584323654723342297, 0, 0])) >> 128
-    }
-    if !(abs_tick & U256_65536).is_zero() {
-        ratio = (ratio * U256::from_limbs([14301143598189091785, 696457651847595233, 0, 0])) >> 128
-    }
-    if !(abs_tick & U256_131072).is_zero() {
-        ratio = (ratio * U256::from_limbs([7393154844743099908, 26294789957452057, 0, 0])) >> 128
-    }
-    if !(abs_tick & U256_262144).is_zero() {
-        ratio = (ratio * U256::from_limbs([2209338891292245656, 37481735321082, 0, 0])) >> 128
-    }
-    if !(abs_tick & U256_524288).is_zero() {
-        ratio = (ratio * U256::from_limbs([10518117631919034274, 76158723, 0, 0])) >> 128
+    if abs_tick > MAX_TICK as u32 {
+        return Err(UniswapV3MathError::TickOutOfBounds { tick });
     }
 
-    if tick > 0 {
-        ratio = U256::MAX / ratio;
+    // the ratio starts at 1 in Q128.128, which does not fit a u128, as long as no bit was applied
+    let mut ratio = 0u128;
+    let mut is_one = true;
+    let mut i = 0;
+    while i < SQRT_RATIO_MULTIPLIERS.len() {
+        if abs_tick & (1 << i) != 0 {
+            ratio = if is_one {
+                SQRT_RATIO_MULTIPLIERS[i]
+            } else {
+                mul_shift_128(ratio, SQRT_RATIO_MULTIPLIERS[i])
+            };
+            is_one = false;
+        }
+        i += 1;
     }
 
-    Ok((ratio >> 32)
-        + if (ratio.wrapping_rem(U256_1 << 32)).is_zero() {
-            U256::ZERO
-        } else {
-            U256_1
-        })
-}
-
-pub fn get_tick_at_sqrt_ratio(sqrt_price_x_96: U256) -> Result<i32, UniswapV3MathError> {
-    if !(sqrt_price_x_96 >= MIN_SQRT_RATIO && sqrt_price_x_96 < MAX_SQRT_RATIO) {
-        return Err(UniswapV3MathError::R);
+    if is_one {
+        return Ok(SqrtPriceX96(U256::from_limbs([0, 1 << 32, 0, 0])));
     }
 
-    let ratio: U256 = sqrt_price_x_96.shl(32);
-    let mut r = ratio;
-    let mut msb = U256::ZERO;
-
-    let mut f = if r > U256::from_limbs([18446744073709551615, 18446744073709551615, 0, 0]) {
-        U256_1.shl(U256_7)
+    let limbs = if tick > 0 {
+        div_max_by(ratio)
     } else {
-        U256::ZERO
+        [ratio as u64, (ratio >> 64) as u64, 0, 0]
     };
-    msb = msb.bitor(f);
-    r = r.shr(f);
 
-    f = if r > U256::from_limbs([18446744073709551615, 0, 0, 0]) {
-        U256_1.shl(U256_6)
-    } else {
-        U256::ZERO
-    };
-    msb = msb.bitor(f);
-    r = r.shr(f);
-
-    f = if r > U256::from_limbs([4294967295, 0, 0, 0]) {
-        U256_1.shl(U256_5)
-    } else {
-        U256::ZERO
-    };
-    msb = msb.bitor(f);
-    r = r.shr(f);
-
-    f = if r > U256::from_limbs([65535, 0, 0, 0]) {
-        U256_1.shl(U256_4)
-    } else {
-        U256::ZERO
-    };
-    msb = msb.bitor(f);
-    r = r.shr(f);
-
-    f = if r > U256_255 {
-        U256_1.shl(U256_3)
-    } else {
-        U256::ZERO
-    };
-    msb = msb.bitor(f);
-    r = r.shr(f);
-
-    f = if r > U256_15 {
-        U256_1.shl(U256_2)
-    } else {
-        U256::ZERO
-    };
-    msb = msb.bitor(f);
-    r = r.shr(f);
+    // shift right by 32 bits, rounding up
+    let round_up = limbs[0] & (u32::MAX as u64) != 0;
+    let mut shifted = [
+        (limbs[0] >> 32) | (limbs[1] << 32),
+        (limbs[1] >> 32) | (limbs[2] << 32),
+        (limbs[2] >> 32) | (limbs[3] << 32),
+        limbs[3] >> 32,
+    ];
+    // the result is below 2^160 so the increment can not carry past the third limb
+    if round_up {
+        shifted[0] = shifted[0].wrapping_add(1);
+        if shifted[0] == 0 {
+            shifted[1] = shifted[1].wrapping_add(1);
+            if shifted[1] == 0 {
+                shifted[2] += 1;
+            }
+        }
+    }
 
-    f = if r > U256_3 {
-        U256_1.shl(U256_1)
-    } else {
-        U256::ZERO
-    };
-    msb = msb.bitor(f);
-    r = r.shr(f);
+    Ok(SqrtPriceX96(U256::from_limbs(shifted)))
+}
 
-    f = if r > U256_1 { U256_1 } else { U256::ZERO };
+pub fn get_tick_at_sqrt_ratio(sqrt_price_x_96: SqrtPriceX96) -> Result<i32, UniswapV3MathError> {
+    let sqrt_price_x_96 = sqrt_price_x_96.0;
+    if !(sqrt_price_x_96 >= MIN_SQRT_RATIO && sqrt_price_x_96 < MAX_SQRT_RATIO) {
+        return Err(UniswapV3MathError::SqrtPriceOutOfBounds { sqrt_price_x_96 });
+    }
 
-    msb = msb.bitor(f);
+    let ratio: U256 = sqrt_price_x_96.shl(32);
+    let msb = U256::from(255 - ratio.leading_zeros());
 
-    r = if msb >= U256_128 {
+    let mut r = if msb >= U256_128 {
         ratio.shr(msb - U256_127)
     } else {
         ratio.shl(U256_127 - msb)
@@ -217,7 +172,7 @@ pub fn get_tick_at_sqrt_ratio(sqrt_price_x_96: U256) -> Result<i32, UniswapV3Mat
 
     let tick = if tick_low == tick_high {
         tick_low
-    } else if get_sqrt_ratio_at_tick(tick_high)? <= sqrt_price_x_96 {
+    } else if get_sqrt_ratio_at_tick(tick_high)?.0 <= sqrt_price_x_96 {
         tick_high
     } else {
         tick_low
@@ -226,26 +181,179 @@ pub fn get_tick_at_sqrt_ratio(sqrt_price_x_96: U256) -> Result<i32, UniswapV3Mat
     Ok(tick)
 }
 
+//Largest multiple of tick_spacing that is at most MAX_TICK
+pub fn max_tick_for_spacing(tick_spacing: i32) -> Result<i32, UniswapV3MathError> {
+    if tick_spacing <= 0 {
+        return Err(UniswapV3MathError::InvalidTickSpacing { tick_spacing });
+    }
+
+    Ok((MAX_TICK / tick_spacing) * tick_spacing)
+}
+
+//Smallest multiple of tick_spacing that is at least MIN_TICK
+pub fn min_tick_for_spacing(tick_spacing: i32) -> Result<i32, UniswapV3MathError> {
+    if tick_spacing <= 0 {
+        return Err(UniswapV3MathError::InvalidTickSpacing { tick_spacing });
+    }
+
+    Ok((MIN_TICK / tick_spacing) * tick_spacing)
+}
+
+// returns (uint128), Tick.tickSpacingToMaxLiquidityPerTick: u128::MAX split evenly across the usable ticks
+pub fn tick_spacing_to_max_liquidity_per_tick(
+    tick_spacing: i32,
+) -> Result<u128, UniswapV3MathError> {
+    let min_tick = min_tick_for_spacing(tick_spacing)?;
+    let max_tick = max_tick_for_spacing(tick_spacing)?;
+    let num_ticks = ((max_tick - min_tick) / tick_spacing) as u128 + 1;
+
+    Ok(u128::MAX / num_ticks)
+}
+
+//Price of token0 in token1 at the tick, 1.0001^tick, in raw token units i.e. not adjusted for decimals.
+//f64 keeps about 15 significant digits, use get_sqrt_ratio_at_tick where the exact ratio matters
+#[cfg(feature = "std")]
+pub fn tick_to_price(tick: i32) -> f64 {
+    1.0001_f64.powi(tick)
+}
+
+//Tick of a raw price, floor(log_1.0001(price)), rounded to the nearest multiple of tick_spacing within the tick
+//bounds. The f64 logarithm can be off by one tick for prices right at a tick boundary, use
+//get_tick_at_sqrt_ratio where the exact tick matters
+#[cfg(feature = "std")]
+pub fn price_to_tick(price: f64, tick_spacing: i32) -> Result<i32, UniswapV3MathError> {
+    if !price.is_finite() || price <= 0.0 {
+        return Err(UniswapV3MathError::InvalidPrice { price });
+    }
+    if tick_spacing <= 0 {
+        return Err(UniswapV3MathError::InvalidTickSpacing { tick_spacing });
+    }
+
+    let tick = (price.ln() / 1.0001_f64.ln()).floor();
+    if tick < MIN_TICK as f64 || tick > MAX_TICK as f64 {
+        return Err(UniswapV3MathError::TickOutOfBounds { tick: tick as i32 });
+    }
+
+    let tick = (tick / tick_spacing as f64).round() as i32 * tick_spacing;
+
+    // the nearest multiple may lie just beyond the bounds
+    Ok(if tick > MAX_TICK {
+        tick - tick_spacing
+    } else if tick < MIN_TICK {
+        tick + tick_spacing
+    } else {
+        tick
+    })
+}
+
 #[cfg(test)]
 mod test {
     use super::*;
+    use alloy_primitives::uint;
+    use proptest::prelude::*;
     use std::{ops::Sub, str::FromStr};
 
     #[test]
     fn test_get_sqrt_ratio_at_tick_bounds() {
         // the function should return an error if the tick is out of bounds
         if let Err(err) = get_sqrt_ratio_at_tick(MIN_TICK - 1) {
-            assert!(matches!(err, UniswapV3MathError::T));
+            assert!(
+                matches!(err, UniswapV3MathError::TickOutOfBounds { tick } if tick == MIN_TICK - 1)
+            );
         } else {
             panic!("get_qrt_ratio_at_tick did not respect lower tick bound")
         }
         if let Err(err) = get_sqrt_ratio_at_tick(MAX_TICK + 1) {
-            assert!(matches!(err, UniswapV3MathError::T));
+            assert!(
+                matches!(err, UniswapV3MathError::TickOutOfBounds { tick } if tick == MAX_TICK + 1)
+            );
         } else {
             panic!("get_qrt_ratio_at_tick did not respect upper tick bound")
         }
     }
 
+    // evaluated at compile time. Result::unwrap is not const and the error can not be dropped in a const, so the
+    // result is forgotten once the ratio is copied out
+    const SQRT_RATIO_AT_500: SqrtPriceX96 = {
+        let result = get_sqrt_ratio_at_tick(500);
+        let sqrt_ratio = match &result {
+            Ok(sqrt_ratio) => *sqrt_ratio,
+            Err(_) => panic!("tick 500 is in bounds"),
+        };
+        core::mem::forget(result);
+        sqrt_ratio
+    };
+
+    #[test]
+    fn test_get_sqrt_ratio_at_tick_const() {
+        assert_eq!(
+            SQRT_RATIO_AT_500,
+            U256::from_str("81233731461783161732293370115").unwrap()
+        );
+        assert_eq!(SQRT_RATIO_AT_500, get_sqrt_ratio_at_tick(500).unwrap());
+
+        const SQRT_RATIO_AT_0: Result<SqrtPriceX96, UniswapV3MathError> = get_sqrt_ratio_at_tick(0);
+        assert_eq!(SQRT_RATIO_AT_0.unwrap(), U256::from(1) << 96);
+    }
+
+    // TickMath.getSqrtRatioAtTick on U256, the way the function was written before it became const. The
+    // multipliers are the Solidity literals and not SQRT_RATIO_MULTIPLIERS, so that a typo in either shows up
+    fn reference_sqrt_ratio_at_tick(tick: i32) -> U256 {
+        let abs_tick = tick.unsigned_abs();
+        let multipliers = [
+            uint!(0xfff97272373d413259a46990580e213a_U256),
+            uint!(0xfff2e50f5f656932ef12357cf3c7fdcc_U256),
+            uint!(0xffe5caca7e10e4e61c3624eaa0941cd0_U256),
+            uint!(0xffcb9843d60f6159c9db58835c926644_U256),
+            uint!(0xff973b41fa98c081472e6896dfb254c0_U256),
+            uint!(0xff2ea16466c96a3843ec78b326b52861_U256),
+            uint!(0xfe5dee046a99a2a811c461f1969c3053_U256),
+            uint!(0xfcbe86c7900a88aedcffc83b479aa3a4_U256),
+            uint!(0xf987a7253ac413176f2b074cf7815e54_U256),
+            uint!(0xf3392b0822b70005940c7a398e4b70f3_U256),
+            uint!(0xe7159475a2c29b7443b29c7fa6e889d9_U256),
+            uint!(0xd097f3bdfd2022b8845ad8f792aa5825_U256),
+            uint!(0xa9f746462d870fdf8a65dc1f90e061e5_U256),
+            uint!(0x70d869a156d2a1b890bb3df62baf32f7_U256),
+            uint!(0x31be135f97d08fd981231505542fcfa6_U256),
+            uint!(0x9aa508b5b7a84e1c677de54f3e99bc9_U256),
+            uint!(0x5d6af8dedb81196699c329225ee604_U256),
+            uint!(0x2216e584f5fa1ea926041bedfe98_U256),
+            uint!(0x48a170391f7dc42444e8fa2_U256),
+        ];
+
+        let mut ratio = if abs_tick & 0x1 != 0 {
+            uint!(0xfffcb933bd6fad37aa2d162d1a594001_U256)
+        } else {
+            uint!(0x100000000000000000000000000000000_U256)
+        };
+        for (i, multiplier) in multipliers.into_iter().enumerate() {
+            if abs_tick & (2 << i) != 0 {
+                ratio = (ratio * multiplier) >> 128;
+            }
+        }
+
+        if tick > 0 {
+            ratio = U256::MAX / ratio;
+        }
+
+        (ratio >> 32) + U256::from((ratio & U256::from(u32::MAX)) != U256::ZERO)
+    }
+
+    #[test]
+    fn test_get_sqrt_ratio_at_tick_matches_reference() {
+        // every tick, so that every carry of mul_shift_128 and every quotient of div_max_by is covered
+        for tick in MIN_TICK..=MAX_TICK {
+            assert_eq!(
+                get_sqrt_ratio_at_tick(tick).unwrap(),
+                reference_sqrt_ratio_at_tick(tick),
+                "tick {tick}"
+            );
+        }
+        assert_eq!(reference_sqrt_ratio_at_tick(MIN_TICK), MIN_SQRT_RATIO);
+        assert_eq!(reference_sqrt_ratio_at_tick(MAX_TICK), MAX_SQRT_RATIO);
+    }
+
     #[test]
     fn test_get_sqrt_ratio_at_tick_values() {
         // test individual values for correct results
@@ -345,19 +453,164 @@ mod test {
     #[test]
     pub fn test_get_tick_at_sqrt_ratio() {
         //throws for too low
-        let result = get_tick_at_sqrt_ratio(MIN_SQRT_RATIO.sub(U256_1));
+        let result = get_tick_at_sqrt_ratio(SqrtPriceX96(MIN_SQRT_RATIO.sub(U256::from(1))));
         assert_eq!(result.unwrap_err().to_string(), "Second inequality must be < because the price can never reach the price at the max tick");
 
         //throws for too high
-        let result = get_tick_at_sqrt_ratio(MAX_SQRT_RATIO);
+        let result = get_tick_at_sqrt_ratio(SqrtPriceX96(MAX_SQRT_RATIO));
         assert_eq!(result.unwrap_err().to_string(), "Second inequality must be < because the price can never reach the price at the max tick");
 
         //ratio of min tick
-        let result = get_tick_at_sqrt_ratio(MIN_SQRT_RATIO).unwrap();
+        let result = get_tick_at_sqrt_ratio(SqrtPriceX96(MIN_SQRT_RATIO)).unwrap();
         assert_eq!(result, MIN_TICK);
 
         //ratio of min tick + 1
-        let result = get_tick_at_sqrt_ratio(U256::from_str("4295343490").unwrap()).unwrap();
+        let result =
+            get_tick_at_sqrt_ratio(SqrtPriceX96(U256::from_str("4295343490").unwrap())).unwrap();
         assert_eq!(result, MIN_TICK + 1);
     }
+
+    #[test]
+    pub fn test_get_tick_at_sqrt_ratio_round_trip() {
+        for tick in MIN_TICK..MAX_TICK {
+            let sqrt_ratio = get_sqrt_ratio_at_tick(tick).unwrap();
+            assert_eq!(get_tick_at_sqrt_ratio(sqrt_ratio).unwrap(), tick);
+        }
+    }
+
+    // sqrt ratios in [MIN_SQRT_RATIO, MAX_SQRT_RATIO) with a uniformly drawn bit length, so that every order of
+    // magnitude of the price is covered instead of almost only values near MAX_SQRT_RATIO. Shrinks towards
+    // shorter values with fewer bits set
+    fn sqrt_ratio_strategy() -> impl Strategy<Value = SqrtPriceX96> {
+        (33..=160_usize, any::<[u64; 3]>())
+            .prop_map(|(bits, limbs)| {
+                let low_bits = U256::from_limbs([limbs[0], limbs[1], limbs[2], 0]) >> (193 - bits);
+                SqrtPriceX96((U256::from(1) << (bits - 1)) | low_bits)
+            })
+            .prop_filter("outside the sqrt ratio range", |sqrt_ratio| {
+                (MIN_SQRT_RATIO..MAX_SQRT_RATIO).contains(&sqrt_ratio.0)
+            })
+    }
+
+    proptest! {
+        #[test]
+        fn test_get_tick_at_sqrt_ratio_of_get_sqrt_ratio_at_tick(tick in MIN_TICK..=MAX_TICK) {
+            let sqrt_ratio = get_sqrt_ratio_at_tick(tick).unwrap();
+
+            // the max tick ratio is excluded from the domain of get_tick_at_sqrt_ratio
+            if tick == MAX_TICK {
+                prop_assert!(get_tick_at_sqrt_ratio(sqrt_ratio).is_err());
+            } else {
+                prop_assert_eq!(get_tick_at_sqrt_ratio(sqrt_ratio).unwrap(), tick);
+            }
+        }
+
+        #[test]
+        fn test_get_sqrt_ratio_at_tick_is_increasing(tick in MIN_TICK..MAX_TICK) {
+            prop_assert!(get_sqrt_ratio_at_tick(tick).unwrap() < get_sqrt_ratio_at_tick(tick + 1).unwrap());
+        }
+
+        #[test]
+        fn test_get_sqrt_ratio_at_tick_of_get_tick_at_sqrt_ratio(sqrt_ratio in sqrt_ratio_strategy()) {
+            let tick = get_tick_at_sqrt_ratio(sqrt_ratio).unwrap();
+
+            // the sqrt ratio lies in [ratio(tick), ratio(tick + 1))
+            prop_assert!(get_sqrt_ratio_at_tick(tick).unwrap() <= sqrt_ratio);
+            prop_assert!(get_sqrt_ratio_at_tick(tick + 1).unwrap() > sqrt_ratio);
+        }
+    }
+
+    #[test]
+    fn test_tick_to_price() {
+        assert_eq!(tick_to_price(0), 1.0);
+        assert_eq!(tick_to_price(1), 1.0001);
+        assert!((tick_to_price(-1) * 1.0001 - 1.0).abs() < 1e-15);
+        assert!((tick_to_price(MAX_TICK) * tick_to_price(MIN_TICK) - 1.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_price_to_tick() -> eyre::Result<()> {
+        //USDC/WETH, 3000 USDC per WETH with USDC (6 decimals) as token0 and WETH (18 decimals) as token1
+        let price = 1e18 / 3000e6;
+        assert_eq!(price_to_tick(price, 1)?, 196256);
+        assert_eq!(price_to_tick(price, 10)?, 196260);
+        assert_eq!(price_to_tick(price, 60)?, 196260);
+
+        //WBTC/USDC, 60000 USDC per WBTC with WBTC (8 decimals) as token0 and USDC (6 decimals) as token1
+        let price = 60000e6 / 1e8;
+        assert_eq!(price_to_tick(price, 1)?, 63972);
+        assert_eq!(price_to_tick(price, 60)?, 63960);
+
+        //prices near the bounds snap to the last multiple of the tick spacing inside them
+        assert_eq!(price_to_tick(tick_to_price(MAX_TICK), 60)?, 887220);
+        assert_eq!(price_to_tick(tick_to_price(MIN_TICK + 1), 60)?, -887220);
+
+        //the f64 round trip is off by at most one tick
+        for tick in (MIN_TICK..MAX_TICK).step_by(997) {
+            let round_trip = price_to_tick(tick_to_price(tick), 1)?;
+            assert!((round_trip - tick).abs() <= 1, "{tick} {round_trip}");
+        }
+
+        for price in [0.0, -1.0, f64::NAN, f64::INFINITY, f64::NEG_INFINITY] {
+            assert!(matches!(
+                price_to_tick(price, 1),
+                Err(UniswapV3MathError::InvalidPrice { .. })
+            ));
+        }
+        assert!(matches!(
+            price_to_tick(1e300, 1),
+            Err(UniswapV3MathError::TickOutOfBounds { .. })
+        ));
+        assert!(matches!(
+            price_to_tick(1.0, 0),
+            Err(UniswapV3MathError::InvalidTickSpacing { tick_spacing: 0 })
+        ));
+        Ok(())
+    }
+
+    #[test]
+    fn test_tick_for_spacing() -> eyre::Result<()> {
+        for (tick_spacing, max_tick) in [(1, 887272), (10, 887270), (60, 887220), (200, 887200)] {
+            assert_eq!(max_tick_for_spacing(tick_spacing)?, max_tick);
+            assert_eq!(min_tick_for_spacing(tick_spacing)?, -max_tick);
+        }
+
+        for tick_spacing in [0, -60] {
+            assert!(matches!(
+                max_tick_for_spacing(tick_spacing),
+                Err(UniswapV3MathError::InvalidTickSpacing { .. })
+            ));
+            assert!(matches!(
+                min_tick_for_spacing(tick_spacing),
+                Err(UniswapV3MathError::InvalidTickSpacing { .. })
+            ));
+        }
+        Ok(())
+    }
+
+    #[test]
+    fn test_tick_spacing_to_max_liquidity_per_tick() -> eyre::Result<()> {
+        for (tick_spacing, max_liquidity_per_tick) in [
+            (1, 191757530477355301479181766273477_u128),
+            (10, 1917569901783203986719870431555990),
+            (60, 11505743598341114571880798222544994),
+            (200, 38350317471085141830651933667504588),
+            // the entire range is a single usable tick pair
+            (887272, u128::MAX / 3),
+            (887273, u128::MAX),
+        ] {
+            assert_eq!(
+                tick_spacing_to_max_liquidity_per_tick(tick_spacing)?,
+                max_liquidity_per_tick
+            );
+        }
+
+        for tick_spacing in [0, -1] {
+            assert!(matches!(
+                tick_spacing_to_max_liquidity_per_tick(tick_spacing),
+                Err(UniswapV3MathError::InvalidTickSpacing { .. })
+            ));
+        }
+        Ok(())
+    }
 }
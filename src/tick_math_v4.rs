@@ -0,0 +1,134 @@
+// Tick math for Uniswap V4 pools.
+//
+// V4's TickMath keeps the V3 tick range [-887272, 887272] and the same sqrt price bounds and magic
+// constants, so get_sqrt_price_at_tick and get_tick_at_sqrt_price return exactly the V3 results.
+// The differences are in naming (sqrt price instead of sqrt ratio), the allowed tick spacings, which
+// in V4 may be any value in [MIN_TICK_SPACING, MAX_TICK_SPACING] rather than a fee tier mapping, and
+// the min_usable_tick / max_usable_tick helpers that V4 exposes on the library.
+
+use alloy_primitives::U256;
+
+use crate::{error::UniswapV3MathError, fixed_point::SqrtPriceX96, tick_math};
+
+pub const MIN_TICK_V4: i32 = tick_math::MIN_TICK;
+pub const MAX_TICK_V4: i32 = tick_math::MAX_TICK;
+
+pub const MIN_TICK_SPACING: i32 = 1;
+pub const MAX_TICK_SPACING: i32 = i16::MAX as i32;
+
+pub const MIN_SQRT_RATIO_V4: U256 = tick_math::MIN_SQRT_RATIO;
+pub const MAX_SQRT_RATIO_V4: U256 = tick_math::MAX_SQRT_RATIO;
+
+// returns (uint160 sqrtPriceX96)
+pub fn get_sqrt_price_at_tick(tick: i32) -> Result<SqrtPriceX96, UniswapV3MathError> {
+    tick_math::get_sqrt_ratio_at_tick(tick)
+}
+
+// returns (int24 tick)
+pub fn get_tick_at_sqrt_price(sqrt_price_x_96: SqrtPriceX96) -> Result<i32, UniswapV3MathError> {
+    tick_math::get_tick_at_sqrt_ratio(sqrt_price_x_96)
+}
+
+// Returns the max usable tick for the given tick spacing, errors with InvalidTickSpacing outside of
+// [MIN_TICK_SPACING, MAX_TICK_SPACING]
+pub fn max_usable_tick(tick_spacing: i32) -> Result<i32, UniswapV3MathError> {
+    if !(MIN_TICK_SPACING..=MAX_TICK_SPACING).contains(&tick_spacing) {
+        return Err(UniswapV3MathError::InvalidTickSpacing { tick_spacing });
+    }
+
+    Ok((MAX_TICK_V4 / tick_spacing) * tick_spacing)
+}
+
+// Returns the min usable tick for the given tick spacing, errors with InvalidTickSpacing outside of
+// [MIN_TICK_SPACING, MAX_TICK_SPACING]
+pub fn min_usable_tick(tick_spacing: i32) -> Result<i32, UniswapV3MathError> {
+    if !(MIN_TICK_SPACING..=MAX_TICK_SPACING).contains(&tick_spacing) {
+        return Err(UniswapV3MathError::InvalidTickSpacing { tick_spacing });
+    }
+
+    Ok((MIN_TICK_V4 / tick_spacing) * tick_spacing)
+}
+
+#[cfg(test)]
+mod test {
+    use std::str::FromStr;
+
+    use alloy_primitives::U256;
+
+    use super::*;
+
+    #[test]
+    fn test_get_sqrt_price_at_tick() {
+        // reverts outside of the tick bounds
+        assert!(get_sqrt_price_at_tick(MIN_TICK_V4 - 1).is_err());
+        assert!(get_sqrt_price_at_tick(MAX_TICK_V4 + 1).is_err());
+
+        // values from the v4-core TickMath tests
+        assert_eq!(
+            get_sqrt_price_at_tick(MIN_TICK_V4).unwrap(),
+            MIN_SQRT_RATIO_V4
+        );
+        assert_eq!(
+            get_sqrt_price_at_tick(MIN_TICK_V4 + 1).unwrap(),
+            U256::from(4295343490_u64)
+        );
+        assert_eq!(
+            get_sqrt_price_at_tick(MAX_TICK_V4 - 1).unwrap(),
+            U256::from_str("1461373636630004318706518188784493106690254656249").unwrap()
+        );
+        assert_eq!(
+            get_sqrt_price_at_tick(MAX_TICK_V4).unwrap(),
+            MAX_SQRT_RATIO_V4
+        );
+        assert_eq!(get_sqrt_price_at_tick(0).unwrap(), U256::from(1_u128 << 96));
+    }
+
+    #[test]
+    fn test_get_tick_at_sqrt_price() {
+        // reverts outside of the sqrt price bounds
+        assert!(get_tick_at_sqrt_price(SqrtPriceX96(MIN_SQRT_RATIO_V4 - U256::from(1))).is_err());
+        assert!(get_tick_at_sqrt_price(SqrtPriceX96(MAX_SQRT_RATIO_V4)).is_err());
+
+        assert_eq!(
+            get_tick_at_sqrt_price(SqrtPriceX96(MIN_SQRT_RATIO_V4)).unwrap(),
+            MIN_TICK_V4
+        );
+        assert_eq!(
+            get_tick_at_sqrt_price(SqrtPriceX96(U256::from(4295343490_u64))).unwrap(),
+            MIN_TICK_V4 + 1
+        );
+        assert_eq!(
+            get_tick_at_sqrt_price(SqrtPriceX96(
+                U256::from_str("1461373636630004318706518188784493106690254656249").unwrap()
+            ))
+            .unwrap(),
+            MAX_TICK_V4 - 1
+        );
+        assert_eq!(
+            get_tick_at_sqrt_price(SqrtPriceX96(MAX_SQRT_RATIO_V4 - U256::from(1))).unwrap(),
+            MAX_TICK_V4 - 1
+        );
+    }
+
+    #[test]
+    fn test_usable_ticks() {
+        assert_eq!(max_usable_tick(MIN_TICK_SPACING).unwrap(), MAX_TICK_V4);
+        assert_eq!(min_usable_tick(MIN_TICK_SPACING).unwrap(), MIN_TICK_V4);
+        assert_eq!(max_usable_tick(60).unwrap(), 887220);
+        assert_eq!(min_usable_tick(60).unwrap(), -887220);
+        assert_eq!(max_usable_tick(MAX_TICK_SPACING).unwrap(), 884709);
+        assert_eq!(min_usable_tick(MAX_TICK_SPACING).unwrap(), -884709);
+
+        // spacings outside of [MIN_TICK_SPACING, MAX_TICK_SPACING] error instead of dividing by zero
+        for tick_spacing in [0, -1, -60, MAX_TICK_SPACING + 1, i32::MIN] {
+            assert!(matches!(
+                max_usable_tick(tick_spacing),
+                Err(UniswapV3MathError::InvalidTickSpacing { tick_spacing: spacing }) if spacing == tick_spacing
+            ));
+            assert!(matches!(
+                min_usable_tick(tick_spacing),
+                Err(UniswapV3MathError::InvalidTickSpacing { tick_spacing: spacing }) if spacing == tick_spacing
+            ));
+        }
+    }
+}
@@ -0,0 +1,353 @@
+use std::str::FromStr;
+
+use alloy_primitives::{I256, U256};
+use wasm_bindgen::prelude::*;
+
+use crate::{fixed_point::SqrtPriceX96, sqrt_price_math, swap_math, tick_math};
+
+// 256 bit values cross the boundary as decimal or 0x prefixed hex strings and u128 values as decimal strings,
+// as neither fits in a JS safe integer. Errors are thrown as JsErrors carrying the error message
+
+//Returns the sqrt ratio at the given tick as a decimal string
+#[wasm_bindgen(js_name = getSqrtRatioAtTick)]
+pub fn get_sqrt_ratio_at_tick(tick: i32) -> Result<String, JsError> {
+    Ok(tick_math::get_sqrt_ratio_at_tick(tick)?.to_string())
+}
+
+//Returns the greatest tick whose sqrt ratio is less than or equal to the given sqrt ratio
+#[wasm_bindgen(js_name = getTickAtSqrtRatio)]
+pub fn get_tick_at_sqrt_ratio(sqrt_price_x_96: String) -> Result<i32, JsError> {
+    Ok(tick_math::get_tick_at_sqrt_ratio(parse_sqrt_price(
+        &sqrt_price_x_96,
+    )?)?)
+}
+
+//Returns the amount of token0 between the two sqrt ratios for the liquidity as a decimal string
+#[wasm_bindgen(js_name = getAmount0Delta)]
+pub fn get_amount_0_delta(
+    sqrt_ratio_a_x_96: String,
+    sqrt_ratio_b_x_96: String,
+    liquidity: String,
+    round_up: bool,
+) -> Result<String, JsError> {
+    Ok(sqrt_price_math::_get_amount_0_delta(
+        parse_sqrt_price(&sqrt_ratio_a_x_96)?,
+        parse_sqrt_price(&sqrt_ratio_b_x_96)?,
+        u128::from_str(&liquidity)?,
+        round_up,
+    )?
+    .to_string())
+}
+
+//Returns the amount of token1 between the two sqrt ratios for the liquidity as a decimal string
+#[wasm_bindgen(js_name = getAmount1Delta)]
+pub fn get_amount_1_delta(
+    sqrt_ratio_a_x_96: String,
+    sqrt_ratio_b_x_96: String,
+    liquidity: String,
+    round_up: bool,
+) -> Result<String, JsError> {
+    Ok(sqrt_price_math::_get_amount_1_delta(
+        parse_sqrt_price(&sqrt_ratio_a_x_96)?,
+        parse_sqrt_price(&sqrt_ratio_b_x_96)?,
+        u128::from_str(&liquidity)?,
+        round_up,
+    )?
+    .to_string())
+}
+
+//Result of computeSwapStep, all values are decimal strings
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct SwapStep {
+    #[wasm_bindgen(js_name = sqrtRatioNextX96)]
+    pub sqrt_ratio_next_x_96: String,
+    #[wasm_bindgen(js_name = amountIn)]
+    pub amount_in: String,
+    #[wasm_bindgen(js_name = amountOut)]
+    pub amount_out: String,
+    #[wasm_bindgen(js_name = feeAmount)]
+    pub fee_amount: String,
+}
+
+//Computes a swap step like SwapMath.computeSwapStep, amount_remaining is a signed decimal string, positive for
+//an exact input and negative for an exact output
+#[wasm_bindgen(js_name = computeSwapStep)]
+pub fn compute_swap_step(
+    sqrt_ratio_current_x_96: String,
+    sqrt_ratio_target_x_96: String,
+    liquidity: String,
+    amount_remaining: String,
+    fee_pips: u32,
+) -> Result<SwapStep, JsError> {
+    let (sqrt_ratio_next_x_96, amount_in, amount_out, fee_amount) = swap_math::compute_swap_step(
+        parse_sqrt_price(&sqrt_ratio_current_x_96)?,
+        parse_sqrt_price(&sqrt_ratio_target_x_96)?,
+        u128::from_str(&liquidity)?,
+        I256::from_str(&amount_remaining)?,
+        fee_pips,
+    )?;
+
+    Ok(SwapStep {
+        sqrt_ratio_next_x_96: sqrt_ratio_next_x_96.to_string(),
+        amount_in: amount_in.to_string(),
+        amount_out: amount_out.to_string(),
+        fee_amount: fee_amount.to_string(),
+    })
+}
+
+//Returns the liquidity received for amount_0 between the two sqrt ratios as a decimal string
+#[wasm_bindgen(js_name = getLiquidityForAmount0)]
+pub fn get_liquidity_for_amount_0(
+    sqrt_ratio_a_x_96: String,
+    sqrt_ratio_b_x_96: String,
+    amount_0: String,
+) -> Result<String, JsError> {
+    Ok(sqrt_price_math::get_liquidity_for_amount_0(
+        parse_sqrt_price(&sqrt_ratio_a_x_96)?,
+        parse_sqrt_price(&sqrt_ratio_b_x_96)?,
+        parse_u256(&amount_0)?,
+    )?
+    .to_string())
+}
+
+//Returns the liquidity received for amount_1 between the two sqrt ratios as a decimal string
+#[wasm_bindgen(js_name = getLiquidityForAmount1)]
+pub fn get_liquidity_for_amount_1(
+    sqrt_ratio_a_x_96: String,
+    sqrt_ratio_b_x_96: String,
+    amount_1: String,
+) -> Result<String, JsError> {
+    Ok(sqrt_price_math::get_liquidity_for_amount_1(
+        parse_sqrt_price(&sqrt_ratio_a_x_96)?,
+        parse_sqrt_price(&sqrt_ratio_b_x_96)?,
+        parse_u256(&amount_1)?,
+    )?
+    .to_string())
+}
+
+//Returns the max liquidity received for the two amounts at the current sqrt ratio as a decimal string
+#[wasm_bindgen(js_name = getLiquidityForAmounts)]
+pub fn get_liquidity_for_amounts(
+    sqrt_ratio_x_96: String,
+    sqrt_ratio_a_x_96: String,
+    sqrt_ratio_b_x_96: String,
+    amount_0: String,
+    amount_1: String,
+) -> Result<String, JsError> {
+    Ok(sqrt_price_math::get_liquidity_for_amounts(
+        parse_sqrt_price(&sqrt_ratio_x_96)?,
+        parse_sqrt_price(&sqrt_ratio_a_x_96)?,
+        parse_sqrt_price(&sqrt_ratio_b_x_96)?,
+        parse_u256(&amount_0)?,
+        parse_u256(&amount_1)?,
+    )?
+    .to_string())
+}
+
+//Result of getAmountsForLiquidity, both values are decimal strings
+#[wasm_bindgen(getter_with_clone)]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct TokenAmounts {
+    #[wasm_bindgen(js_name = amount0)]
+    pub amount_0: String,
+    #[wasm_bindgen(js_name = amount1)]
+    pub amount_1: String,
+}
+
+//Returns the token amounts held by the liquidity between the two sqrt ratios at the current sqrt ratio
+#[wasm_bindgen(js_name = getAmountsForLiquidity)]
+pub fn get_amounts_for_liquidity(
+    sqrt_ratio_x_96: String,
+    sqrt_ratio_a_x_96: String,
+    sqrt_ratio_b_x_96: String,
+    liquidity: String,
+) -> Result<TokenAmounts, JsError> {
+    let (amount_0, amount_1) = sqrt_price_math::get_amounts_for_liquidity(
+        parse_sqrt_price(&sqrt_ratio_x_96)?,
+        parse_sqrt_price(&sqrt_ratio_a_x_96)?,
+        parse_sqrt_price(&sqrt_ratio_b_x_96)?,
+        u128::from_str(&liquidity)?,
+    )?;
+
+    Ok(TokenAmounts {
+        amount_0: amount_0.to_string(),
+        amount_1: amount_1.to_string(),
+    })
+}
+
+// accepts decimal and 0x prefixed hex strings
+fn parse_u256(value: &str) -> Result<U256, JsError> {
+    Ok(U256::from_str(value)?)
+}
+
+fn parse_sqrt_price(value: &str) -> Result<SqrtPriceX96, JsError> {
+    parse_u256(value).map(SqrtPriceX96)
+}
+
+// The vectors also run natively with cargo test --features wasm, where building a JsError panics, so the error
+// cases are only checked on wasm32
+#[cfg(test)]
+mod test {
+    #[cfg(target_arch = "wasm32")]
+    use wasm_bindgen_test::wasm_bindgen_test;
+
+    use super::{
+        compute_swap_step, get_amount_0_delta, get_amount_1_delta, get_amounts_for_liquidity,
+        get_liquidity_for_amount_0, get_liquidity_for_amount_1, get_liquidity_for_amounts,
+        get_sqrt_ratio_at_tick, get_tick_at_sqrt_ratio, SwapStep, TokenAmounts,
+    };
+
+    // sqrt ratios of the prices 1, 1.0001^1000 and 1.21
+    const PRICE_1: &str = "79228162514264337593543950336";
+    const PRICE_TICK_1000: &str = "83290069058676223003182343270";
+    const PRICE_1_21: &str = "87150978765690771352898345369";
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn test_tick_math_round_trip() {
+        for (tick, sqrt_price_x_96) in [
+            (-887272, "4295128739"),
+            (0, "79228162514264337593543950336"),
+            (50, "79426470787362580746886972461"),
+            (150000, "143194173941309278083010301478497"),
+        ] {
+            assert_eq!(get_sqrt_ratio_at_tick(tick).unwrap(), sqrt_price_x_96);
+            assert_eq!(
+                get_tick_at_sqrt_ratio(sqrt_price_x_96.to_string()).unwrap(),
+                tick
+            );
+        }
+
+        // hex input, 2^96
+        assert_eq!(
+            get_tick_at_sqrt_ratio("0x1000000000000000000000000".to_string()).unwrap(),
+            0
+        );
+        if cfg!(target_arch = "wasm32") {
+            assert!(get_sqrt_ratio_at_tick(887273).is_err());
+            assert!(get_tick_at_sqrt_ratio("not a number".to_string()).is_err());
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn test_amount_deltas() {
+        let liquidity = "1000000000000000000".to_string();
+        for (round_up, amount_0, amount_1) in [
+            (true, "90909090909090910", "100000000000000000"),
+            (false, "90909090909090909", "99999999999999999"),
+        ] {
+            assert_eq!(
+                get_amount_0_delta(
+                    PRICE_1.to_string(),
+                    PRICE_1_21.to_string(),
+                    liquidity.clone(),
+                    round_up
+                )
+                .unwrap(),
+                amount_0
+            );
+            assert_eq!(
+                get_amount_1_delta(
+                    PRICE_1.to_string(),
+                    PRICE_1_21.to_string(),
+                    liquidity.clone(),
+                    round_up
+                )
+                .unwrap(),
+                amount_1
+            );
+        }
+
+        if cfg!(target_arch = "wasm32") {
+            assert!(get_amount_0_delta(
+                PRICE_1.to_string(),
+                PRICE_1_21.to_string(),
+                "-1".to_string(),
+                true
+            )
+            .is_err());
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn test_compute_swap_step() {
+        assert_eq!(
+            compute_swap_step(
+                PRICE_1.to_string(),
+                PRICE_1_21.to_string(),
+                "2000000000000000000".to_string(),
+                "1000000000000000000".to_string(),
+                600
+            )
+            .unwrap(),
+            SwapStep {
+                sqrt_ratio_next_x_96: PRICE_1_21.to_string(),
+                amount_in: "200000000000000000".to_string(),
+                amount_out: "181818181818181818".to_string(),
+                fee_amount: "120072043225936".to_string(),
+            }
+        );
+
+        if cfg!(target_arch = "wasm32") {
+            assert!(compute_swap_step(
+                PRICE_1.to_string(),
+                PRICE_1_21.to_string(),
+                "2000000000000000000".to_string(),
+                "1000000000000000000".to_string(),
+                1_000_001
+            )
+            .is_err());
+        }
+    }
+
+    #[cfg_attr(target_arch = "wasm32", wasm_bindgen_test)]
+    #[cfg_attr(not(target_arch = "wasm32"), test)]
+    fn test_liquidity_for_amounts() {
+        let amount = "1000000000000000000".to_string();
+        assert_eq!(
+            get_liquidity_for_amount_0(
+                PRICE_TICK_1000.to_string(),
+                PRICE_1_21.to_string(),
+                amount.clone()
+            )
+            .unwrap(),
+            "23729919349859225842"
+        );
+        assert_eq!(
+            get_liquidity_for_amount_1(
+                PRICE_1.to_string(),
+                PRICE_TICK_1000.to_string(),
+                amount.clone()
+            )
+            .unwrap(),
+            "19505166268106646078"
+        );
+        assert_eq!(
+            get_liquidity_for_amounts(
+                PRICE_TICK_1000.to_string(),
+                PRICE_1.to_string(),
+                PRICE_1_21.to_string(),
+                amount.clone(),
+                amount
+            )
+            .unwrap(),
+            "19505166268106646078"
+        );
+        assert_eq!(
+            get_amounts_for_liquidity(
+                PRICE_TICK_1000.to_string(),
+                PRICE_1.to_string(),
+                PRICE_1_21.to_string(),
+                "1000000000000000000".to_string()
+            )
+            .unwrap(),
+            TokenAmounts {
+                amount_0: "42140893327812020".to_string(),
+                amount_1: "51268468376766590".to_string(),
+            }
+        );
+    }
+}
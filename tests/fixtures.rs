@@ -0,0 +1,144 @@
+//! Fixtures of TickMath, SqrtPriceMath and SwapMath, the output of `scripts/generate_fixtures.py`, a Python port
+//! of the Solidity sources in `Uniswap/`. They are not taken from an EVM, so they only check this crate against that
+//! port. U256 values are 0x prefixed hex strings and signed amounts carry a leading minus, both parsed by the serde
+//! impls of alloy_primitives, which the dev-dependencies enable so that the test runs without the `serde` feature.
+
+use std::{fs, path::Path};
+
+use alloy_primitives::{I256, U256};
+use serde::{de::DeserializeOwned, Deserialize};
+use uniswap_v3_math::{
+    fixed_point::SqrtPriceX96,
+    sqrt_price_math::{_get_amount_0_delta, _get_amount_1_delta},
+    swap_math::compute_swap_step,
+    tick_math::{get_sqrt_ratio_at_tick, get_tick_at_sqrt_ratio},
+};
+
+fn load<T: DeserializeOwned>(name: &str) -> eyre::Result<Vec<T>> {
+    let path = Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("tests/fixtures")
+        .join(name);
+
+    Ok(serde_json::from_str(&fs::read_to_string(path)?)?)
+}
+
+#[derive(Debug, Deserialize)]
+struct SqrtRatioAtTick {
+    tick: i32,
+    sqrt_ratio_x_96: U256,
+}
+
+#[derive(Debug, Deserialize)]
+struct TickAtSqrtRatio {
+    sqrt_ratio_x_96: U256,
+    tick: i32,
+}
+
+#[derive(Debug, Deserialize)]
+struct AmountDeltas {
+    sqrt_ratio_a_x_96: U256,
+    sqrt_ratio_b_x_96: U256,
+    liquidity: u128,
+    round_up: bool,
+    amount_0: U256,
+    amount_1: U256,
+}
+
+#[derive(Debug, Deserialize)]
+struct SwapStep {
+    sqrt_ratio_current_x_96: U256,
+    sqrt_ratio_target_x_96: U256,
+    liquidity: u128,
+    amount_remaining: I256,
+    fee_pips: u32,
+    sqrt_ratio_next_x_96: U256,
+    amount_in: U256,
+    amount_out: U256,
+    fee_amount: U256,
+}
+
+#[test]
+fn test_get_sqrt_ratio_at_tick_fixtures() -> eyre::Result<()> {
+    let rows: Vec<SqrtRatioAtTick> = load("get_sqrt_ratio_at_tick.json")?;
+    assert!(rows.len() > 17_000);
+
+    for row in rows {
+        assert_eq!(
+            get_sqrt_ratio_at_tick(row.tick)?,
+            SqrtPriceX96(row.sqrt_ratio_x_96),
+            "{row:?}"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn test_get_tick_at_sqrt_ratio_fixtures() -> eyre::Result<()> {
+    let rows: Vec<TickAtSqrtRatio> = load("get_tick_at_sqrt_ratio.json")?;
+    assert!(rows.len() >= 5000);
+
+    for row in rows {
+        assert_eq!(
+            get_tick_at_sqrt_ratio(SqrtPriceX96(row.sqrt_ratio_x_96))?,
+            row.tick,
+            "{row:?}"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn test_amount_delta_fixtures() -> eyre::Result<()> {
+    let rows: Vec<AmountDeltas> = load("amount_deltas.json")?;
+    assert!(rows.len() > 3000);
+
+    for row in rows {
+        assert_eq!(
+            _get_amount_0_delta(
+                SqrtPriceX96(row.sqrt_ratio_a_x_96),
+                SqrtPriceX96(row.sqrt_ratio_b_x_96),
+                row.liquidity,
+                row.round_up
+            )?,
+            row.amount_0,
+            "{row:?}"
+        );
+        assert_eq!(
+            _get_amount_1_delta(
+                SqrtPriceX96(row.sqrt_ratio_a_x_96),
+                SqrtPriceX96(row.sqrt_ratio_b_x_96),
+                row.liquidity,
+                row.round_up
+            )?,
+            row.amount_1,
+            "{row:?}"
+        );
+    }
+    Ok(())
+}
+
+#[test]
+fn test_compute_swap_step_fixtures() -> eyre::Result<()> {
+    let rows: Vec<SwapStep> = load("compute_swap_step.json")?;
+    assert!(rows.len() >= 3000);
+
+    for row in rows {
+        assert_eq!(
+            compute_swap_step(
+                SqrtPriceX96(row.sqrt_ratio_current_x_96),
+                SqrtPriceX96(row.sqrt_ratio_target_x_96),
+                row.liquidity,
+                row.amount_remaining,
+                row.fee_pips
+            )?,
+            (
+                SqrtPriceX96(row.sqrt_ratio_next_x_96),
+                row.amount_in,
+                row.amount_out,
+                row.fee_amount
+            ),
+            "{row:?}"
+        );
+    }
+    Ok(())
+}
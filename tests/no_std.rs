@@ -0,0 +1,82 @@
+//! Smoke test that only links `core`, run it with `cargo test --no-default-features --test no_std`
+#![no_std]
+
+use alloy_primitives::{I256, U256};
+use uniswap_v3_math::{
+    error::UniswapV3MathError,
+    fixed_point::SqrtPriceX96,
+    sqrt_price_math::encode_sqrt_ratio_x_96,
+    swap_math::compute_swap_step,
+    tick_bitmap::{flip_tick, next_initialized_tick_within_one_word, TickBitmap, TickBitmapWords},
+    tick_math::{
+        get_sqrt_ratio_at_tick, get_tick_at_sqrt_ratio, MAX_SQRT_RATIO, MAX_TICK, MIN_SQRT_RATIO,
+        MIN_TICK,
+    },
+};
+
+#[test]
+fn test_tick_math_without_std() {
+    assert_eq!(get_sqrt_ratio_at_tick(MIN_TICK).unwrap(), MIN_SQRT_RATIO);
+    assert_eq!(get_sqrt_ratio_at_tick(MAX_TICK).unwrap(), MAX_SQRT_RATIO);
+    assert_eq!(get_sqrt_ratio_at_tick(0).unwrap(), U256::from(1) << 96);
+
+    for tick in [MIN_TICK, -50000, -1, 0, 1, 50000, MAX_TICK - 1] {
+        let sqrt_ratio = get_sqrt_ratio_at_tick(tick).unwrap();
+        assert_eq!(get_tick_at_sqrt_ratio(sqrt_ratio).unwrap(), tick);
+    }
+
+    assert!(get_tick_at_sqrt_ratio(SqrtPriceX96(MAX_SQRT_RATIO)).is_err());
+    assert_eq!(
+        encode_sqrt_ratio_x_96(U256::from(1), U256::from(1)).unwrap(),
+        U256::from(1) << 96
+    );
+}
+
+#[test]
+fn test_tick_bitmap_without_std() {
+    let mut tick_bitmap = TickBitmapWords::new();
+    flip_tick(&mut tick_bitmap, 120, 60).unwrap();
+
+    assert_eq!(
+        next_initialized_tick_within_one_word(&tick_bitmap, 0, 60, false).unwrap(),
+        (120, true)
+    );
+
+    let mut tick_bitmap = TickBitmap::from(tick_bitmap);
+    tick_bitmap.flip_tick(-120, 60).unwrap();
+    assert!(tick_bitmap.is_initialized(-120, 60));
+    assert!(tick_bitmap
+        .iter_initialized(.., 60)
+        .unwrap()
+        .eq([-120, 120]));
+}
+
+#[test]
+fn test_swap_math_without_std() {
+    let price = get_sqrt_ratio_at_tick(0).unwrap();
+    let target = get_sqrt_ratio_at_tick(-60).unwrap();
+    let amount_remaining = I256::from_raw(U256::from(1_000_000_000_000_000_000_u128));
+
+    let (sqrt_price_next, amount_in, amount_out, fee_amount) = compute_swap_step(
+        price,
+        target,
+        2_000_000_000_000_000_000,
+        amount_remaining,
+        3000,
+    )
+    .unwrap();
+    assert!(sqrt_price_next >= target && sqrt_price_next < price);
+    assert!(amount_in + fee_amount <= amount_remaining.into_raw());
+    assert!(!amount_out.is_zero());
+}
+
+#[test]
+fn test_error_without_std() {
+    fn source(error: &dyn core::error::Error) -> Option<&dyn core::error::Error> {
+        error.source()
+    }
+
+    let error = get_sqrt_ratio_at_tick(MAX_TICK + 1).unwrap_err();
+    assert!(matches!(error, UniswapV3MathError::TickOutOfBounds { .. }));
+    assert!(source(&error).is_none());
+}
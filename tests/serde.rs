@@ -0,0 +1,154 @@
+//! Round trips of the pool snapshot types through JSON and bincode, a human readable and a binary format,
+//! run it with `cargo test --features serde --test serde`
+#![cfg(feature = "serde")]
+
+use std::{collections::HashMap, fmt::Debug};
+
+use alloy_primitives::{I256, U256};
+use serde::{de::DeserializeOwned, Serialize};
+use uniswap_v3_math::{
+    error::UniswapV3MathError, fixed_point::FeeGrowthX128, oracle::Observation, pool::PoolState,
+    swap_math::compute_swap_step_struct, swap_simulation::SwapResult, tick::TickInfo,
+    tick_bitmap::TickBitmap, tick_math::get_sqrt_ratio_at_tick,
+};
+
+fn round_trip<T: Serialize + DeserializeOwned + PartialEq + Debug>(value: &T) -> eyre::Result<()> {
+    let json = serde_json::to_string(value)?;
+    assert_eq!(&serde_json::from_str::<T>(&json)?, value);
+
+    let bytes = bincode::serialize(value)?;
+    assert_eq!(&bincode::deserialize::<T>(&bytes)?, value);
+    Ok(())
+}
+
+#[test]
+fn test_pool_snapshot_round_trip() -> eyre::Result<()> {
+    let pool_state = PoolState {
+        sqrt_price_x_96: get_sqrt_ratio_at_tick(-201090)?,
+        liquidity: 21_000_000_000_000_000_000,
+        tick: -201090,
+        fee: 3000,
+        tick_spacing: 60,
+        fee_growth_global_0_x_128: FeeGrowthX128(U256::MAX),
+        fee_growth_global_1_x_128: FeeGrowthX128(U256::from(1) << 200),
+        fee_protocol: (6 << 4) | 4,
+        protocol_fees_token_0: u128::MAX,
+        protocol_fees_token_1: 1,
+    };
+    round_trip(&pool_state)?;
+
+    let mut tick_bitmap = TickBitmap::new();
+    let mut ticks = HashMap::new();
+    for (tick, liquidity_net) in [
+        (-887220, 1e18 as i128),
+        (-600, 2e18 as i128),
+        (600, -2e18 as i128),
+    ] {
+        tick_bitmap.flip_tick(tick, 60)?;
+        ticks.insert(
+            tick,
+            TickInfo {
+                liquidity_gross: liquidity_net.unsigned_abs(),
+                liquidity_net,
+                fee_growth_outside_0_x_128: FeeGrowthX128(
+                    U256::MAX - U256::from(tick.unsigned_abs()),
+                ),
+                tick_cumulative_outside: -120,
+                seconds_outside: 7,
+                initialized: true,
+                ..Default::default()
+            },
+        );
+    }
+    round_trip(&tick_bitmap)?;
+    round_trip(&ticks)?;
+
+    round_trip(&Observation {
+        block_timestamp: 1_700_000_000,
+        tick_cumulative: -12_345_678_901,
+        seconds_per_liquidity_cumulative_x_128: U256::MAX >> 96,
+        initialized: true,
+    })?;
+
+    round_trip(&compute_swap_step_struct(
+        pool_state.sqrt_price_x_96,
+        get_sqrt_ratio_at_tick(-201000)?,
+        pool_state.liquidity,
+        I256::try_from(1e18 as i128)?,
+        3000,
+    )?)?;
+
+    round_trip(&SwapResult {
+        amount_0: I256::MIN,
+        amount_1: I256::MAX,
+        sqrt_price_x_96: pool_state.sqrt_price_x_96,
+        tick: -201090,
+        liquidity: u128::MAX,
+        ticks_crossed: 3,
+        fee_growth_x_128: FeeGrowthX128(U256::MAX),
+        protocol_fee: u128::MAX,
+    })?;
+    Ok(())
+}
+
+#[test]
+fn test_json_numbers_are_hex_strings() -> eyre::Result<()> {
+    let tick_info = TickInfo {
+        fee_growth_outside_0_x_128: FeeGrowthX128(U256::from(255)),
+        ..Default::default()
+    };
+
+    let json = serde_json::to_value(tick_info)?;
+    assert_eq!(json["fee_growth_outside_0_x_128"], "0xff");
+
+    let json = serde_json::to_value(SwapResult {
+        amount_0: I256::try_from(-1)?,
+        amount_1: I256::try_from(255)?,
+        ..Default::default()
+    })?;
+    assert_eq!(json["amount_0"], "-0x1");
+    assert_eq!(json["amount_1"], "0xff");
+    Ok(())
+}
+
+#[test]
+fn test_binary_numbers_are_raw_words() -> eyre::Result<()> {
+    // bincode is not human readable, so U256 and I256 are written as length prefixed 32 byte big endian words
+    // instead of the hex strings of the JSON representation
+    let encoded_word = |word: [u8; 32]| [32_u64.to_le_bytes().as_slice(), &word].concat();
+
+    let tick_info = TickInfo {
+        fee_growth_outside_0_x_128: FeeGrowthX128(U256::from(255)),
+        ..Default::default()
+    };
+    let bytes = bincode::serialize(&tick_info)?;
+    let expected = encoded_word(U256::from(255).to_be_bytes());
+    assert!(bytes
+        .windows(expected.len())
+        .any(|window| window == expected));
+
+    // negative I256 values are written as their two's complement word
+    let bytes = bincode::serialize(&SwapResult {
+        amount_0: I256::MINUS_ONE,
+        ..Default::default()
+    })?;
+    let expected = encoded_word([0xff; 32]);
+    assert_eq!(bytes[..expected.len()], expected);
+    assert_eq!(
+        bincode::deserialize::<SwapResult>(&bytes)?.amount_0,
+        I256::MINUS_ONE
+    );
+    Ok(())
+}
+
+#[test]
+fn test_error_round_trip() -> eyre::Result<()> {
+    let error = UniswapV3MathError::SqrtPriceOutOfBounds {
+        sqrt_price_x_96: U256::MAX,
+    };
+
+    let bytes = bincode::serialize(&error)?;
+    let decoded: UniswapV3MathError = bincode::deserialize(&bytes)?;
+    assert_eq!(format!("{decoded:?}"), format!("{error:?}"));
+    Ok(())
+}
@@ -0,0 +1,42 @@
+// Round trips known tick / sqrt price pairs through the wasm bindings from node. Build the bindings into pkg/ with
+//   cargo rustc --lib --release --target wasm32-unknown-unknown --features wasm --crate-type cdylib
+//   wasm-bindgen --target nodejs --out-dir pkg target/wasm32-unknown-unknown/release/uniswap_v3_math.wasm
+// and run it with
+//   node --experimental-strip-types --test tests/wasm/round_trip.test.mts
+import assert from "node:assert/strict";
+import test from "node:test";
+
+import { getSqrtRatioAtTick, getTickAtSqrtRatio } from "../../pkg/uniswap_v3_math.js";
+
+// ticks and their sqrt ratios as returned by TickMath.getSqrtRatioAtTick
+const VECTORS: Array<[tick: number, sqrtPriceX96: string]> = [
+  [-887272, "4295128739"],
+  [-200300, "3545176043869541311161154"],
+  [-100, "78833030112140176575862854579"],
+  [0, "79228162514264337593543950336"],
+  [100, "79625275426524748796330556128"],
+  [200300, "1770603676012448931537915781225536"],
+  [887200, "1456195216270955103206513029158776779468408838535"],
+];
+
+test("getSqrtRatioAtTick and getTickAtSqrtRatio round trip", () => {
+  for (const [tick, sqrtPriceX96] of VECTORS) {
+    assert.equal(getSqrtRatioAtTick(tick), sqrtPriceX96);
+    assert.equal(getTickAtSqrtRatio(sqrtPriceX96), tick);
+  }
+});
+
+test("getTickAtSqrtRatio accepts hex strings", () => {
+  assert.equal(getTickAtSqrtRatio("0x1000000000000000000000000"), 0);
+  assert.equal(getTickAtSqrtRatio("0x" + BigInt(VECTORS[5][1]).toString(16)), 200300);
+});
+
+test("errors are thrown with the error message", () => {
+  assert.throws(() => getSqrtRatioAtTick(887273), /maximum tick/);
+  // the max sqrt ratio itself is excluded like in TickMath.getTickAtSqrtRatio
+  assert.throws(
+    () => getTickAtSqrtRatio(getSqrtRatioAtTick(887272)),
+    /price can never reach the price at the max tick/,
+  );
+  assert.throws(() => getTickAtSqrtRatio("not a number"));
+});